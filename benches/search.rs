@@ -0,0 +1,58 @@
+/*
+ * Compara los algoritmos de búsqueda sobre un texto grande, se ejecuta con `cargo bench`
+ */
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use the_basics::ownership::search::{AhoCorasick, Horspool, Kmp, Matcher, Naive};
+
+const WORDS: [&str; 12] = [
+    "ownership", "borrow", "slice", "heap", "stack", "lifetime", "string", "vector", "move",
+    "drop", "scope", "reference",
+];
+
+/*
+ * Un generador congruencial lineal es suficiente para que el texto sea el mismo en cada ejecución
+ */
+fn build_text(size: usize) -> String {
+    let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut text = String::with_capacity(size + 16);
+    while text.len() < size {
+        seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+        text.push_str(WORDS[(seed >> 33) as usize % WORDS.len()]);
+        text.push(if seed.is_multiple_of(17) { '\n' } else { ' ' });
+    }
+    text
+}
+
+fn bench<M: Matcher>(name: &str, matcher: &M, text: &str) {
+    let rounds = 5;
+    let mut total = Duration::ZERO;
+    let mut found = 0;
+    for _ in 0..rounds {
+        let start = Instant::now();
+        found = black_box(matcher.count(black_box(text)));
+        total += start.elapsed();
+    }
+    let per_round = total / rounds;
+    let mb_per_sec = text.len() as f64 / per_round.as_secs_f64() / 1_000_000.0;
+    println!("{name:<24} {found:>8} matches {per_round:>12.2?} {mb_per_sec:>10.1} MB/s");
+}
+
+fn main() {
+    for size in [1_000_000, 16_000_000] {
+        let text = build_text(size);
+        println!("text: {} bytes", text.len());
+        for pattern in ["heap", "lifetime reference", "ownership borrow slice"] {
+            println!("pattern: {pattern:?}");
+            bench("naive", &Naive::new(pattern), &text);
+            bench("kmp", &Kmp::new(pattern), &text);
+            bench("horspool", &Horspool::new(pattern), &text);
+            bench("aho-corasick", &AhoCorasick::new(&[pattern]), &text);
+        }
+        let patterns = ["panic", "heap stack", "drop scope", "move", "lifetime"];
+        println!("patterns: {patterns:?}");
+        bench("aho-corasick (5)", &AhoCorasick::new(&patterns), &text);
+        println!();
+    }
+}
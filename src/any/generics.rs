@@ -3,26 +3,42 @@
  * es decir, escribimos un `placeholder` en lugar de especificar el tipo, lo que nos da la flexibilidad
  * de aceptar cualquier tipo
  */
-#[derive(Debug)]
-struct Point<T> {
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point<T> {
     /*
      * Los tipos `placeholders` son especificados en tiempo de compilación, y son manejados como
      * parámetros
      */
-    x: T,
-    y: T
+    pub x: T,
+    pub y: T
 }
 
 impl Point<f32> {
-    fn distance_from_origin(&self) -> f32 {
+    pub fn distance_from_origin(&self) -> f32 {
         (self.x.powi(2) + self.y.powi(2)).sqrt()
     }
 }
 
+/*
+ * Un `Point<T>` y una `tuple` `(T, T)` guardan lo mismo, las conversiones van en los dos sentidos.
+ * `types::matrix` usa `Point<T>` para las transformaciones en 2D y `(T, T, T)` para las de 3D
+ */
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
 #[derive(Debug)]
-struct OtherPoint<T, U> {
-    x: T,
-    y: U
+pub struct OtherPoint<T, U> {
+    pub x: T,
+    pub y: U
 }
 
 /*
@@ -30,16 +46,16 @@ struct OtherPoint<T, U> {
  * y deben de definir por lo menos el mismo número de la `struct`
  */
 impl<T, U> OtherPoint<T, U> {
-    fn new(x: T, y: U) -> Self {
+    pub fn new(x: T, y: U) -> Self {
         Self { x, y }
     }
 
-    fn x(&self) -> &T {
+    pub fn x(&self) -> &T {
         &self.x
     }
 }
 
-fn run() {
+pub fn run() {
     /*
      * El tipo de `T` debe de ser el mismo para la declaración
      */
@@ -55,10 +71,10 @@ fn run() {
  * En este caso, `T` es un genérico que representa cualquier tipo que implemente el `trait` `PartialOrd`
  * (para que los elementos se puedan comparar).
  */
-fn largest<T: PartialOrd>(list: &[T]) -> &T {
+pub fn largest<T: PartialOrd>(list: &[T]) -> &T {
     let mut largest = &list[0];
     for i in list.iter() {
-        if i < largest {
+        if i > largest {
             largest = i;
         }
     }
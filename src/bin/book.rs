@@ -0,0 +1,179 @@
+/*
+ * `book` convierte los archivos de `src` en un libro estático, un capítulo por archivo. El
+ * resultado son archivos que se pueden copiar a cualquier servidor, no hace falta ningún servicio
+ *
+ *  * book [--src DIR] [--lessons DIR] [--out DIR] [--format html|markdown|all] [--lang es|en]
+ *
+ *  book/html/index.html            la tabla de contenido
+ *  book/html/flow-loops.html       un capítulo
+ *  book/markdown/SUMMARY.md        el índice para `mdBook`
+ *  book/markdown/flow-loops.md
+ *
+ * Con `--lang en` se usan las traducciones de los archivos `.lesson`, un texto sin traducir se
+ * muestra en español
+ */
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use the_basics::lessons::book::{self, Book, Chapter};
+use the_basics::lessons::lesson::{Language, Lesson};
+
+const USAGE: &str =
+    "usage: book [--src DIR] [--lessons DIR] [--out DIR] [--format html|markdown|all] [--lang es|en]";
+
+struct Options {
+    src: PathBuf,
+    lessons: PathBuf,
+    out: PathBuf,
+    html: bool,
+    markdown: bool,
+    language: Language,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        src: PathBuf::from("src"),
+        lessons: PathBuf::from("lessons"),
+        out: PathBuf::from("book"),
+        html: true,
+        markdown: true,
+        language: Language::Spanish,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{name} needs a value\n{USAGE}"));
+        match arg.as_str() {
+            "--src" => options.src = PathBuf::from(value("--src")?),
+            "--lessons" => options.lessons = PathBuf::from(value("--lessons")?),
+            "--out" => options.out = PathBuf::from(value("--out")?),
+            "--format" => {
+                let format = value("--format")?;
+                (options.html, options.markdown) = match format.as_str() {
+                    "html" => (true, false),
+                    "markdown" | "md" => (false, true),
+                    "all" => (true, true),
+                    _ => {
+                        return Err(format!(
+                            "unknown format {format}, use html, markdown or all"
+                        ))
+                    }
+                };
+            }
+            "--lang" => {
+                let code = value("--lang")?;
+                options.language = Language::from_code(&code)
+                    .ok_or(format!("unknown language {code}, use es or en"))?;
+            }
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            other => return Err(format!("unknown argument {other}\n{USAGE}")),
+        }
+    }
+    Ok(options)
+}
+
+/*
+ * Las rutas relativas a `src` con `/`, sin importar el sistema operativo, `flow/loops.rs`
+ */
+fn sources(root: &Path, dir: &Path, paths: &mut Vec<String>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            sources(root, &path, paths)?;
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            let parts: Vec<_> = relative.iter().map(|part| part.to_string_lossy()).collect();
+            paths.push(parts.join("/"));
+        }
+    }
+    Ok(())
+}
+
+fn chapters(options: &Options) -> Result<Vec<Chapter>, String> {
+    let mut paths = Vec::new();
+    sources(&options.src, &options.src, &mut paths)
+        .map_err(|error| format!("{}: {error}", options.src.display()))?;
+    let mut chapters = Vec::new();
+    for path in paths.iter().filter(|path| book::position(path).is_some()) {
+        let file = options.src.join(path);
+        let source =
+            fs::read_to_string(&file).map_err(|error| format!("{}: {error}", file.display()))?;
+        let lesson_file = options.lessons.join(path.replace(".rs", ".lesson"));
+        let translation = match fs::read_to_string(&lesson_file) {
+            Ok(text) => Some(
+                Lesson::parse(&text)
+                    .map_err(|error| format!("{}: {error}", lesson_file.display()))?,
+            ),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+            Err(error) => return Err(format!("{}: {error}", lesson_file.display())),
+        };
+        chapters.push(Chapter::new(path, &source, translation.as_ref()));
+    }
+    Ok(chapters)
+}
+
+fn write(dir: &Path, name: &str, text: &str) -> Result<(), String> {
+    let path = dir.join(name);
+    fs::write(&path, text).map_err(|error| format!("{}: {error}", path.display()))
+}
+
+fn export(book: &Book, dir: &Path, html: bool) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|error| format!("{}: {error}", dir.display()))?;
+    for (index, chapter) in book.chapters.iter().enumerate() {
+        if html {
+            write(dir, &format!("{}.html", chapter.name()), &book.html(index))?;
+        } else {
+            write(
+                dir,
+                &format!("{}.md", chapter.name()),
+                &book.markdown(index),
+            )?;
+        }
+    }
+    let (name, text) = if html {
+        ("index.html", book.index())
+    } else {
+        ("SUMMARY.md", book.summary())
+    };
+    write(dir, name, &text)?;
+    Ok(dir.join(name))
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let book = Book::new(options.language, chapters(options)?);
+    let translated: usize = book.chapters.iter().map(|c| c.lesson.translated()).sum();
+    let steps: usize = book.chapters.iter().map(|c| c.lesson.steps.len()).sum();
+    println!(
+        "{} chapters in {} topics, {steps} steps, {translated} translated",
+        book.chapters.len(),
+        book.topics().len(),
+    );
+    for (enabled, html, dir) in [
+        (options.html, true, "html"),
+        (options.markdown, false, "markdown"),
+    ] {
+        if enabled {
+            let index = export(&book, &options.out.join(dir), html)?;
+            println!("{}", index.display());
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::from(2);
+        }
+    };
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("book: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
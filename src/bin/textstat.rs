@@ -0,0 +1,331 @@
+/*
+ * `textstat` cuenta las líneas, palabras, `bytes` y `chars` de archivos o de la entrada estándar,
+ * encuentra la línea más larga y busca una palabra mostrando el número de línea y su contexto.
+ * Todo se hace con `slices` prestados del texto leído, no se copia ninguna línea.
+ * Las líneas y las palabras se cortan con `get_lines` y `get_words` de `string_slices.rs`, que
+ * devuelven la posición de cada palabra porque para resaltarla no basta con el `slice`
+ *
+ *  * textstat [--lossy] [--longest] [--find WORD] [-C N] [--color | --no-color] [FILE...]
+ */
+use std::borrow::Cow;
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Read, Write};
+use std::process::ExitCode;
+
+use the_basics::ownership::string_slices::{get_lines, get_words};
+
+const USAGE: &str = "usage: textstat [--lossy] [--longest] [--find WORD] [-C N] [--color | --no-color] [FILE...]";
+
+struct Options {
+    lossy: bool,
+    longest: bool,
+    find: Option<String>,
+    context: usize,
+    color: bool,
+    help: bool,
+    files: Vec<String>,
+}
+
+#[derive(Default)]
+struct Counts {
+    lines: usize,
+    words: usize,
+    bytes: usize,
+    chars: usize,
+}
+
+impl Counts {
+    /*
+     * Los `bytes` se cuentan en la entrada original, con `--lossy` cada `byte` inválido ocupa 3
+     * `bytes` en el texto decodificado como `U+FFFD`
+     */
+    fn of(raw: &[u8], text: &str) -> Self {
+        Self {
+            lines: get_lines(text).count(),
+            words: get_words(text).count(),
+            bytes: raw.len(),
+            chars: text.chars().count(),
+        }
+    }
+
+    fn add(&mut self, other: &Counts) {
+        self.lines += other.lines;
+        self.words += other.words;
+        self.bytes += other.bytes;
+        self.chars += other.chars;
+    }
+
+    fn print(&self, name: &str) {
+        println!("{:>8} {:>8} {:>8} {:>8} {}", self.lines, self.words, self.bytes, self.chars, name);
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        lossy: false,
+        longest: false,
+        find: None,
+        context: 0,
+        color: io::stdout().is_terminal(),
+        help: false,
+        files: Vec::new(),
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--lossy" => options.lossy = true,
+            "--longest" => options.longest = true,
+            "--color" => options.color = true,
+            "--no-color" => options.color = false,
+            "--find" => {
+                let word = args.next().ok_or("--find needs a WORD")?;
+                options.find = Some(word);
+            }
+            "-C" | "--context" => {
+                let lines = args.next().ok_or("-C needs a number of lines")?;
+                options.context = lines
+                    .parse()
+                    .map_err(|_| format!("invalid number of context lines: {lines}"))?;
+            }
+            "-h" | "--help" => options.help = true,
+            "-" => options.files.push(arg),
+            flag if flag.starts_with('-') => return Err(format!("unknown option {flag}\n{USAGE}")),
+            _ => options.files.push(arg),
+        }
+    }
+    Ok(options)
+}
+
+fn read_input(path: &str) -> io::Result<Vec<u8>> {
+    if path == "-" {
+        let mut bytes = Vec::new();
+        io::stdin().read_to_end(&mut bytes)?;
+        Ok(bytes)
+    } else {
+        fs::read(path)
+    }
+}
+
+/*
+ * Un `String` solo puede contener `UTF-8` válido, si los `bytes` no lo son se informa la línea y la
+ * posición del primer `byte` inválido, o con `--lossy` se reemplaza por `U+FFFD`
+ */
+fn decode(bytes: &[u8], lossy: bool) -> Result<Cow<'_, str>, String> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Ok(Cow::Borrowed(text)),
+        Err(_) if lossy => Ok(String::from_utf8_lossy(bytes)),
+        Err(error) => {
+            let offset = error.valid_up_to();
+            let line = bytes[..offset].iter().filter(|&&b| b == b'\n').count() + 1;
+            Err(format!(
+                "invalid UTF-8 at byte {offset} (line {line}), use --lossy to replace it"
+            ))
+        }
+    }
+}
+
+/*
+ * La línea más larga se mide en `chars` y no en `bytes`
+ */
+fn longest_line(text: &str) -> Option<(usize, &str)> {
+    get_lines(text)
+        .enumerate()
+        .max_by(|(a_index, a), (b_index, b)| {
+            a.chars().count().cmp(&b.chars().count()).then(b_index.cmp(a_index))
+        })
+}
+
+fn highlight(line: &str, word: &str, color: bool) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut last = 0;
+    for (start, found) in get_words(line).filter(|(_, found)| *found == word) {
+        out.push_str(&line[last..start]);
+        if color {
+            out.push_str("\x1b[1;31m");
+            out.push_str(found);
+            out.push_str("\x1b[0m");
+        } else {
+            out.push('[');
+            out.push_str(found);
+            out.push(']');
+        }
+        last = start + found.len();
+    }
+    out.push_str(&line[last..]);
+    out
+}
+
+/*
+ * Se busca la palabra completa, `slice` no coincide dentro de `slices`. Las líneas de contexto se
+ * imprimen con `-` y las líneas con coincidencias con `:`, como lo hace `grep`
+ */
+fn find_word(
+    out: &mut impl Write,
+    name: &str,
+    text: &str,
+    word: &str,
+    context: usize,
+    color: bool,
+) -> io::Result<usize> {
+    let lines: Vec<&str> = get_lines(text).collect();
+    let hits: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| get_words(line).any(|(_, found)| found == word))
+        .map(|(index, _)| index)
+        .collect();
+    let mut printed_until = 0;
+    for (position, &hit) in hits.iter().enumerate() {
+        let from = hit.saturating_sub(context).max(printed_until);
+        let to = (hit + context + 1).min(lines.len());
+        if position > 0 && context > 0 && from > printed_until {
+            writeln!(out, "--")?;
+        }
+        for (index, line) in lines.iter().enumerate().take(to).skip(from) {
+            if hits.binary_search(&index).is_ok() {
+                writeln!(out, "{name}:{}:{}", index + 1, highlight(line, word, color))?;
+            } else {
+                writeln!(out, "{name}-{}-{}", index + 1, line)?;
+            }
+        }
+        printed_until = to;
+    }
+    Ok(hits.len())
+}
+
+fn main() -> ExitCode {
+    let mut options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::from(2);
+        }
+    };
+    if options.help {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+    if options.files.is_empty() {
+        options.files.push("-".to_string());
+    }
+    let mut total = Counts::default();
+    let mut found = 0;
+    let mut failed = false;
+    for path in &options.files {
+        let name = if path == "-" { "(stdin)" } else { path };
+        let bytes = match read_input(path) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                eprintln!("textstat: {name}: {error}");
+                failed = true;
+                continue;
+            }
+        };
+        let text = match decode(&bytes, options.lossy) {
+            Ok(text) => text,
+            Err(message) => {
+                eprintln!("textstat: {name}: {message}");
+                failed = true;
+                continue;
+            }
+        };
+        match &options.find {
+            Some(word) => {
+                let mut out = io::stdout().lock();
+                match find_word(&mut out, name, &text, word, options.context, options.color) {
+                    Ok(hits) => found += hits,
+                    Err(error) => {
+                        eprintln!("textstat: {name}: {error}");
+                        failed = true;
+                    }
+                }
+            }
+            None => {
+                let counts = Counts::of(&bytes, &text);
+                counts.print(name);
+                total.add(&counts);
+            }
+        }
+        if options.longest {
+            if let Some((index, line)) = longest_line(&text) {
+                println!("{name}: longest line {} ({} chars): {}", index + 1, line.chars().count(), line);
+            }
+        }
+    }
+    if options.find.is_none() && options.files.len() > 1 {
+        total.print("total");
+    }
+    /*
+     * Como `grep`, si no se encontró la palabra el código de salida es `1`
+     */
+    if failed || (options.find.is_some() && found == 0) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+     * La salida de `find_word` como texto, sin color
+     */
+    fn find(text: &str, word: &str, context: usize) -> (String, usize) {
+        let mut out = Vec::new();
+        let hits = find_word(&mut out, "f", text, word, context, false).unwrap();
+        (String::from_utf8(out).unwrap(), hits)
+    }
+
+    #[test]
+    fn decode_strict_and_lossy() {
+        assert!(matches!(decode("año".as_bytes(), false), Ok(Cow::Borrowed("año"))));
+        let bytes = b"uno\ndos\xff\n";
+        assert_eq!(
+            decode(bytes, false).unwrap_err(),
+            "invalid UTF-8 at byte 7 (line 2), use --lossy to replace it"
+        );
+        let text = decode(bytes, true).unwrap();
+        assert!(matches!(text, Cow::Owned(_)));
+        assert_eq!(text, "uno\ndos\u{FFFD}\n");
+        let counts = Counts::of(bytes, &text);
+        assert_eq!((counts.lines, counts.words, counts.bytes, counts.chars), (2, 2, 9, 9));
+    }
+
+    #[test]
+    fn longest_line_counts_chars() {
+        assert_eq!(longest_line(""), None);
+        assert_eq!(longest_line("ññññ\nabcde\n"), Some((1, "abcde")));
+        assert_eq!(longest_line("ab\ncd\nx"), Some((0, "ab")));
+        assert_eq!(longest_line("a\r\nñññ\r\n"), Some((1, "ñññ")));
+    }
+
+    #[test]
+    fn highlight_whole_words() {
+        assert_eq!(
+            highlight("slice, slices y slice", "slice", false),
+            "[slice], slices y [slice]"
+        );
+        assert_eq!(highlight("ñu ñu", "ñu", true), "\x1b[1;31mñu\x1b[0m \x1b[1;31mñu\x1b[0m");
+        assert_eq!(highlight("nada", "slice", false), "nada");
+    }
+
+    #[test]
+    fn find_word_context_at_the_edges() {
+        let text = "uno x\ndos\ntres\ncuatro\ncinco\nseis x";
+        let (out, hits) = find(text, "x", 1);
+        assert_eq!(hits, 2);
+        assert_eq!(out, "f:1:uno [x]\nf-2-dos\n--\nf-5-cinco\nf:6:seis [x]\n");
+        /*
+         * Los contextos que se tocan o se enciman no repiten líneas ni imprimen `--`
+         */
+        for context in [2, 3, 10] {
+            let (out, _) = find(text, "x", context);
+            assert_eq!(out.lines().count(), 6, "-C {context}");
+            assert!(!out.contains("--"));
+        }
+        let (out, hits) = find(text, "y", 1);
+        assert_eq!((out.as_str(), hits), ("", 0));
+    }
+}
@@ -4,7 +4,7 @@
 use std::collections::HashMap;
 
 
-fn main() {
+pub fn run() {
     let mut scores = HashMap::new();
 
     scores.insert(String::from("Blue"), 10);
@@ -0,0 +1,2 @@
+pub mod hashmap;
+pub mod vector;
@@ -1,8 +1,11 @@
+use crate::custom_types::result::{self, ExampleResult, LessonError};
+
 /*
  * Una colección dinámica se almacena en el `heap`, su tamaño no se conoce en tiempo de compilación y
  * puede crecer o disminuir mientras el programa se ejecuta
  */
-fn main() {
+#[allow(clippy::vec_init_then_push, clippy::useless_vec)]
+pub fn run() {
     /*
      * Un `vector` es una estructura de datos que te permite almacenar más de un elemento de forma
      * contigua, uno después de otro en memoria, y solo pueden almacenar datos del mismo tipo. Una
@@ -36,8 +39,12 @@ fn main() {
     println!("One color: {red}");
     alloc_vec();
 
-    let ele = get_ele(nums, 1);
-    println!("Element: {ele}");
+    if let ExampleResult::Ok(ele) = get_ele(nums.clone(), 1) {
+        println!("Element: {ele}");
+    }
+    if let ExampleResult::Err(error) = get_ele(nums, 10).context("reading element 10 of nums") {
+        println!("{}", error.report());
+    }
     multi_refs();
     let mut integers = vec![1, 2, 3, 4, 4];
     alter_content_vec(&mut integers);
@@ -72,27 +79,20 @@ fn alloc_vec() {
     println!("Capacity: {} and lenght: {}", chars.capacity(), chars.len());
 }
 
-fn get_ele(vec: Vec<i32>, index: usize) -> i32 {
+fn get_ele(vec: Vec<i32>, index: usize) -> ExampleResult<i32, LessonError> {
     /*
      * El método `.get(index)` devuelve un `Option<&I>`, donde si existe un espacio y un elemento
      * en ese `index` devolvera su variante `Some` con una referencia de ese elemento
+     *
+     * Si intentamos acceder a un `index` que este fuera del rango de `vector`, el método `.get(index)`
+     * devolvera la variante `None` sin lanzar un `panic`. Antes se devolvía `-1`, pero `-1` también
+     * puede ser un elemento válido, el error `IndexOutOfBounds` no se puede confundir con un valor
      */
-    match vec.get(index) {
-        Some(ele) => {
-            println!("The element in the index: {index} is {ele}");
-            ele.clone()
-        },
-        /*
-         * Si intentamos acceder a un `index` que este fuera del rango de `vector`, el método `.get(index)`
-         * devolvera la variante `None` sin lanzar un `panic`
-         */
-        None => {
-            println!("There is no element here");
-            -1
-        }
-    }
+    result::get_at(&vec, index)
+        .inspect(|ele| println!("The element in the index: {index} is {ele}"))
 }
 
+#[allow(clippy::useless_vec)]
 fn multi_refs() {
     /*
      * ¿Por qué debería importarle a una referencia al primer elemento lo que cambia al final del
@@ -113,6 +113,8 @@ fn multi_refs() {
      */
     let first = &names[0];
     /*
+     * El módulo `ownership::memory` dibuja este diagrama a partir de una simulación, se puede ver
+     * con `cargo run -- memory`
      * 
      *                   STACK                                     HEAP
      *     first                       names
@@ -152,9 +154,29 @@ fn alter_content_vec(vec: &mut Vec<i32>) {
     println!("Vec: {:?}", vec);
 }
 
+#[allow(clippy::ptr_arg)]
 fn alter_elements_in_vec(vex: &mut Vec<i32>) {
     for x in vex.iter_mut() {
         *x *= 2;
         println!("Element: {x}");
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_ele_reports_out_of_bounds() {
+        let nums = vec![2, 3, 5];
+        assert!(matches!(get_ele(nums.clone(), 1), ExampleResult::Ok(3)));
+        assert!(matches!(
+            get_ele(nums, 10),
+            ExampleResult::Err(LessonError::IndexOutOfBounds { index: 10, len: 3 })
+        ));
+        assert!(matches!(
+            get_ele(Vec::new(), 0),
+            ExampleResult::Err(LessonError::IndexOutOfBounds { index: 0, len: 0 })
+        ));
+    }
+}
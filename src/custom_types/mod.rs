@@ -0,0 +1,2 @@
+pub mod optional;
+pub mod result;
@@ -3,16 +3,452 @@
  * sin embrago, `Rust` si provee el concepto de un valor presente y de uno ausente con la `enum`
  * `Option<T>`
  */
-enum ExampleOption<T> {
+use super::result::{self, ExampleResult, LessonError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ExampleOption<T> {
     Some(T),
     /*
      * La variante `None` representa el concepto de `null` un null es un valor que actualmente no
      * es válido o está ausente por alguna razón.
      */
+    #[default]
     None
 }
 
-fn main() {
+/*
+ * Los métodos son los mismos de `Option<T>` y se construyen solo con `match`. La mayoría toma
+ * `self` por valor, para no mover el contenido se usa primero `as_ref()` o `as_mut()`
+ *
+ *  ExampleOption<T>  --as_ref()-->  ExampleOption<&T>  --map(f)-->  ExampleOption<U>
+ */
+impl<T> ExampleOption<T> {
+    pub const fn is_some(&self) -> bool {
+        matches!(self, ExampleOption::Some(_))
+    }
+
+    pub const fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+
+    pub fn is_some_and(self, f: impl FnOnce(T) -> bool) -> bool {
+        match self {
+            ExampleOption::Some(value) => f(value),
+            ExampleOption::None => false,
+        }
+    }
+
+    /*
+     * `&ExampleOption<T>` a `ExampleOption<&T>`, el valor se queda en su lugar
+     */
+    pub const fn as_ref(&self) -> ExampleOption<&T> {
+        match self {
+            ExampleOption::Some(value) => ExampleOption::Some(value),
+            ExampleOption::None => ExampleOption::None,
+        }
+    }
+
+    pub fn as_mut(&mut self) -> ExampleOption<&mut T> {
+        match self {
+            ExampleOption::Some(value) => ExampleOption::Some(value),
+            ExampleOption::None => ExampleOption::None,
+        }
+    }
+
+    pub fn expect(self, message: &str) -> T {
+        match self {
+            ExampleOption::Some(value) => value,
+            ExampleOption::None => panic!("{message}"),
+        }
+    }
+
+    pub fn unwrap(self) -> T {
+        match self {
+            ExampleOption::Some(value) => value,
+            ExampleOption::None => panic!("called `ExampleOption::unwrap()` on a `None` value"),
+        }
+    }
+
+    pub fn unwrap_or(self, default: T) -> T {
+        match self {
+            ExampleOption::Some(value) => value,
+            ExampleOption::None => default,
+        }
+    }
+
+    /*
+     * A diferencia de `unwrap_or`, el valor por defecto solo se calcula cuando hace falta
+     */
+    pub fn unwrap_or_else(self, f: impl FnOnce() -> T) -> T {
+        match self {
+            ExampleOption::Some(value) => value,
+            ExampleOption::None => f(),
+        }
+    }
+
+    pub fn unwrap_or_default(self) -> T
+    where
+        T: Default,
+    {
+        self.unwrap_or_else(T::default)
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ExampleOption<U> {
+        match self {
+            ExampleOption::Some(value) => ExampleOption::Some(f(value)),
+            ExampleOption::None => ExampleOption::None,
+        }
+    }
+
+    pub fn map_or<U>(self, default: U, f: impl FnOnce(T) -> U) -> U {
+        match self {
+            ExampleOption::Some(value) => f(value),
+            ExampleOption::None => default,
+        }
+    }
+
+    pub fn map_or_else<U>(self, default: impl FnOnce() -> U, f: impl FnOnce(T) -> U) -> U {
+        match self {
+            ExampleOption::Some(value) => f(value),
+            ExampleOption::None => default(),
+        }
+    }
+
+    pub fn inspect(self, f: impl FnOnce(&T)) -> Self {
+        if let ExampleOption::Some(value) = &self {
+            f(value);
+        }
+        self
+    }
+
+    /*
+     * `Some(v)` a `Ok(v)` y `None` al error que se indique
+     */
+    pub fn ok_or<E>(self, error: E) -> Result<T, E> {
+        match self {
+            ExampleOption::Some(value) => Ok(value),
+            ExampleOption::None => Err(error),
+        }
+    }
+
+    pub fn ok_or_else<E>(self, error: impl FnOnce() -> E) -> Result<T, E> {
+        match self {
+            ExampleOption::Some(value) => Ok(value),
+            ExampleOption::None => Err(error()),
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.as_ref(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            inner: self.as_mut(),
+        }
+    }
+
+    pub fn and<U>(self, other: ExampleOption<U>) -> ExampleOption<U> {
+        match self {
+            ExampleOption::Some(_) => other,
+            ExampleOption::None => ExampleOption::None,
+        }
+    }
+
+    /*
+     * Encadena operaciones que también pueden no tener valor, el primer `None` detiene la cadena
+     *
+     *  Some(4) -> and_then(sqrt) -> Some(2) -> and_then(half) -> Some(1)
+     *  Some(3) -> and_then(sqrt) -> None    -> and_then(half) -> None
+     */
+    pub fn and_then<U>(self, f: impl FnOnce(T) -> ExampleOption<U>) -> ExampleOption<U> {
+        match self {
+            ExampleOption::Some(value) => f(value),
+            ExampleOption::None => ExampleOption::None,
+        }
+    }
+
+    pub fn filter(self, predicate: impl FnOnce(&T) -> bool) -> Self {
+        match self {
+            ExampleOption::Some(value) if predicate(&value) => ExampleOption::Some(value),
+            _ => ExampleOption::None,
+        }
+    }
+
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            ExampleOption::Some(value) => ExampleOption::Some(value),
+            ExampleOption::None => other,
+        }
+    }
+
+    pub fn or_else(self, f: impl FnOnce() -> Self) -> Self {
+        match self {
+            ExampleOption::Some(value) => ExampleOption::Some(value),
+            ExampleOption::None => f(),
+        }
+    }
+
+    /*
+     * `Some` solo si exactamente uno de los dos tiene valor
+     */
+    pub fn xor(self, other: Self) -> Self {
+        match (self, other) {
+            (ExampleOption::Some(value), ExampleOption::None)
+            | (ExampleOption::None, ExampleOption::Some(value)) => ExampleOption::Some(value),
+            _ => ExampleOption::None,
+        }
+    }
+
+    pub fn insert(&mut self, value: T) -> &mut T {
+        *self = ExampleOption::Some(value);
+        match self {
+            ExampleOption::Some(value) => value,
+            ExampleOption::None => unreachable!(),
+        }
+    }
+
+    pub fn get_or_insert(&mut self, value: T) -> &mut T {
+        self.get_or_insert_with(|| value)
+    }
+
+    pub fn get_or_insert_with(&mut self, f: impl FnOnce() -> T) -> &mut T {
+        if self.is_none() {
+            *self = ExampleOption::Some(f());
+        }
+        match self {
+            ExampleOption::Some(value) => value,
+            ExampleOption::None => unreachable!(),
+        }
+    }
+
+    /*
+     * Saca el valor y deja `None` en su lugar, sirve para mover un valor que está detrás de `&mut`
+     *
+     *  slot: Some(5)  --take()-->  slot: None, devuelve Some(5)
+     */
+    pub fn take(&mut self) -> Self {
+        std::mem::replace(self, ExampleOption::None)
+    }
+
+    pub fn take_if(&mut self, predicate: impl FnOnce(&mut T) -> bool) -> Self {
+        if self.as_mut().map_or(false, predicate) {
+            self.take()
+        } else {
+            ExampleOption::None
+        }
+    }
+
+    /*
+     * Pone el nuevo valor y devuelve el anterior
+     */
+    pub fn replace(&mut self, value: T) -> Self {
+        std::mem::replace(self, ExampleOption::Some(value))
+    }
+
+    pub fn zip<U>(self, other: ExampleOption<U>) -> ExampleOption<(T, U)> {
+        match (self, other) {
+            (ExampleOption::Some(a), ExampleOption::Some(b)) => ExampleOption::Some((a, b)),
+            _ => ExampleOption::None,
+        }
+    }
+
+    pub fn zip_with<U, R>(
+        self,
+        other: ExampleOption<U>,
+        f: impl FnOnce(T, U) -> R,
+    ) -> ExampleOption<R> {
+        self.zip(other).map(|(a, b)| f(a, b))
+    }
+
+    pub fn into_option(self) -> Option<T> {
+        self.into()
+    }
+}
+
+impl<T, U> ExampleOption<(T, U)> {
+    pub fn unzip(self) -> (ExampleOption<T>, ExampleOption<U>) {
+        match self {
+            ExampleOption::Some((a, b)) => (ExampleOption::Some(a), ExampleOption::Some(b)),
+            ExampleOption::None => (ExampleOption::None, ExampleOption::None),
+        }
+    }
+}
+
+impl<T> ExampleOption<ExampleOption<T>> {
+    pub fn flatten(self) -> ExampleOption<T> {
+        self.and_then(|inner| inner)
+    }
+}
+
+impl<T, E> ExampleOption<Result<T, E>> {
+    /*
+     * `Some(Ok(v))` a `Ok(Some(v))`, permite usar `?` con un valor opcional que puede fallar
+     */
+    pub fn transpose(self) -> Result<ExampleOption<T>, E> {
+        match self {
+            ExampleOption::Some(Ok(value)) => Ok(ExampleOption::Some(value)),
+            ExampleOption::Some(Err(error)) => Err(error),
+            ExampleOption::None => Ok(ExampleOption::None),
+        }
+    }
+}
+
+impl<T: Copy> ExampleOption<&T> {
+    pub fn copied(self) -> ExampleOption<T> {
+        self.map(|value| *value)
+    }
+}
+
+impl<T: Clone> ExampleOption<&T> {
+    pub fn cloned(self) -> ExampleOption<T> {
+        self.map(T::clone)
+    }
+}
+
+/*
+ * La conversión con `Option<T>` no pierde información, las dos `enums` tienen la misma forma
+ */
+impl<T> From<Option<T>> for ExampleOption<T> {
+    fn from(option: Option<T>) -> Self {
+        match option {
+            Some(value) => ExampleOption::Some(value),
+            None => ExampleOption::None,
+        }
+    }
+}
+
+impl<T> From<ExampleOption<T>> for Option<T> {
+    fn from(option: ExampleOption<T>) -> Self {
+        match option {
+            ExampleOption::Some(value) => Some(value),
+            ExampleOption::None => None,
+        }
+    }
+}
+
+/*
+ * Un `ExampleOption` es un iterador de cero o un elemento, así `for` y `flatten` lo pueden recorrer
+ */
+pub struct Iter<'a, T> {
+    inner: ExampleOption<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.take().into()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from(self.inner.is_some());
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+pub struct IterMut<'a, T> {
+    inner: ExampleOption<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.inner.take().into()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from(self.inner.is_some());
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next()
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+pub struct IntoIter<T> {
+    inner: ExampleOption<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.inner.take().into()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from(self.inner.is_some());
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.next()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for ExampleOption<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { inner: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ExampleOption<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ExampleOption<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/*
+ * Reúne un iterador de `ExampleOption<T>` en una colección, el primer `None` hace que todo sea
+ * `None`
+ *
+ *  [Some(1), Some(2), Some(3)]  ->  Some([1, 2, 3])
+ *  [Some(1), None,    Some(3)]  ->  None
+ */
+impl<T, C: FromIterator<T>> FromIterator<ExampleOption<T>> for ExampleOption<C> {
+    fn from_iter<I: IntoIterator<Item = ExampleOption<T>>>(iter: I) -> Self {
+        let collected: Option<C> = iter.into_iter().map(Option::from).collect();
+        collected.into()
+    }
+}
+
+pub fn run() {
     /*
      * La `enum` `Option<T>` sigue siendo una enumeración normal, su variante con parte genérica
      * `<T>` significa que la variante `Some` del puede contener un dato de cualquier tipo
@@ -36,18 +472,207 @@ fn main() {
     let absent_number: Option<i32> = None;
     println!("None value: {:?}", absent_number);
     let value = get_some(10);
-    println!("Value: {:?}", value);
+    println!("Value: {:?}", value.ok());
+    match get_some(5) {
+        ExampleResult::Ok(value) => println!("Value at len: {value}"),
+        ExampleResult::Err(error) => println!("Value at len: {error}"),
+    }
+    /*
+     * `ExampleOption<T>` se comporta igual que `Option<T>`, cada método es solo un `match` sobre sus
+     * dos variantes, y se puede convertir en los dos sentidos con `into()`
+     */
+    let example: ExampleOption<u32> = get_some(2).ok();
+    let doubled = example.map(|n| n * 2).filter(|n| *n > 4);
+    println!("ExampleOption: {:?} -> {:?}", example, doubled);
+    let back: Option<u32> = doubled.into();
+    println!("Back to Option: {:?}", back);
 }
 
 /*
  * La `enum` `Option<T>` elimina el riesgo de asumir incorrectamente que un valor no-nulo si sea
- * nulo
+ * nulo. Cuando además importa por qué no hay valor, `ExampleResult<T, E>` lleva el error
  */
-fn get_some(index: usize) -> Option<u32> {
+pub fn get_some(index: usize) -> ExampleResult<u32, LessonError> {
     let numbers: [u32; 5] = [1, 2, 3, 4, 5];
-    if index > numbers.len() {
-        None
-    } else {
-        Some(numbers[index])
+    /*
+     * Los índices válidos son `0..len`, con `index == len` el acceso `numbers[index]` causaría un
+     * `panic`
+     */
+    result::get_at(&numbers, index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+     * Cada prueba hace lo mismo con `Option<T>` y con `ExampleOption<T>`, y compara los resultados
+     * después de convertir con `From`
+     */
+    const SAMPLES: [Option<i32>; 4] = [Some(4), Some(-3), Some(0), None];
+
+    fn std_of<T>(example: ExampleOption<T>) -> Option<T> {
+        example.into()
+    }
+
+    #[test]
+    fn from_conversions_round_trip() {
+        for sample in SAMPLES {
+            let example = ExampleOption::from(sample);
+            assert_eq!(example.is_some(), sample.is_some());
+            assert_eq!(example.is_none(), sample.is_none());
+            assert_eq!(std_of(example), sample);
+            assert_eq!(example.into_option(), sample);
+        }
+        assert_eq!(ExampleOption::<u8>::default(), ExampleOption::None);
+    }
+
+    #[test]
+    fn map_and_then_filter_match_std() {
+        let half = |n: i32| if n % 2 == 0 { Some(n / 2) } else { None };
+        for sample in SAMPLES {
+            let example = ExampleOption::from(sample);
+            assert_eq!(std_of(example.map(|n| n * 10)), sample.map(|n| n * 10));
+            assert_eq!(example.map_or(-1, |n| n + 1), sample.map_or(-1, |n| n + 1));
+            assert_eq!(
+                example.map_or_else(|| 7, |n| n - 1),
+                sample.map_or_else(|| 7, |n| n - 1)
+            );
+            assert_eq!(
+                std_of(example.and_then(|n| half(n).into())),
+                sample.and_then(half)
+            );
+            assert_eq!(std_of(example.filter(|n| *n > 0)), sample.filter(|n| *n > 0));
+            assert_eq!(example.is_some_and(|n| n < 0), sample.is_some_and(|n| n < 0));
+            assert_eq!(example.unwrap_or(9), sample.unwrap_or(9));
+            assert_eq!(example.unwrap_or_default(), sample.unwrap_or_default());
+        }
+    }
+
+    #[test]
+    fn or_and_xor_match_std() {
+        for a in SAMPLES {
+            for b in SAMPLES {
+                let (x, y) = (ExampleOption::from(a), ExampleOption::from(b));
+                assert_eq!(std_of(x.or(y)), a.or(b), "{a:?} or {b:?}");
+                let mut calls = [0, 0];
+                let example = x.or_else(|| {
+                    calls[0] += 1;
+                    y
+                });
+                let std = a.or_else(|| {
+                    calls[1] += 1;
+                    b
+                });
+                assert_eq!((std_of(example), calls[0]), (std, calls[1]));
+                assert_eq!(std_of(x.and(y)), a.and(b));
+                assert_eq!(std_of(x.xor(y)), a.xor(b));
+                assert_eq!(std_of(x.zip(y)), a.zip(b));
+                assert_eq!(std_of(x.zip_with(y, |p, q| p * q)), a.zip(b).map(|(p, q)| p * q));
+            }
+        }
+    }
+
+    #[test]
+    fn take_replace_and_insert_match_std() {
+        for sample in SAMPLES {
+            let (mut std, mut example) = (sample, ExampleOption::from(sample));
+            assert_eq!(std_of(example.take()), std.take());
+            assert_eq!(std_of(example), std);
+
+            let (mut std, mut example) = (sample, ExampleOption::from(sample));
+            assert_eq!(std_of(example.replace(8)), std.replace(8));
+            assert_eq!(std_of(example), std);
+
+            let (mut std, mut example) = (sample, ExampleOption::from(sample));
+            assert_eq!(*example.get_or_insert(5), *std.get_or_insert(5));
+            *example.insert(6) += 1;
+            *std.insert(6) += 1;
+            assert_eq!(std_of(example), std);
+
+            let (mut std, mut example) = (sample, ExampleOption::from(sample));
+            assert_eq!(std_of(example.take_if(|n| *n < 0)), std.take_if(|n| *n < 0));
+            assert_eq!(std_of(example), std);
+        }
+    }
+
+    #[test]
+    fn ok_or_matches_std() {
+        for sample in SAMPLES {
+            let example = ExampleOption::from(sample);
+            assert_eq!(example.ok_or("missing"), sample.ok_or("missing"));
+            let mut calls = [0, 0];
+            let example = example.ok_or_else(|| {
+                calls[0] += 1;
+                0u8
+            });
+            let std = sample.ok_or_else(|| {
+                calls[1] += 1;
+                0u8
+            });
+            assert_eq!((example, calls[0]), (std, calls[1]));
+        }
+    }
+
+    #[test]
+    fn iterators_match_std() {
+        for sample in SAMPLES {
+            let mut example = ExampleOption::from(sample);
+            let mut std = sample;
+            assert!(example.iter().eq(sample.iter()));
+            assert_eq!(example.iter().len(), sample.iter().len());
+            assert!(example.iter().rev().eq(sample.iter().rev()));
+            example.iter_mut().for_each(|n| *n *= 3);
+            std.iter_mut().for_each(|n| *n *= 3);
+            assert_eq!(std_of(example), std);
+            assert!((&example).into_iter().eq(&std));
+            let collected: Vec<_> = example.into_iter().collect();
+            assert_eq!(collected, std.into_iter().collect::<Vec<_>>());
+        }
+        let mut numbers = vec![1, 2];
+        for n in ExampleOption::Some(3) {
+            numbers.push(n);
+        }
+        assert_eq!(numbers, [1, 2, 3]);
+    }
+
+    #[test]
+    fn collect_matches_std() {
+        let all = [Some(1), Some(2), Some(3)];
+        let gap = [Some(1), None, Some(3)];
+        for items in [&all[..], &gap[..], &[]] {
+            let std: Option<Vec<i32>> = items.iter().copied().collect();
+            let example: ExampleOption<Vec<i32>> =
+                items.iter().copied().map(ExampleOption::from).collect();
+            assert_eq!(std_of(example), std);
+        }
+    }
+
+    #[test]
+    fn nested_and_borrowed_match_std() {
+        let value = 5;
+        let nested: Option<Option<i32>> = Some(Some(value));
+        let example = ExampleOption::Some(ExampleOption::Some(value));
+        assert_eq!(std_of(example.flatten()), nested.flatten());
+        assert_eq!(std_of(ExampleOption::Some(&value).copied()), Some(&value).copied());
+        let text = String::from("hola");
+        assert_eq!(std_of(ExampleOption::Some(&text).cloned()), Some(&text).cloned());
+        let (left, right) = ExampleOption::Some((1, 'a')).unzip();
+        assert_eq!((std_of(left), std_of(right)), Some((1, 'a')).unzip());
+        let results: [Option<Result<i32, &str>>; 3] = [Some(Ok(1)), Some(Err("no")), None];
+        for sample in results {
+            let example = ExampleOption::from(sample);
+            assert_eq!(example.transpose().map(std_of), sample.transpose());
+        }
+        let mut owned = ExampleOption::Some(String::from("a"));
+        owned.as_mut().map(|text| text.push('b'));
+        assert_eq!(owned.as_ref().map(|text| text.len()), ExampleOption::Some(2));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn get_some_stays_in_bounds() {
+        assert!(matches!(get_some(4), ExampleResult::Ok(5)));
+        assert!(matches!(get_some(5), ExampleResult::Err(_)));
+        assert!(get_some(10).ok().is_none());
+    }
+}
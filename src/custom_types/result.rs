@@ -0,0 +1,551 @@
+/*
+ * `Option<T>` representa un valor que puede no existir, `Result<T, E>` representa una operación que
+ * puede fallar y en ese caso dice por qué. Igual que `ExampleOption<T>`, es una `enum` normal
+ *
+ *  ExampleResult<T, E>
+ *  ├── Ok(T)     la operación terminó y este es su valor
+ *  └── Err(E)    la operación falló y este es el error
+ *
+ * En lugar de devolver un valor mágico como `-1` cuando algo sale mal, la firma de la función
+ * obliga a quien la llama a revisar los dos casos
+ */
+use std::error::Error;
+use std::fmt::{self, Write};
+
+use super::optional::{self, ExampleOption};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExampleResult<T, E> {
+    Ok(T),
+    Err(E),
+}
+
+impl<T, E> ExampleResult<T, E> {
+    pub const fn is_ok(&self) -> bool {
+        matches!(self, ExampleResult::Ok(_))
+    }
+
+    pub const fn is_err(&self) -> bool {
+        !self.is_ok()
+    }
+
+    pub fn is_ok_and(self, f: impl FnOnce(T) -> bool) -> bool {
+        match self {
+            ExampleResult::Ok(value) => f(value),
+            ExampleResult::Err(_) => false,
+        }
+    }
+
+    pub fn is_err_and(self, f: impl FnOnce(E) -> bool) -> bool {
+        match self {
+            ExampleResult::Ok(_) => false,
+            ExampleResult::Err(error) => f(error),
+        }
+    }
+
+    /*
+     * Descarta el error y se queda con el valor, o al revés con `err()`
+     */
+    pub fn ok(self) -> ExampleOption<T> {
+        match self {
+            ExampleResult::Ok(value) => ExampleOption::Some(value),
+            ExampleResult::Err(_) => ExampleOption::None,
+        }
+    }
+
+    pub fn err(self) -> ExampleOption<E> {
+        match self {
+            ExampleResult::Ok(_) => ExampleOption::None,
+            ExampleResult::Err(error) => ExampleOption::Some(error),
+        }
+    }
+
+    pub const fn as_ref(&self) -> ExampleResult<&T, &E> {
+        match self {
+            ExampleResult::Ok(value) => ExampleResult::Ok(value),
+            ExampleResult::Err(error) => ExampleResult::Err(error),
+        }
+    }
+
+    pub fn as_mut(&mut self) -> ExampleResult<&mut T, &mut E> {
+        match self {
+            ExampleResult::Ok(value) => ExampleResult::Ok(value),
+            ExampleResult::Err(error) => ExampleResult::Err(error),
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ExampleResult<U, E> {
+        match self {
+            ExampleResult::Ok(value) => ExampleResult::Ok(f(value)),
+            ExampleResult::Err(error) => ExampleResult::Err(error),
+        }
+    }
+
+    pub fn map_or<U>(self, default: U, f: impl FnOnce(T) -> U) -> U {
+        match self {
+            ExampleResult::Ok(value) => f(value),
+            ExampleResult::Err(_) => default,
+        }
+    }
+
+    pub fn map_or_else<U>(self, default: impl FnOnce(E) -> U, f: impl FnOnce(T) -> U) -> U {
+        match self {
+            ExampleResult::Ok(value) => f(value),
+            ExampleResult::Err(error) => default(error),
+        }
+    }
+
+    /*
+     * Transforma solo el error, sirve para pasar de un tipo de error a otro más general
+     */
+    pub fn map_err<F>(self, f: impl FnOnce(E) -> F) -> ExampleResult<T, F> {
+        match self {
+            ExampleResult::Ok(value) => ExampleResult::Ok(value),
+            ExampleResult::Err(error) => ExampleResult::Err(f(error)),
+        }
+    }
+
+    pub fn inspect(self, f: impl FnOnce(&T)) -> Self {
+        if let ExampleResult::Ok(value) = &self {
+            f(value);
+        }
+        self
+    }
+
+    pub fn inspect_err(self, f: impl FnOnce(&E)) -> Self {
+        if let ExampleResult::Err(error) = &self {
+            f(error);
+        }
+        self
+    }
+
+    pub fn and<U>(self, other: ExampleResult<U, E>) -> ExampleResult<U, E> {
+        match self {
+            ExampleResult::Ok(_) => other,
+            ExampleResult::Err(error) => ExampleResult::Err(error),
+        }
+    }
+
+    /*
+     * Encadena operaciones que pueden fallar, el primer `Err` detiene la cadena y llega intacto
+     * hasta el final
+     */
+    pub fn and_then<U>(self, f: impl FnOnce(T) -> ExampleResult<U, E>) -> ExampleResult<U, E> {
+        match self {
+            ExampleResult::Ok(value) => f(value),
+            ExampleResult::Err(error) => ExampleResult::Err(error),
+        }
+    }
+
+    pub fn or<F>(self, other: ExampleResult<T, F>) -> ExampleResult<T, F> {
+        match self {
+            ExampleResult::Ok(value) => ExampleResult::Ok(value),
+            ExampleResult::Err(_) => other,
+        }
+    }
+
+    pub fn or_else<F>(self, f: impl FnOnce(E) -> ExampleResult<T, F>) -> ExampleResult<T, F> {
+        match self {
+            ExampleResult::Ok(value) => ExampleResult::Ok(value),
+            ExampleResult::Err(error) => f(error),
+        }
+    }
+
+    pub fn unwrap_or(self, default: T) -> T {
+        match self {
+            ExampleResult::Ok(value) => value,
+            ExampleResult::Err(_) => default,
+        }
+    }
+
+    pub fn unwrap_or_else(self, f: impl FnOnce(E) -> T) -> T {
+        match self {
+            ExampleResult::Ok(value) => value,
+            ExampleResult::Err(error) => f(error),
+        }
+    }
+
+    pub fn unwrap_or_default(self) -> T
+    where
+        T: Default,
+    {
+        self.unwrap_or_else(|_| T::default())
+    }
+
+    pub fn expect(self, message: &str) -> T
+    where
+        E: fmt::Debug,
+    {
+        match self {
+            ExampleResult::Ok(value) => value,
+            ExampleResult::Err(error) => panic!("{message}: {error:?}"),
+        }
+    }
+
+    pub fn unwrap(self) -> T
+    where
+        E: fmt::Debug,
+    {
+        match self {
+            ExampleResult::Ok(value) => value,
+            ExampleResult::Err(error) => {
+                panic!("called `ExampleResult::unwrap()` on an `Err` value: {error:?}")
+            }
+        }
+    }
+
+    pub fn expect_err(self, message: &str) -> E
+    where
+        T: fmt::Debug,
+    {
+        match self {
+            ExampleResult::Ok(value) => panic!("{message}: {value:?}"),
+            ExampleResult::Err(error) => error,
+        }
+    }
+
+    pub fn unwrap_err(self) -> E
+    where
+        T: fmt::Debug,
+    {
+        match self {
+            ExampleResult::Ok(value) => {
+                panic!("called `ExampleResult::unwrap_err()` on an `Ok` value: {value:?}")
+            }
+            ExampleResult::Err(error) => error,
+        }
+    }
+
+    pub fn iter(&self) -> optional::IntoIter<&T> {
+        self.as_ref().ok().into_iter()
+    }
+
+    /*
+     * El operador `?` solo funciona con los tipos que implementan el `trait` `Try`, que todavía es
+     * inestable. `into_result()?` convierte primero al `Result` de la biblioteca estándar
+     *
+     *  fn first(list: &[i32]) -> Result<i32, LessonError> {
+     *      let value = get_at(list, 0).into_result()?;
+     *      Ok(value)
+     *  }
+     */
+    pub fn into_result(self) -> Result<T, E> {
+        self.into()
+    }
+}
+
+impl<T, E: Error + Send + Sync + 'static> ExampleResult<T, E> {
+    /*
+     * Envuelve el error con una descripción de lo que se estaba haciendo, el error original se
+     * conserva como `source()`
+     *
+     *  reading the third word            <- context
+     *  └── word 10 not found, ...        <- source
+     */
+    pub fn context(self, context: impl Into<String>) -> ExampleResult<T, LessonError> {
+        self.map_err(|error| LessonError::Context {
+            context: context.into(),
+            source: Box::new(error),
+        })
+    }
+
+    /*
+     * Igual que `context`, pero el texto solo se construye si hay un error
+     */
+    pub fn with_context<S: Into<String>>(
+        self,
+        f: impl FnOnce() -> S,
+    ) -> ExampleResult<T, LessonError> {
+        self.map_err(|error| LessonError::Context {
+            context: f().into(),
+            source: Box::new(error),
+        })
+    }
+}
+
+impl<T, E> ExampleResult<ExampleOption<T>, E> {
+    pub fn transpose(self) -> ExampleOption<ExampleResult<T, E>> {
+        match self {
+            ExampleResult::Ok(ExampleOption::Some(value)) => {
+                ExampleOption::Some(ExampleResult::Ok(value))
+            }
+            ExampleResult::Ok(ExampleOption::None) => ExampleOption::None,
+            ExampleResult::Err(error) => ExampleOption::Some(ExampleResult::Err(error)),
+        }
+    }
+}
+
+impl<T, E> ExampleResult<ExampleResult<T, E>, E> {
+    pub fn flatten(self) -> ExampleResult<T, E> {
+        self.and_then(|inner| inner)
+    }
+}
+
+impl<T, E> From<Result<T, E>> for ExampleResult<T, E> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(value) => ExampleResult::Ok(value),
+            Err(error) => ExampleResult::Err(error),
+        }
+    }
+}
+
+impl<T, E> From<ExampleResult<T, E>> for Result<T, E> {
+    fn from(result: ExampleResult<T, E>) -> Self {
+        match result {
+            ExampleResult::Ok(value) => Ok(value),
+            ExampleResult::Err(error) => Err(error),
+        }
+    }
+}
+
+impl<T, E> IntoIterator for ExampleResult<T, E> {
+    type Item = T;
+    type IntoIter = optional::IntoIter<T>;
+
+    fn into_iter(self) -> optional::IntoIter<T> {
+        self.ok().into_iter()
+    }
+}
+
+/*
+ * Reúne un iterador de `ExampleResult<T, E>` en una colección, el primer `Err` detiene todo
+ *
+ *  [Ok(1), Ok(2), Ok(3)]    ->  Ok([1, 2, 3])
+ *  [Ok(1), Err(e), Err(f)]  ->  Err(e)
+ */
+impl<T, E, C: FromIterator<T>> FromIterator<ExampleResult<T, E>> for ExampleResult<C, E> {
+    fn from_iter<I: IntoIterator<Item = ExampleResult<T, E>>>(iter: I) -> Self {
+        let collected: Result<C, E> = iter.into_iter().map(Result::from).collect();
+        collected.into()
+    }
+}
+
+/*
+ * Los errores de las lecciones, cada variante guarda los datos necesarios para explicar qué pasó
+ */
+#[derive(Debug)]
+pub enum LessonError {
+    IndexOutOfBounds {
+        index: usize,
+        len: usize,
+    },
+    WordNotFound {
+        index: usize,
+        words: usize,
+    },
+    /*
+     * Un error con una descripción de lo que se estaba haciendo, `source` es el error original
+     */
+    Context {
+        context: String,
+        source: Box<dyn Error + Send + Sync>,
+    },
+}
+
+impl fmt::Display for LessonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LessonError::IndexOutOfBounds { index, len } => {
+                write!(f, "index {index} is out of bounds, the length is {len}")
+            }
+            LessonError::WordNotFound { index, words: 0 } => {
+                write!(f, "word {index} not found, the text has no words")
+            }
+            LessonError::WordNotFound { index, words } => write!(
+                f,
+                "word {index} not found, the text has {words} words (0..{words})"
+            ),
+            LessonError::Context { context, .. } => write!(f, "{context}"),
+        }
+    }
+}
+
+impl Error for LessonError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LessonError::Context { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl LessonError {
+    /*
+     * El error y todas sus causas, desde el más general hasta el original
+     */
+    pub fn chain(&self) -> Chain<'_> {
+        Chain {
+            next: Some(self),
+        }
+    }
+
+    /*
+     * El error original, el último de la cadena
+     */
+    pub fn root_cause(&self) -> &(dyn Error + 'static) {
+        self.chain().last().unwrap_or(self)
+    }
+
+    /*
+     * El mensaje completo, cada causa en su propia línea
+     *
+     *  error: reading the third word
+     *
+     *  Caused by:
+     *      0: looking up word 10
+     *      1: word 10 not found, the text has 5 words (0..5)
+     */
+    pub fn report(&self) -> String {
+        let mut out = format!("error: {self}");
+        let causes: Vec<_> = self.chain().skip(1).collect();
+        if !causes.is_empty() {
+            out.push_str("\n\nCaused by:");
+            for (index, cause) in causes.iter().enumerate() {
+                let _ = write!(out, "\n    {index}: {cause}");
+            }
+        }
+        out
+    }
+}
+
+pub struct Chain<'a> {
+    next: Option<&'a (dyn Error + 'static)>,
+}
+
+impl<'a> Iterator for Chain<'a> {
+    type Item = &'a (dyn Error + 'static);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = current.source();
+        Some(current)
+    }
+}
+
+/*
+ * Devuelve el elemento en `index` o un error que dice cuántos elementos hay
+ */
+pub fn get_at<T: Copy>(items: &[T], index: usize) -> ExampleResult<T, LessonError> {
+    match items.get(index) {
+        Some(item) => ExampleResult::Ok(*item),
+        None => ExampleResult::Err(LessonError::IndexOutOfBounds {
+            index,
+            len: items.len(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::num::ParseIntError;
+
+    fn parse(text: &str) -> ExampleResult<i32, ParseIntError> {
+        text.parse::<i32>().into()
+    }
+
+    /*
+     * Tres niveles, dos `context` sobre un `ParseIntError`
+     */
+    fn nested() -> LessonError {
+        parse("diez")
+            .context("reading the limit")
+            .context("loading basics.conf")
+            .unwrap_err()
+    }
+
+    #[test]
+    fn context_wraps_the_error() {
+        assert!(matches!(parse("7").context("unused"), ExampleResult::Ok(7)));
+        let error = parse("x").context("reading the limit").unwrap_err();
+        assert_eq!(error.to_string(), "reading the limit");
+        let source = error.source().expect("the original error");
+        assert!(source.downcast_ref::<ParseIntError>().is_some());
+    }
+
+    #[test]
+    fn with_context_is_lazy() {
+        let calls = Cell::new(0);
+        let context = || {
+            calls.set(calls.get() + 1);
+            "reading the limit"
+        };
+        assert!(parse("7").with_context(context).is_ok());
+        assert_eq!(calls.get(), 0);
+        let error = parse("x").with_context(context).unwrap_err();
+        assert_eq!(calls.get(), 1);
+        assert_eq!(error.to_string(), "reading the limit");
+    }
+
+    #[test]
+    fn chain_and_root_cause() {
+        let error = nested();
+        let chain: Vec<String> = error.chain().map(ToString::to_string).collect();
+        assert_eq!(
+            chain,
+            ["loading basics.conf", "reading the limit", "invalid digit found in string"]
+        );
+        assert!(error.root_cause().downcast_ref::<ParseIntError>().is_some());
+        /*
+         * Un error sin causa es su propia causa raíz
+         */
+        let alone = LessonError::IndexOutOfBounds { index: 3, len: 2 };
+        assert_eq!(alone.chain().count(), 1);
+        assert_eq!(alone.root_cause().to_string(), alone.to_string());
+    }
+
+    #[test]
+    fn report_lists_every_cause() {
+        assert_eq!(
+            nested().report(),
+            "error: loading basics.conf\n\
+             \n\
+             Caused by:\n\
+             \x20   0: reading the limit\n\
+             \x20   1: invalid digit found in string"
+        );
+        let words = LessonError::WordNotFound { index: 4, words: 0 };
+        assert_eq!(words.report(), "error: word 4 not found, the text has no words");
+    }
+
+    #[test]
+    fn collect_stops_at_the_first_error() {
+        let all: ExampleResult<Vec<i32>, ParseIntError> =
+            ["1", "2", "3"].map(parse).into_iter().collect();
+        assert!(matches!(all, ExampleResult::Ok(ref values) if values == &[1, 2, 3]));
+        let seen = Cell::new(0);
+        let first: ExampleResult<Vec<i32>, LessonError> = [0, 5, 1, 9]
+            .iter()
+            .map(|index| {
+                seen.set(seen.get() + 1);
+                get_at(&[10, 20], *index)
+            })
+            .collect();
+        assert!(matches!(
+            first,
+            ExampleResult::Err(LessonError::IndexOutOfBounds { index: 5, len: 2 })
+        ));
+        assert_eq!(seen.get(), 2);
+        let empty: ExampleResult<String, ParseIntError> =
+            std::iter::empty::<ExampleResult<char, ParseIntError>>().collect();
+        assert!(matches!(empty, ExampleResult::Ok(ref text) if text.is_empty()));
+    }
+
+    #[test]
+    fn into_result_works_with_question_mark() {
+        fn sum(texts: &[&str]) -> Result<i32, ParseIntError> {
+            let mut total = 0;
+            for text in texts {
+                total += parse(text).into_result()?;
+            }
+            Ok(total)
+        }
+        assert_eq!(sum(&["1", "2"]), Ok(3));
+        assert!(sum(&["1", "dos"]).is_err());
+        assert_eq!(parse("4").into_result(), Ok(4));
+        let back: ExampleResult<i32, ParseIntError> = Ok(4).into();
+        assert!(matches!(back, ExampleResult::Ok(4)));
+    }
+}
@@ -1,8 +1,10 @@
+use crate::flow::rules::{self, Mode};
+
 /*
  * Los `Control flow` son estructuras que determinan que código se ejecutara dependiendo de si la
  * condición es `true` y correr código  repetidamente mientras (while) la condición sea `true`
  */
-fn main() {
+pub fn run() {
     /*
      * Una expresión `if` permite bifurcar el código dependiendo de la condición que evalua, 
      * "Si esta condicion se cumple ejecuta este código, si no se cumple ejecuta este otro código"
@@ -24,18 +26,26 @@ fn main() {
     }
     /*
      * Si el bloque `if` contiene más de un caso a evaluar `Rust` va a ejecutar el bloque para la
-     * primera condición donde se evalue como `true`
+     * primera condición donde se evalue como `true`, por eso el orden importa. El `0` también es
+     * par, si la condición de `% 2 == 0` fuera primero nunca se llegaría a `Is zero`
      */
-    if number_to % 2 == 0 && number_to < MAX_NUMBER {
-        println!("Is odd and less than {}", MAX_NUMBER);
-    } else if number_to % 1 == 0 && number_to < MAX_NUMBER {
-        println!("Is even and less than {}", MAX_NUMBER);
-    } else if number_to < MAX_NUMBER {
+    if number_to >= MAX_NUMBER {
+        println!("Number out of limit")
+    } else if number_to == 0 {
         println!("Is zero");
+    } else if number_to % 2 == 0 {
+        println!("Is even and less than {}", MAX_NUMBER);
     } else {
-        println!("Number out of limit")
+        println!("Is odd and less than {}", MAX_NUMBER);
     }
-    
+    /*
+     * La misma cadena escrita como reglas de `flow::rules`, la explicación muestra qué condiciones
+     * se evaluaron hasta encontrar la primera que se cumple
+     */
+    let chain = rules::limit_chain(MAX_NUMBER.into());
+    let outcome = chain.evaluate(&number_to.into(), Mode::FirstMatch);
+    print!("{}", outcome.explain());
+
     /*
      * Como `if` es una `expression` se puede asignar a un `statement` siempre y cuando la última
      * línea sea una `expresión implícita de retorno`
@@ -0,0 +1,441 @@
+/*
+ * El árbol de sintaxis separa lo que es `statement` de lo que es `expression`. Un `Stmt` no tiene
+ * valor, una `Expr` siempre tiene uno, aunque sea `()`. Un `Block` es una lista de `statements`
+ * seguida de una expresión opcional sin `;`, la `tail`, que es el valor del bloque
+ *
+ *  {                     Block
+ *      let a = 10;         Stmt::Let
+ *      a + 5;              Stmt::Expr, el `;` descarta el valor
+ *      a * 2               tail, el valor del bloque
+ *  }
+ */
+use std::fmt::{self, Write};
+
+use crate::ownership::borrowck::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Type {
+    Int,
+    Bool,
+    Unit,
+    /*
+     * El tipo de `break` y `continue`, nunca produce un valor y se acomoda a cualquier otro tipo
+     */
+    Never,
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Int => write!(f, "i64"),
+            Type::Bool => write!(f, "bool"),
+            Type::Unit => write!(f, "()"),
+            Type::Never => write!(f, "!"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Neg,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+}
+
+impl BinaryOp {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Rem => "%",
+            BinaryOp::Eq => "==",
+            BinaryOp::Ne => "!=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Le => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Ge => ">=",
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+        }
+    }
+
+    /*
+     * La fuerza con la que el operador atrae a sus operandos en el `Pratt parser`, el de más
+     * fuerza se agrupa primero
+     */
+    pub fn binding_power(self) -> u8 {
+        match self {
+            BinaryOp::Or => 2,
+            BinaryOp::And => 3,
+            BinaryOp::Eq
+            | BinaryOp::Ne
+            | BinaryOp::Lt
+            | BinaryOp::Le
+            | BinaryOp::Gt
+            | BinaryOp::Ge => 4,
+            BinaryOp::Add | BinaryOp::Sub => 5,
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => 6,
+        }
+    }
+
+    pub fn is_comparison(self) -> bool {
+        self.binding_power() == 4
+    }
+}
+
+pub const ASSIGN_POWER: u8 = 1;
+pub const PREFIX_POWER: u8 = 7;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprKind {
+    Int(i64),
+    Bool(bool),
+    Var(String),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    /*
+     * `x = value` o `x += value`, la asignación es una expresión de tipo `()`
+     */
+    Assign {
+        name: String,
+        name_span: Span,
+        op: Option<BinaryOp>,
+        value: Box<Expr>,
+    },
+    Block(Block),
+    If {
+        condition: Box<Expr>,
+        then: Block,
+        otherwise: Option<Box<Expr>>,
+    },
+    Loop(Block),
+    While {
+        condition: Box<Expr>,
+        body: Block,
+    },
+    Break(Option<Box<Expr>>),
+    Continue,
+}
+
+impl Expr {
+    /*
+     * Las expresiones que terminan en `}` pueden ir como `statement` sin `;`
+     */
+    pub fn is_block_like(&self) -> bool {
+        matches!(
+            self.kind,
+            ExprKind::Block(_) | ExprKind::If { .. } | ExprKind::Loop(_) | ExprKind::While { .. }
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stmt {
+    Let {
+        name: String,
+        name_span: Span,
+        mutable: bool,
+        ty: Option<(Type, Span)>,
+        value: Expr,
+    },
+    /*
+     * `semi` es `false` para un bloque como `if` o `loop` que se usa como `statement` sin `;`
+     */
+    Expr {
+        expr: Expr,
+        semi: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub stmts: Vec<Stmt>,
+    pub tail: Option<Box<Expr>>,
+    pub span: Span,
+}
+
+/*
+ * Escribe el programa con un formato uniforme, cuatro espacios por nivel y los paréntesis justos
+ * para conservar el orden de las operaciones
+ */
+pub fn pretty(block: &Block) -> String {
+    let mut out = String::new();
+    write_contents(&mut out, block, 0);
+    out
+}
+
+fn indent(out: &mut String, level: usize) {
+    out.push_str(&"    ".repeat(level));
+}
+
+fn write_contents(out: &mut String, block: &Block, level: usize) {
+    for stmt in &block.stmts {
+        indent(out, level);
+        match stmt {
+            Stmt::Let {
+                name,
+                mutable,
+                ty,
+                value,
+                ..
+            } => {
+                out.push_str("let ");
+                if *mutable {
+                    out.push_str("mut ");
+                }
+                out.push_str(name);
+                if let Some((ty, _)) = ty {
+                    let _ = write!(out, ": {ty}");
+                }
+                out.push_str(" = ");
+                write_expr(out, value, level, 0);
+                out.push(';');
+            }
+            Stmt::Expr { expr, semi } => {
+                write_expr(out, expr, level, 0);
+                if *semi {
+                    out.push(';');
+                }
+            }
+        }
+        out.push('\n');
+    }
+    if let Some(tail) = &block.tail {
+        indent(out, level);
+        write_expr(out, tail, level, 0);
+        out.push('\n');
+    }
+}
+
+fn write_block(out: &mut String, block: &Block, level: usize) {
+    if block.stmts.is_empty() && block.tail.is_none() {
+        out.push_str("{}");
+        return;
+    }
+    out.push_str("{\n");
+    write_contents(out, block, level + 1);
+    indent(out, level);
+    out.push('}');
+}
+
+/*
+ * `min` es la fuerza mínima que necesita la expresión para ir sin paréntesis en ese lugar
+ */
+fn write_expr(out: &mut String, expr: &Expr, level: usize, min: u8) {
+    let power = match &expr.kind {
+        ExprKind::Binary(op, ..) => op.binding_power(),
+        ExprKind::Assign { .. } | ExprKind::Break(_) => ASSIGN_POWER,
+        _ => u8::MAX,
+    };
+    if power < min {
+        out.push('(');
+        write_expr(out, expr, level, 0);
+        out.push(')');
+        return;
+    }
+    match &expr.kind {
+        ExprKind::Int(value) => {
+            let _ = write!(out, "{value}");
+        }
+        ExprKind::Bool(value) => {
+            let _ = write!(out, "{value}");
+        }
+        ExprKind::Var(name) => out.push_str(name),
+        ExprKind::Unary(op, operand) => {
+            out.push(if *op == UnaryOp::Neg { '-' } else { '!' });
+            write_expr(out, operand, level, PREFIX_POWER);
+        }
+        /*
+         * Un `if` o un bloque a la izquierda necesita paréntesis, al inicio de un `statement`
+         * terminaría en su `}`
+         */
+        ExprKind::Binary(op, left, right) if left.is_block_like() => {
+            out.push('(');
+            write_expr(out, left, level, 0);
+            let _ = write!(out, ") {} ", op.symbol());
+            write_expr(out, right, level, power + 1);
+        }
+        ExprKind::Binary(op, left, right) => {
+            write_expr(out, left, level, power);
+            let _ = write!(out, " {} ", op.symbol());
+            write_expr(out, right, level, power + 1);
+        }
+        ExprKind::Assign {
+            name, op, value, ..
+        } => {
+            let symbol = op.map_or(String::new(), |op| op.symbol().to_string());
+            let _ = write!(out, "{name} {symbol}= ");
+            write_expr(out, value, level, ASSIGN_POWER);
+        }
+        ExprKind::Block(block) => write_block(out, block, level),
+        ExprKind::If {
+            condition,
+            then,
+            otherwise,
+        } => {
+            out.push_str("if ");
+            write_expr(out, condition, level, 0);
+            out.push(' ');
+            write_block(out, then, level);
+            if let Some(otherwise) = otherwise {
+                out.push_str(" else ");
+                write_expr(out, otherwise, level, 0);
+            }
+        }
+        ExprKind::Loop(body) => {
+            out.push_str("loop ");
+            write_block(out, body, level);
+        }
+        ExprKind::While { condition, body } => {
+            out.push_str("while ");
+            write_expr(out, condition, level, 0);
+            out.push(' ');
+            write_block(out, body, level);
+        }
+        ExprKind::Break(value) => {
+            out.push_str("break");
+            if let Some(value) = value {
+                out.push(' ');
+                write_expr(out, value, level, ASSIGN_POWER);
+            }
+        }
+        ExprKind::Continue => out.push_str("continue"),
+    }
+}
+
+/*
+ * El árbol con cada nodo marcado como `statement` o `expression`
+ *
+ *  block
+ *  ├── let x (statement)
+ *  │   └── 5
+ *  └── + (tail expression)
+ */
+pub fn tree(block: &Block) -> String {
+    let mut out = String::from("block\n");
+    write_block_tree(&mut out, block, "");
+    out
+}
+
+fn write_block_tree(out: &mut String, block: &Block, prefix: &str) {
+    let count = block.stmts.len() + usize::from(block.tail.is_some());
+    for (index, stmt) in block.stmts.iter().enumerate() {
+        let last = index + 1 == count;
+        match stmt {
+            Stmt::Let {
+                name,
+                mutable,
+                value,
+                ..
+            } => {
+                let binding = if *mutable { "let mut" } else { "let" };
+                branch(out, prefix, last, &format!("{binding} {name} (statement)"));
+                let child = child_prefix(prefix, last);
+                write_expr_tree(out, value, &child, true, "");
+            }
+            Stmt::Expr { expr, semi } => {
+                let note = if *semi {
+                    "expression statement, value discarded by `;`"
+                } else {
+                    "expression statement"
+                };
+                write_expr_tree(out, expr, prefix, last, note);
+            }
+        }
+    }
+    if let Some(tail) = &block.tail {
+        write_expr_tree(
+            out,
+            tail,
+            prefix,
+            true,
+            "tail expression, value of the block",
+        );
+    }
+}
+
+fn branch(out: &mut String, prefix: &str, last: bool, text: &str) {
+    let connector = if last { "└── " } else { "├── " };
+    let _ = writeln!(out, "{prefix}{connector}{text}");
+}
+
+fn child_prefix(prefix: &str, last: bool) -> String {
+    format!("{prefix}{}", if last { "    " } else { "│   " })
+}
+
+fn write_expr_tree(out: &mut String, expr: &Expr, prefix: &str, last: bool, note: &str) {
+    let label = match &expr.kind {
+        ExprKind::Int(value) => value.to_string(),
+        ExprKind::Bool(value) => value.to_string(),
+        ExprKind::Var(name) => name.clone(),
+        ExprKind::Unary(UnaryOp::Neg, _) => "-".to_string(),
+        ExprKind::Unary(UnaryOp::Not, _) => "!".to_string(),
+        ExprKind::Binary(op, ..) => op.symbol().to_string(),
+        ExprKind::Assign { name, op, .. } => {
+            format!("{name} {}=", op.map_or("", |op| op.symbol()))
+        }
+        ExprKind::Block(_) => "block".to_string(),
+        ExprKind::If { .. } => "if".to_string(),
+        ExprKind::Loop(_) => "loop".to_string(),
+        ExprKind::While { .. } => "while".to_string(),
+        ExprKind::Break(_) => "break".to_string(),
+        ExprKind::Continue => "continue".to_string(),
+    };
+    match note {
+        "" => branch(out, prefix, last, &label),
+        note => branch(out, prefix, last, &format!("{label} ({note})")),
+    }
+    let child = child_prefix(prefix, last);
+    match &expr.kind {
+        ExprKind::Unary(_, operand) => write_expr_tree(out, operand, &child, true, ""),
+        ExprKind::Binary(_, left, right) => {
+            write_expr_tree(out, left, &child, false, "");
+            write_expr_tree(out, right, &child, true, "");
+        }
+        ExprKind::Assign { value, .. } => write_expr_tree(out, value, &child, true, ""),
+        ExprKind::Block(block) | ExprKind::Loop(block) => write_block_tree(out, block, &child),
+        ExprKind::If {
+            condition,
+            then,
+            otherwise,
+        } => {
+            write_expr_tree(out, condition, &child, false, "condition");
+            branch(out, &child, otherwise.is_none(), "then");
+            write_block_tree(out, then, &child_prefix(&child, otherwise.is_none()));
+            if let Some(otherwise) = otherwise {
+                write_expr_tree(out, otherwise, &child, true, "else");
+            }
+        }
+        ExprKind::While { condition, body } => {
+            write_expr_tree(out, condition, &child, false, "condition");
+            branch(out, &child, true, "body");
+            write_block_tree(out, body, &child_prefix(&child, true));
+        }
+        ExprKind::Break(Some(value)) => write_expr_tree(out, value, &child, true, ""),
+        _ => {}
+    }
+}
@@ -0,0 +1,306 @@
+/*
+ * El evaluador recorre el árbol que ya pasó la revisión de tipos. `break` y `continue` no son
+ * valores, salen de las expresiones como un `Flow` hasta llegar al `loop` que los recibe
+ *
+ *  let v = loop {         Flow::Break(Value::Int(20)) sube desde el `if` hasta el `loop`
+ *      counter += 1;
+ *      if counter == 10 {
+ *          break counter * 2;
+ *      }
+ *  };
+ *
+ * La aritmética es la de `i64` en modo `debug`, un desbordamiento es un `panic`
+ */
+use std::collections::HashMap;
+use std::fmt;
+
+use super::ast::{BinaryOp, Block, Expr, ExprKind, Stmt, UnaryOp};
+use crate::ownership::borrowck::diagnostic::line_col;
+use crate::ownership::borrowck::Span;
+
+/*
+ * Un programa que no termina se detiene después de este número de vueltas en total
+ */
+pub const MAX_ITERATIONS: usize = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Value {
+    Int(i64),
+    Bool(bool),
+    Unit,
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(value) => write!(f, "{value}"),
+            Value::Bool(value) => write!(f, "{value}"),
+            Value::Unit => write!(f, "()"),
+        }
+    }
+}
+
+/*
+ * El equivalente a un `panic` de `Rust`, con el lugar del programa que lo provocó
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Panic {
+    pub message: String,
+    pub span: Span,
+}
+
+impl Panic {
+    pub fn render(&self, source: &str, file: &str) -> String {
+        let (line, column) = line_col(source, self.span.start);
+        format!(
+            "thread 'main' panicked at {file}:{line}:{column}:\n{}",
+            self.message
+        )
+    }
+}
+
+enum Flow {
+    Break(Value),
+    Continue,
+    Panic(Panic),
+}
+
+impl From<Panic> for Flow {
+    fn from(panic: Panic) -> Self {
+        Flow::Panic(panic)
+    }
+}
+
+/*
+ * El valor del programa y el valor final de cada variable del nivel superior
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Evaluation {
+    pub value: Value,
+    pub bindings: Vec<(String, Value)>,
+}
+
+struct Evaluator {
+    scopes: Vec<HashMap<String, Value>>,
+    order: Vec<String>,
+    iterations: usize,
+}
+
+fn int(value: Value) -> i64 {
+    match value {
+        Value::Int(value) => value,
+        _ => 0,
+    }
+}
+
+fn boolean(value: Value) -> bool {
+    value == Value::Bool(true)
+}
+
+/*
+ * Los mismos mensajes que usa `rustc` para los `panic` de la aritmética
+ */
+fn arithmetic(op: BinaryOp, left: i64, right: i64, span: Span) -> Result<i64, Panic> {
+    let (result, verb) = match op {
+        BinaryOp::Add => (left.checked_add(right), "add"),
+        BinaryOp::Sub => (left.checked_sub(right), "subtract"),
+        BinaryOp::Mul => (left.checked_mul(right), "multiply"),
+        BinaryOp::Div if right == 0 => {
+            return Err(Panic {
+                message: "attempt to divide by zero".to_string(),
+                span,
+            })
+        }
+        BinaryOp::Rem if right == 0 => {
+            return Err(Panic {
+                message: "attempt to calculate the remainder with a divisor of zero".to_string(),
+                span,
+            })
+        }
+        BinaryOp::Div => (left.checked_div(right), "divide"),
+        BinaryOp::Rem => (left.checked_rem(right), "calculate the remainder"),
+        _ => return Ok(0),
+    };
+    result.ok_or_else(|| Panic {
+        message: format!("attempt to {verb} with overflow"),
+        span,
+    })
+}
+
+impl Evaluator {
+    fn variable(&mut self, name: &str) -> Option<&mut Value> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(name))
+    }
+
+    fn block(&mut self, block: &Block) -> Result<Value, Flow> {
+        self.scopes.push(HashMap::new());
+        let result = self.block_contents(block);
+        self.scopes.pop();
+        result
+    }
+
+    fn block_contents(&mut self, block: &Block) -> Result<Value, Flow> {
+        for stmt in &block.stmts {
+            match stmt {
+                Stmt::Let { name, value, .. } => {
+                    let value = self.expr(value)?;
+                    if self.scopes.len() == 1 && !self.order.contains(name) {
+                        self.order.push(name.clone());
+                    }
+                    if let Some(scope) = self.scopes.last_mut() {
+                        scope.insert(name.clone(), value);
+                    }
+                }
+                Stmt::Expr { expr, .. } => {
+                    self.expr(expr)?;
+                }
+            }
+        }
+        match &block.tail {
+            Some(tail) => self.expr(tail),
+            None => Ok(Value::Unit),
+        }
+    }
+
+    fn tick(&mut self, span: Span) -> Result<(), Panic> {
+        self.iterations += 1;
+        if self.iterations > MAX_ITERATIONS {
+            return Err(Panic {
+                message: format!("loop ran more than {MAX_ITERATIONS} iterations, stopped"),
+                span,
+            });
+        }
+        Ok(())
+    }
+
+    fn expr(&mut self, expr: &Expr) -> Result<Value, Flow> {
+        Ok(match &expr.kind {
+            ExprKind::Int(value) => Value::Int(*value),
+            ExprKind::Bool(value) => Value::Bool(*value),
+            ExprKind::Var(name) => self.variable(name).map_or(Value::Unit, |value| *value),
+            ExprKind::Unary(UnaryOp::Neg, operand) => {
+                let value = int(self.expr(operand)?);
+                Value::Int(value.checked_neg().ok_or_else(|| Panic {
+                    message: "attempt to negate with overflow".to_string(),
+                    span: expr.span,
+                })?)
+            }
+            ExprKind::Unary(UnaryOp::Not, operand) => Value::Bool(!boolean(self.expr(operand)?)),
+            ExprKind::Binary(op, left, right) => {
+                let left = self.expr(left)?;
+                /*
+                 * `&&` y `||` no evalúan el lado derecho si el izquierdo ya decide el resultado
+                 */
+                match op {
+                    BinaryOp::And if !boolean(left) => return Ok(Value::Bool(false)),
+                    BinaryOp::Or if boolean(left) => return Ok(Value::Bool(true)),
+                    _ => {}
+                }
+                let right = self.expr(right)?;
+                self.binary(*op, left, right, expr.span)?
+            }
+            ExprKind::Assign {
+                name, op, value, ..
+            } => {
+                let value = self.expr(value)?;
+                let current = self.variable(name).map_or(Value::Unit, |value| *value);
+                let value = match op {
+                    Some(op) => self.binary(*op, current, value, expr.span)?,
+                    None => value,
+                };
+                if let Some(slot) = self.variable(name) {
+                    *slot = value;
+                }
+                Value::Unit
+            }
+            ExprKind::Block(block) => self.block(block)?,
+            ExprKind::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                if boolean(self.expr(condition)?) {
+                    self.block(then)?
+                } else if let Some(otherwise) = otherwise {
+                    self.expr(otherwise)?
+                } else {
+                    Value::Unit
+                }
+            }
+            ExprKind::Loop(body) => loop {
+                self.tick(expr.span)?;
+                match self.block(body) {
+                    Ok(_) | Err(Flow::Continue) => {}
+                    Err(Flow::Break(value)) => break value,
+                    Err(panic) => return Err(panic),
+                }
+            },
+            ExprKind::While { condition, body } => {
+                while boolean(self.expr(condition)?) {
+                    self.tick(expr.span)?;
+                    match self.block(body) {
+                        Ok(_) | Err(Flow::Continue) => {}
+                        Err(Flow::Break(_)) => break,
+                        Err(panic) => return Err(panic),
+                    }
+                }
+                Value::Unit
+            }
+            ExprKind::Break(value) => {
+                let value = match value {
+                    Some(value) => self.expr(value)?,
+                    None => Value::Unit,
+                };
+                return Err(Flow::Break(value));
+            }
+            ExprKind::Continue => return Err(Flow::Continue),
+        })
+    }
+
+    fn binary(
+        &mut self,
+        op: BinaryOp,
+        left: Value,
+        right: Value,
+        span: Span,
+    ) -> Result<Value, Panic> {
+        Ok(match op {
+            BinaryOp::Eq => Value::Bool(left == right),
+            BinaryOp::Ne => Value::Bool(left != right),
+            BinaryOp::Lt => Value::Bool(int(left) < int(right)),
+            BinaryOp::Le => Value::Bool(int(left) <= int(right)),
+            BinaryOp::Gt => Value::Bool(int(left) > int(right)),
+            BinaryOp::Ge => Value::Bool(int(left) >= int(right)),
+            BinaryOp::And => Value::Bool(boolean(left) && boolean(right)),
+            BinaryOp::Or => Value::Bool(boolean(left) || boolean(right)),
+            op => Value::Int(arithmetic(op, int(left), int(right), span)?),
+        })
+    }
+}
+
+/*
+ * Evalúa un programa que ya pasó `typeck::check`, con un programa sin revisar los tipos
+ * incorrectos se leen como `0` o `false`
+ */
+pub fn eval(program: &Block) -> Result<Evaluation, Panic> {
+    let mut evaluator = Evaluator {
+        scopes: vec![HashMap::new()],
+        order: Vec::new(),
+        iterations: 0,
+    };
+    let value = match evaluator.block_contents(program) {
+        Ok(value) | Err(Flow::Break(value)) => value,
+        Err(Flow::Continue) => Value::Unit,
+        Err(Flow::Panic(panic)) => return Err(panic),
+    };
+    let globals = evaluator.scopes.pop().unwrap_or_default();
+    let bindings = evaluator
+        .order
+        .into_iter()
+        .filter_map(|name| globals.get(&name).map(|value| (name, *value)))
+        .collect();
+    Ok(Evaluation { value, bindings })
+}
@@ -0,0 +1,202 @@
+/*
+ * El `lexer` convierte el programa en `tokens`, los operadores de dos caracteres como `==`, `&&`
+ * o `+=` se leen antes que los de uno
+ */
+use crate::ownership::borrowck::{Diagnostic, Span};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenKind {
+    Int(i64),
+    Ident(String),
+    True,
+    False,
+    Let,
+    Mut,
+    If,
+    Else,
+    Loop,
+    While,
+    Break,
+    Continue,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    Bang,
+    Eq,
+    EqEq,
+    NotEq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    AndAnd,
+    OrOr,
+    PlusEq,
+    MinusEq,
+    StarEq,
+    SlashEq,
+    PercentEq,
+    Colon,
+    Semi,
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    Eof,
+}
+
+impl TokenKind {
+    pub fn describe(&self) -> String {
+        let text = match self {
+            TokenKind::Int(value) => return format!("`{value}`"),
+            TokenKind::Ident(name) => return format!("`{name}`"),
+            TokenKind::Eof => return "end of input".to_string(),
+            TokenKind::True => "true",
+            TokenKind::False => "false",
+            TokenKind::Let => "let",
+            TokenKind::Mut => "mut",
+            TokenKind::If => "if",
+            TokenKind::Else => "else",
+            TokenKind::Loop => "loop",
+            TokenKind::While => "while",
+            TokenKind::Break => "break",
+            TokenKind::Continue => "continue",
+            TokenKind::Plus => "+",
+            TokenKind::Minus => "-",
+            TokenKind::Star => "*",
+            TokenKind::Slash => "/",
+            TokenKind::Percent => "%",
+            TokenKind::Bang => "!",
+            TokenKind::Eq => "=",
+            TokenKind::EqEq => "==",
+            TokenKind::NotEq => "!=",
+            TokenKind::Lt => "<",
+            TokenKind::Le => "<=",
+            TokenKind::Gt => ">",
+            TokenKind::Ge => ">=",
+            TokenKind::AndAnd => "&&",
+            TokenKind::OrOr => "||",
+            TokenKind::PlusEq => "+=",
+            TokenKind::MinusEq => "-=",
+            TokenKind::StarEq => "*=",
+            TokenKind::SlashEq => "/=",
+            TokenKind::PercentEq => "%=",
+            TokenKind::Colon => ":",
+            TokenKind::Semi => ";",
+            TokenKind::LParen => "(",
+            TokenKind::RParen => ")",
+            TokenKind::LBrace => "{",
+            TokenKind::RBrace => "}",
+        };
+        format!("`{text}`")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+const OPERATORS: [(&str, TokenKind); 26] = [
+    ("==", TokenKind::EqEq),
+    ("!=", TokenKind::NotEq),
+    ("<=", TokenKind::Le),
+    (">=", TokenKind::Ge),
+    ("&&", TokenKind::AndAnd),
+    ("||", TokenKind::OrOr),
+    ("+=", TokenKind::PlusEq),
+    ("-=", TokenKind::MinusEq),
+    ("*=", TokenKind::StarEq),
+    ("/=", TokenKind::SlashEq),
+    ("%=", TokenKind::PercentEq),
+    ("+", TokenKind::Plus),
+    ("-", TokenKind::Minus),
+    ("*", TokenKind::Star),
+    ("/", TokenKind::Slash),
+    ("%", TokenKind::Percent),
+    ("!", TokenKind::Bang),
+    ("=", TokenKind::Eq),
+    ("<", TokenKind::Lt),
+    (">", TokenKind::Gt),
+    (":", TokenKind::Colon),
+    (";", TokenKind::Semi),
+    ("(", TokenKind::LParen),
+    (")", TokenKind::RParen),
+    ("{", TokenKind::LBrace),
+    ("}", TokenKind::RBrace),
+];
+
+pub fn lex(source: &str) -> Result<Vec<Token>, Diagnostic> {
+    let mut tokens = Vec::new();
+    let bytes = source.as_bytes();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let start = pos;
+        let c = bytes[pos];
+        if c.is_ascii_whitespace() {
+            pos += 1;
+            continue;
+        }
+        /*
+         * Los comentarios de una línea `//` se ignoran
+         */
+        if source[pos..].starts_with("//") {
+            pos = source[pos..]
+                .find('\n')
+                .map_or(bytes.len(), |index| pos + index);
+            continue;
+        }
+        let kind = if c.is_ascii_digit() {
+            while pos < bytes.len() && (bytes[pos].is_ascii_digit() || bytes[pos] == b'_') {
+                pos += 1;
+            }
+            let digits = source[start..pos].replace('_', "");
+            let value = digits.parse().map_err(|_| {
+                Diagnostic::syntax("integer literal is too large", Span::new(start, pos))
+            })?;
+            TokenKind::Int(value)
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            while pos < bytes.len() && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_') {
+                pos += 1;
+            }
+            match &source[start..pos] {
+                "true" => TokenKind::True,
+                "false" => TokenKind::False,
+                "let" => TokenKind::Let,
+                "mut" => TokenKind::Mut,
+                "if" => TokenKind::If,
+                "else" => TokenKind::Else,
+                "loop" => TokenKind::Loop,
+                "while" => TokenKind::While,
+                "break" => TokenKind::Break,
+                "continue" => TokenKind::Continue,
+                name => TokenKind::Ident(name.to_string()),
+            }
+        } else {
+            let (text, kind) = OPERATORS
+                .iter()
+                .find(|(text, _)| source[pos..].starts_with(text))
+                .ok_or_else(|| {
+                    let c = source[pos..].chars().next().unwrap_or('?');
+                    Diagnostic::syntax(
+                        format!("unknown start of token: {c}"),
+                        Span::new(start, start + c.len_utf8()),
+                    )
+                })?;
+            pos += text.len();
+            kind.clone()
+        };
+        tokens.push(Token {
+            kind,
+            span: Span::new(start, pos),
+        });
+    }
+    tokens.push(Token {
+        kind: TokenKind::Eof,
+        span: Span::new(bytes.len(), bytes.len()),
+    });
+    Ok(tokens)
+}
@@ -0,0 +1,363 @@
+/*
+ * Un intérprete para un lenguaje pequeño basado en expresiones, igual que `Rust`. Sirve para ver en
+ * ejecución lo que explica `flow/statemantes_expressions.rs`
+ *
+ *  let y = {
+ *      let a = 10;         `statement`, no tiene valor
+ *      let x = a + 5;      `statement`, `a + 5` es una `expression` dentro de él
+ *      x + 1               `expression` sin `;`, es el valor del bloque
+ *  };
+ *
+ * El programa pasa por cuatro etapas, cada una puede detenerlo con un error
+ *
+ *  texto -> lexer::lex -> parser::parse -> typeck::check -> eval::eval -> valor
+ *          tokens        árbol           tipos            `panic`
+ *
+ * Solo hay enteros `i64`, `bool` y `()`, no hay funciones ni `String`
+ */
+pub mod ast;
+pub mod eval;
+pub mod lexer;
+pub mod parser;
+pub mod typeck;
+
+use std::fmt::Write;
+
+pub use ast::{pretty, tree, Block, Type};
+pub use eval::{Evaluation, Panic, Value};
+
+use crate::ownership::borrowck::Diagnostic;
+
+/*
+ * El programa de ejemplo sigue la lección de `statements` y `expressions` y las de `if` y `loop`
+ */
+pub const EXAMPLE: &str = r#"let x = 5;
+let res = 5 + 6;
+
+// un bloque es una expresión, su valor es la última expresión sin `;`
+let y = {
+    let a = 10;
+    let x = a + 5;
+    x + 1
+};
+
+// con `;` el valor se descarta y el bloque vale `()`
+let unit = {
+    let a = 10;
+    a + 5;
+};
+
+// `if` es una expresión, las dos ramas tienen el mismo tipo
+let number = if x > 5 { 5 } else { 6 };
+let parity = if number % 2 == 0 { 0 } else { 1 };
+
+// `loop` devuelve el valor que acompaña a `break`
+let mut counter = 0;
+let doubled = loop {
+    counter += 1;
+    if counter == 10 {
+        break counter * 2;
+    }
+};
+
+let mut countdown = 3;
+while countdown != 0 {
+    countdown -= 1;
+}
+
+res + y + doubled
+"#;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub program: Option<Block>,
+    pub ty: Option<Type>,
+    pub diagnostics: Vec<Diagnostic>,
+    pub result: Option<Result<Evaluation, Panic>>,
+}
+
+impl Outcome {
+    pub fn is_ok(&self) -> bool {
+        self.diagnostics.is_empty() && matches!(self.result, Some(Ok(_)))
+    }
+
+    /*
+     * Las variables del nivel superior y el valor del programa con su tipo
+     */
+    pub fn render(&self, source: &str, file: &str) -> String {
+        let mut out = String::new();
+        if !self.diagnostics.is_empty() {
+            for diagnostic in &self.diagnostics {
+                let _ = writeln!(out, "{}", diagnostic.render(source, file));
+            }
+            match self.diagnostics.len() {
+                1 => out.push_str("error: aborting due to 1 previous error\n"),
+                count => {
+                    let _ = writeln!(out, "error: aborting due to {count} previous errors");
+                }
+            }
+            return out;
+        }
+        match &self.result {
+            Some(Ok(evaluation)) => {
+                let width = evaluation
+                    .bindings
+                    .iter()
+                    .map(|(name, _)| name.len())
+                    .max()
+                    .unwrap_or(0);
+                for (name, value) in &evaluation.bindings {
+                    let _ = writeln!(out, "{name:<width$} = {value}");
+                }
+                if !evaluation.bindings.is_empty() {
+                    out.push('\n');
+                }
+                let ty = self.ty.unwrap_or(Type::Unit);
+                let _ = writeln!(out, "value: {} : {ty}", evaluation.value);
+            }
+            Some(Err(panic)) => {
+                let _ = writeln!(out, "{}", panic.render(source, file));
+            }
+            None => {}
+        }
+        out
+    }
+}
+
+/*
+ * Los errores de sintaxis detienen todo, los de tipos se reportan juntos y el programa no se evalúa
+ */
+pub fn run(source: &str) -> Outcome {
+    let program = match lexer::lex(source).and_then(|tokens| parser::parse(&tokens)) {
+        Ok(program) => program,
+        Err(diagnostic) => {
+            return Outcome {
+                program: None,
+                ty: None,
+                diagnostics: vec![diagnostic],
+                result: None,
+            }
+        }
+    };
+    match typeck::check(&program) {
+        Ok(ty) => Outcome {
+            result: Some(eval::eval(&program)),
+            program: Some(program),
+            ty: Some(ty),
+            diagnostics: Vec::new(),
+        },
+        Err(diagnostics) => Outcome {
+            program: Some(program),
+            ty: None,
+            diagnostics,
+            result: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ast::{Expr, ExprKind, Stmt};
+    use super::*;
+    use crate::ownership::borrowck::Span;
+
+    fn codes(source: &str) -> Vec<&'static str> {
+        run(source)
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.code.unwrap_or("syntax"))
+            .collect()
+    }
+
+    fn value(source: &str) -> Value {
+        match run(source).result {
+            Some(Ok(evaluation)) => evaluation.value,
+            other => panic!("{source}: {other:?}"),
+        }
+    }
+
+    /*
+     * `pretty()` no conserva las posiciones ni los comentarios, así que se comparan los árboles
+     * con todos los `Span` en cero
+     */
+    fn erase_block(block: &mut Block) {
+        block.span = Span::default();
+        for stmt in &mut block.stmts {
+            match stmt {
+                Stmt::Let {
+                    name_span,
+                    ty,
+                    value,
+                    ..
+                } => {
+                    *name_span = Span::default();
+                    if let Some((_, span)) = ty {
+                        *span = Span::default();
+                    }
+                    erase_expr(value);
+                }
+                Stmt::Expr { expr, .. } => erase_expr(expr),
+            }
+        }
+        if let Some(tail) = &mut block.tail {
+            erase_expr(tail);
+        }
+    }
+
+    fn erase_expr(expr: &mut Expr) {
+        expr.span = Span::default();
+        match &mut expr.kind {
+            ExprKind::Unary(_, operand) => erase_expr(operand),
+            ExprKind::Binary(_, left, right) => {
+                erase_expr(left);
+                erase_expr(right);
+            }
+            ExprKind::Assign {
+                name_span, value, ..
+            } => {
+                *name_span = Span::default();
+                erase_expr(value);
+            }
+            ExprKind::Block(block) | ExprKind::Loop(block) => erase_block(block),
+            ExprKind::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                erase_expr(condition);
+                erase_block(then);
+                if let Some(otherwise) = otherwise {
+                    erase_expr(otherwise);
+                }
+            }
+            ExprKind::While { condition, body } => {
+                erase_expr(condition);
+                erase_block(body);
+            }
+            ExprKind::Break(Some(value)) => erase_expr(value),
+            ExprKind::Int(_)
+            | ExprKind::Bool(_)
+            | ExprKind::Var(_)
+            | ExprKind::Break(None)
+            | ExprKind::Continue => {}
+        }
+    }
+
+    fn parse_source(source: &str) -> Block {
+        let mut block = lexer::lex(source)
+            .and_then(|tokens| parser::parse(&tokens))
+            .unwrap_or_else(|diagnostic| panic!("{}", diagnostic.render(source, "test.rs")));
+        erase_block(&mut block);
+        block
+    }
+
+    #[test]
+    fn example_program_value() {
+        let outcome = run(EXAMPLE);
+        assert!(outcome.is_ok(), "{}", outcome.render(EXAMPLE, "example.rs"));
+        assert_eq!(outcome.ty, Some(Type::Int));
+        let Some(Ok(evaluation)) = outcome.result else {
+            unreachable!()
+        };
+        assert_eq!(evaluation.value, Value::Int(47));
+        let binding = |name: &str| {
+            evaluation
+                .bindings
+                .iter()
+                .find(|(binding, _)| binding == name)
+                .map(|(_, value)| *value)
+        };
+        assert_eq!(binding("y"), Some(Value::Int(16)));
+        assert_eq!(binding("unit"), Some(Value::Unit));
+        assert_eq!(binding("number"), Some(Value::Int(6)));
+        assert_eq!(binding("doubled"), Some(Value::Int(20)));
+        assert_eq!(binding("countdown"), Some(Value::Int(0)));
+    }
+
+    #[test]
+    fn precedence_and_blocks() {
+        assert_eq!(value("1 + 2 * 3"), Value::Int(7));
+        assert_eq!(value("1 - 2 - 3"), Value::Int(-4));
+        assert_eq!(value("-(2 + 3) * 2"), Value::Int(-10));
+        assert_eq!(value("let mut x = 1; let y = x = 5; x"), Value::Int(5));
+        assert_eq!(value("!(1 < 2) || 3 == 3 && true"), Value::Bool(true));
+        assert_eq!(value("{ let a = 1; a + 1; }"), Value::Unit);
+        assert_eq!(value("if false { 1 } else if true { 2 } else { 3 }"), Value::Int(2));
+    }
+
+    #[test]
+    fn type_errors() {
+        assert_eq!(codes("let x: i64 = true;"), ["E0308"]);
+        assert_eq!(codes("if 1 { 2 } else { 3 }"), ["E0308"]);
+        assert_eq!(codes("if true { 1 } else { false }"), ["E0308"]);
+        assert_eq!(codes("if true { 1 }"), ["E0317"]);
+        assert_eq!(codes("1 + true"), ["E0369"]);
+        assert_eq!(codes("-true"), ["E0600"]);
+        assert_eq!(codes("!1"), ["E0600"]);
+        assert_eq!(codes("let x = 1; x = 2;"), ["E0384"]);
+        assert_eq!(codes("y + 1"), ["E0425"]);
+        assert_eq!(codes("let v = loop { if true { break 1; } break false; };"), ["E0308"]);
+        /*
+         * Los errores de tipos se reportan juntos y el programa no se evalúa
+         */
+        let outcome = run("let a: bool = 1;\nlet b = 2 + false;\n");
+        assert_eq!(outcome.diagnostics.len(), 2);
+        assert_eq!(outcome.result, None);
+    }
+
+    #[test]
+    fn break_outside_loop() {
+        assert_eq!(codes("break;"), ["E0268"]);
+        assert_eq!(codes("let x = { break 5; };"), ["E0268"]);
+        assert_eq!(codes("continue;"), ["E0268"]);
+        assert_eq!(codes("while true { break 1; }"), ["E0571"]);
+        assert!(run("loop { break; }").is_ok());
+        assert!(run("let mut i = 0; while i < 3 { i += 1; continue; }").is_ok());
+    }
+
+    #[test]
+    fn runtime_panics() {
+        let panic = |source: &str| match run(source).result {
+            Some(Err(panic)) => panic.message,
+            other => panic!("{source}: {other:?}"),
+        };
+        assert_eq!(panic("let zero = 0; 1 / zero"), "attempt to divide by zero");
+        assert_eq!(panic("9223372036854775807 + 1"), "attempt to add with overflow");
+        assert!(panic("loop {}").starts_with("loop ran more than"));
+    }
+
+    #[test]
+    fn pretty_printer_round_trip() {
+        let sources = [
+            EXAMPLE,
+            "1 - (2 - 3)",
+            "(1 + 2) * -(3 % 2)",
+            "let mut x = 0;\nx += if x == 0 { 1 } else { 2 };\n(if true { 1 } else { 2 }) - 1",
+            "let t: bool = !(1 < 2) || (true && false);\nlet u: () = {};\nt",
+            "let mut i = 0;\nlet v = loop { i += 1; if i > 3 { break i * 2; } };\nwhile i != 0 { i -= 1; }\nv",
+            "let a = 1;\nlet b = { let c = a; c };\n{ b }",
+        ];
+        for source in sources {
+            let program = parse_source(source);
+            let printed = pretty(&program);
+            assert_eq!(parse_source(&printed), program, "{source}\n---\n{printed}");
+            assert_eq!(pretty(&parse_source(&printed)), printed);
+        }
+    }
+
+    #[test]
+    fn deep_nesting_is_a_diagnostic() {
+        for (open, close) in [("(", ")"), ("{", "}"), ("-", ""), ("if true { ", " } else { 0 }")] {
+            let source = format!("{}1{}", open.repeat(100_000), close.repeat(100_000));
+            let outcome = run(&source);
+            assert_eq!(outcome.diagnostics.len(), 1, "{open}");
+            assert!(
+                outcome.diagnostics[0].message.contains("nested more than"),
+                "{}",
+                outcome.diagnostics[0].message
+            );
+        }
+        let source = format!("{}1{}", "(".repeat(100), ")".repeat(100));
+        assert_eq!(value(&source), Value::Int(1));
+    }
+}
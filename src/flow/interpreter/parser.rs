@@ -0,0 +1,435 @@
+/*
+ * Un `Pratt parser`, cada operador tiene una fuerza `binding power` y una expresión se extiende
+ * mientras el siguiente operador tenga más fuerza que el mínimo que se pidió
+ *
+ *  1 + 2 * 3       `*` (6) atrae al `2` con más fuerza que `+` (5)  ->  1 + (2 * 3)
+ *  1 - 2 - 3       a la misma fuerza gana el de la izquierda          ->  (1 - 2) - 3
+ *  x = y = 3       la asignación asocia a la derecha                  ->  x = (y = 3)
+ *
+ * Igual que en `Rust`, un `if`, `loop`, `while` o bloque al inicio de un `statement` termina en su
+ * `}`, `if c { 1 } else { 2 } - 1` son dos `statements` y no una resta
+ */
+use super::ast::{
+    BinaryOp, Block, Expr, ExprKind, Stmt, Type, UnaryOp, ASSIGN_POWER, PREFIX_POWER,
+};
+use super::lexer::{Token, TokenKind};
+use crate::ownership::borrowck::{Diagnostic, Span};
+
+/*
+ * Cada paréntesis, bloque u operador prefijo anidado es una llamada recursiva más, sin un límite
+ * `((((...` con miles de niveles desborda el `stack` antes de llegar a un error
+ */
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    depth: usize,
+}
+
+fn binary_op(kind: &TokenKind) -> Option<BinaryOp> {
+    Some(match kind {
+        TokenKind::Plus => BinaryOp::Add,
+        TokenKind::Minus => BinaryOp::Sub,
+        TokenKind::Star => BinaryOp::Mul,
+        TokenKind::Slash => BinaryOp::Div,
+        TokenKind::Percent => BinaryOp::Rem,
+        TokenKind::EqEq => BinaryOp::Eq,
+        TokenKind::NotEq => BinaryOp::Ne,
+        TokenKind::Lt => BinaryOp::Lt,
+        TokenKind::Le => BinaryOp::Le,
+        TokenKind::Gt => BinaryOp::Gt,
+        TokenKind::Ge => BinaryOp::Ge,
+        TokenKind::AndAnd => BinaryOp::And,
+        TokenKind::OrOr => BinaryOp::Or,
+        _ => return None,
+    })
+}
+
+/*
+ * `None` para `=`, el operador de `+=` y compañía
+ */
+fn assign_op(kind: &TokenKind) -> Option<Option<BinaryOp>> {
+    Some(match kind {
+        TokenKind::Eq => None,
+        TokenKind::PlusEq => Some(BinaryOp::Add),
+        TokenKind::MinusEq => Some(BinaryOp::Sub),
+        TokenKind::StarEq => Some(BinaryOp::Mul),
+        TokenKind::SlashEq => Some(BinaryOp::Div),
+        TokenKind::PercentEq => Some(BinaryOp::Rem),
+        _ => return None,
+    })
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> &TokenKind {
+        &self.tokens[self.pos].kind
+    }
+
+    fn span(&self) -> Span {
+        self.tokens[self.pos].span
+    }
+
+    fn previous_span(&self) -> Span {
+        self.tokens[self.pos.saturating_sub(1)].span
+    }
+
+    fn bump(&mut self) -> &'a Token {
+        let token = &self.tokens[self.pos];
+        if token.kind != TokenKind::Eof {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, kind: &TokenKind) -> bool {
+        if self.peek() == kind {
+            self.bump();
+            return true;
+        }
+        false
+    }
+
+    fn unexpected(&self, expected: &str) -> Diagnostic {
+        Diagnostic::syntax(
+            format!("expected {expected}, found {}", self.peek().describe()),
+            self.span(),
+        )
+    }
+
+    /*
+     * Se llama al entrar a una expresión o un bloque, `leave()` al salir
+     */
+    fn enter(&mut self) -> Result<(), Diagnostic> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(Diagnostic::syntax(
+                format!("expression nested more than {MAX_DEPTH} levels deep"),
+                self.span(),
+            )
+            .note("split the expression into smaller ones with `let`"));
+        }
+        Ok(())
+    }
+
+    fn leave(&mut self) {
+        self.depth -= 1;
+    }
+
+    fn expect(&mut self, kind: &TokenKind) -> Result<Span, Diagnostic> {
+        if self.peek() == kind {
+            return Ok(self.bump().span);
+        }
+        Err(self.unexpected(&kind.describe()))
+    }
+
+    /*
+     * El contenido de un bloque hasta `end`, el programa completo es un bloque sin llaves
+     */
+    fn block_contents(&mut self, end: &TokenKind, start: usize) -> Result<Block, Diagnostic> {
+        let mut stmts = Vec::new();
+        let mut tail: Option<Box<Expr>> = None;
+        while self.peek() != end {
+            if let Some(previous) = tail.take() {
+                stmts.push(Stmt::Expr {
+                    expr: *previous,
+                    semi: false,
+                });
+            }
+            if self.eat(&TokenKind::Semi) {
+                continue;
+            }
+            if *self.peek() == TokenKind::Let {
+                stmts.push(self.let_stmt()?);
+                continue;
+            }
+            let expr = match self.peek() {
+                TokenKind::LBrace | TokenKind::If | TokenKind::Loop | TokenKind::While => {
+                    self.block_like()?
+                }
+                _ => self.expr(0)?,
+            };
+            if self.eat(&TokenKind::Semi) {
+                stmts.push(Stmt::Expr { expr, semi: true });
+            } else if self.peek() == end || expr.is_block_like() {
+                tail = Some(Box::new(expr));
+            } else {
+                return Err(self.unexpected("`;` or `}`"));
+            }
+        }
+        let end_span = self.span();
+        Ok(Block {
+            stmts,
+            tail,
+            span: Span::new(start, end_span.end),
+        })
+    }
+
+    fn block(&mut self) -> Result<Block, Diagnostic> {
+        let open = self.expect(&TokenKind::LBrace)?;
+        let block = self.block_contents(&TokenKind::RBrace, open.start)?;
+        self.expect(&TokenKind::RBrace)?;
+        Ok(block)
+    }
+
+    fn let_stmt(&mut self) -> Result<Stmt, Diagnostic> {
+        self.bump();
+        let mutable = self.eat(&TokenKind::Mut);
+        let (name, name_span) = match self.peek() {
+            TokenKind::Ident(name) => (name.clone(), self.bump().span),
+            _ => return Err(self.unexpected("identifier")),
+        };
+        let ty = if self.eat(&TokenKind::Colon) {
+            Some(self.type_name()?)
+        } else {
+            None
+        };
+        if *self.peek() == TokenKind::Semi {
+            return Err(
+                Diagnostic::syntax("`let` without a value is not supported", self.span())
+                    .note("give the variable its first value with `let name = value;`"),
+            );
+        }
+        self.expect(&TokenKind::Eq)?;
+        let value = self.expr(0)?;
+        self.expect(&TokenKind::Semi)?;
+        Ok(Stmt::Let {
+            name,
+            name_span,
+            mutable,
+            ty,
+            value,
+        })
+    }
+
+    fn type_name(&mut self) -> Result<(Type, Span), Diagnostic> {
+        let span = self.span();
+        let ty = match self.peek() {
+            TokenKind::Ident(name) if name == "i64" => Type::Int,
+            TokenKind::Ident(name) if name == "bool" => Type::Bool,
+            TokenKind::LParen => {
+                self.bump();
+                self.expect(&TokenKind::RParen)?;
+                return Ok((Type::Unit, span.to(self.previous_span())));
+            }
+            _ => return Err(self.unexpected("`i64`, `bool` or `()`")),
+        };
+        self.bump();
+        Ok((ty, span))
+    }
+
+    fn block_like(&mut self) -> Result<Expr, Diagnostic> {
+        self.enter()?;
+        let expr = self.block_like_inner();
+        self.leave();
+        expr
+    }
+
+    fn block_like_inner(&mut self) -> Result<Expr, Diagnostic> {
+        let start = self.span();
+        let kind = match self.peek() {
+            TokenKind::LBrace => ExprKind::Block(self.block()?),
+            TokenKind::Loop => {
+                self.bump();
+                ExprKind::Loop(self.block()?)
+            }
+            TokenKind::While => {
+                self.bump();
+                let condition = Box::new(self.condition()?);
+                ExprKind::While {
+                    condition,
+                    body: self.block()?,
+                }
+            }
+            _ => return self.if_expr(),
+        };
+        Ok(Expr {
+            kind,
+            span: start.to(self.previous_span()),
+        })
+    }
+
+    fn if_expr(&mut self) -> Result<Expr, Diagnostic> {
+        let start = self.expect(&TokenKind::If)?;
+        let condition = Box::new(self.condition()?);
+        let then = self.block()?;
+        let otherwise = match self.eat(&TokenKind::Else) {
+            false => None,
+            true if *self.peek() == TokenKind::If => Some(Box::new(self.if_expr()?)),
+            true => {
+                let open = self.span();
+                let block = self.block()?;
+                Some(Box::new(Expr {
+                    kind: ExprKind::Block(block),
+                    span: open.to(self.previous_span()),
+                }))
+            }
+        };
+        Ok(Expr {
+            kind: ExprKind::If {
+                condition,
+                then,
+                otherwise,
+            },
+            span: start.to(self.previous_span()),
+        })
+    }
+
+    /*
+     * La condición de `if` y `while` no puede empezar con `{`, la llave es el inicio del cuerpo
+     */
+    fn condition(&mut self) -> Result<Expr, Diagnostic> {
+        if *self.peek() == TokenKind::LBrace {
+            return Err(self.unexpected("a condition"));
+        }
+        self.expr(0)
+    }
+
+    /*
+     * El corazón del `Pratt parser`, primero un operando o un prefijo y después operadores
+     * mientras tengan al menos `min` de fuerza
+     */
+    fn expr(&mut self, min: u8) -> Result<Expr, Diagnostic> {
+        self.enter()?;
+        let expr = self.operators(min);
+        self.leave();
+        expr
+    }
+
+    fn operators(&mut self, min: u8) -> Result<Expr, Diagnostic> {
+        let mut left = self.prefix()?;
+        loop {
+            if let Some(op) = assign_op(self.peek()) {
+                if ASSIGN_POWER < min {
+                    break;
+                }
+                let op_span = self.bump().span;
+                let ExprKind::Var(name) = &left.kind else {
+                    return Err(
+                        Diagnostic::error("E0070", "invalid left-hand side of assignment")
+                            .primary(op_span, "cannot assign to this expression")
+                            .secondary(left.span, ""),
+                    );
+                };
+                let value = self.expr(ASSIGN_POWER)?;
+                let span = left.span.to(value.span);
+                left = Expr {
+                    kind: ExprKind::Assign {
+                        name: name.clone(),
+                        name_span: left.span,
+                        op,
+                        value: Box::new(value),
+                    },
+                    span,
+                };
+                continue;
+            }
+            let Some(op) = binary_op(self.peek()) else {
+                break;
+            };
+            let power = op.binding_power();
+            if power < min {
+                break;
+            }
+            let op_span = self.bump().span;
+            let right = self.expr(power + 1)?;
+            if op.is_comparison() {
+                if let Some(next) = binary_op(self.peek()).filter(|next| next.is_comparison()) {
+                    return Err(Diagnostic::syntax(
+                        "comparison operators cannot be chained",
+                        op_span,
+                    )
+                    .secondary(self.span(), "")
+                    .note(format!(
+                        "split the comparison into two: `a {} b && b {} c`",
+                        op.symbol(),
+                        next.symbol()
+                    )));
+                }
+            }
+            let span = left.span.to(right.span);
+            left = Expr {
+                kind: ExprKind::Binary(op, Box::new(left), Box::new(right)),
+                span,
+            };
+        }
+        Ok(left)
+    }
+
+    fn prefix(&mut self) -> Result<Expr, Diagnostic> {
+        let start = self.span();
+        let kind = match self.peek().clone() {
+            TokenKind::Int(value) => {
+                self.bump();
+                ExprKind::Int(value)
+            }
+            TokenKind::True | TokenKind::False => {
+                let value = *self.peek() == TokenKind::True;
+                self.bump();
+                ExprKind::Bool(value)
+            }
+            TokenKind::Ident(name) => {
+                self.bump();
+                ExprKind::Var(name)
+            }
+            TokenKind::Minus | TokenKind::Bang => {
+                let op = if *self.peek() == TokenKind::Minus {
+                    UnaryOp::Neg
+                } else {
+                    UnaryOp::Not
+                };
+                self.bump();
+                ExprKind::Unary(op, Box::new(self.expr(PREFIX_POWER)?))
+            }
+            TokenKind::LParen => {
+                self.bump();
+                if self.eat(&TokenKind::RParen) {
+                    return Err(Diagnostic::syntax(
+                        "the unit value `()` is not an expression here",
+                        start.to(self.previous_span()),
+                    ));
+                }
+                let inner = self.expr(0)?;
+                self.expect(&TokenKind::RParen)?;
+                return Ok(Expr {
+                    kind: inner.kind,
+                    span: start.to(self.previous_span()),
+                });
+            }
+            TokenKind::LBrace | TokenKind::If | TokenKind::Loop | TokenKind::While => {
+                return self.block_like();
+            }
+            TokenKind::Break => {
+                self.bump();
+                let value = match self.peek() {
+                    TokenKind::Semi | TokenKind::RBrace | TokenKind::RParen | TokenKind::Eof => {
+                        None
+                    }
+                    _ => Some(Box::new(self.expr(ASSIGN_POWER)?)),
+                };
+                ExprKind::Break(value)
+            }
+            TokenKind::Continue => {
+                self.bump();
+                ExprKind::Continue
+            }
+            TokenKind::Let => return Err(self.unexpected("expression").note(
+                "`let` is a statement, it does not produce a value that can be assigned or used",
+            )),
+            _ => return Err(self.unexpected("expression")),
+        };
+        Ok(Expr {
+            kind,
+            span: start.to(self.previous_span()),
+        })
+    }
+}
+
+pub fn parse(tokens: &[Token]) -> Result<Block, Diagnostic> {
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        depth: 0,
+    };
+    parser.block_contents(&TokenKind::Eof, 0)
+}
@@ -0,0 +1,377 @@
+/*
+ * La revisión de tipos corre antes de evaluar, igual que en `rustc` un programa con errores de tipo
+ * no se ejecuta. Cada expresión tiene un tipo, `i64`, `bool`, `()` o `!`
+ *
+ *  let y = { let a = 10; a + 5 };      el bloque es `i64` por su `tail`
+ *  let z = { let a = 10; a + 5; };     el `;` descarta el valor, el bloque es `()`
+ *  let w = if x > 5 { 1 } else { 0 };  las dos ramas deben tener el mismo tipo
+ *  let v = loop { break 42; };         el `loop` tiene el tipo de sus `break`
+ */
+use std::collections::HashMap;
+
+use super::ast::{BinaryOp, Block, Expr, ExprKind, Stmt, Type, UnaryOp};
+use crate::ownership::borrowck::{Diagnostic, Span};
+
+struct Binding {
+    ty: Option<Type>,
+    mutable: bool,
+    span: Span,
+}
+
+struct LoopFrame {
+    /*
+     * `while` solo acepta `break` sin valor, su tipo siempre es `()`
+     */
+    is_while: bool,
+    ty: Option<Type>,
+    first_break: Option<Span>,
+}
+
+struct Checker {
+    scopes: Vec<HashMap<String, Binding>>,
+    loops: Vec<LoopFrame>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/*
+ * `None` es un tipo desconocido por un error anterior, no genera más errores
+ */
+type Ty = Option<Type>;
+
+fn mismatch(expected: Type, found: Type, span: Span) -> Diagnostic {
+    Diagnostic::error("E0308", "mismatched types")
+        .primary(span, format!("expected `{expected}`, found `{found}`"))
+}
+
+/*
+ * El tipo común de dos ramas, `!` se acomoda a cualquiera
+ */
+fn unify(a: Type, b: Type) -> Option<Type> {
+    match (a, b) {
+        (Type::Never, other) | (other, Type::Never) => Some(other),
+        (a, b) if a == b => Some(a),
+        _ => None,
+    }
+}
+
+impl Checker {
+    fn lookup(&self, name: &str) -> Option<&Binding> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    fn expect(&mut self, expr: &Expr, expected: Type) -> Ty {
+        let found = self.expr(expr)?;
+        if unify(expected, found) != Some(expected) {
+            self.diagnostics.push(mismatch(expected, found, expr.span));
+            return None;
+        }
+        Some(found)
+    }
+
+    fn block(&mut self, block: &Block) -> Ty {
+        self.scopes.push(HashMap::new());
+        let mut diverges = false;
+        for stmt in &block.stmts {
+            match stmt {
+                Stmt::Let {
+                    name,
+                    name_span,
+                    mutable,
+                    ty,
+                    value,
+                } => {
+                    let found = match ty {
+                        Some((ty, _)) => self.expect(value, *ty).map(|_| *ty),
+                        None => self.expr(value),
+                    };
+                    diverges |= found == Some(Type::Never);
+                    let binding = Binding {
+                        ty: found,
+                        mutable: *mutable,
+                        span: *name_span,
+                    };
+                    if let Some(scope) = self.scopes.last_mut() {
+                        scope.insert(name.clone(), binding);
+                    }
+                }
+                Stmt::Expr { expr, semi: true } => {
+                    diverges |= self.expr(expr) == Some(Type::Never);
+                }
+                /*
+                 * Un bloque sin `;` en medio del bloque tiene que ser `()`, si no el valor se
+                 * perdería sin avisar
+                 */
+                Stmt::Expr { expr, semi: false } => {
+                    let found = self.expr(expr);
+                    diverges |= found == Some(Type::Never);
+                    if let Some(found) = found.filter(|ty| unify(Type::Unit, *ty).is_none()) {
+                        self.diagnostics.push(
+                            mismatch(Type::Unit, found, expr.span)
+                                .note("add `;` after the block to discard its value"),
+                        );
+                    }
+                }
+            }
+        }
+        let ty = match &block.tail {
+            Some(tail) => self.expr(tail),
+            None if diverges => Some(Type::Never),
+            None => Some(Type::Unit),
+        };
+        self.scopes.pop();
+        ty
+    }
+
+    fn expr(&mut self, expr: &Expr) -> Ty {
+        match &expr.kind {
+            ExprKind::Int(_) => Some(Type::Int),
+            ExprKind::Bool(_) => Some(Type::Bool),
+            ExprKind::Var(name) => match self.lookup(name) {
+                Some(binding) => binding.ty,
+                None => {
+                    self.diagnostics.push(
+                        Diagnostic::error(
+                            "E0425",
+                            format!("cannot find value `{name}` in this scope"),
+                        )
+                        .primary(expr.span, "not found in this scope"),
+                    );
+                    None
+                }
+            },
+            ExprKind::Unary(op, operand) => {
+                let expected = match op {
+                    UnaryOp::Neg => Type::Int,
+                    UnaryOp::Not => Type::Bool,
+                };
+                let found = self.expr(operand)?;
+                if unify(expected, found) != Some(expected) {
+                    let symbol = if *op == UnaryOp::Neg { '-' } else { '!' };
+                    self.diagnostics.push(
+                        Diagnostic::error(
+                            "E0600",
+                            format!("cannot apply unary operator `{symbol}` to type `{found}`"),
+                        )
+                        .primary(expr.span, format!("cannot apply unary operator `{symbol}`")),
+                    );
+                    return None;
+                }
+                Some(expected)
+            }
+            ExprKind::Binary(op, left, right) => self.binary(*op, left, right, expr.span),
+            ExprKind::Assign {
+                name,
+                name_span,
+                op,
+                value,
+            } => {
+                let Some(binding) = self.lookup(name) else {
+                    self.diagnostics.push(
+                        Diagnostic::error(
+                            "E0425",
+                            format!("cannot find value `{name}` in this scope"),
+                        )
+                        .primary(*name_span, "not found in this scope"),
+                    );
+                    self.expr(value);
+                    return Some(Type::Unit);
+                };
+                let (target, mutable, declared) = (binding.ty, binding.mutable, binding.span);
+                if !mutable {
+                    self.diagnostics.push(
+                        Diagnostic::error(
+                            "E0384",
+                            format!("cannot assign twice to immutable variable `{name}`"),
+                        )
+                        .secondary(declared, format!("first assignment to `{name}`"))
+                        .primary(expr.span, "cannot assign twice to immutable variable")
+                        .note(format!(
+                            "consider making this binding mutable: `mut {name}`"
+                        )),
+                    );
+                }
+                match (op, target) {
+                    (Some(op), Some(_)) => {
+                        let left = Expr {
+                            kind: ExprKind::Var(name.clone()),
+                            span: *name_span,
+                        };
+                        self.binary(*op, &left, value, expr.span);
+                    }
+                    (None, Some(target)) => {
+                        self.expect(value, target);
+                    }
+                    (_, None) => {
+                        self.expr(value);
+                    }
+                }
+                Some(Type::Unit)
+            }
+            ExprKind::Block(block) => self.block(block),
+            ExprKind::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                self.expect(condition, Type::Bool);
+                let then_ty = self.block(then);
+                let Some(otherwise) = otherwise else {
+                    /*
+                     * Sin `else` el `if` puede no ejecutarse, su valor solo puede ser `()`
+                     */
+                    if let Some(found) = then_ty.filter(|ty| unify(Type::Unit, *ty).is_none()) {
+                        let span = then.tail.as_ref().map_or(then.span, |tail| tail.span);
+                        self.diagnostics.push(
+                            Diagnostic::error("E0317", "`if` may be missing an `else` clause")
+                                .primary(span, format!("found `{found}`"))
+                                .note(format!("`if` expressions without `else` evaluate to `()`, add an `else` that returns `{found}`")),
+                        );
+                        return None;
+                    }
+                    return Some(Type::Unit);
+                };
+                let else_ty = self.expr(otherwise);
+                let (then_ty, else_ty) = (then_ty?, else_ty?);
+                match unify(then_ty, else_ty) {
+                    Some(ty) => Some(ty),
+                    None => {
+                        let then_span = then.tail.as_ref().map_or(then.span, |tail| tail.span);
+                        self.diagnostics.push(
+                            Diagnostic::error("E0308", "`if` and `else` have incompatible types")
+                                .secondary(
+                                    then_span,
+                                    format!("expected because of this, `{then_ty}`"),
+                                )
+                                .primary(
+                                    otherwise.span,
+                                    format!("expected `{then_ty}`, found `{else_ty}`"),
+                                ),
+                        );
+                        None
+                    }
+                }
+            }
+            ExprKind::Loop(body) => {
+                self.loops.push(LoopFrame {
+                    is_while: false,
+                    ty: None,
+                    first_break: None,
+                });
+                self.block(body);
+                let frame = self.loops.pop()?;
+                match frame.first_break {
+                    None => Some(Type::Never),
+                    Some(_) => frame.ty,
+                }
+            }
+            ExprKind::While { condition, body } => {
+                self.expect(condition, Type::Bool);
+                self.loops.push(LoopFrame {
+                    is_while: true,
+                    ty: Some(Type::Unit),
+                    first_break: None,
+                });
+                self.block(body);
+                self.loops.pop();
+                Some(Type::Unit)
+            }
+            ExprKind::Break(value) => {
+                let found = match value {
+                    Some(value) => self.expr(value),
+                    None => Some(Type::Unit),
+                };
+                let Some(frame) = self.loops.last_mut() else {
+                    self.diagnostics.push(
+                        Diagnostic::error("E0268", "`break` outside of a loop")
+                            .primary(expr.span, "cannot `break` outside of a loop"),
+                    );
+                    return Some(Type::Never);
+                };
+                if frame.is_while && value.is_some() {
+                    self.diagnostics.push(
+                        Diagnostic::error("E0571", "`break` with value from a `while` loop")
+                            .primary(expr.span, "can only break with a value inside `loop`"),
+                    );
+                    return Some(Type::Never);
+                }
+                match (frame.first_break, frame.ty, found) {
+                    (None, _, found) => {
+                        frame.first_break = Some(expr.span);
+                        frame.ty = found;
+                    }
+                    (Some(first), Some(expected), Some(found)) => match unify(expected, found) {
+                        Some(ty) => frame.ty = Some(ty),
+                        None => {
+                            let span = value.as_ref().map_or(expr.span, |value| value.span);
+                            self.diagnostics
+                                .push(mismatch(expected, found, span).secondary(
+                                    first,
+                                    format!("expected because of this `break`, `{expected}`"),
+                                ));
+                        }
+                    },
+                    _ => {}
+                }
+                Some(Type::Never)
+            }
+            ExprKind::Continue => {
+                if self.loops.is_empty() {
+                    self.diagnostics.push(
+                        Diagnostic::error("E0268", "`continue` outside of a loop")
+                            .primary(expr.span, "cannot `continue` outside of a loop"),
+                    );
+                }
+                Some(Type::Never)
+            }
+        }
+    }
+
+    fn binary(&mut self, op: BinaryOp, left: &Expr, right: &Expr, span: Span) -> Ty {
+        let (operand, result) = match op {
+            BinaryOp::And | BinaryOp::Or => (Some(Type::Bool), Type::Bool),
+            BinaryOp::Eq | BinaryOp::Ne => (None, Type::Bool),
+            op if op.is_comparison() => (Some(Type::Int), Type::Bool),
+            _ => (Some(Type::Int), Type::Int),
+        };
+        let left_ty = self.expr(left);
+        let right_ty = self.expr(right);
+        let (left_ty, right_ty) = (left_ty?, right_ty?);
+        let expected = operand.unwrap_or(left_ty);
+        let compatible = [left_ty, right_ty]
+            .iter()
+            .all(|ty| unify(expected, *ty) == Some(expected));
+        if !compatible {
+            self.diagnostics.push(
+                Diagnostic::error(
+                    "E0369",
+                    format!(
+                        "cannot apply binary operator `{}` to `{left_ty}` and `{right_ty}`",
+                        op.symbol()
+                    ),
+                )
+                .secondary(left.span, format!("{left_ty}"))
+                .primary(span, "")
+                .secondary(right.span, format!("{right_ty}")),
+            );
+            return None;
+        }
+        Some(result)
+    }
+}
+
+/*
+ * Devuelve el tipo del programa o todos los errores encontrados
+ */
+pub fn check(program: &Block) -> Result<Type, Vec<Diagnostic>> {
+    let mut checker = Checker {
+        scopes: Vec::new(),
+        loops: Vec::new(),
+        diagnostics: Vec::new(),
+    };
+    let ty = checker.block(program);
+    match ty {
+        _ if !checker.diagnostics.is_empty() => Err(checker.diagnostics),
+        Some(ty) => Ok(ty),
+        None => Ok(Type::Unit),
+    }
+}
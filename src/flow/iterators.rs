@@ -0,0 +1,569 @@
+/*
+ * Un iterador es cualquier tipo que implemente el `trait` `Iterator`, solo hace falta el método
+ * `next()`, que devuelve `Some(item)` mientras haya elementos y `None` al terminar
+ *
+ *  loop {                              for item in iter {
+ *      match iter.next() {                 ...
+ *          Some(item) => ...,    <==>  }
+ *          None => break,
+ *      }
+ *  }
+ *
+ * Un adaptador es un iterador que envuelve a otro y transforma lo que produce, nada se calcula
+ * hasta que alguien llama a `next()`. Aquí están escritos desde cero, sin usar los de `std`
+ */
+use std::collections::VecDeque;
+
+/*
+ * Cuenta hacia atrás desde `from` hasta `1`, como el `while` de `while_flow`
+ */
+#[derive(Debug, Clone)]
+pub struct Countdown {
+    current: u32,
+}
+
+pub fn countdown(from: u32) -> Countdown {
+    Countdown { current: from }
+}
+
+impl Iterator for Countdown {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.current == 0 {
+            return None;
+        }
+        self.current -= 1;
+        Some(self.current + 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.current as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Countdown {}
+
+/*
+ * El primer elemento y después uno de cada `step`
+ *
+ *  [0, 1, 2, 3, 4, 5, 6].every_nth(3)  ->  [0, 3, 6]
+ */
+#[derive(Debug, Clone)]
+pub struct StepBy<I> {
+    iter: I,
+    step: usize,
+    first: bool,
+}
+
+impl<I: Iterator> Iterator for StepBy<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.first {
+            self.first = false;
+            return self.iter.next();
+        }
+        for _ in 1..self.step {
+            self.iter.next()?;
+        }
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iter.size_hint();
+        let count = |len: usize| match self.first {
+            true if len == 0 => 0,
+            true => 1 + (len - 1) / self.step,
+            false => len / self.step,
+        };
+        (count(low), high.map(count))
+    }
+}
+
+/*
+ * Ventanas de `size` elementos que se traslapan, cada ventana avanza un elemento
+ *
+ *  [1, 2, 3, 4].windows(2)  ->  [1, 2], [2, 3], [3, 4]
+ */
+#[derive(Debug, Clone)]
+pub struct Windows<I: Iterator> {
+    iter: I,
+    size: usize,
+    window: VecDeque<I::Item>,
+}
+
+impl<I> Iterator for Windows<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        if self.window.len() == self.size {
+            self.window.pop_front();
+        }
+        while self.window.len() < self.size {
+            self.window.push_back(self.iter.next()?);
+        }
+        Some(self.window.iter().cloned().collect())
+    }
+}
+
+/*
+ * Grupos de `size` elementos que no se traslapan, el último puede ser más corto
+ *
+ *  [1, 2, 3, 4, 5].chunks(2)  ->  [1, 2], [3, 4], [5]
+ */
+#[derive(Debug, Clone)]
+pub struct Chunks<I> {
+    iter: I,
+    size: usize,
+}
+
+impl<I: Iterator> Iterator for Chunks<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        let mut chunk = Vec::with_capacity(self.size);
+        while chunk.len() < self.size {
+            match self.iter.next() {
+                Some(item) => chunk.push(item),
+                None => break,
+            }
+        }
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(chunk)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iter.size_hint();
+        let count = |len: usize| len.div_ceil(self.size);
+        (count(low), high.map(count))
+    }
+}
+
+/*
+ * Alterna un elemento de cada iterador, cuando uno termina sigue con el resto del otro
+ *
+ *  [1, 2, 3].interleave([10, 20])  ->  [1, 10, 2, 20, 3]
+ */
+#[derive(Debug, Clone)]
+pub struct Interleave<I, J> {
+    a: I,
+    b: J,
+    next_b: bool,
+}
+
+impl<I, J> Iterator for Interleave<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.next_b = !self.next_b;
+        if self.next_b {
+            self.a.next().or_else(|| self.b.next())
+        } else {
+            self.b.next().or_else(|| self.a.next())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_low, a_high) = self.a.size_hint();
+        let (b_low, b_high) = self.b.size_hint();
+        let high = match (a_high, b_high) {
+            (Some(a), Some(b)) => a.checked_add(b),
+            _ => None,
+        };
+        (a_low.saturating_add(b_low), high)
+    }
+}
+
+/*
+ * Quita los elementos repetidos que están uno junto al otro
+ *
+ *  [1, 1, 2, 2, 2, 1].dedup()  ->  [1, 2, 1]
+ */
+#[derive(Debug, Clone)]
+pub struct Dedup<I: Iterator> {
+    iter: I,
+    last: Option<I::Item>,
+}
+
+impl<I> Iterator for Dedup<I>
+where
+    I: Iterator,
+    I::Item: PartialEq + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        loop {
+            let item = self.iter.next()?;
+            if self.last.as_ref() != Some(&item) {
+                self.last = Some(item.clone());
+                return Some(item);
+            }
+        }
+    }
+}
+
+/*
+ * Agrupa los elementos consecutivos que tienen la misma clave
+ *
+ *  [1, 3, 2, 4, 5].group_by(|n| n % 2)  ->  (1, [1, 3]), (0, [2, 4]), (1, [5])
+ */
+#[derive(Debug, Clone)]
+pub struct GroupBy<I: Iterator, F, K> {
+    iter: I,
+    key: F,
+    pending: Option<(K, I::Item)>,
+}
+
+impl<I, F, K> Iterator for GroupBy<I, F, K>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    type Item = (K, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<(K, Vec<I::Item>)> {
+        let (key, first) = match self.pending.take() {
+            Some(pending) => pending,
+            None => {
+                let item = self.iter.next()?;
+                ((self.key)(&item), item)
+            }
+        };
+        let mut group = vec![first];
+        for item in self.iter.by_ref() {
+            let next_key = (self.key)(&item);
+            if next_key != key {
+                self.pending = Some((next_key, item));
+                break;
+            }
+            group.push(item);
+        }
+        Some((key, group))
+    }
+}
+
+/*
+ * Todas las parejas de un elemento de cada iterador, el segundo se recorre de nuevo para cada
+ * elemento del primero, por eso tiene que ser `Clone`
+ *
+ *  [1, 2].cartesian(['a', 'b'])  ->  (1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')
+ */
+#[derive(Debug, Clone)]
+pub struct Cartesian<I: Iterator, J> {
+    a: I,
+    current: Option<I::Item>,
+    b: J,
+    b_original: J,
+}
+
+impl<I, J> Iterator for Cartesian<I, J>
+where
+    I: Iterator,
+    I::Item: Clone,
+    J: Iterator + Clone,
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<(I::Item, J::Item)> {
+        loop {
+            if self.current.is_none() {
+                self.current = Some(self.a.next()?);
+                self.b = self.b_original.clone();
+            }
+            match (&self.current, self.b.next()) {
+                (Some(a), Some(b)) => return Some((a.clone(), b)),
+                _ => self.current = None,
+            }
+        }
+    }
+}
+
+/*
+ * Lleva un estado de un elemento al siguiente y produce el estado después de cada paso, como una
+ * variable acumuladora dentro de un `for`
+ *
+ *  [1, 2, 3, 4].accumulate(0, |total, n| total + n)  ->  [1, 3, 6, 10]
+ */
+#[derive(Debug, Clone)]
+pub struct Accumulate<I, S, F> {
+    iter: I,
+    state: S,
+    f: F,
+}
+
+impl<I, S, F> Iterator for Accumulate<I, S, F>
+where
+    I: Iterator,
+    S: Clone,
+    F: FnMut(&S, I::Item) -> S,
+{
+    type Item = S;
+
+    fn next(&mut self) -> Option<S> {
+        let item = self.iter.next()?;
+        self.state = (self.f)(&self.state, item);
+        Some(self.state.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/*
+ * Un `extension trait` agrega métodos a un tipo que no es nuestro. Con la implementación para todo
+ * `I: Iterator`, cualquier iterador puede usar estos adaptadores en una cadena
+ *
+ *  (1..=6).chunks(2).map(|pair| pair.iter().sum::<i32>())  ->  [3, 7, 11]
+ */
+pub trait IteratorExt: Iterator + Sized {
+    /*
+     * `std` ya tiene `step_by`, con el mismo nombre la llamada sería ambigua
+     */
+    fn every_nth(self, step: usize) -> StepBy<Self> {
+        assert!(step != 0, "step must be greater than zero");
+        StepBy {
+            iter: self,
+            step,
+            first: true,
+        }
+    }
+
+    fn windows(self, size: usize) -> Windows<Self> {
+        assert!(size != 0, "window size must be greater than zero");
+        Windows {
+            iter: self,
+            size,
+            window: VecDeque::with_capacity(size),
+        }
+    }
+
+    fn chunks(self, size: usize) -> Chunks<Self> {
+        assert!(size != 0, "chunk size must be greater than zero");
+        Chunks { iter: self, size }
+    }
+
+    fn interleave<J>(self, other: J) -> Interleave<Self, J::IntoIter>
+    where
+        J: IntoIterator<Item = Self::Item>,
+    {
+        Interleave {
+            a: self,
+            b: other.into_iter(),
+            next_b: false,
+        }
+    }
+
+    fn dedup(self) -> Dedup<Self> {
+        Dedup {
+            iter: self,
+            last: None,
+        }
+    }
+
+    fn group_by<K, F>(self, key: F) -> GroupBy<Self, F, K>
+    where
+        F: FnMut(&Self::Item) -> K,
+    {
+        GroupBy {
+            iter: self,
+            key,
+            pending: None,
+        }
+    }
+
+    fn cartesian<J>(self, other: J) -> Cartesian<Self, J::IntoIter>
+    where
+        J: IntoIterator,
+        J::IntoIter: Clone,
+    {
+        let b = other.into_iter();
+        Cartesian {
+            a: self,
+            current: None,
+            b: b.clone(),
+            b_original: b,
+        }
+    }
+
+    fn accumulate<S, F>(self, init: S, f: F) -> Accumulate<Self, S, F>
+    where
+        F: FnMut(&S, Self::Item) -> S,
+    {
+        Accumulate {
+            iter: self,
+            state: init,
+            f,
+        }
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+     * En cada paso el `size_hint` es exacto cuando el iterador de adentro también lo es
+     */
+    fn assert_exact_hints<I: Iterator + Clone>(iter: I) {
+        let mut iter = iter;
+        loop {
+            let remaining = iter.clone().count();
+            assert_eq!(iter.size_hint(), (remaining, Some(remaining)));
+            if iter.next().is_none() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn countdown_counts_to_one() {
+        assert_eq!(countdown(3).collect::<Vec<_>>(), [3, 2, 1]);
+        assert_eq!(countdown(0).next(), None);
+        assert_eq!(countdown(5).len(), 5);
+        assert_exact_hints(countdown(4));
+    }
+
+    #[test]
+    fn every_nth_matches_step_by() {
+        let data: Vec<u32> = (0..23).collect();
+        for len in 0..data.len() {
+            for step in 1..6 {
+                let ours: Vec<u32> = data[..len].iter().copied().every_nth(step).collect();
+                let std: Vec<u32> = data[..len].iter().copied().step_by(step).collect();
+                assert_eq!(ours, std, "len {len}, step {step}");
+                assert_exact_hints(data[..len].iter().every_nth(step));
+            }
+        }
+    }
+
+    #[test]
+    fn windows_match_slice_windows() {
+        let data = [1, 2, 3, 4, 5];
+        for size in 1..=7 {
+            let ours: Vec<Vec<i32>> = data.iter().copied().windows(size).collect();
+            let std: Vec<Vec<i32>> = data.windows(size).map(<[i32]>::to_vec).collect();
+            assert_eq!(ours, std, "size {size}");
+        }
+        /*
+         * Una ventana más grande que la entrada no produce nada
+         */
+        assert_eq!(data.iter().windows(6).next(), None);
+        assert_eq!([0u8; 0].iter().windows(1).next(), None);
+    }
+
+    #[test]
+    fn chunks_match_slice_chunks() {
+        let data = [1, 2, 3, 4, 5, 6, 7];
+        for size in 1..=9 {
+            let ours: Vec<Vec<i32>> = data.iter().copied().chunks(size).collect();
+            let std: Vec<Vec<i32>> = data.chunks(size).map(<[i32]>::to_vec).collect();
+            assert_eq!(ours, std, "size {size}");
+            assert_exact_hints(data.iter().chunks(size));
+        }
+        assert_eq!([0u8; 0].iter().chunks(3).next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be greater than zero")]
+    fn chunks_of_zero_panic() {
+        let _ = [1].iter().chunks(0);
+    }
+
+    #[test]
+    fn interleave_uneven() {
+        let long = [1, 2, 3, 4, 5];
+        let short = [10, 20];
+        assert_eq!(
+            long.iter().copied().interleave(short).collect::<Vec<_>>(),
+            [1, 10, 2, 20, 3, 4, 5]
+        );
+        assert_eq!(
+            short.iter().copied().interleave(long).collect::<Vec<_>>(),
+            [10, 1, 20, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            [0; 0].iter().copied().interleave(short).collect::<Vec<_>>(),
+            [10, 20]
+        );
+        assert_exact_hints(long.iter().interleave(short.iter()));
+        assert_exact_hints(short.iter().interleave(long.iter()));
+    }
+
+    #[test]
+    fn dedup_keeps_separated_repeats() {
+        let data = [1, 1, 2, 2, 2, 1, 3, 3];
+        assert_eq!(data.iter().dedup().copied().collect::<Vec<_>>(), [1, 2, 1, 3]);
+        let mut std = data.to_vec();
+        std.dedup();
+        assert_eq!(data.iter().copied().dedup().collect::<Vec<_>>(), std);
+        assert_eq!([0; 0].iter().dedup().next(), None);
+    }
+
+    #[test]
+    fn group_by_consecutive_keys() {
+        let groups: Vec<(i32, Vec<i32>)> =
+            [1, 3, 2, 4, 5].into_iter().group_by(|n| n % 2).collect();
+        assert_eq!(groups, [(1, vec![1, 3]), (0, vec![2, 4]), (1, vec![5])]);
+        let words = ["ala", "arco", "bote", "a"];
+        let groups: Vec<(char, Vec<&str>)> = words
+            .into_iter()
+            .group_by(|word| word.chars().next().unwrap())
+            .collect();
+        assert_eq!(groups, [('a', vec!["ala", "arco"]), ('b', vec!["bote"]), ('a', vec!["a"])]);
+        assert_eq!([0; 0].into_iter().group_by(|n| *n).next(), None);
+    }
+
+    #[test]
+    fn cartesian_pairs() {
+        let pairs: Vec<(i32, char)> = [1, 2].into_iter().cartesian(['a', 'b', 'c']).collect();
+        let nested: Vec<(i32, char)> = [1, 2]
+            .into_iter()
+            .flat_map(|n| ['a', 'b', 'c'].into_iter().map(move |c| (n, c)))
+            .collect();
+        assert_eq!(pairs, nested);
+        /*
+         * Si cualquiera de los dos lados está vacío no hay parejas
+         */
+        assert_eq!([1, 2].into_iter().cartesian([0u8; 0]).next(), None);
+        assert_eq!([0u8; 0].into_iter().cartesian([1, 2]).next(), None);
+    }
+
+    #[test]
+    fn accumulate_running_state() {
+        let totals: Vec<i32> =
+            [1, 2, 3, 4].into_iter().accumulate(0, |total, n| total + n).collect();
+        assert_eq!(totals, [1, 3, 6, 10]);
+        let longest: Vec<usize> = ["a", "abc", "ab", "abcd"]
+            .into_iter()
+            .accumulate(0, |best, word| (*best).max(word.len()))
+            .collect();
+        assert_eq!(longest, [1, 3, 3, 4]);
+        assert_exact_hints([1, 2, 3].iter().accumulate(0, |total, n| total + n));
+    }
+
+    #[test]
+    fn adapters_chain() {
+        let sums: Vec<i32> = (1..=6).chunks(2).map(|pair| pair.iter().sum::<i32>()).collect();
+        assert_eq!(sums, [3, 7, 11]);
+        let odd_windows: Vec<Vec<i32>> = (1..=9).every_nth(2).windows(2).collect();
+        assert_eq!(odd_windows, [vec![1, 3], vec![3, 5], vec![5, 7], vec![7, 9]]);
+    }
+}
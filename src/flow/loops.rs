@@ -1,9 +1,11 @@
+use crate::flow::iterators::{self, IteratorExt};
+
 /*
  * Una estructura de control repetitivas son necesarias cuando se quiere ejecutar un bloque de código
  * varias veces un número finito de veces. Se caracterizan por tener un punto inicial, y una sentencia
  * que cuando no se cumpla indica que debe de terminar
  */
-fn loop_flow() {
+pub fn loop_flow() {
     /*
      * En `Rust` existe una estructura de control `loop` que repite un bloque de código indefinidamente
      * hasta que se evalue una instrucción que lo detenga explicitamente
@@ -31,10 +33,15 @@ fn loop_flow() {
         }
     }; // -> Los bloques de una `expression` siempre termina en un `;`
     println!("Result: {result}");
-    
+    /*
+     * El mismo `loop` como cadena de iteradores, `find` hace el papel de `break` y devuelve el
+     * primer elemento que cumple la condición
+     */
+    let result = (1..).find(|counter| *counter == 10).map(|counter| counter + 12);
+    println!("Result: {:?}", result);
 }
 
-fn while_flow() {
+pub fn while_flow() {
     /*
      * El flujo de control `while` es una estructura repetitiva que siempre se va a ejecutar
      * simpre y cuando la condición sea `true` y terminar cunado sea `false`
@@ -54,10 +61,19 @@ fn while_flow() {
         counter -= 1;
     }
     println!("LIFTOFF!!");
-
+    /*
+     * `Countdown` es un iterador que guarda el contador por dentro, el `for` ya no necesita la
+     * variable mutable ni la condición. Aquí los números salen todos al mismo tiempo,
+     * `flow::timers` agrega un segundo entre cada uno, `cargo run -- countdown 3` lo muestra con el
+     * reloj real
+     */
+    for counter in iterators::countdown(3) {
+        println!("Counting... {}", counter);
+    }
+    println!("LIFTOFF!!");
 }
 
-fn for_flow() {
+pub fn for_flow() {
     let a = [10, 20, 30, 40, 50];
     /*
      * Esta forma de recorrer una colección es valida, pero puede ocasionar una serie de errores
@@ -88,5 +104,119 @@ fn for_flow() {
     for (index, value) in numbers.iter().enumerate() {
         println!("The value at index {} is {}", index, value);
     }
+}
+
+/*
+ * Cada patrón aparece dos veces, primero como un ciclo escrito a mano y después como una cadena de
+ * adaptadores de `flow::iterators`
+ */
+pub fn adaptors_flow() {
+    let readings = [3, 3, 5, 8, 8, 8, 2, 2, 9];
+    /*
+     * Las diferencias entre lecturas consecutivas, a mano hay que cuidar el `index + 1`
+     */
+    let mut differences = Vec::new();
+    let mut index = 0;
+    while index + 1 < readings.len() {
+        differences.push(readings[index + 1] - readings[index]);
+        index += 1;
+    }
+    let chained: Vec<i32> = readings.iter().windows(2).map(|pair| pair[1] - pair[0]).collect();
+    println!("Differences: {:?} {:?}", differences, chained);
+    /*
+     * Sumas de tres en tres, el último grupo puede quedar incompleto
+     */
+    let mut sums = Vec::new();
+    let mut current = 0;
+    for (index, value) in readings.iter().enumerate() {
+        current += value;
+        if index % 3 == 2 || index == readings.len() - 1 {
+            sums.push(current);
+            current = 0;
+        }
+    }
+    let chained: Vec<i32> = readings
+        .iter()
+        .chunks(3)
+        .map(|chunk| chunk.into_iter().sum())
+        .collect();
+    println!("Sums of three: {:?} {:?}", sums, chained);
+    /*
+     * Quitar los repetidos consecutivos comparando con el último que se guardó
+     */
+    let mut unique: Vec<i32> = Vec::new();
+    for value in readings {
+        if unique.last() != Some(&value) {
+            unique.push(value);
+        }
+    }
+    let chained: Vec<i32> = readings.into_iter().dedup().collect();
+    println!("Without repeats: {:?} {:?}", unique, chained);
+    /*
+     * Contar cuántas veces se repite cada lectura seguida
+     */
+    let mut runs: Vec<(i32, usize)> = Vec::new();
+    for value in readings {
+        match runs.last_mut() {
+            Some((last, count)) if *last == value => *count += 1,
+            _ => runs.push((value, 1)),
+        }
+    }
+    let chained: Vec<(i32, usize)> = readings
+        .into_iter()
+        .group_by(|value| *value)
+        .map(|(value, group)| (value, group.len()))
+        .collect();
+    println!("Runs: {:?} {:?}", runs, chained);
+    /*
+     * El total acumulado después de cada lectura
+     */
+    let mut totals = Vec::new();
+    let mut total = 0;
+    for value in readings {
+        total += value;
+        totals.push(total);
+    }
+    let chained: Vec<i32> = readings
+        .into_iter()
+        .accumulate(0, |total, value| total + value)
+        .collect();
+    println!("Running total: {:?} {:?}", totals, chained);
+    /*
+     * Una lectura de cada cuatro, empezando por la primera
+     */
+    let mut sampled = Vec::new();
+    let mut index = 0;
+    while index < readings.len() {
+        sampled.push(readings[index]);
+        index += 4;
+    }
+    let chained: Vec<i32> = readings.into_iter().every_nth(4).collect();
+    println!("Every 4th: {:?} {:?}", sampled, chained);
+    /*
+     * Alternar dos listas y formar todas las parejas posibles con dos ciclos anidados
+     */
+    let (left, right) = ([1, 2, 3], [10, 20]);
+    let mut mixed: Vec<i32> = Vec::new();
+    for index in 0..left.len().max(right.len()) {
+        mixed.extend(left.get(index).copied());
+        mixed.extend(right.get(index).copied());
+    }
+    let chained: Vec<i32> = left.into_iter().interleave(right).collect();
+    println!("Interleaved: {:?} {:?}", mixed, chained);
+    let mut pairs = Vec::new();
+    for a in left {
+        for b in right {
+            pairs.push((a, b));
+        }
+    }
+    let chained: Vec<(i32, i32)> = left.into_iter().cartesian(right).collect();
+    println!("Pairs: {:?} {:?}", pairs, chained);
+}
 
+pub fn run() {
+    loop_flow();
+    while_flow();
+    for_flow();
+    adaptors_flow();
 }
\ No newline at end of file
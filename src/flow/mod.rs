@@ -0,0 +1,6 @@
+pub mod if_expression;
+pub mod interpreter;
+pub mod iterators;
+pub mod loops;
+pub mod rules;
+pub mod timers;
@@ -0,0 +1,615 @@
+/*
+ * Una cadena de `if` / `else if` es una lista de reglas en orden, gana la primera condición que sea
+ * `true`. Aquí cada regla es un dato, una condición, una etiqueta y una prioridad, y la lista se
+ * puede armar, combinar y explicar en tiempo de ejecución
+ *
+ *  if n >= 100 { "out of limit" }        Rule::new("out of limit", 30, n >= 100)
+ *  else if n == 0 { "zero" }       ==>   Rule::new("zero", 20, n == 0)
+ *  else if n % 2 == 0 { "even" }         Rule::new("even", 10, n % 2 == 0)
+ *  else { "odd" }                        Rule::new("odd", 0, always)
+ *
+ * Con `Mode::FirstMatch` se comporta como la cadena de `if`, con `Mode::AllMatches` se obtienen
+ * todas las etiquetas que aplican
+ */
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::fmt::{self, Write};
+use std::rc::Rc;
+
+/*
+ * Una condición con su descripción, la descripción es lo que aparece en la explicación. Se puede
+ * clonar porque el `closure` se comparte con `Rc`
+ */
+pub struct Predicate<T: ?Sized> {
+    description: String,
+    test: Rc<dyn Fn(&T) -> bool>,
+}
+
+impl<T: ?Sized> Clone for Predicate<T> {
+    fn clone(&self) -> Self {
+        Self {
+            description: self.description.clone(),
+            test: Rc::clone(&self.test),
+        }
+    }
+}
+
+impl<T: ?Sized + 'static> Predicate<T> {
+    pub fn new(description: impl Into<String>, test: impl Fn(&T) -> bool + 'static) -> Self {
+        Self {
+            description: description.into(),
+            test: Rc::new(test),
+        }
+    }
+
+    pub fn always() -> Self {
+        Self::new("always", |_| true)
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn test(&self, value: &T) -> bool {
+        (self.test)(value)
+    }
+
+    /*
+     * Igual que `&&`, el lado derecho no se evalúa si el izquierdo es `false`
+     */
+    pub fn and(self, other: Self) -> Self {
+        let description = format!("({} && {})", self.description, other.description);
+        Self::new(description, move |value| {
+            self.test(value) && other.test(value)
+        })
+    }
+
+    pub fn or(self, other: Self) -> Self {
+        let description = format!("({} || {})", self.description, other.description);
+        Self::new(description, move |value| {
+            self.test(value) || other.test(value)
+        })
+    }
+
+    pub fn negate(self) -> Self {
+        let description = match self.description.starts_with('(') {
+            true => format!("!{}", self.description),
+            false => format!("!({})", self.description),
+        };
+        Self::new(description, move |value| !self.test(value))
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Predicate<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Predicate({})", self.description)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Rule<T: ?Sized> {
+    pub label: String,
+    /*
+     * Las reglas con mayor prioridad se evalúan primero, con la misma prioridad se respeta el
+     * orden en que se agregaron
+     */
+    pub priority: i32,
+    pub predicate: Predicate<T>,
+}
+
+impl<T: ?Sized + 'static> Rule<T> {
+    pub fn new(label: impl Into<String>, priority: i32, predicate: Predicate<T>) -> Self {
+        Self {
+            label: label.into(),
+            priority,
+            predicate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    FirstMatch,
+    AllMatches,
+}
+
+/*
+ * Una línea de la explicación, una por cada regla que se evaluó
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub label: String,
+    pub priority: i32,
+    pub condition: String,
+    pub matched: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub mode: Mode,
+    pub labels: Vec<String>,
+    pub trace: Vec<Step>,
+    /*
+     * Las reglas que no se evaluaron porque `FirstMatch` ya encontró una
+     */
+    pub skipped: usize,
+}
+
+impl Outcome {
+    pub fn first(&self) -> Option<&str> {
+        self.labels.first().map(String::as_str)
+    }
+
+    pub fn matches(&self, label: &str) -> bool {
+        self.labels.iter().any(|found| found == label)
+    }
+
+    /*
+     * Cada regla evaluada en orden, con su prioridad, su condición y si se cumplió
+     *
+     *   1. [ 30] out of limit  n >= 100    no
+     *   2. [ 20] zero          n == 0      no
+     *   3. [ 10] even          n % 2 == 0  yes  <- first match
+     */
+    pub fn explain(&self) -> String {
+        let label_width = self
+            .trace
+            .iter()
+            .map(|step| step.label.len())
+            .max()
+            .unwrap_or(0);
+        let condition_width = self
+            .trace
+            .iter()
+            .map(|step| step.condition.len())
+            .max()
+            .unwrap_or(0);
+        let mut out = String::new();
+        let mut first = true;
+        for (index, step) in self.trace.iter().enumerate() {
+            let _ = write!(
+                out,
+                "{:>3}. [{:>3}] {:<label_width$}  {:<condition_width$}  {}",
+                index + 1,
+                step.priority,
+                step.label,
+                step.condition,
+                if step.matched { "yes" } else { "no" },
+            );
+            if step.matched && first && self.mode == Mode::FirstMatch {
+                out.push_str("  <- first match");
+            }
+            first &= !step.matched;
+            out.push('\n');
+        }
+        if self.skipped > 0 {
+            let rules = if self.skipped == 1 { "rule" } else { "rules" };
+            let _ = writeln!(out, "     {} more {rules} not evaluated", self.skipped);
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleSet<T: ?Sized> {
+    rules: Vec<Rule<T>>,
+}
+
+impl<T: ?Sized + 'static> Default for RuleSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ?Sized + 'static> RuleSet<T> {
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    pub fn rule(
+        mut self,
+        label: impl Into<String>,
+        priority: i32,
+        predicate: Predicate<T>,
+    ) -> Self {
+        self.push(Rule::new(label, priority, predicate));
+        self
+    }
+
+    /*
+     * Inserta la regla después de todas las que tienen una prioridad mayor o igual, así la lista
+     * siempre está en el orden de evaluación
+     */
+    pub fn push(&mut self, rule: Rule<T>) {
+        let index = self
+            .rules
+            .iter()
+            .position(|existing| existing.priority < rule.priority)
+            .unwrap_or(self.rules.len());
+        self.rules.insert(index, rule);
+    }
+
+    /*
+     * Une dos conjuntos de reglas, cada una conserva su prioridad
+     */
+    pub fn merge(mut self, other: RuleSet<T>) -> Self {
+        for rule in other.rules {
+            self.push(rule);
+        }
+        self
+    }
+
+    pub fn rules(&self) -> &[Rule<T>] {
+        &self.rules
+    }
+
+    pub fn evaluate(&self, value: &T, mode: Mode) -> Outcome {
+        let mut outcome = Outcome {
+            mode,
+            labels: Vec::new(),
+            trace: Vec::new(),
+            skipped: 0,
+        };
+        for (index, rule) in self.rules.iter().enumerate() {
+            let matched = rule.predicate.test(value);
+            outcome.trace.push(Step {
+                label: rule.label.clone(),
+                priority: rule.priority,
+                condition: rule.predicate.description().to_string(),
+                matched,
+            });
+            if matched {
+                outcome.labels.push(rule.label.clone());
+                if mode == Mode::FirstMatch {
+                    outcome.skipped = self.rules.len() - index - 1;
+                    break;
+                }
+            }
+        }
+        outcome
+    }
+
+    /*
+     * La etiqueta de la primera regla que se cumple, como el valor de una cadena de `if`
+     */
+    pub fn classify(&self, value: &T) -> Option<String> {
+        self.rules
+            .iter()
+            .find(|rule| rule.predicate.test(value))
+            .map(|rule| rule.label.clone())
+    }
+}
+
+/*
+ * Los clasificadores de números, cada uno cubre todos los casos con reglas que no se traslapan
+ *
+ * `classify` necesita que `perfection` termine en poco tiempo, por eso solo acepta hasta
+ * `MAX_DIVISOR_SUM`. La factorización por división llega como mucho a la raíz, `10^6` vueltas
+ */
+pub const MAX_DIVISOR_SUM: i64 = 1_000_000_000_000;
+
+/*
+ * `Miller-Rabin` con bases fijas, para `n < 3.3 * 10^24` probar los primeros doce primos como
+ * testigos no tiene falsos positivos, así que para `i64` la respuesta es exacta
+ *
+ *  n - 1 = d * 2^s    con `d` impar
+ *  `n` es primo si para cada base `a`, `a^d % n == 1` o `a^(d * 2^r) % n == n - 1` para algún
+ *  `r < s`
+ */
+pub fn is_prime(n: i64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    let n = n as u64;
+    if let Some(&small) = WITNESSES.iter().find(|&&witness| n.is_multiple_of(witness)) {
+        return n == small;
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let mul = |a: u64, b: u64| (a as u128 * b as u128 % n as u128) as u64;
+    let pow = |mut base: u64, mut exp: u64| {
+        let mut result = 1;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = mul(result, base);
+            }
+            base = mul(base, base);
+            exp >>= 1;
+        }
+        result
+    };
+    WITNESSES.iter().all(|&witness| {
+        let mut x = pow(witness, d);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul(x, x);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/*
+ * La suma de los divisores de `n` sin contar a `n`. La suma de todos los divisores se arma con la
+ * factorización, cada primo `p` que aparece `k` veces aporta `1 + p + ... + p^k`, y en cuanto lo
+ * que queda sin factorizar es primo se deja de dividir
+ *
+ *  28 = 2^2 * 7  ->  (1 + 2 + 4) * (1 + 7) - 28  =  56 - 28  =  1 + 2 + 4 + 7 + 14  =  28
+ */
+pub fn proper_divisor_sum(n: i64) -> i64 {
+    if n < 2 {
+        return 0;
+    }
+    let mut rest = n;
+    let mut total: i128 = 1;
+    let mut divisor = 2;
+    let mut rest_is_prime = is_prime(rest);
+    while !rest_is_prime && divisor <= rest / divisor {
+        if rest % divisor == 0 {
+            let (mut power, mut sum): (i128, i128) = (1, 1);
+            while rest % divisor == 0 {
+                rest /= divisor;
+                power *= divisor as i128;
+                sum += power;
+            }
+            total *= sum;
+            rest_is_prime = is_prime(rest);
+        }
+        divisor += if divisor == 2 { 1 } else { 2 };
+    }
+    if rest > 1 {
+        total *= 1 + rest as i128;
+    }
+    i64::try_from(total - n as i128).unwrap_or(i64::MAX)
+}
+
+/*
+ * Con `Mode::AllMatches` varias reglas preguntan lo mismo por el mismo número, el resultado del
+ * último número se guarda y cada valor se calcula una sola vez por número
+ */
+fn remember<R: Copy + 'static>(compute: impl Fn(i64) -> R + 'static) -> impl Fn(i64) -> R + Clone {
+    let compute: Rc<dyn Fn(i64) -> R> = Rc::new(compute);
+    let last: Rc<Cell<Option<(i64, R)>>> = Rc::new(Cell::new(None));
+    move |n| match last.get() {
+        Some((value, result)) if value == n => result,
+        _ => {
+            let result = compute(n);
+            last.set(Some((n, result)));
+            result
+        }
+    }
+}
+
+pub fn parity() -> RuleSet<i64> {
+    RuleSet::new()
+        .rule(
+            "even",
+            0,
+            Predicate::new("n % 2 == 0", |n: &i64| n % 2 == 0),
+        )
+        .rule("odd", 0, Predicate::new("n % 2 != 0", |n: &i64| n % 2 != 0))
+}
+
+pub fn sign() -> RuleSet<i64> {
+    RuleSet::new()
+        .rule("negative", 0, Predicate::new("n < 0", |n: &i64| *n < 0))
+        .rule("zero", 0, Predicate::new("n == 0", |n: &i64| *n == 0))
+        .rule("positive", 0, Predicate::new("n > 0", |n: &i64| *n > 0))
+}
+
+/*
+ * `low..=high`, por debajo, dentro o por encima
+ */
+pub fn range(low: i64, high: i64) -> RuleSet<i64> {
+    RuleSet::new()
+        .rule(
+            format!("below {low}"),
+            0,
+            Predicate::new(format!("n < {low}"), move |n: &i64| *n < low),
+        )
+        .rule(
+            format!("within {low}..={high}"),
+            0,
+            Predicate::new(format!("{low} <= n <= {high}"), move |n: &i64| {
+                (low..=high).contains(n)
+            }),
+        )
+        .rule(
+            format!("above {high}"),
+            0,
+            Predicate::new(format!("n > {high}"), move |n: &i64| *n > high),
+        )
+}
+
+/*
+ * El `0` y el `1` no son primos ni compuestos
+ */
+pub fn primality() -> RuleSet<i64> {
+    let is_prime = remember(is_prime);
+    let prime = Predicate::new("is_prime(n)", move |n: &i64| is_prime(*n));
+    let at_least_two = Predicate::new("n >= 2", |n: &i64| *n >= 2);
+    RuleSet::new()
+        .rule("prime", 0, prime.clone())
+        .rule("composite", 0, at_least_two.clone().and(prime.negate()))
+        .rule("neither prime nor composite", 0, at_least_two.negate())
+}
+
+/*
+ * Un número es perfecto si la suma de sus divisores propios es igual a él, abundante si es mayor
+ * y deficiente si es menor. Solo aplica a los positivos
+ */
+pub fn perfection() -> RuleSet<i64> {
+    let positive = || Predicate::new("n > 0", |n: &i64| *n > 0);
+    let divisor_sum = remember(proper_divisor_sum);
+    let compare = |description: &str, expected: Ordering| {
+        let divisor_sum = divisor_sum.clone();
+        Predicate::new(description, move |n: &i64| divisor_sum(*n).cmp(n) == expected)
+    };
+    RuleSet::new()
+        .rule(
+            "perfect",
+            0,
+            positive().and(compare("divisor_sum(n) == n", Ordering::Equal)),
+        )
+        .rule(
+            "abundant",
+            0,
+            positive().and(compare("divisor_sum(n) > n", Ordering::Greater)),
+        )
+        .rule(
+            "deficient",
+            0,
+            positive().and(compare("divisor_sum(n) < n", Ordering::Less)),
+        )
+}
+
+/*
+ * Todos los clasificadores juntos, pensado para `Mode::AllMatches`
+ */
+pub fn classifier(max: i64) -> RuleSet<i64> {
+    sign()
+        .merge(parity())
+        .merge(range(0, max))
+        .merge(primality())
+        .merge(perfection())
+}
+
+/*
+ * La cadena de `if` de `flow/if_expression.rs` escrita como reglas, el orden importa, el `0` es
+ * par y por eso su regla tiene más prioridad que la de `even`
+ */
+pub fn limit_chain(max: i64) -> RuleSet<i64> {
+    RuleSet::new()
+        .rule(
+            "out of limit",
+            30,
+            Predicate::new(format!("n >= {max}"), move |n: &i64| *n >= max),
+        )
+        .rule("zero", 20, Predicate::new("n == 0", |n: &i64| *n == 0))
+        .rule(
+            format!("even and less than {max}"),
+            10,
+            Predicate::new("n % 2 == 0", |n: &i64| n % 2 == 0),
+        )
+        .rule(format!("odd and less than {max}"), 0, Predicate::always())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+     * Las versiones lentas contra las que se comparan las rápidas
+     */
+    fn naive_is_prime(n: i64) -> bool {
+        n >= 2 && (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0)
+    }
+
+    fn naive_divisor_sum(n: i64) -> i64 {
+        (1..n).filter(|d| n % d == 0).sum()
+    }
+
+    #[test]
+    fn is_prime_matches_trial_division() {
+        for n in -10..20_000 {
+            assert_eq!(is_prime(n), naive_is_prime(n), "{n}");
+        }
+        /*
+         * `561` es un número de `Carmichael` y `3215031751` engaña a las bases `2`, `3`, `5` y `7`
+         */
+        for composite in [561, 3_215_031_751, 999_966_000_289, i64::MAX] {
+            assert!(!is_prime(composite), "{composite}");
+        }
+        for prime in [999_999_999_989, 2_147_483_647, 9_223_372_036_854_775_783] {
+            assert!(is_prime(prime), "{prime}");
+        }
+    }
+
+    #[test]
+    fn divisor_sum_matches_naive() {
+        for n in -5..3_000 {
+            assert_eq!(proper_divisor_sum(n), naive_divisor_sum(n.max(0)), "{n}");
+        }
+        for perfect in [6, 28, 496, 8128, 33_550_336, 8_589_869_056] {
+            assert_eq!(proper_divisor_sum(perfect), perfect);
+        }
+        assert_eq!(proper_divisor_sum(999_999_999_989), 1);
+        assert_eq!(proper_divisor_sum(999_966_000_289), 1 + 999_983);
+    }
+
+    #[test]
+    fn remember_computes_once_per_value() {
+        let calls = Rc::new(Cell::new(0));
+        let counted = Rc::clone(&calls);
+        let square = remember(move |n| {
+            counted.set(counted.get() + 1);
+            n * n
+        });
+        let copy = square.clone();
+        assert_eq!((square(3), copy(3), square(3)), (9, 9, 9));
+        assert_eq!(calls.get(), 1);
+        assert_eq!(square(4), 16);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn classifier_labels() {
+        let classifier = classifier(100);
+        let labels = |n: i64| classifier.evaluate(&n, Mode::AllMatches).labels;
+        assert_eq!(
+            labels(28),
+            ["positive", "even", "within 0..=100", "composite", "perfect"]
+        );
+        assert_eq!(
+            labels(0),
+            ["zero", "even", "within 0..=100", "neither prime nor composite"]
+        );
+        assert_eq!(
+            labels(-7),
+            ["negative", "odd", "below 0", "neither prime nor composite"]
+        );
+        assert_eq!(
+            labels(MAX_DIVISOR_SUM - 11),
+            ["positive", "odd", "above 100", "prime", "deficient"]
+        );
+        assert_eq!(labels(12)[4], "abundant");
+    }
+
+    #[test]
+    fn limit_chain_is_first_match() {
+        let chain = limit_chain(100);
+        let first = |n: i64| chain.classify(&n);
+        assert_eq!(first(100).as_deref(), Some("out of limit"));
+        assert_eq!(first(0).as_deref(), Some("zero"));
+        assert_eq!(first(42).as_deref(), Some("even and less than 100"));
+        assert_eq!(first(7).as_deref(), Some("odd and less than 100"));
+
+        let outcome = chain.evaluate(&42, Mode::FirstMatch);
+        assert_eq!(outcome.first(), Some("even and less than 100"));
+        assert_eq!(outcome.skipped, 1);
+        assert_eq!(
+            outcome.explain(),
+            "  1. [ 30] out of limit            n >= 100    no\n\
+             \x20 2. [ 20] zero                    n == 0      no\n\
+             \x20 3. [ 10] even and less than 100  n % 2 == 0  yes  <- first match\n\
+             \x20    1 more rule not evaluated\n"
+        );
+    }
+
+    #[test]
+    fn predicates_compose() {
+        let even = Predicate::new("n % 2 == 0", |n: &i64| n % 2 == 0);
+        let small = Predicate::new("n < 10", |n: &i64| *n < 10);
+        let both = even.clone().and(small.clone());
+        assert_eq!(both.description(), "(n % 2 == 0 && n < 10)");
+        assert_eq!(both.clone().negate().description(), "!(n % 2 == 0 && n < 10)");
+        assert_eq!(even.clone().negate().description(), "!(n % 2 == 0)");
+        assert!(both.test(&4) && !both.test(&12) && !both.test(&3));
+        assert!(even.or(small).test(&3));
+    }
+}
@@ -37,6 +37,10 @@ fn statements_expressions() {
         let x = a + 5;
         x + 1 // -> es el valor devuelto por el bloque
     };
+    /*
+     * `cargo run -- expr --tree` ejecuta este mismo bloque en un intérprete pequeño y marca cada
+     * nodo como `statement` o `expression`
+     */
     println!("Value of X: {x} and value of y: {y}");
 
 }
@@ -0,0 +1,543 @@
+/*
+ * Un planificador cooperativo, no hay hilos ni `async`. Cada tarea tiene una fecha límite
+ * `deadline` y el ciclo principal duerme hasta la más cercana, ejecuta las que ya vencieron y repite
+ *
+ *  tiempo   0ms        1000ms       2000ms       3000ms
+ *           |------------|------------|------------|
+ *  every    *            *            *            *        periódica cada 1000ms
+ *  once                       *                             una sola vez a los 1500ms
+ *
+ * El reloj es un `trait`, `SystemClock` usa el tiempo real y `MockClock` solo avanza cuando se le
+ * pide, así se puede simular una hora de tareas en un instante
+ */
+use std::cell::Cell;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt;
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/*
+ * El tiempo se mide como `Duration` desde que se creó el reloj
+ */
+pub trait Clock {
+    fn now(&self) -> Duration;
+    fn sleep(&self, duration: Duration);
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/*
+ * Las copias de un `MockClock` comparten el mismo tiempo, una se le da al planificador y con la
+ * otra se avanza el tiempo desde fuera. `sleep` avanza el tiempo sin esperar
+ */
+#[derive(Debug, Clone, Default)]
+pub struct MockClock {
+    now: Rc<Cell<Duration>>,
+}
+
+impl MockClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+
+    pub fn set(&self, now: Duration) {
+        self.now.set(now);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TaskId(u64);
+
+impl fmt::Display for TaskId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/*
+ * Cancela una tarea desde fuera del planificador o desde otra tarea. Cancelar es idempotente y una
+ * tarea cancelada no vuelve a ejecutarse aunque ya haya vencido
+ */
+#[derive(Debug, Clone)]
+pub struct Handle {
+    id: TaskId,
+    cancelled: Rc<Cell<bool>>,
+}
+
+impl Handle {
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.set(true);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.get()
+    }
+}
+
+/*
+ * Lo que recibe una tarea cada vez que se ejecuta
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tick {
+    pub id: TaskId,
+    /*
+     * Empieza en `1`
+     */
+    pub run: u64,
+    pub deadline: Duration,
+    pub now: Duration,
+    /*
+     * Los periodos que se saltaron porque el ciclo llegó tarde
+     */
+    pub missed: u64,
+}
+
+impl Tick {
+    pub fn late(&self) -> Duration {
+        self.now.saturating_sub(self.deadline)
+    }
+}
+
+enum Schedule {
+    Once,
+    Every(Duration),
+}
+
+struct Task {
+    schedule: Schedule,
+    cancelled: Rc<Cell<bool>>,
+    run: u64,
+    action: Box<dyn FnMut(&Tick)>,
+}
+
+pub struct Scheduler<C: Clock> {
+    clock: C,
+    next_id: u64,
+    tasks: HashMap<TaskId, Task>,
+    /*
+     * El `BinaryHeap` de `std` devuelve el mayor, con `Reverse` devuelve la fecha más cercana. El
+     * `TaskId` desempata, a la misma hora se ejecuta primero la tarea más antigua
+     */
+    queue: BinaryHeap<Reverse<(Duration, TaskId)>>,
+}
+
+impl<C: Clock> Scheduler<C> {
+    pub fn new(clock: C) -> Self {
+        Self {
+            clock,
+            next_id: 0,
+            tasks: HashMap::new(),
+            queue: BinaryHeap::new(),
+        }
+    }
+
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    pub fn now(&self) -> Duration {
+        self.clock.now()
+    }
+
+    fn add(
+        &mut self,
+        deadline: Duration,
+        schedule: Schedule,
+        cancelled: Rc<Cell<bool>>,
+        action: Box<dyn FnMut(&Tick)>,
+    ) -> Handle {
+        self.next_id += 1;
+        let id = TaskId(self.next_id);
+        self.tasks.insert(
+            id,
+            Task {
+                schedule,
+                cancelled: Rc::clone(&cancelled),
+                run: 0,
+                action,
+            },
+        );
+        self.queue.push(Reverse((deadline, id)));
+        Handle { id, cancelled }
+    }
+
+    /*
+     * Un `timer` de una sola vez que vence después de `delay`
+     */
+    pub fn once(&mut self, delay: Duration, action: impl FnMut(&Tick) + 'static) -> Handle {
+        let deadline = self.now() + delay;
+        let cancelled = Rc::new(Cell::new(false));
+        self.add(deadline, Schedule::Once, cancelled, Box::new(action))
+    }
+
+    /*
+     * Una tarea periódica, la primera vez vence después de un `period`. Las fechas se calculan
+     * desde el inicio y no desde la última ejecución, así el retraso de una vuelta no se suma a
+     * las siguientes
+     *
+     *  deadline(n) = inicio + n * period        sin `drift`
+     *  deadline(n) = ejecución(n - 1) + period  con `drift`, cada retraso se acumula
+     */
+    pub fn every(&mut self, period: Duration, action: impl FnMut(&Tick) + 'static) -> Handle {
+        assert!(!period.is_zero(), "period must be greater than zero");
+        let deadline = self.now() + period;
+        let cancelled = Rc::new(Cell::new(false));
+        self.add(
+            deadline,
+            Schedule::Every(period),
+            cancelled,
+            Box::new(action),
+        )
+    }
+
+    /*
+     * Cuenta desde `from` hasta `1`, el primero de inmediato y uno más por `period`, y un
+     * `period` después del `1` llama a `done`. Es el `while` de `flow::loops::while_flow` con
+     * tiempo entre cada vuelta
+     */
+    pub fn countdown(
+        &mut self,
+        from: u32,
+        period: Duration,
+        mut on_tick: impl FnMut(u32) + 'static,
+        done: impl FnOnce() + 'static,
+    ) -> Handle {
+        assert!(!period.is_zero(), "period must be greater than zero");
+        let cancelled = Rc::new(Cell::new(false));
+        let stop = Rc::clone(&cancelled);
+        let mut remaining = from;
+        let mut done = Some(done);
+        let action = move |_: &Tick| {
+            if remaining == 0 {
+                stop.set(true);
+                if let Some(done) = done.take() {
+                    done();
+                }
+                return;
+            }
+            on_tick(remaining);
+            remaining -= 1;
+        };
+        /*
+         * La tarea se cancela a sí misma con la misma bandera que ve el `Handle`
+         */
+        let deadline = self.now();
+        self.add(
+            deadline,
+            Schedule::Every(period),
+            cancelled,
+            Box::new(action),
+        )
+    }
+
+    /*
+     * Las tareas que siguen activas, sin contar las canceladas
+     */
+    pub fn len(&self) -> usize {
+        self.tasks
+            .values()
+            .filter(|task| !task.cancelled.get())
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /*
+     * La fecha de la próxima tarea activa, las canceladas se descartan al pasar por aquí
+     */
+    pub fn next_deadline(&mut self) -> Option<Duration> {
+        while let Some(Reverse((deadline, id))) = self.queue.peek().copied() {
+            match self.tasks.get(&id) {
+                Some(task) if !task.cancelled.get() => return Some(deadline),
+                _ => {
+                    self.queue.pop();
+                    self.tasks.remove(&id);
+                }
+            }
+        }
+        None
+    }
+
+    /*
+     * Ejecuta todas las tareas que ya vencieron y devuelve cuántas se ejecutaron. No duerme
+     */
+    pub fn run_pending(&mut self) -> usize {
+        let now = self.now();
+        let mut ran = 0;
+        while let Some(deadline) = self.next_deadline() {
+            if deadline > now {
+                break;
+            }
+            let Some(Reverse((_, id))) = self.queue.pop() else {
+                break;
+            };
+            let Some(task) = self.tasks.get_mut(&id) else {
+                continue;
+            };
+            task.run += 1;
+            let (next, missed) = match task.schedule {
+                Schedule::Once => (None, 0),
+                Schedule::Every(period) => {
+                    /*
+                     * Si el ciclo llegó tarde por más de un periodo, los periodos perdidos no se
+                     * ejecutan de golpe, se saltan y se informan en `missed`
+                     */
+                    let behind = (now - deadline).as_nanos() / period.as_nanos();
+                    let missed = u64::try_from(behind).unwrap_or(u64::MAX);
+                    let skip = u32::try_from(behind + 1).unwrap_or(u32::MAX);
+                    (Some(deadline + period * skip), missed)
+                }
+            };
+            let tick = Tick {
+                id,
+                run: task.run,
+                deadline,
+                now,
+                missed,
+            };
+            (task.action)(&tick);
+            ran += 1;
+            match next {
+                Some(next) if !task.cancelled.get() => self.queue.push(Reverse((next, id))),
+                _ => {
+                    self.tasks.remove(&id);
+                }
+            }
+        }
+        ran
+    }
+
+    /*
+     * Duerme hasta cada fecha y ejecuta las tareas hasta que no quede ninguna o pase `limit`.
+     * Devuelve cuántas ejecuciones hubo
+     */
+    pub fn run_for(&mut self, limit: Duration) -> usize {
+        let end = self.now().saturating_add(limit);
+        let mut ran = 0;
+        while let Some(deadline) = self.next_deadline() {
+            if deadline > end {
+                break;
+            }
+            let now = self.now();
+            if deadline > now {
+                self.clock.sleep(deadline - now);
+            }
+            ran += self.run_pending();
+        }
+        ran
+    }
+
+    /*
+     * Igual que `run_for` sin límite, una tarea periódica que nunca se cancela no termina
+     */
+    pub fn run_until_idle(&mut self) -> usize {
+        self.run_for(Duration::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    /*
+     * Un registro compartido entre las tareas y la prueba, cada entrada dice qué pasó y a qué hora
+     */
+    fn log() -> Rc<RefCell<Vec<String>>> {
+        Rc::new(RefCell::new(Vec::new()))
+    }
+
+    #[test]
+    fn every_is_drift_free() {
+        let clock = MockClock::new();
+        let mut scheduler = Scheduler::new(clock.clone());
+        let ticks = Rc::new(RefCell::new(Vec::new()));
+        let seen = Rc::clone(&ticks);
+        scheduler.every(ms(100), move |tick| seen.borrow_mut().push(*tick));
+
+        clock.set(ms(100));
+        assert_eq!(scheduler.run_pending(), 1);
+        /*
+         * Tarde por menos de un periodo, la siguiente fecha sigue en `n * period` y no en
+         * `now + period`
+         */
+        clock.set(ms(230));
+        assert_eq!(scheduler.run_pending(), 1);
+        assert_eq!(scheduler.next_deadline(), Some(ms(300)));
+        /*
+         * Tarde por más de un periodo, los periodos perdidos se saltan y se cuentan
+         */
+        clock.set(ms(590));
+        assert_eq!(scheduler.run_pending(), 1);
+        assert_eq!(scheduler.next_deadline(), Some(ms(600)));
+        clock.set(ms(600));
+        assert_eq!(scheduler.run_pending(), 1);
+
+        let ticks = ticks.borrow();
+        let summary: Vec<_> = ticks
+            .iter()
+            .map(|tick| (tick.run, tick.deadline, tick.now, tick.missed))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (1, ms(100), ms(100), 0),
+                (2, ms(200), ms(230), 0),
+                (3, ms(300), ms(590), 2),
+                (4, ms(600), ms(600), 0),
+            ]
+        );
+        assert_eq!(ticks[2].late(), ms(290));
+    }
+
+    #[test]
+    fn run_for_sleeps_until_each_deadline() {
+        let clock = MockClock::new();
+        let mut scheduler = Scheduler::new(clock.clone());
+        let deadlines = Rc::new(RefCell::new(Vec::new()));
+        let seen = Rc::clone(&deadlines);
+        scheduler.every(ms(250), move |tick| {
+            assert_eq!(tick.deadline, tick.now);
+            seen.borrow_mut().push(tick.deadline);
+        });
+        assert_eq!(scheduler.run_for(ms(1000)), 4);
+        assert_eq!(*deadlines.borrow(), [ms(250), ms(500), ms(750), ms(1000)]);
+        assert_eq!(clock.now(), ms(1000));
+        assert_eq!(scheduler.len(), 1);
+    }
+
+    #[test]
+    fn cancel_stops_a_task() {
+        let clock = MockClock::new();
+        let mut scheduler = Scheduler::new(clock.clone());
+        let events = log();
+
+        let seen = Rc::clone(&events);
+        let once = scheduler.once(ms(100), move |_| seen.borrow_mut().push("once".into()));
+        once.cancel();
+        once.cancel();
+        assert!(once.is_cancelled());
+        assert!(scheduler.is_empty());
+
+        /*
+         * Una tarea cancela a otra, aunque ya haya vencido en la misma vuelta no se ejecuta
+         */
+        let seen = Rc::clone(&events);
+        let target: Rc<RefCell<Option<Handle>>> = Rc::default();
+        let slot = Rc::clone(&target);
+        scheduler.every(ms(100), move |tick| {
+            seen.borrow_mut().push(format!("stop {}", tick.run));
+            if let (2, Some(handle)) = (tick.run, slot.borrow().as_ref()) {
+                handle.cancel();
+            }
+        });
+        let seen = Rc::clone(&events);
+        let ping = scheduler.every(ms(100), move |tick| {
+            seen.borrow_mut().push(format!("ping {}", tick.run));
+        });
+        *target.borrow_mut() = Some(ping.clone());
+        assert_eq!(scheduler.len(), 2);
+        assert_eq!(scheduler.run_for(ms(450)), 5);
+        assert!(ping.is_cancelled());
+        assert_eq!(scheduler.len(), 1);
+        assert_eq!(
+            *events.borrow(),
+            ["stop 1", "ping 1", "stop 2", "stop 3", "stop 4"]
+        );
+    }
+
+    #[test]
+    fn countdown_runs_in_order() {
+        let clock = MockClock::new();
+        let mut scheduler = Scheduler::new(clock.clone());
+        let events = log();
+
+        let (seen, now) = (Rc::clone(&events), clock.clone());
+        let finished = Rc::clone(&events);
+        let done = clock.clone();
+        let countdown = scheduler.countdown(
+            3,
+            ms(1000),
+            move |n| seen.borrow_mut().push(format!("{n} at {:?}", now.now())),
+            move || finished.borrow_mut().push(format!("LIFTOFF at {:?}", done.now())),
+        );
+        /*
+         * Dos tareas a la misma hora se ejecutan en el orden en que se crearon
+         */
+        for name in ["first", "second"] {
+            let seen = Rc::clone(&events);
+            scheduler.once(ms(1500), move |tick| {
+                seen.borrow_mut().push(format!("{name} at {:?}", tick.now));
+            });
+        }
+
+        assert_eq!(scheduler.run_until_idle(), 6);
+        assert_eq!(
+            *events.borrow(),
+            [
+                "3 at 0ns",
+                "2 at 1s",
+                "first at 1.5s",
+                "second at 1.5s",
+                "1 at 2s",
+                "LIFTOFF at 3s",
+            ]
+        );
+        assert!(countdown.is_cancelled());
+        assert!(scheduler.is_empty());
+        assert_eq!(scheduler.next_deadline(), None);
+    }
+}
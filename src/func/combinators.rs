@@ -0,0 +1,150 @@
+/*
+ * Las funciones y los `closures` también son valores, se pueden pasar como argumento y devolver
+ * desde otra función. Un combinador recibe funciones y devuelve una función nueva sin ejecutar
+ * ninguna
+ *
+ *  compose(f, g)     x  --g-->  y  --f-->  z       f(g(x)), como en matemáticas
+ *  pipe(f, g)        x  --f-->  y  --g-->  z       g(f(x)), en el orden en que se lee
+ *  partial(f, a)     b  -->  f(a, b)               fija el primer argumento
+ *  curry(f)          a  -->  (b  -->  f(a, b))     un argumento a la vez
+ *
+ * `add` e `is_bigger_than` de `func/functions.rs` se reconstruyen aquí con estos combinadores
+ */
+use std::time::Duration;
+
+use super::functions::{add, is_bigger_than};
+use super::memo;
+use super::retry::{Backoff, Policy};
+use crate::flow::timers::{Clock, MockClock};
+
+pub fn compose<A, B, C>(f: impl Fn(B) -> C, g: impl Fn(A) -> B) -> impl Fn(A) -> C {
+    move |a| f(g(a))
+}
+
+pub fn pipe<A, B, C>(f: impl Fn(A) -> B, g: impl Fn(B) -> C) -> impl Fn(A) -> C {
+    move |a| g(f(a))
+}
+
+/*
+ * El argumento fijo se clona en cada llamada, la función resultante se puede llamar muchas veces
+ */
+pub fn partial<A: Clone, B, R>(f: impl Fn(A, B) -> R, a: A) -> impl Fn(B) -> R {
+    move |b| f(a.clone(), b)
+}
+
+pub fn partial_right<A, B: Clone, R>(f: impl Fn(A, B) -> R, b: B) -> impl Fn(A) -> R {
+    move |a| f(a, b.clone())
+}
+
+/*
+ * `Rust` no deja escribir `impl Fn(A) -> impl Fn(B) -> R`, la función de adentro va en una `Box`
+ *
+ *  let add = curry(|a, b| a + b);
+ *  add(10)(14)  ->  24
+ */
+pub fn curry<A, B, R, F>(f: F) -> impl Fn(A) -> Box<dyn Fn(B) -> R>
+where
+    A: Clone + 'static,
+    B: 'static,
+    R: 'static,
+    F: Fn(A, B) -> R + Clone + 'static,
+{
+    move |a| Box::new(partial(f.clone(), a))
+}
+
+pub fn uncurry<A, B, R, G>(f: impl Fn(A) -> G) -> impl Fn(A, B) -> R
+where
+    G: Fn(B) -> R,
+{
+    move |a, b| f(a)(b)
+}
+
+/*
+ * Intercambia los dos argumentos
+ *
+ *  flip(is_bigger_than)(number, compare) == is_bigger_than(compare, number)
+ */
+pub fn flip<A, B, R>(f: impl Fn(A, B) -> R) -> impl Fn(B, A) -> R {
+    move |b, a| f(a, b)
+}
+
+pub fn negate<A>(predicate: impl Fn(A) -> bool) -> impl Fn(A) -> bool {
+    move |a| !predicate(a)
+}
+
+/*
+ * `pipe` para valores, una cadena de transformaciones se lee de izquierda a derecha sin variables
+ * intermedias
+ *
+ *  10.pipe(add_ten).pipe(double)  ->  40
+ */
+pub trait Pipe: Sized {
+    fn pipe<R>(self, f: impl FnOnce(Self) -> R) -> R {
+        f(self)
+    }
+}
+
+impl<T> Pipe for T {}
+
+pub fn run() {
+    /*
+     * `add(10, 14)` en partes, primero se fija el `10` y después se pasa el `14`
+     */
+    let add_ten = partial(add, 10);
+    println!("partial(add, 10)(14) = {}", add_ten(14));
+    let curried = curry(add);
+    println!("curry(add)(10)(14) = {}", curried(10)(14));
+    println!("uncurry(curry(add))(10, 14) = {}", uncurry(curried)(10, 14));
+
+    /*
+     * `is_bigger_than(100, number)` como un predicado de un solo argumento
+     */
+    let bigger_than_hundred = partial(is_bigger_than, 100);
+    let smaller_than_hundred = flip(is_bigger_than);
+    let not_bigger = negate(partial(is_bigger_than, 100));
+    for number in [99, 100, 101] {
+        println!(
+            "{number}: bigger than 100? {}, 100 bigger than it? {}, not bigger? {}",
+            bigger_than_hundred(number),
+            smaller_than_hundred(100, number),
+            not_bigger(number)
+        );
+    }
+
+    /*
+     * Sumar `10` y después comparar, los dos pasos en una sola función
+     */
+    let add_ten_and_check = pipe(partial(add, 10), partial(is_bigger_than, 100));
+    let check_after_add = compose(partial(is_bigger_than, 100), partial(add, 10));
+    println!(
+        "pipe: 91 + 10 > 100? {}, compose: 90 + 10 > 100? {}",
+        add_ten_and_check(91),
+        check_after_add(90)
+    );
+    let total = 10.pipe(partial(add, 14)).pipe(partial_right(add, 6));
+    println!("10.pipe(+14).pipe(+6) = {total}");
+
+    /*
+     * `add` con cache, la segunda llamada con los mismos argumentos no ejecuta la función
+     */
+    let cached_add = memo::memoize(2, |(first, second)| add(first, second));
+    for pair in [(10, 14), (10, 14), (1, 2), (3, 4), (10, 14)] {
+        println!("memoized add{pair:?} = {}", cached_add.call(pair));
+    }
+    println!("{}", cached_add.stats());
+
+    /*
+     * Una comparación que falla las primeras dos veces, con un reloj simulado los reintentos no
+     * esperan de verdad
+     */
+    let clock = MockClock::new();
+    let policy = Policy::new(4, Backoff::exponential(Duration::from_millis(100), 2));
+    let result = policy.run(&clock, |attempt| {
+        if attempt.number < 3 {
+            Err(format!("attempt {} timed out", attempt.number))
+        } else {
+            Ok(is_bigger_than(100, add(attempt.number as i32, 100)))
+        }
+    });
+    println!("retried is_bigger_than: {result:?} after {:?}", clock.now());
+}
@@ -1,9 +1,11 @@
+use super::i18n::{I18nError, Locale, Translator};
+
 /*
  * Las funciones son bloques de código con un conjunto de instrucciones que pueden ser ejecutadas.
  * Para `Rust` no es importante en donde se definan las funciones, siempre y cuando esten
  * definidas en algun lugar
  */
-fn add(first: i32, second: i32) -> i32 {
+pub(crate) fn add(first: i32, second: i32) -> i32 {
     first + second
 }
 
@@ -12,6 +14,7 @@ fn add(first: i32, second: i32) -> i32 {
  * `basic_function` con parentesis `()` y las llaves `curly brackets` que le indican al compilador
  * donde comienza el cuerpo de la función y donde termina
  */
+#[allow(clippy::unused_unit)]
 fn basic_function() {
     /*
      * El tipo `unit` es un tipo especial que representa la ausencia de un valor significativo y se
@@ -26,32 +29,32 @@ fn basic_function() {
  * `Rust` cuando defines una función, debes especificar los tipos de los parámetros así el compilador
  * sabe exactamente que tipos se manejan
  */
-fn is_bigger_than(compare: i32, number: i32) -> bool {
+pub(crate) fn is_bigger_than(compare: i32, number: i32) -> bool {
     compare < number
 }
 
 /*
  * Las funciones pueden devolver valores después de ser invocadas, el valor de retorno no se "nombra"
  * solo se indica depués de la flecha `->`
+ *
+ * El saludo sale de `Translator::greet` en `func/i18n.rs`, que elige el mensaje con nombre o sin
+ * nombre en el idioma `locale`
  */
-fn concat_name(name: &str) -> String {
-    if name.len() == 0 {
-        /*
-         * En `Rust` no necesariamente la última línea del cuerpo de la función es la setencia
-         * del valor de retorno, se puede devolver un valor antes usando `return`
-         */
-        return String::from("Hello");
-    }
-    let mut g = "Hello ".to_owned();
-    g.push_str(name);
+fn concat_name(translator: &Translator, locale: &Locale, name: &str) -> Result<String, I18nError> {
+    /*
+     * En `Rust` no necesariamente la última línea del cuerpo de la función es la setencia del
+     * valor de retorno, se puede devolver un valor antes usando `return`. El operador `?` es un
+     * `return` de ese tipo, si `greet` falla la función devuelve el `Err` en esta línea
+     */
+    let greeting = translator.greet(locale, name)?;
     /*
      * En `Rust` se puede indicar la sentencia de retorno omitiendo la palabra `return` y el punto y
      * coma `;`
      */
-    g
+    Ok(greeting)
 }
 
-fn run() {
+pub fn run() {
     let res = add(10, 14);
     println!("Result: {res}");
     let compare = 100;
@@ -68,9 +71,50 @@ fn run() {
      * Los argumentos son valores concretos que son pasados a las funciones en el momento de
      * su llamada, es decir cuando se invoca
      */
-    let greeting: String = concat_name("Yael");
-    println!("{}", greeting);
+    let translator = Translator::builtin();
+    for tag in ["es", "en", "pt-BR"] {
+        let locale: Locale = tag.parse().expect("valid locale");
+        for name in ["Yael", ""] {
+            match concat_name(&translator, &locale, name) {
+                Ok(greeting) => println!("{tag}: {greeting}"),
+                Err(error) => eprintln!("{tag}: {error}"),
+            }
+        }
+    }
 
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    #[test]
+    fn concat_name_uses_the_catalog() {
+        let translator = Translator::builtin();
+        let es: Locale = "es".parse().unwrap();
+        assert_eq!(concat_name(&translator, &es, "  Yael "), Ok("Hola Yael".to_string()));
+        assert_eq!(concat_name(&translator, &es, "   "), Ok("Hola".to_string()));
+        for tag in ["en", "pt-BR"] {
+            let locale: Locale = tag.parse().unwrap();
+            assert_eq!(
+                concat_name(&translator, &locale, "Yael"),
+                translator.greet(&locale, "Yael")
+            );
+        }
+        assert_eq!(add(10, 14), 24);
+        assert!(is_bigger_than(100, 101) && !is_bigger_than(100, 100));
+    }
+
+    #[test]
+    fn concat_name_returns_the_error() {
+        let fr: Locale = "fr".parse().unwrap();
+        let empty = Translator::new(fr.clone());
+        assert_eq!(
+            concat_name(&empty, &fr, "Yael"),
+            Err(I18nError::MissingMessage {
+                key: "greeting".to_string(),
+                tried: vec![fr],
+            })
+        );
+    }
+}
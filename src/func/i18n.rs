@@ -0,0 +1,721 @@
+/*
+ * Los textos que ve el usuario no van en el código, van en un catálogo por idioma `locale`. El
+ * código pide un mensaje por su clave y le pasa los valores de los `placeholders`
+ *
+ *  greeting = Hola {name}          catálogo `es`
+ *  greeting = Hello {name}         catálogo `en`
+ *
+ *  translator.format(&"es".parse()?, "greeting", &[("name", &"Yael")])  ->  "Hola Yael"
+ *
+ * Si un idioma no tiene un mensaje se busca en la cadena de respaldo `fallback`, primero el idioma
+ * sin región y al final el idioma por defecto
+ *
+ *  pt-BR  ->  pt  ->  es  ->  en
+ */
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/*
+ * Un idioma y una región opcional, `es`, `pt-BR`. Acepta también el formato de `LANG`,
+ * `es_MX.UTF-8`
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Locale {
+    language: String,
+    region: Option<String>,
+}
+
+impl Locale {
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    /*
+     * `pt-BR` sin región es `pt`, un idioma sin región no tiene padre
+     */
+    pub fn parent(&self) -> Option<Locale> {
+        self.region.as_ref().map(|_| Locale {
+            language: self.language.clone(),
+            region: None,
+        })
+    }
+
+    /*
+     * Las mismas variables que revisa `gettext`, en el mismo orden. `C` y `POSIX` no son idiomas
+     */
+    pub fn from_env(vars: impl IntoIterator<Item = (String, String)>) -> Option<Locale> {
+        let vars: HashMap<String, String> = vars.into_iter().collect();
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| vars.get(*name))
+            .filter(|value| !value.is_empty() && *value != "C" && *value != "POSIX")
+            .find_map(|value| value.parse().ok())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = I18nError;
+
+    fn from_str(text: &str) -> Result<Self, I18nError> {
+        let invalid = || I18nError::InvalidLocale(text.to_string());
+        let tag = text.split(['.', '@']).next().unwrap_or_default();
+        let mut parts = tag.split(['-', '_']);
+        let language = parts.next().unwrap_or_default();
+        let region = parts.next();
+        if parts.next().is_some()
+            || !(2..=3).contains(&language.len())
+            || !language.chars().all(|c| c.is_ascii_alphabetic())
+        {
+            return Err(invalid());
+        }
+        let region = match region {
+            Some(region)
+                if (2..=3).contains(&region.len())
+                    && region.chars().all(|c| c.is_ascii_alphanumeric()) =>
+            {
+                Some(region.to_ascii_uppercase())
+            }
+            Some(_) => return Err(invalid()),
+            None => None,
+        };
+        Ok(Locale {
+            language: language.to_ascii_lowercase(),
+            region,
+        })
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.region {
+            Some(region) => write!(f, "{}-{region}", self.language),
+            None => write!(f, "{}", self.language),
+        }
+    }
+}
+
+/*
+ * Las categorías de plural de `CLDR`, cada idioma usa solo algunas
+ *
+ *  en   1 -> one                    todo lo demás -> other
+ *  es   1 -> one   1000000 -> many  todo lo demás -> other
+ *  pt   0, 1 -> one  1000000 -> many  todo lo demás -> other
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Plural {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl Plural {
+    pub fn name(self) -> &'static str {
+        match self {
+            Plural::Zero => "zero",
+            Plural::One => "one",
+            Plural::Two => "two",
+            Plural::Few => "few",
+            Plural::Many => "many",
+            Plural::Other => "other",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Plural> {
+        [
+            Plural::Zero,
+            Plural::One,
+            Plural::Two,
+            Plural::Few,
+            Plural::Many,
+            Plural::Other,
+        ]
+        .into_iter()
+        .find(|plural| plural.name() == name)
+    }
+
+    /*
+     * La regla de cada idioma para números enteros, un idioma sin regla usa la de `en`
+     */
+    pub fn for_count(language: &str, count: u64) -> Plural {
+        let millions = count != 0 && count.is_multiple_of(1_000_000);
+        match language {
+            "es" | "it" if count == 1 => Plural::One,
+            "es" | "it" if millions => Plural::Many,
+            "pt" | "fr" if count <= 1 => Plural::One,
+            "pt" | "fr" if millions => Plural::Many,
+            "es" | "it" | "pt" | "fr" => Plural::Other,
+            _ if count == 1 => Plural::One,
+            _ => Plural::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Text(String),
+    /*
+     * Una forma por categoría, `other` es obligatoria y se usa cuando falta la que pide la regla
+     */
+    Plural(HashMap<Plural, String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum I18nError {
+    InvalidLocale(String),
+    Syntax {
+        locale: Locale,
+        line: usize,
+        message: String,
+    },
+    MissingMessage {
+        key: String,
+        tried: Vec<Locale>,
+    },
+    MissingArgument {
+        key: String,
+        locale: Locale,
+        name: String,
+    },
+    NotPlural {
+        key: String,
+        locale: Locale,
+    },
+}
+
+impl fmt::Display for I18nError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            I18nError::InvalidLocale(text) => write!(f, "invalid locale {text:?}"),
+            I18nError::Syntax {
+                locale,
+                line,
+                message,
+            } => write!(f, "catalog {locale}, line {line}: {message}"),
+            I18nError::MissingMessage { key, tried } => {
+                let tried: Vec<String> = tried.iter().map(Locale::to_string).collect();
+                write!(f, "no message `{key}` in {}", tried.join(", "))
+            }
+            I18nError::MissingArgument { key, locale, name } => {
+                write!(f, "message `{key}` ({locale}) needs the argument `{name}`")
+            }
+            I18nError::NotPlural { key, locale } => {
+                write!(f, "message `{key}` ({locale}) has no plural forms")
+            }
+        }
+    }
+}
+
+impl std::error::Error for I18nError {}
+
+/*
+ * Los mensajes de un idioma. El formato es una clave por línea, las formas de plural llevan la
+ * categoría entre corchetes
+ *
+ *  # comentario
+ *  greeting = Hola {name}
+ *  people[one] = {count} persona
+ *  people[other] = {count} personas
+ *
+ * Un `{` literal se escribe `{{`
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Catalog {
+    locale: Locale,
+    messages: HashMap<String, Message>,
+}
+
+impl Catalog {
+    pub fn parse(locale: Locale, text: &str) -> Result<Catalog, I18nError> {
+        let mut messages = HashMap::new();
+        let mut plurals = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let syntax = |message: String| I18nError::Syntax {
+                locale: locale.clone(),
+                line: index + 1,
+                message,
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| syntax(format!("expected `key = text`, found {line:?}")))?;
+            let (key, value) = (key.trim(), value.trim().to_string());
+            check_placeholders(&value).map_err(syntax)?;
+            let Some((key, plural)) = key.split_once('[') else {
+                messages.insert(key.to_string(), Message::Text(value));
+                continue;
+            };
+            let category = plural
+                .strip_suffix(']')
+                .and_then(Plural::from_name)
+                .ok_or_else(|| syntax(format!("unknown plural category in `{key}[{plural}`")))?;
+            let entry = messages
+                .entry(key.to_string())
+                .or_insert_with(|| Message::Plural(HashMap::new()));
+            match entry {
+                Message::Plural(forms) => {
+                    if forms.is_empty() {
+                        plurals.push((key.to_string(), index + 1));
+                    }
+                    forms.insert(category, value);
+                }
+                Message::Text(_) => {
+                    return Err(syntax(format!("`{key}` is both a text and a plural")));
+                }
+            }
+        }
+        for (key, line) in plurals {
+            if let Some(Message::Plural(forms)) = messages.get(&key) {
+                if !forms.contains_key(&Plural::Other) {
+                    return Err(I18nError::Syntax {
+                        locale,
+                        line,
+                        message: format!("plural `{key}` needs an `other` form"),
+                    });
+                }
+            }
+        }
+        Ok(Catalog { locale, messages })
+    }
+
+    pub fn locale(&self) -> &Locale {
+        &self.locale
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Message> {
+        self.messages.get(key)
+    }
+}
+
+fn check_placeholders(text: &str) -> Result<(), String> {
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("unclosed placeholder in {text:?}")),
+                    }
+                }
+                if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    return Err(format!("invalid placeholder {{{name}}} in {text:?}"));
+                }
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+            }
+            '}' => return Err(format!("unmatched `}}` in {text:?}, write `}}}}`")),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/*
+ * Reemplaza cada `{name}` por su argumento, la función que busca el argumento decide qué hacer
+ * cuando falta
+ */
+fn interpolate(
+    text: &str,
+    mut lookup: impl FnMut(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                out.push('{');
+            }
+            '{' => {
+                let name: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                out.push_str(&lookup(&name).ok_or(name)?);
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                out.push('}');
+            }
+            c => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+pub type Args<'a> = [(&'a str, &'a dyn fmt::Display)];
+
+pub const ES: &str = "\
+# Español
+greeting = Hola {name}
+greeting.anonymous = Hola
+farewell = Adiós {name}, hasta pronto
+people[one] = Hay {count} persona
+people[many] = Hay {count} de personas
+people[other] = Hay {count} personas
+lesson.title = Lección {path}
+lesson.line = línea {line}
+lesson.running = >> Ejecutando {name}()
+lesson.untranslated = [sin traducción al inglés]
+lesson.stale = [el texto en español cambió después de esta traducción]
+";
+
+pub const EN: &str = "\
+# English
+greeting = Hello {name}
+greeting.anonymous = Hello
+farewell = Goodbye {name}, see you soon
+people[one] = There is {count} person
+people[other] = There are {count} people
+lesson.title = Lesson {path}
+lesson.line = line {line}
+lesson.running = >> Running {name}()
+lesson.untranslated = [not translated yet, showing the Spanish text]
+lesson.stale = [the Spanish text changed after this translation]
+";
+
+/*
+ * `farewell` falta a propósito, se resuelve con la cadena `pt -> es`
+ */
+pub const PT: &str = "\
+# Português
+greeting = Olá {name}
+greeting.anonymous = Olá
+people[one] = Há {count} pessoa
+people[many] = Há {count} de pessoas
+people[other] = Há {count} pessoas
+lesson.title = Lição {path}
+lesson.line = linha {line}
+lesson.running = >> Executando {name}()
+";
+
+pub struct Translator {
+    default: Locale,
+    catalogs: HashMap<Locale, Catalog>,
+    fallbacks: HashMap<String, Locale>,
+}
+
+impl Translator {
+    pub fn new(default: Locale) -> Self {
+        Self {
+            default,
+            catalogs: HashMap::new(),
+            fallbacks: HashMap::new(),
+        }
+    }
+
+    /*
+     * Los catálogos `es`, `en` y `pt` con `en` por defecto y `pt` respaldado por `es`
+     */
+    pub fn builtin() -> Self {
+        let locale = |tag: &str| tag.parse::<Locale>().expect("built-in locale");
+        let mut translator = Self::new(locale("en"));
+        for (tag, text) in [("es", ES), ("en", EN), ("pt", PT)] {
+            let catalog = Catalog::parse(locale(tag), text).expect("built-in catalog");
+            translator.add(catalog);
+        }
+        translator.fallback("pt", locale("es"));
+        translator
+    }
+
+    pub fn add(&mut self, catalog: Catalog) {
+        self.catalogs.insert(catalog.locale.clone(), catalog);
+    }
+
+    /*
+     * Cuando un idioma no tiene un mensaje se busca en `to` antes que en el idioma por defecto
+     */
+    pub fn fallback(&mut self, language: &str, to: Locale) {
+        self.fallbacks.insert(language.to_string(), to);
+    }
+
+    pub fn default_locale(&self) -> &Locale {
+        &self.default
+    }
+
+    /*
+     * El idioma pedido, su padre, los respaldos configurados y al final el idioma por defecto, sin
+     * repetir ninguno, así un ciclo entre respaldos no se queda dando vueltas
+     */
+    pub fn chain(&self, locale: &Locale) -> Vec<Locale> {
+        let mut chain = Vec::new();
+        let mut next = Some(locale.clone());
+        while let Some(locale) = next.take() {
+            if chain.contains(&locale) {
+                break;
+            }
+            next = locale
+                .parent()
+                .or_else(|| self.fallbacks.get(locale.language()).cloned());
+            chain.push(locale);
+        }
+        for locale in [self.default.clone()]
+            .into_iter()
+            .chain(self.default.parent())
+        {
+            if !chain.contains(&locale) {
+                chain.push(locale);
+            }
+        }
+        chain
+    }
+
+    /*
+     * El mensaje y el idioma del catálogo donde se encontró
+     */
+    pub fn lookup(&self, locale: &Locale, key: &str) -> Result<(&Locale, &Message), I18nError> {
+        let chain = self.chain(locale);
+        chain
+            .iter()
+            .filter_map(|locale| self.catalogs.get(locale))
+            .find_map(|catalog| Some((&catalog.locale, catalog.get(key)?)))
+            .ok_or_else(|| I18nError::MissingMessage {
+                key: key.to_string(),
+                tried: chain,
+            })
+    }
+
+    pub fn format(&self, locale: &Locale, key: &str, args: &Args) -> Result<String, I18nError> {
+        let (found, message) = self.lookup(locale, key)?;
+        let text = match message {
+            Message::Text(text) => text,
+            Message::Plural(forms) => &forms[&Plural::Other],
+        };
+        self.interpolate(found, key, text, args, None)
+    }
+
+    /*
+     * La forma se elige con la regla del idioma donde se encontró el mensaje, no la del idioma
+     * pedido, y `{count}` queda disponible como argumento
+     */
+    pub fn format_plural(
+        &self,
+        locale: &Locale,
+        key: &str,
+        count: u64,
+        args: &Args,
+    ) -> Result<String, I18nError> {
+        let (found, message) = self.lookup(locale, key)?;
+        let Message::Plural(forms) = message else {
+            return Err(I18nError::NotPlural {
+                key: key.to_string(),
+                locale: found.clone(),
+            });
+        };
+        let category = Plural::for_count(found.language(), count);
+        let text = forms.get(&category).unwrap_or(&forms[&Plural::Other]);
+        self.interpolate(found, key, text, args, Some(count))
+    }
+
+    fn interpolate(
+        &self,
+        locale: &Locale,
+        key: &str,
+        text: &str,
+        args: &Args,
+        count: Option<u64>,
+    ) -> Result<String, I18nError> {
+        interpolate(text, |name| {
+            args.iter()
+                .find(|(arg, _)| *arg == name)
+                .map(|(_, value)| value.to_string())
+                .or_else(|| count.filter(|_| name == "count").map(|n| n.to_string()))
+        })
+        .map_err(|name| I18nError::MissingArgument {
+            key: key.to_string(),
+            locale: locale.clone(),
+            name,
+        })
+    }
+
+    /*
+     * El saludo de `func/functions.rs::concat_name` en cualquier idioma. Un nombre vacío o con
+     * solo espacios da el saludo sin nombre
+     */
+    pub fn greet(&self, locale: &Locale, name: &str) -> Result<String, I18nError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return self.format(locale, "greeting.anonymous", &[]);
+        }
+        self.format(locale, "greeting", &[("name", &name)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locale(tag: &str) -> Locale {
+        tag.parse().unwrap()
+    }
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn locale_from_str() {
+        let mx = locale("es_MX.UTF-8");
+        assert_eq!((mx.language(), mx.region()), ("es", Some("MX")));
+        assert_eq!(mx.to_string(), "es-MX");
+        assert_eq!(locale("PT-br").to_string(), "pt-BR");
+        assert_eq!(locale("sr_RS@latin").to_string(), "sr-RS");
+        assert_eq!(locale("es-419").region(), Some("419"));
+        assert_eq!(mx.parent(), Some(locale("es")));
+        assert_eq!(locale("es").parent(), None);
+        for bad in ["", "e", "espa", "e1", "es-", "es-M", "es-MEXI", "es-MX-x", "es-M!"] {
+            assert_eq!(bad.parse::<Locale>(), Err(I18nError::InvalidLocale(bad.to_string())));
+        }
+    }
+
+    #[test]
+    fn from_env_skips_c_and_posix() {
+        let env = vars(&[("LC_ALL", "C"), ("LC_MESSAGES", "POSIX"), ("LANG", "pt_BR.UTF-8")]);
+        assert_eq!(Locale::from_env(env), Some(locale("pt-BR")));
+        let env = vars(&[("LC_ALL", ""), ("LC_MESSAGES", "es_MX"), ("LANG", "en_US")]);
+        assert_eq!(Locale::from_env(env), Some(locale("es-MX")));
+        assert_eq!(Locale::from_env(vars(&[("LANG", "C.UTF-8")])), None);
+        assert_eq!(Locale::from_env(vars(&[("LANG", "POSIX")])), None);
+        assert_eq!(Locale::from_env(vars(&[])), None);
+    }
+
+    #[test]
+    fn plural_rules() {
+        use Plural::{Many, One, Other};
+        let cases = [
+            ("es", [Other, One, Other, Many, Other]),
+            ("pt", [One, One, Other, Many, Other]),
+            ("en", [Other, One, Other, Other, Other]),
+        ];
+        for (language, expected) in cases {
+            let counts = [0, 1, 2, 1_000_000, 1_000_001];
+            let got: Vec<Plural> =
+                counts.iter().map(|n| Plural::for_count(language, *n)).collect();
+            assert_eq!(got, expected, "{language}");
+        }
+        let translator = Translator::builtin();
+        let people = |tag: &str, count| {
+            translator.format_plural(&locale(tag), "people", count, &[]).unwrap()
+        };
+        assert_eq!(people("es", 1_000_000), "Hay 1000000 de personas");
+        assert_eq!(people("en", 1_000_000), "There are 1000000 people");
+        assert_eq!(people("pt", 0), "Há 0 pessoa");
+    }
+
+    #[test]
+    fn fallback_chain() {
+        let translator = Translator::builtin();
+        let tags: Vec<String> = translator
+            .chain(&locale("pt-BR"))
+            .iter()
+            .map(Locale::to_string)
+            .collect();
+        assert_eq!(tags, ["pt-BR", "pt", "es", "en"]);
+        assert_eq!(
+            translator.format(&locale("pt-BR"), "farewell", &[("name", &"Yael")]),
+            Ok("Adiós Yael, hasta pronto".to_string())
+        );
+    }
+
+    #[test]
+    fn chain_stops_on_a_cycle() {
+        let mut translator = Translator::new(locale("en-US"));
+        translator.fallback("es", locale("pt"));
+        translator.fallback("pt", locale("es"));
+        translator.fallback("en", locale("en"));
+        let tags: Vec<String> =
+            translator.chain(&locale("es-MX")).iter().map(Locale::to_string).collect();
+        assert_eq!(tags, ["es-MX", "es", "pt", "en-US", "en"]);
+        let tags: Vec<String> =
+            translator.chain(&locale("en")).iter().map(Locale::to_string).collect();
+        assert_eq!(tags, ["en", "en-US"]);
+    }
+
+    #[test]
+    fn catalog_errors() {
+        let syntax = |line, message: &str| I18nError::Syntax {
+            locale: locale("es"),
+            line,
+            message: message.to_string(),
+        };
+        let parse = |text| Catalog::parse(locale("es"), text);
+        assert_eq!(
+            parse("# plural\nitems[one] = uno\nitems[many] = muchos\n"),
+            Err(syntax(2, "plural `items` needs an `other` form"))
+        );
+        assert_eq!(
+            parse("items = texto\nitems[other] = otros\n"),
+            Err(syntax(2, "`items` is both a text and a plural"))
+        );
+        assert_eq!(
+            parse("greeting = Hola {name"),
+            Err(syntax(1, "unclosed placeholder in \"Hola {name\""))
+        );
+        assert_eq!(
+            parse("\ngreeting Hola"),
+            Err(syntax(2, "expected `key = text`, found \"greeting Hola\""))
+        );
+        assert_eq!(
+            parse("items[dos] = dos"),
+            Err(syntax(1, "unknown plural category in `items[dos]`"))
+        );
+        assert_eq!(
+            parse("a = {}"),
+            Err(syntax(1, "invalid placeholder {} in \"{}\""))
+        );
+        assert_eq!(
+            parse("a = x }"),
+            Err(syntax(1, "unmatched `}` in \"x }\", write `}}`"))
+        );
+    }
+
+    #[test]
+    fn doubled_braces_are_literal() {
+        let mut translator = Translator::new(locale("es"));
+        let catalog = Catalog::parse(locale("es"), "set = {{{name}}} y }}").unwrap();
+        translator.add(catalog);
+        assert_eq!(
+            translator.format(&locale("es"), "set", &[("name", &"x")]),
+            Ok("{x} y }".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_argument() {
+        let translator = Translator::builtin();
+        let error = translator.format(&locale("pt-BR"), "greeting", &[]).unwrap_err();
+        assert_eq!(
+            error,
+            I18nError::MissingArgument {
+                key: "greeting".to_string(),
+                locale: locale("pt"),
+                name: "name".to_string(),
+            }
+        );
+        assert_eq!(error.to_string(), "message `greeting` (pt) needs the argument `name`");
+        assert_eq!(
+            translator.format_plural(&locale("es"), "greeting", 1, &[]),
+            Err(I18nError::NotPlural {
+                key: "greeting".to_string(),
+                locale: locale("es"),
+            })
+        );
+    }
+}
@@ -0,0 +1,388 @@
+/*
+ * Memoizar es guardar el resultado de una función pura para no calcularlo de nuevo con los mismos
+ * argumentos. El cache tiene un límite, cuando se llena sale la entrada que lleva más tiempo sin
+ * usarse `least recently used`
+ *
+ *  más reciente                       menos reciente
+ *  head -> [c] <-> [a] <-> [d] <-> [b] <- tail        get(d) mueve `d` al frente
+ *  head -> [d] <-> [c] <-> [a] <-> [b] <- tail        insert(e) con el cache lleno saca `b`
+ *
+ * La lista doblemente ligada usa índices de un `Vec` en lugar de punteros, como el `Arena` de
+ * `ownership/arena`, así no hace falta `unsafe` ni `Rc<RefCell<_>>`
+ */
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+#[derive(Debug, Clone)]
+struct Node<K, V> {
+    key: K,
+    value: V,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    map: HashMap<K, usize>,
+    nodes: Vec<Node<K, V>>,
+    head: Option<usize>,
+    tail: Option<usize>,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity != 0, "capacity must be greater than zero");
+        Self {
+            capacity,
+            map: HashMap::with_capacity(capacity),
+            nodes: Vec::with_capacity(capacity),
+            head: None,
+            tail: None,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn unlink(&mut self, index: usize) {
+        let (prev, next) = (self.nodes[index].prev, self.nodes[index].next);
+        match prev {
+            Some(prev) => self.nodes[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.nodes[next].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    fn push_front(&mut self, index: usize) {
+        self.nodes[index].prev = None;
+        self.nodes[index].next = self.head;
+        match self.head {
+            Some(head) => self.nodes[head].prev = Some(index),
+            None => self.tail = Some(index),
+        }
+        self.head = Some(index);
+    }
+
+    /*
+     * Leer una entrada la marca como la más reciente, por eso necesita `&mut self`
+     */
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let index = *self.map.get(key)?;
+        self.unlink(index);
+        self.push_front(index);
+        Some(&self.nodes[index].value)
+    }
+
+    /*
+     * Como `get` sin cambiar el orden
+     */
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.map.get(key).map(|&index| &self.nodes[index].value)
+    }
+
+    /*
+     * Devuelve la entrada que salió del cache, el valor anterior de `key` o la menos reciente si
+     * el cache estaba lleno. El lugar de la que sale se reutiliza, el `Vec` nunca crece más que
+     * `capacity`
+     */
+    pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some(&index) = self.map.get(&key) {
+            self.unlink(index);
+            self.push_front(index);
+            let old = std::mem::replace(&mut self.nodes[index].value, value);
+            return Some((key, old));
+        }
+        if self.nodes.len() < self.capacity {
+            let index = self.nodes.len();
+            self.nodes.push(Node {
+                key: key.clone(),
+                value,
+                prev: None,
+                next: None,
+            });
+            self.map.insert(key, index);
+            self.push_front(index);
+            return None;
+        }
+        let index = self.tail?;
+        self.unlink(index);
+        let node = &mut self.nodes[index];
+        let old_key = std::mem::replace(&mut node.key, key.clone());
+        let old_value = std::mem::replace(&mut node.value, value);
+        self.map.remove(&old_key);
+        self.map.insert(key, index);
+        self.push_front(index);
+        Some((old_key, old_value))
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.nodes.clear();
+        self.head = None;
+        self.tail = None;
+    }
+
+    /*
+     * De la más reciente a la menos reciente
+     */
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            cache: self,
+            next: self.head,
+        }
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    cache: &'a LruCache<K, V>,
+    next: Option<usize>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let node = &self.cache.nodes[self.next?];
+        self.next = node.next;
+        Some((&node.key, &node.value))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses, {}/{} entries",
+            self.hits, self.misses, self.len, self.capacity
+        )
+    }
+}
+
+/*
+ * La versión con cache que recibe la función para sus llamadas recursivas
+ */
+pub type Recurse<'a, K, V> = dyn Fn(K) -> V + 'a;
+
+/*
+ * Una función con su cache. La función recibe como primer argumento la versión con cache de sí
+ * misma, así las llamadas recursivas también pasan por el cache
+ *
+ *  let fib = memoize_recursive(64, |fib, n: u64| if n < 2 { n } else { fib(n - 1) + fib(n - 2) });
+ *
+ * `call` toma `&self`, el cache va en un `RefCell` y el préstamo se suelta antes de llamar a la
+ * función para que la recursión pueda volver a tomarlo
+ */
+pub struct Memoized<K, V, F> {
+    f: F,
+    cache: RefCell<LruCache<K, V>>,
+    hits: Cell<u64>,
+    misses: Cell<u64>,
+}
+
+impl<K, V, F> Memoized<K, V, F>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    F: Fn(&Recurse<K, V>, K) -> V,
+{
+    pub fn call(&self, key: K) -> V {
+        if let Some(value) = self.cache.borrow_mut().get(&key) {
+            self.hits.set(self.hits.get() + 1);
+            return value.clone();
+        }
+        self.misses.set(self.misses.get() + 1);
+        let recurse = |key: K| self.call(key);
+        let value = (self.f)(&recurse, key.clone());
+        self.cache.borrow_mut().insert(key, value.clone());
+        value
+    }
+
+    pub fn stats(&self) -> Stats {
+        let cache = self.cache.borrow();
+        Stats {
+            hits: self.hits.get(),
+            misses: self.misses.get(),
+            len: cache.len(),
+            capacity: cache.capacity(),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.cache.borrow_mut().clear();
+        self.hits.set(0);
+        self.misses.set(0);
+    }
+}
+
+/*
+ * Para más de un argumento la clave es una tupla
+ *
+ *  let add = memoize(128, |(first, second): (i32, i32)| first + second);
+ *  add.call((10, 14))
+ */
+pub fn memoize<K, V>(
+    capacity: usize,
+    f: impl Fn(K) -> V,
+) -> Memoized<K, V, impl Fn(&Recurse<K, V>, K) -> V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    memoize_recursive(capacity, move |_: &Recurse<K, V>, key: K| f(key))
+}
+
+pub fn memoize_recursive<K, V, F>(capacity: usize, f: F) -> Memoized<K, V, F>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    F: Fn(&Recurse<K, V>, K) -> V,
+{
+    Memoized {
+        f,
+        cache: RefCell::new(LruCache::new(capacity)),
+        hits: Cell::new(0),
+        misses: Cell::new(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys<V>(cache: &LruCache<char, V>) -> String {
+        cache.iter().map(|(key, _)| *key).collect()
+    }
+
+    #[test]
+    fn get_moves_to_front_and_insert_evicts_the_tail() {
+        let mut cache = LruCache::new(4);
+        for (value, key) in "bdac".chars().enumerate() {
+            assert_eq!(cache.insert(key, value), None);
+        }
+        assert_eq!(keys(&cache), "cadb");
+        /*
+         * El mismo ejemplo del diagrama del inicio del archivo
+         */
+        assert_eq!(cache.get(&'d'), Some(&1));
+        assert_eq!(keys(&cache), "dcab");
+        assert_eq!(cache.insert('e', 4), Some(('b', 0)));
+        assert_eq!(keys(&cache), "edca");
+        assert!(!cache.contains(&'b'));
+        assert_eq!(cache.len(), 4);
+    }
+
+    #[test]
+    fn peek_keeps_order_and_insert_replaces() {
+        let mut cache = LruCache::new(2);
+        cache.insert('a', 1);
+        cache.insert('b', 2);
+        assert_eq!(cache.peek(&'a'), Some(&1));
+        assert_eq!(keys(&cache), "ba");
+        /*
+         * Reemplazar un valor lo marca como reciente y devuelve el anterior
+         */
+        assert_eq!(cache.insert('a', 10), Some(('a', 1)));
+        assert_eq!(keys(&cache), "ab");
+        assert_eq!(cache.insert('c', 3), Some(('b', 2)));
+        assert_eq!(cache.get(&'b'), None);
+        assert_eq!(cache.peek(&'a'), Some(&10));
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(keys(&cache), "");
+        assert_eq!(cache.insert('z', 0), None);
+    }
+
+    /*
+     * Un modelo lento, un `Vec` de la más reciente a la menos reciente
+     */
+    #[test]
+    fn matches_a_vec_model() {
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = move |bound: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % bound
+        };
+        let mut cache = LruCache::new(5);
+        let mut model: Vec<(u64, u64)> = Vec::new();
+        for step in 0..2_000 {
+            let key = next(9);
+            if next(2) == 0 {
+                let found = model.iter().position(|(k, _)| *k == key);
+                let expected = found.map(|index| model.remove(index));
+                if let Some(entry) = expected {
+                    model.insert(0, entry);
+                }
+                assert_eq!(cache.get(&key), expected.map(|(_, v)| v).as_ref(), "{step}");
+            } else {
+                let expected = match model.iter().position(|(k, _)| *k == key) {
+                    Some(index) => Some(model.remove(index)),
+                    None if model.len() == 5 => model.pop(),
+                    None => None,
+                };
+                model.insert(0, (key, step));
+                assert_eq!(cache.insert(key, step), expected, "{step}");
+            }
+            let order: Vec<_> = cache.iter().map(|(k, v)| (*k, *v)).collect();
+            assert_eq!(order, model, "{step}");
+        }
+    }
+
+    #[test]
+    fn memoized_recursion_counts_hits() {
+        let fib = memoize_recursive(64, |fib: &Recurse<u64, u64>, n: u64| {
+            if n < 2 {
+                n
+            } else {
+                fib(n - 1) + fib(n - 2)
+            }
+        });
+        assert_eq!(fib.call(50), 12_586_269_025);
+        /*
+         * Cada `n` se calcula una vez, `fib(n - 2)` siempre se encuentra en el cache
+         */
+        let stats = fib.stats();
+        assert_eq!((stats.misses, stats.hits, stats.len), (51, 48, 51));
+        fib.call(50);
+        assert_eq!(fib.stats().hits, 49);
+        fib.clear();
+        assert_eq!(fib.stats().to_string(), "0 hits, 0 misses, 0/64 entries");
+    }
+
+    #[test]
+    fn memoize_evicts_with_the_cache() {
+        let add = memoize(2, |(first, second): (i32, i32)| first + second);
+        for pair in [(10, 14), (10, 14), (1, 2), (3, 4), (10, 14)] {
+            assert_eq!(add.call(pair), pair.0 + pair.1);
+        }
+        assert_eq!(add.stats().to_string(), "1 hits, 4 misses, 2/2 entries");
+    }
+}
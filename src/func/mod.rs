@@ -0,0 +1,5 @@
+pub mod functions;
+pub mod combinators;
+pub mod memo;
+pub mod retry;
+pub mod i18n;
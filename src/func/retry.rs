@@ -0,0 +1,318 @@
+/*
+ * Reintentar una operación que puede fallar por un momento, esperando cada vez un poco más
+ *
+ *  intento 1  falla  -- espera 100ms -->
+ *  intento 2  falla  -- espera 200ms -->
+ *  intento 3  falla  -- espera 400ms -->
+ *  intento 4  ok
+ *
+ * La espera se hace con el `Clock` de `flow::timers`, con `MockClock` los reintentos no duermen y
+ * se puede revisar cuánto tiempo se habría esperado
+ */
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use crate::flow::timers::Clock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    Constant(Duration),
+    Linear {
+        initial: Duration,
+        step: Duration,
+    },
+    /*
+     * `initial * factor^(n - 1)` sin pasar de `max`
+     */
+    Exponential {
+        initial: Duration,
+        factor: u32,
+        max: Duration,
+    },
+}
+
+impl Backoff {
+    pub fn exponential(initial: Duration, factor: u32) -> Self {
+        Backoff::Exponential {
+            initial,
+            factor,
+            max: Duration::MAX,
+        }
+    }
+
+    /*
+     * La espera antes del reintento `retry`, el primer reintento es el `1`
+     */
+    pub fn delay(&self, retry: u32) -> Duration {
+        let n = retry.saturating_sub(1);
+        match *self {
+            Backoff::Constant(delay) => delay,
+            Backoff::Linear { initial, step } => step
+                .checked_mul(n)
+                .and_then(|extra| initial.checked_add(extra))
+                .unwrap_or(Duration::MAX),
+            Backoff::Exponential {
+                initial,
+                factor,
+                max,
+            } => factor
+                .checked_pow(n)
+                .and_then(|multiplier| initial.checked_mul(multiplier))
+                .map_or(max, |delay| delay.min(max)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attempt {
+    /*
+     * Empieza en `1`
+     */
+    pub number: u32,
+    /*
+     * El tiempo desde el primer intento
+     */
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryError<E> {
+    pub attempts: u32,
+    pub elapsed: Duration,
+    /*
+     * `true` cuando el error no se podía reintentar y no se agotaron los intentos
+     */
+    pub permanent: bool,
+    pub error: E,
+}
+
+impl<E: fmt::Display> fmt::Display for RetryError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let attempts = match self.attempts {
+            1 => "1 attempt".to_string(),
+            n => format!("{n} attempts"),
+        };
+        if self.permanent {
+            write!(f, "permanent error after {attempts}: {}", self.error)
+        } else {
+            write!(
+                f,
+                "gave up after {attempts} in {:?}: {}",
+                self.elapsed, self.error
+            )
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for RetryError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Policy {
+    pub max_attempts: u32,
+    pub backoff: Backoff,
+}
+
+impl Policy {
+    pub fn new(max_attempts: u32, backoff: Backoff) -> Self {
+        assert!(max_attempts != 0, "max_attempts must be greater than zero");
+        Self {
+            max_attempts,
+            backoff,
+        }
+    }
+
+    /*
+     * Las esperas entre intentos, una menos que `max_attempts`
+     */
+    pub fn delays(&self) -> impl Iterator<Item = Duration> + '_ {
+        (1..self.max_attempts).map(|retry| self.backoff.delay(retry))
+    }
+
+    pub fn run<T, E, C: Clock>(
+        &self,
+        clock: &C,
+        op: impl FnMut(Attempt) -> Result<T, E>,
+    ) -> Result<T, RetryError<E>> {
+        self.run_if(clock, op, |_| true)
+    }
+
+    /*
+     * Solo reintenta los errores para los que `retryable` devuelve `true`, los demás se devuelven
+     * de inmediato
+     */
+    pub fn run_if<T, E, C: Clock>(
+        &self,
+        clock: &C,
+        mut op: impl FnMut(Attempt) -> Result<T, E>,
+        retryable: impl Fn(&E) -> bool,
+    ) -> Result<T, RetryError<E>> {
+        let start = clock.now();
+        let mut number = 1;
+        loop {
+            let attempt = Attempt {
+                number,
+                elapsed: clock.now().saturating_sub(start),
+            };
+            let error = match op(attempt) {
+                Ok(value) => return Ok(value),
+                Err(error) => error,
+            };
+            let permanent = !retryable(&error);
+            if permanent || number == self.max_attempts {
+                return Err(RetryError {
+                    attempts: number,
+                    elapsed: clock.now().saturating_sub(start),
+                    permanent,
+                    error,
+                });
+            }
+            clock.sleep(self.backoff.delay(number));
+            number += 1;
+        }
+    }
+}
+
+/*
+ * Envuelve una función para que cada llamada se reintente con `policy`, el argumento se clona en
+ * cada intento
+ *
+ *  let mut fetch = retrying(policy, clock, |id: u32| download(id));
+ *  fetch(7)?;
+ */
+pub fn retrying<A, T, E, C>(
+    policy: Policy,
+    clock: C,
+    mut f: impl FnMut(A) -> Result<T, E>,
+) -> impl FnMut(A) -> Result<T, RetryError<E>>
+where
+    A: Clone,
+    C: Clock,
+{
+    move |arg| policy.run(&clock, |_| f(arg.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flow::timers::MockClock;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn backoff_delays() {
+        let delays = |backoff: Backoff| -> Vec<_> { (1..=5).map(|n| backoff.delay(n)).collect() };
+        assert_eq!(delays(Backoff::Constant(ms(50))), [ms(50); 5]);
+        assert_eq!(
+            delays(Backoff::Linear {
+                initial: ms(100),
+                step: ms(50)
+            }),
+            [ms(100), ms(150), ms(200), ms(250), ms(300)]
+        );
+        assert_eq!(
+            delays(Backoff::exponential(ms(100), 2)),
+            [ms(100), ms(200), ms(400), ms(800), ms(1600)]
+        );
+        let capped = Backoff::Exponential {
+            initial: ms(100),
+            factor: 3,
+            max: ms(1000),
+        };
+        assert_eq!(delays(capped), [ms(100), ms(300), ms(900), ms(1000), ms(1000)]);
+        /*
+         * Los desbordamientos se quedan en el límite en lugar de hacer `panic`
+         */
+        assert_eq!(capped.delay(u32::MAX), ms(1000));
+        assert_eq!(Backoff::exponential(ms(1), 10).delay(40), Duration::MAX);
+        let linear = Backoff::Linear {
+            initial: ms(1),
+            step: Duration::MAX,
+        };
+        assert_eq!(linear.delay(3), Duration::MAX);
+    }
+
+    #[test]
+    fn run_sleeps_between_attempts() {
+        let clock = MockClock::new();
+        let policy = Policy::new(4, Backoff::exponential(ms(100), 2));
+        assert_eq!(policy.delays().collect::<Vec<_>>(), [ms(100), ms(200), ms(400)]);
+        let mut attempts = Vec::new();
+        let result = policy.run(&clock, |attempt| {
+            attempts.push(attempt);
+            if attempt.number < 3 {
+                Err("timed out")
+            } else {
+                Ok(attempt.number)
+            }
+        });
+        assert_eq!(result, Ok(3));
+        let elapsed: Vec<_> = attempts.iter().map(|attempt| attempt.elapsed).collect();
+        assert_eq!(elapsed, [ms(0), ms(100), ms(300)]);
+        assert_eq!(clock.now(), ms(300));
+    }
+
+    #[test]
+    fn run_gives_up_after_max_attempts() {
+        let clock = MockClock::new();
+        clock.set(ms(5_000));
+        let policy = Policy::new(4, Backoff::exponential(ms(100), 2));
+        let error = policy
+            .run(&clock, |attempt| Err::<(), _>(attempt.number))
+            .unwrap_err();
+        assert_eq!(
+            error,
+            RetryError {
+                attempts: 4,
+                elapsed: ms(700),
+                permanent: false,
+                error: 4,
+            }
+        );
+        assert_eq!(error.to_string(), "gave up after 4 attempts in 700ms: 4");
+        assert_eq!(clock.now(), ms(5_700));
+    }
+
+    #[test]
+    fn permanent_errors_are_not_retried() {
+        let clock = MockClock::new();
+        let policy = Policy::new(5, Backoff::Constant(ms(10)));
+        let error = policy
+            .run_if(
+                &clock,
+                |attempt| Err::<(), _>(if attempt.number < 2 { "busy" } else { "denied" }),
+                |error| *error == "busy",
+            )
+            .unwrap_err();
+        assert_eq!((error.attempts, error.permanent), (2, true));
+        assert_eq!(error.to_string(), "permanent error after 2 attempts: denied");
+        assert_eq!(clock.now(), ms(10));
+    }
+
+    #[test]
+    fn retrying_wraps_a_function() {
+        let clock = MockClock::new();
+        let mut calls = 0;
+        let mut half = retrying(
+            Policy::new(3, Backoff::Constant(ms(25))),
+            clock.clone(),
+            |n: u32| {
+                calls += 1;
+                if calls % 2 == 1 {
+                    Err(format!("call {calls} failed"))
+                } else {
+                    Ok(n / 2)
+                }
+            },
+        );
+        assert_eq!(half(10), Ok(5));
+        assert_eq!(half(8), Ok(4));
+        assert_eq!(clock.now(), ms(50));
+    }
+}
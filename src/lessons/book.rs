@@ -0,0 +1,726 @@
+/*
+ * Los archivos de `src` se leen como un libro, cada archivo es un capítulo. Los comentarios son
+ * el texto y el código que los sigue se muestra resaltado debajo
+ *
+ *  src/flow/loops.rs  --extract-->  Lesson  --Book-->  flow-loops.html
+ *                                                      flow-loops.md
+ *
+ * Los capítulos siguen el orden del curso, primero los tipos y al final los genéricos. Una línea
+ * del comentario que empieza con espacios es parte de un diagrama y se copia tal cual
+ */
+use super::highlight::{self, escape};
+use super::lesson::{Language, Lesson};
+
+pub struct Topic {
+    /*
+     * El directorio dentro de `src`
+     */
+    pub dir: &'static str,
+    pub es: &'static str,
+    pub en: &'static str,
+}
+
+impl Topic {
+    pub fn title(&self, language: Language) -> &'static str {
+        match language {
+            Language::Spanish => self.es,
+            Language::English => self.en,
+        }
+    }
+}
+
+pub const TOPICS: &[Topic] = &[
+    Topic {
+        dir: "types",
+        es: "Tipos y variables",
+        en: "Types and variables",
+    },
+    Topic {
+        dir: "func",
+        es: "Funciones",
+        en: "Functions",
+    },
+    Topic {
+        dir: "flow",
+        es: "Control de flujo",
+        en: "Control flow",
+    },
+    Topic {
+        dir: "ownership",
+        es: "Ownership y préstamos",
+        en: "Ownership and borrowing",
+    },
+    Topic {
+        dir: "custom_types",
+        es: "Tipos propios",
+        en: "Custom types",
+    },
+    Topic {
+        dir: "collections",
+        es: "Colecciones",
+        en: "Collections",
+    },
+    Topic {
+        dir: "any",
+        es: "Genéricos, traits y lifetimes",
+        en: "Generics, traits and lifetimes",
+    },
+];
+
+/*
+ * Las lecciones en el orden en que se escribieron, los demás archivos de cada tema van después
+ * ordenados por nombre
+ */
+pub const COURSE: &[&str] = &[
+    "types/variables_const.rs",
+    "types/primitive_types.rs",
+    "types/integers.rs",
+    "func/functions.rs",
+    "flow/statemantes_expressions.rs",
+    "flow/if_expression.rs",
+    "flow/loops.rs",
+    "ownership/ownership.rs",
+    "ownership/borrowing.rs",
+    "ownership/slice.rs",
+    "ownership/string.rs",
+    "ownership/string_slices.rs",
+    "custom_types/structures.rs",
+    "custom_types/enums.rs",
+    "custom_types/pattern_match.rs",
+    "collections/vector.rs",
+    "collections/hashmap.rs",
+    "any/generics.rs",
+    "any/traits.rs",
+    "any/lifetimes.rs",
+];
+
+/*
+ * El tema y el lugar dentro del tema, `None` para un archivo fuera de los temas como `main.rs`
+ */
+pub fn position(path: &str) -> Option<(usize, usize)> {
+    let dir = path.split('/').next()?;
+    let topic = TOPICS.iter().position(|topic| topic.dir == dir)?;
+    let order = COURSE
+        .iter()
+        .position(|lesson| *lesson == path)
+        .unwrap_or(usize::MAX);
+    Some((topic, order))
+}
+
+pub struct Chapter {
+    pub lesson: Lesson,
+}
+
+impl Chapter {
+    /*
+     * El texto en español sale del código, las traducciones de un `.lesson` anterior si lo hay
+     */
+    pub fn new(path: &str, source: &str, translation: Option<&Lesson>) -> Chapter {
+        let mut lesson = Lesson::extract(path, source);
+        if let Some(translation) = translation {
+            lesson.merge(translation);
+        }
+        Chapter { lesson }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.lesson.path
+    }
+
+    /*
+     * Todos los capítulos quedan en un solo directorio, `ownership/borrowck/checker.rs` se
+     * convierte en `ownership-borrowck-checker`
+     */
+    pub fn name(&self) -> String {
+        self.path().trim_end_matches(".rs").replace('/', "-")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    Paragraph(String),
+    /*
+     * Las líneas tal como están en el comentario, sin la sangría común
+     */
+    Diagram(String),
+    List { ordered: bool, items: Vec<String> },
+}
+
+/*
+ * `1) Cada valor...` o `- Cada valor...`, devuelve el texto sin la marca
+ */
+fn list_item(line: &str) -> Option<(bool, &str)> {
+    if let Some(rest) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return Some((false, rest));
+    }
+    let digits = line.find(|c: char| !c.is_ascii_digit())?;
+    let rest = line[digits..]
+        .strip_prefix(") ")
+        .or_else(|| line[digits..].strip_prefix(". "))?;
+    (digits > 0).then_some((true, rest))
+}
+
+pub fn blocks(prose: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut diagram: Vec<&str> = Vec::new();
+    let flush = |diagram: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        while diagram.last().is_some_and(|line| line.trim().is_empty()) {
+            diagram.pop();
+        }
+        if diagram.is_empty() {
+            return;
+        }
+        let indent = diagram
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let lines: Vec<&str> = diagram
+            .drain(..)
+            .map(|line| line.get(indent..).unwrap_or("").trim_end())
+            .collect();
+        blocks.push(Block::Diagram(lines.join("\n")));
+    };
+    /*
+     * Una línea vacía cierra el párrafo, la siguiente línea empieza otro
+     */
+    let mut open = false;
+    for line in prose.lines() {
+        let blank = line.trim().is_empty();
+        if line.starts_with([' ', '\t']) && !blank {
+            diagram.push(line);
+            open = false;
+            continue;
+        }
+        if blank {
+            /*
+             * Dentro de un diagrama es parte del dibujo si el diagrama sigue
+             */
+            if !diagram.is_empty() {
+                diagram.push(line);
+            }
+            open = false;
+            continue;
+        }
+        flush(&mut diagram, &mut blocks);
+        let text = line.trim();
+        if let Some((ordered, item)) = list_item(text) {
+            match blocks.last_mut() {
+                Some(Block::List {
+                    ordered: last,
+                    items,
+                }) if *last == ordered => items.push(item.to_string()),
+                _ => blocks.push(Block::List {
+                    ordered,
+                    items: vec![item.to_string()],
+                }),
+            }
+        } else if open {
+            let last = match blocks.last_mut() {
+                Some(Block::Paragraph(last)) => Some(last),
+                Some(Block::List { items, .. }) => items.last_mut(),
+                _ => None,
+            };
+            if let Some(last) = last {
+                last.push(' ');
+                last.push_str(text);
+            }
+        } else {
+            blocks.push(Block::Paragraph(text.to_string()));
+        }
+        open = true;
+    }
+    flush(&mut diagram, &mut blocks);
+    blocks
+}
+
+/*
+ * El texto entre comillas invertidas es código, el resto se escapa
+ */
+fn inline_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for (index, part) in text.split('`').enumerate() {
+        if index % 2 == 1 {
+            out.push_str(&format!("<code>{}</code>", escape(part)));
+        } else {
+            out.push_str(&escape(part));
+        }
+    }
+    out
+}
+
+/*
+ * En `Markdown` un `<` fuera del código se leería como `HTML`
+ */
+fn inline_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for (index, part) in text.split('`').enumerate() {
+        if index > 0 {
+            out.push('`');
+        }
+        if index % 2 == 1 {
+            out.push_str(part);
+        } else {
+            out.push_str(&part.replace('<', "&lt;"));
+        }
+    }
+    out
+}
+
+const STYLE: &str = "
+body { max-width: 46rem; margin: 2rem auto; padding: 0 1rem; font: 17px/1.6 sans-serif;
+       color: #222; }
+nav { display: flex; gap: 1rem; justify-content: space-between; font-size: 0.9rem; }
+h1 { margin-top: 1.5rem; }
+h2 { margin-top: 2.5rem; border-bottom: 1px solid #ddd; }
+h3 { margin-top: 2rem; font-size: 1rem; }
+code { font: 0.9em/1.4 monospace; background: #f4f4f4; padding: 0 0.2em; }
+pre { background: #f7f7f7; border-left: 3px solid #ccc; padding: 0.8rem; overflow-x: auto; }
+pre code { background: none; padding: 0; }
+pre.diagram { background: none; border-left: none; }
+.untranslated { border-left: 3px solid #e0b040; padding-left: 0.8rem; }
+.kw { color: #8959a8; } .ty { color: #3e999f; } .ma { color: #c82829; }
+.st { color: #718c00; } .nu { color: #f5871f; } .lt { color: #eab700; }
+.co { color: #8e908c; font-style: italic; } .at { color: #4271ae; }
+ol.toc > li { margin-top: 1rem; font-weight: bold; }
+ol.toc ol { font-weight: normal; }
+";
+
+pub struct Book {
+    pub language: Language,
+    /*
+     * En el orden del libro, sin los archivos que no tienen comentarios
+     */
+    pub chapters: Vec<Chapter>,
+}
+
+impl Book {
+    /*
+     * Ordena los capítulos por tema y deja fuera los archivos que no son de ningún tema
+     */
+    pub fn new(language: Language, chapters: Vec<Chapter>) -> Book {
+        let mut chapters: Vec<_> = chapters
+            .into_iter()
+            .filter(|chapter| !chapter.lesson.steps.is_empty())
+            .filter_map(|chapter| Some((position(chapter.path())?, chapter)))
+            .collect();
+        chapters.sort_by(|(a, a_chapter), (b, b_chapter)| {
+            a.cmp(b)
+                .then_with(|| a_chapter.path().cmp(b_chapter.path()))
+        });
+        Book {
+            language,
+            chapters: chapters.into_iter().map(|(_, chapter)| chapter).collect(),
+        }
+    }
+
+    fn label(&self, key: &str) -> &'static str {
+        match (self.language, key) {
+            (Language::Spanish, "contents") => "Contenido",
+            (Language::Spanish, "previous") => "Anterior",
+            (Language::Spanish, "next") => "Siguiente",
+            (Language::Spanish, "title") => "Lo básico de Rust",
+            (Language::Spanish, "untranslated") => "Sin traducir",
+            (Language::English, "contents") => "Contents",
+            (Language::English, "previous") => "Previous",
+            (Language::English, "next") => "Next",
+            (Language::English, "title") => "The basics of Rust",
+            (Language::English, "untranslated") => "Not translated yet",
+            _ => "",
+        }
+    }
+
+    /*
+     * Los temas que tienen capítulos, cada uno con los índices de sus capítulos
+     */
+    pub fn topics(&self) -> Vec<(&'static Topic, Vec<usize>)> {
+        let mut topics: Vec<(&'static Topic, Vec<usize>)> = Vec::new();
+        for (index, chapter) in self.chapters.iter().enumerate() {
+            let Some((topic, _)) = position(chapter.path()) else {
+                continue;
+            };
+            match topics.last_mut() {
+                Some((last, chapters)) if last.dir == TOPICS[topic].dir => chapters.push(index),
+                _ => topics.push((&TOPICS[topic], vec![index])),
+            }
+        }
+        topics
+    }
+
+    /*
+     * `3.2`, el número del tema dentro del libro y del capítulo dentro del tema
+     */
+    pub fn number(&self, index: usize) -> String {
+        self.topics()
+            .iter()
+            .enumerate()
+            .find_map(|(topic, (_, chapters))| {
+                let chapter = chapters.iter().position(|&other| other == index)?;
+                Some(format!("{}.{}", topic + 1, chapter + 1))
+            })
+            .unwrap_or_default()
+    }
+
+    fn heading(&self, index: usize) -> String {
+        format!("{} {}", self.number(index), self.chapters[index].path())
+    }
+
+    /*
+     * El `item` se muestra como subtítulo cuando cambia, `fn loop_flow`
+     */
+    fn sections(&self, index: usize) -> Vec<(Option<&str>, &str, bool, &str)> {
+        let mut sections = Vec::new();
+        let mut item = None;
+        for step in &self.chapters[index].lesson.steps {
+            let title = (step.item != item)
+                .then_some(step.item.as_deref())
+                .flatten();
+            item = step.item.clone();
+            let (text, fallback) = step.text(self.language);
+            sections.push((title, text, fallback, step.code.as_str()));
+        }
+        sections
+    }
+
+    pub fn markdown(&self, index: usize) -> String {
+        let mut out = format!("# {}\n", self.heading(index));
+        for (title, text, _, code) in self.sections(index) {
+            if let Some(title) = title {
+                out.push_str(&format!("\n## `{title}`\n"));
+            }
+            for block in blocks(text) {
+                out.push('\n');
+                match block {
+                    Block::Paragraph(text) => {
+                        out.push_str(&format!("{}\n", inline_markdown(&text)))
+                    }
+                    Block::Diagram(text) => out.push_str(&format!("```text\n{text}\n```\n")),
+                    Block::List { ordered, items } => {
+                        for (number, item) in items.iter().enumerate() {
+                            let marker = if ordered {
+                                format!("{}.", number + 1)
+                            } else {
+                                "-".to_string()
+                            };
+                            out.push_str(&format!("{marker} {}\n", inline_markdown(item)));
+                        }
+                    }
+                }
+            }
+            if !code.trim().is_empty() {
+                out.push_str(&format!("\n```rust\n{code}\n```\n"));
+            }
+        }
+        out
+    }
+
+    /*
+     * El índice en el formato de `mdBook`
+     */
+    pub fn summary(&self) -> String {
+        let mut out = format!("# {}\n", self.label("title"));
+        for (topic, chapters) in self.topics() {
+            out.push_str(&format!("\n# {}\n\n", topic.title(self.language)));
+            for index in chapters {
+                let chapter = &self.chapters[index];
+                out.push_str(&format!(
+                    "- [{}]({}.md)\n",
+                    self.heading(index),
+                    chapter.name()
+                ));
+            }
+        }
+        out
+    }
+
+    fn page(&self, title: &str, body: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+             <title>{}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n{body}</body>\n</html>\n",
+            self.language.code(),
+            escape(title),
+        )
+    }
+
+    fn navigation(&self, index: usize) -> String {
+        let link = |other: Option<usize>, key: &str| match other
+            .and_then(|other| self.chapters.get(other))
+        {
+            Some(chapter) => format!(
+                "<a href=\"{}.html\">{}</a>",
+                chapter.name(),
+                self.label(key)
+            ),
+            None => "<span></span>".to_string(),
+        };
+        format!(
+            "<nav>{}<a href=\"index.html\">{}</a>{}</nav>\n",
+            link(index.checked_sub(1), "previous"),
+            self.label("contents"),
+            link(Some(index + 1), "next"),
+        )
+    }
+
+    pub fn html(&self, index: usize) -> String {
+        let heading = self.heading(index);
+        let mut body = self.navigation(index);
+        body.push_str(&format!("<h1>{}</h1>\n", escape(&heading)));
+        for (title, text, fallback, code) in self.sections(index) {
+            if let Some(title) = title {
+                body.push_str(&format!("<h2><code>{}</code></h2>\n", escape(title)));
+            }
+            if fallback {
+                body.push_str(&format!(
+                    "<div class=\"untranslated\" lang=\"es\" title=\"{}\">\n",
+                    self.label("untranslated")
+                ));
+            }
+            for block in blocks(text) {
+                match block {
+                    Block::Paragraph(text) => {
+                        body.push_str(&format!("<p>{}</p>\n", inline_html(&text)))
+                    }
+                    Block::Diagram(text) => {
+                        body.push_str(&format!("<pre class=\"diagram\">{}</pre>\n", escape(&text)))
+                    }
+                    Block::List { ordered, items } => {
+                        let tag = if ordered { "ol" } else { "ul" };
+                        body.push_str(&format!("<{tag}>\n"));
+                        for item in items {
+                            body.push_str(&format!("<li>{}</li>\n", inline_html(&item)));
+                        }
+                        body.push_str(&format!("</{tag}>\n"));
+                    }
+                }
+            }
+            if fallback {
+                body.push_str("</div>\n");
+            }
+            if !code.trim().is_empty() {
+                body.push_str(&format!(
+                    "<pre><code class=\"language-rust\">{}</code></pre>\n",
+                    highlight::html(code)
+                ));
+            }
+        }
+        body.push_str(&self.navigation(index));
+        self.page(&heading, &body)
+    }
+
+    /*
+     * La tabla de contenido, un tema por sección en el orden del curso
+     */
+    pub fn index(&self) -> String {
+        let title = self.label("title");
+        let mut body = format!("<h1>{}</h1>\n<ol class=\"toc\">\n", escape(title));
+        for (topic, chapters) in self.topics() {
+            body.push_str(&format!(
+                "<li>{}\n<ol>\n",
+                escape(topic.title(self.language))
+            ));
+            for index in chapters {
+                let chapter = &self.chapters[index];
+                body.push_str(&format!(
+                    "<li><a href=\"{}.html\">{}</a></li>\n",
+                    chapter.name(),
+                    escape(chapter.path())
+                ));
+            }
+            body.push_str("</ol>\n</li>\n");
+        }
+        body.push_str("</ol>\n");
+        self.page(title, &body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+     * Un capítulo con un solo comentario, sin comentarios no entra al libro
+     */
+    fn chapter(path: &str, comment: &str) -> Chapter {
+        let source = if comment.is_empty() {
+            "pub fn run() {}\n".to_string()
+        } else {
+            format!("/*\n * {comment}\n */\npub fn run() {{}}\n")
+        };
+        Chapter::new(path, &source, None)
+    }
+
+    #[test]
+    fn paragraphs_join_lines() {
+        assert_eq!(
+            blocks("Una línea\nsigue el párrafo\n\nOtro párrafo"),
+            [
+                Block::Paragraph("Una línea sigue el párrafo".to_string()),
+                Block::Paragraph("Otro párrafo".to_string()),
+            ]
+        );
+        assert_eq!(blocks(""), []);
+        assert_eq!(blocks("\n\n"), []);
+    }
+
+    #[test]
+    fn indented_lines_are_diagrams() {
+        let prose = "Antes\n\n   +---+\n   | a |\n\n     +---+\n\n\nDespués";
+        assert_eq!(
+            blocks(prose),
+            [
+                Block::Paragraph("Antes".to_string()),
+                Block::Diagram("+---+\n| a |\n\n  +---+".to_string()),
+                Block::Paragraph("Después".to_string()),
+            ]
+        );
+        /*
+         * Una línea sin sangría cierra el diagrama aunque no haya una línea vacía
+         */
+        assert_eq!(
+            blocks(" x -> y\ntexto"),
+            [
+                Block::Diagram("x -> y".to_string()),
+                Block::Paragraph("texto".to_string()),
+            ]
+        );
+        assert_eq!(blocks("\tuno  \n\t\tdos"), [Block::Diagram("uno\n\tdos".to_string())]);
+    }
+
+    #[test]
+    fn lists_ordered_and_not() {
+        let prose = "Hay dos formas\n\n1) Una `Arena`\n   no, esto es diagrama\n2. Un `SlotMap`\n\
+                     que sigue\n- suelto\n* otro\n\n10) diez";
+        assert_eq!(
+            blocks(prose),
+            [
+                Block::Paragraph("Hay dos formas".to_string()),
+                Block::List {
+                    ordered: true,
+                    items: vec!["Una `Arena`".to_string()],
+                },
+                Block::Diagram("no, esto es diagrama".to_string()),
+                Block::List {
+                    ordered: true,
+                    items: vec!["Un `SlotMap` que sigue".to_string()],
+                },
+                Block::List {
+                    ordered: false,
+                    items: vec!["suelto".to_string(), "otro".to_string()],
+                },
+                Block::List {
+                    ordered: true,
+                    items: vec!["diez".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn list_markers_need_a_space() {
+        assert_eq!(list_item("1) uno"), Some((true, "uno")));
+        assert_eq!(list_item("12. doce"), Some((true, "doce")));
+        assert_eq!(list_item("- menos"), Some((false, "menos")));
+        assert_eq!(list_item("-1 no"), None);
+        assert_eq!(list_item(") nada"), None);
+        assert_eq!(list_item("3.14 no"), None);
+        assert_eq!(list_item("*/"), None);
+    }
+
+    #[test]
+    fn inline_code_is_escaped() {
+        assert_eq!(
+            inline_html("Un `Vec<&str>` & <b>texto</b>"),
+            "Un <code>Vec&lt;&amp;str&gt;</code> &amp; &lt;b&gt;texto&lt;/b&gt;"
+        );
+        /*
+         * En `Markdown` el código queda intacto y fuera de él solo importa `<`
+         */
+        assert_eq!(inline_markdown("Un `Vec<&str>` & <b>"), "Un `Vec<&str>` & &lt;b>");
+        assert_eq!(inline_html("sin cerrar `a<b"), "sin cerrar <code>a&lt;b</code>");
+    }
+
+    #[test]
+    fn chapters_escape_prose_and_code() {
+        let source = "/*\n * Compara `a < b` & <b>\n */\n\
+                      pub fn less(a: i32, b: i32) -> bool {\n    a < b && b > 0\n}\n";
+        let book = Book::new(
+            Language::Spanish,
+            vec![Chapter::new("types/compare.rs", source, None)],
+        );
+        let html = book.html(0);
+        assert!(html.contains("<h2><code>fn less</code></h2>"));
+        assert!(html.contains("<p>Compara <code>a &lt; b</code> &amp; &lt;b&gt;</p>"));
+        assert!(html.contains(
+            "<span class=\"kw\">pub</span> <span class=\"kw\">fn</span> less(a: i32, b: i32)"
+        ));
+        assert!(html.contains("a &lt; b &amp;&amp; b &gt; <span class=\"nu\">0</span>"));
+        assert!(html.contains("<title>1.1 types/compare.rs</title>"));
+        assert_eq!(
+            book.markdown(0),
+            "# 1.1 types/compare.rs\n\n## `fn less`\n\nCompara `a < b` & &lt;b>\n\n\
+             ```rust\npub fn less(a: i32, b: i32) -> bool {\n    a < b && b > 0\n}\n```\n"
+        );
+    }
+
+    #[test]
+    fn topics_follow_the_course() {
+        let book = Book::new(
+            Language::English,
+            vec![
+                chapter("any/generics.rs", "Genéricos"),
+                chapter("types/zebra.rs", "Fuera del curso"),
+                chapter("main.rs", "Sin tema"),
+                chapter("flow/loops.rs", "Ciclos"),
+                chapter("types/integers.rs", "Enteros"),
+                chapter("flow/empty.rs", ""),
+                chapter("types/alpha.rs", "Fuera del curso"),
+                chapter("types/variables_const.rs", "Variables"),
+            ],
+        );
+        let paths: Vec<&str> = book.chapters.iter().map(Chapter::path).collect();
+        /*
+         * Primero los del curso en su orden, después los demás por nombre
+         */
+        assert_eq!(
+            paths,
+            [
+                "types/variables_const.rs",
+                "types/integers.rs",
+                "types/alpha.rs",
+                "types/zebra.rs",
+                "flow/loops.rs",
+                "any/generics.rs",
+            ]
+        );
+        let topics: Vec<(&str, Vec<usize>)> = book
+            .topics()
+            .into_iter()
+            .map(|(topic, chapters)| (topic.dir, chapters))
+            .collect();
+        assert_eq!(
+            topics,
+            [
+                ("types", vec![0, 1, 2, 3]),
+                ("flow", vec![4]),
+                ("any", vec![5]),
+            ]
+        );
+        assert_eq!(book.number(3), "1.4");
+        assert_eq!(book.number(5), "3.1");
+        assert_eq!(book.number(6), "");
+        assert!(book
+            .summary()
+            .contains("\n# Control flow\n\n- [2.1 flow/loops.rs](flow-loops.md)\n"));
+    }
+
+    #[test]
+    fn position_of_a_path() {
+        assert_eq!(position("types/variables_const.rs"), Some((0, 0)));
+        assert_eq!(position("any/lifetimes.rs"), Some((6, COURSE.len() - 1)));
+        assert_eq!(position("ownership/arena/graph.rs"), Some((3, usize::MAX)));
+        assert_eq!(position("main.rs"), None);
+        assert_eq!(chapter("ownership/arena/graph.rs", "Grafo").name(), "ownership-arena-graph");
+    }
+}
@@ -0,0 +1,488 @@
+/*
+ * Cada lección es un archivo de `Rust` donde la explicación va en bloques `/* */` y el ejemplo en
+ * el código que sigue. `extract` separa el archivo en segmentos, un bloque con el código que hay
+ * hasta el siguiente bloque, y le asigna a cada uno el `item` al que pertenece
+ *
+ *  /*                                  <- segmento 1, item `fn loop_flow`
+ *   * Una estructura de control ...
+ *   */
+ *  pub fn loop_flow() {
+ *      /*                              <- segmento 2, item `fn loop_flow`, dentro del cuerpo
+ *       * En `Rust` existe ...
+ *       */
+ *      let mut counter = 0;
+ *
+ * Solo cuentan los bloques que ocupan sus propias líneas, un `/* */` en medio de una línea de
+ * código se queda en el código. Los `strings`, `chars` y comentarios `//` se ignoran al buscar
+ * bloques y llaves, un comentario escrito dentro de un texto no abre un comentario
+ */
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+    /*
+     * `fn`, `struct`, `impl`, ...
+     */
+    pub kind: &'static str,
+    /*
+     * El nombre, o la cabecera completa para `impl` y `use`, `impl<T> From<(T, T)> for Point<T>`
+     */
+    pub name: String,
+    pub line: usize,
+    /*
+     * Desde el primer atributo o `pub` hasta la llave o el `;` que lo cierra
+     */
+    pub start: usize,
+    pub end: usize,
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            "impl" | "use" => write!(f, "{}", self.name),
+            kind => write!(f, "{kind} {}", self.name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /*
+     * La línea donde empieza el bloque, desde `1`
+     */
+    pub line: usize,
+    /*
+     * La columna donde empieza el bloque, `0` para uno al nivel del archivo
+     */
+    pub indent: usize,
+    /*
+     * `true` cuando el bloque está dentro de las llaves de su `item`
+     */
+    pub inner: bool,
+    pub item: Option<usize>,
+    pub prose: String,
+    pub code: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+    pub items: Vec<Item>,
+    pub segments: Vec<Segment>,
+    /*
+     * El código antes del primer bloque, normalmente los `use`
+     */
+    pub preamble: String,
+}
+
+impl Source {
+    pub fn item(&self, segment: &Segment) -> Option<&Item> {
+        segment.item.map(|index| &self.items[index])
+    }
+}
+
+const ITEM_KEYWORDS: &[&str] = &[
+    "fn",
+    "struct",
+    "enum",
+    "trait",
+    "impl",
+    "const",
+    "static",
+    "type",
+    "mod",
+    "use",
+    "macro_rules",
+];
+
+struct Comment {
+    start: usize,
+    end: usize,
+}
+
+/*
+ * Copia el texto reemplazando comentarios, `strings` y `chars` por espacios, los saltos de línea
+ * se quedan. Las posiciones no cambian, así las llaves y palabras se buscan en la copia y el texto
+ * se toma del original. Devuelve también los bloques que ocupan sus propias líneas
+ */
+fn mask(source: &str) -> (Vec<u8>, Vec<Comment>) {
+    let bytes = source.as_bytes();
+    let mut masked = bytes.to_vec();
+    let mut comments = Vec::new();
+    let blank = |masked: &mut Vec<u8>, from: usize, to: usize| {
+        for byte in &mut masked[from..to] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    };
+    let ident = |index: usize| {
+        index > 0 && (bytes[index - 1].is_ascii_alphanumeric() || bytes[index - 1] == b'_')
+    };
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+        if rest.starts_with(b"//") {
+            let end = source[i..].find('\n').map_or(bytes.len(), |n| i + n);
+            blank(&mut masked, i, end);
+            i = end;
+        } else if rest.starts_with(b"/*") {
+            let mut depth = 0;
+            let mut end = i;
+            while end < bytes.len() {
+                if bytes[end..].starts_with(b"/*") {
+                    depth += 1;
+                    end += 2;
+                } else if bytes[end..].starts_with(b"*/") {
+                    depth -= 1;
+                    end += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    end += 1;
+                }
+            }
+            let line_start = source[..i].rfind('\n').map_or(0, |n| n + 1);
+            let line_end = source[end..].find('\n').map_or(bytes.len(), |n| end + n);
+            if source[line_start..i].trim().is_empty() && source[end..line_end].trim().is_empty() {
+                comments.push(Comment { start: i, end });
+            }
+            blank(&mut masked, i, end);
+            i = end;
+        } else if (rest.starts_with(b"r\"") || rest.starts_with(b"r#")) && !ident(i)
+            || (rest.starts_with(b"br\"") || rest.starts_with(b"br#")) && !ident(i)
+        {
+            let open = i + rest.iter().position(|b| *b == b'r').unwrap_or(0) + 1;
+            let hashes = bytes[open..].iter().take_while(|b| **b == b'#').count();
+            if bytes.get(open + hashes) != Some(&b'"') {
+                i += 1;
+                continue;
+            }
+            let close = format!("\"{}", "#".repeat(hashes));
+            let body = open + hashes + 1;
+            let end = source[body..]
+                .find(&close)
+                .map_or(bytes.len(), |n| body + n + close.len());
+            blank(&mut masked, i, end);
+            i = end;
+        } else if rest[0] == b'"' {
+            let mut end = i + 1;
+            while end < bytes.len() && bytes[end] != b'"' {
+                end += if bytes[end] == b'\\' { 2 } else { 1 };
+            }
+            let end = (end + 1).min(bytes.len());
+            blank(&mut masked, i, end);
+            i = end;
+        } else if rest[0] == b'\'' {
+            /*
+             * `'a'` y `'\n'` son `chars`, `'a` sin cerrar es un `lifetime`
+             */
+            let next = source[i + 1..].chars().next();
+            let end = match next {
+                Some('\\') => source[i + 2..].find('\'').map(|n| i + 2 + n + 1),
+                Some(c) if source[i + 1 + c.len_utf8()..].starts_with('\'') => {
+                    Some(i + 1 + c.len_utf8() + 1)
+                }
+                _ => None,
+            };
+            match end {
+                Some(end) => {
+                    blank(&mut masked, i, end);
+                    i = end;
+                }
+                None => i += 1,
+            }
+        } else {
+            i += 1;
+        }
+    }
+    (masked, comments)
+}
+
+fn line_of(source: &str, offset: usize) -> usize {
+    source[..offset].matches('\n').count() + 1
+}
+
+/*
+ * Los `items` al nivel del archivo. Un `item` empieza con la primera palabra después del anterior
+ * y termina con la llave que regresa al nivel `0` o con un `;` en el nivel `0`
+ *
+ *  #[derive(Debug)]      <- start
+ *  pub struct Point<T> {
+ *      x: T,
+ *  }                     <- end
+ */
+fn items(source: &str, masked: &[u8]) -> Vec<Item> {
+    let text = String::from_utf8_lossy(masked);
+    let mut items = Vec::new();
+    let (mut braces, mut parens, mut brackets) = (0i32, 0i32, 0i32);
+    let mut start: Option<usize> = None;
+    let mut current: Option<(&'static str, String, usize)> = None;
+    let mut i = 0;
+    let bytes = masked;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        let top = braces == 0 && parens == 0 && brackets == 0;
+        if top && start.is_none() && !byte.is_ascii_whitespace() {
+            start = Some(i);
+        }
+        if top && current.is_none() && (byte.is_ascii_alphabetic() || byte == b'_') {
+            let word_end = text[i..]
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .map_or(text.len(), |n| i + n);
+            let word = &text[i..word_end];
+            /*
+             * En `const fn` la palabra que cuenta es `fn`
+             */
+            let const_fn = word == "const" && text[word_end..].trim_start().starts_with("fn ");
+            let keyword = ITEM_KEYWORDS.iter().find(|keyword| **keyword == word);
+            if let Some(kind) = keyword.filter(|_| !const_fn) {
+                let name = item_name(kind, &text[word_end..]);
+                current = Some((kind, name, i));
+            }
+            i = word_end;
+            continue;
+        }
+        let mut close = None;
+        match byte {
+            b'{' => braces += 1,
+            b'}' => {
+                braces -= 1;
+                if braces == 0 && parens == 0 && brackets == 0 {
+                    let after = text[i + 1..].trim_start();
+                    close = Some(match after.starts_with(';') {
+                        true => text.len() - after.len() + 1,
+                        false => i + 1,
+                    });
+                }
+            }
+            b'(' => parens += 1,
+            b')' => parens -= 1,
+            b'[' => brackets += 1,
+            b']' => brackets -= 1,
+            b';' if top => close = Some(i + 1),
+            _ => {}
+        }
+        if let Some(end) = close {
+            if let (Some((kind, name, keyword)), Some(begin)) = (current.take(), start) {
+                items.push(Item {
+                    kind,
+                    name,
+                    line: line_of(source, keyword),
+                    start: begin,
+                    end,
+                });
+            }
+            start = None;
+            i = end;
+            continue;
+        }
+        i += 1;
+    }
+    items
+}
+
+/*
+ * El nombre de `fn run()` es `run`, el de un `impl` es la cabecera hasta la llave
+ */
+fn item_name(kind: &str, rest: &str) -> String {
+    match kind {
+        "impl" => {
+            let head = rest.split('{').next().unwrap_or_default();
+            let head = head.split_whitespace().collect::<Vec<_>>().join(" ");
+            format!("impl{}{head}", if head.starts_with('<') { "" } else { " " })
+        }
+        "use" => {
+            let path = rest.split(';').next().unwrap_or_default();
+            format!("use {}", path.split_whitespace().collect::<String>())
+        }
+        _ => rest
+            .trim_start_matches(|c: char| c.is_whitespace() || c == '!')
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+/*
+ * El texto de un bloque sin `/*`, `*/` ni la columna de `*` de cada línea. La sangría que queda
+ * después de `* ` se conserva, los diagramas llegan completos
+ *
+ *      /*
+ *       * Texto               ->   Texto
+ *       *
+ *       *  a -> b                   a -> b
+ *       */
+ */
+pub fn prose(comment: &str) -> String {
+    let inner = comment
+        .strip_prefix("/*")
+        .and_then(|text| text.strip_suffix("*/"))
+        .unwrap_or(comment);
+    let lines: Vec<String> = inner
+        .lines()
+        .map(|line| {
+            let line = line.trim_start();
+            let line = match line.strip_prefix('*') {
+                Some(rest) => rest.strip_prefix(' ').unwrap_or(rest),
+                None => line,
+            };
+            line.trim_end().to_string()
+        })
+        .collect();
+    let first = lines.iter().position(|line| !line.is_empty());
+    let last = lines.iter().rposition(|line| !line.is_empty());
+    match (first, last) {
+        (Some(first), Some(last)) => lines[first..=last].join("\n"),
+        _ => String::new(),
+    }
+}
+
+/*
+ * Quita las líneas vacías del principio y del final y hasta `indent` espacios de cada línea, el
+ * código que sigue a un bloque queda alineado con el bloque. Las llaves que cierran niveles de
+ * afuera no tienen esa sangría y se quedan en la columna `0`
+ */
+pub fn dedent(code: &str, indent: usize) -> String {
+    let lines: Vec<&str> = code.lines().collect();
+    let first = lines.iter().position(|line| !line.trim().is_empty());
+    let last = lines.iter().rposition(|line| !line.trim().is_empty());
+    let (Some(first), Some(last)) = (first, last) else {
+        return String::new();
+    };
+    lines[first..=last]
+        .iter()
+        .map(|line| {
+            let spaces = line.len() - line.trim_start_matches(' ').len();
+            line[spaces.min(indent)..].trim_end()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn extract(source: &str) -> Source {
+    let (masked, comments) = mask(source);
+    let items = items(source, &masked);
+    let mut segments = Vec::new();
+    for (index, comment) in comments.iter().enumerate() {
+        let code_end = comments.get(index + 1).map_or(source.len(), |next| {
+            source[..next.start]
+                .rfind('\n')
+                .map_or(next.start, |n| n + 1)
+        });
+        let code_start = source[comment.end..]
+            .find('\n')
+            .map_or(source.len(), |n| comment.end + n + 1);
+        let line_start = source[..comment.start].rfind('\n').map_or(0, |n| n + 1);
+        /*
+         * Un bloque dentro de un `item` pertenece a ese `item`, uno afuera al siguiente
+         */
+        let inside = items
+            .iter()
+            .position(|item| item.start < comment.start && comment.end <= item.end);
+        let item = inside.or_else(|| items.iter().position(|item| item.start >= comment.end));
+        let indent = comment.start - line_start;
+        segments.push(Segment {
+            line: line_of(source, comment.start),
+            indent,
+            inner: inside.is_some(),
+            item,
+            prose: prose(&source[comment.start..comment.end]),
+            code: dedent(&source[code_start.min(code_end)..code_end], indent),
+        });
+    }
+    let preamble_end = comments
+        .first()
+        .map_or(source.len(), |comment| comment.start);
+    Source {
+        items,
+        segments,
+        preamble: dedent(&source[..preamble_end], 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"use std::fmt;
+
+/*
+ * Un punto en el plano
+ */
+pub struct Point {
+    x: i32,
+}
+
+/*
+ * Escribe el punto
+ *
+ *  (x)  ->  "(1)"
+ */
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /*
+         * Un `string` o un `char` con una llave no abre nada
+         */
+        let open = "/* {";
+        let brace = '{'; /* al final de la línea no cuenta */
+        // tampoco /* un comentario de línea
+        write!(f, "({}){open}{brace}", self.x)
+    }
+}
+"#;
+
+    #[test]
+    fn segments_and_items() {
+        let source = extract(SOURCE);
+        let items: Vec<String> = source.items.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            items,
+            ["use std::fmt", "struct Point", "impl fmt::Display for Point"]
+        );
+        assert_eq!(source.preamble, "use std::fmt;");
+
+        let segments: Vec<_> = source
+            .segments
+            .iter()
+            .map(|segment| (segment.line, segment.indent, segment.inner, segment.item))
+            .collect();
+        assert_eq!(
+            segments,
+            [(3, 0, false, Some(1)), (10, 0, false, Some(2)), (17, 8, true, Some(2))]
+        );
+        assert_eq!(source.segments[0].prose, "Un punto en el plano");
+        assert_eq!(source.segments[0].code, "pub struct Point {\n    x: i32,\n}");
+        /*
+         * La sangría del diagrama se conserva
+         */
+        assert_eq!(source.segments[1].prose, "Escribe el punto\n\n (x)  ->  \"(1)\"");
+        assert_eq!(
+            source.segments[1].code,
+            "impl fmt::Display for Point {\n    \
+             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {"
+        );
+        assert_eq!(
+            source.segments[2].prose,
+            "Un `string` o un `char` con una llave no abre nada"
+        );
+        assert!(source.segments[2]
+            .code
+            .starts_with("let open = \"/* {\";\nlet brace = '{'; /* al final"));
+        assert!(source.segments[2].code.ends_with("\n}\n}"));
+    }
+
+    #[test]
+    fn dedent_keeps_outer_braces() {
+        assert_eq!(dedent("\n\n        a\n            b\n    }\n}\n\n", 8), "a\n    b\n}\n}");
+        assert_eq!(dedent("  \n\t\n", 4), "");
+    }
+
+    #[test]
+    fn prose_without_stars() {
+        assert_eq!(prose("/* una línea */"), "una línea");
+        assert_eq!(prose("/*\n *\n * a\n *   b\n *\n */"), "a\n  b");
+        assert_eq!(prose("/**/"), "");
+    }
+}
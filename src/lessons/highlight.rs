@@ -0,0 +1,392 @@
+/*
+ * Un resaltador de sintaxis pequeño para el libro. No entiende `Rust`, solo separa el texto en
+ * piezas que se pintan distinto, igual que hace un editor antes de tener un `parser`
+ *
+ *  pub fn largest<T: PartialOrd>(list: &[T]) -> &T {
+ *  ^^^ ^^                                           keyword
+ *                 ^  ^^^^^^^^^                      type, empieza con mayúscula
+ *  println!("{}", 'a');                             macro, string y char
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token {
+    Keyword,
+    Type,
+    Macro,
+    String,
+    Char,
+    Number,
+    Lifetime,
+    Comment,
+    Attribute,
+    Plain,
+}
+
+impl Token {
+    /*
+     * La clase de `CSS` de cada pieza, `Plain` no lleva `span`
+     */
+    pub fn class(self) -> Option<&'static str> {
+        match self {
+            Token::Keyword => Some("kw"),
+            Token::Type => Some("ty"),
+            Token::Macro => Some("ma"),
+            Token::String | Token::Char => Some("st"),
+            Token::Number => Some("nu"),
+            Token::Lifetime => Some("lt"),
+            Token::Comment => Some("co"),
+            Token::Attribute => Some("at"),
+            Token::Plain => None,
+        }
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe",
+    "use", "where", "while", "async", "await",
+];
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/*
+ * La posición donde termina un comentario `/* */`, los comentarios se pueden anidar
+ */
+fn block_comment_end(code: &str, start: usize) -> usize {
+    let bytes = code.as_bytes();
+    let mut depth = 0;
+    let mut i = start;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"/*") {
+            depth += 1;
+            i += 2;
+        } else if bytes[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    code.len()
+}
+
+fn string_end(code: &str, start: usize) -> usize {
+    let mut escaped = false;
+    for (offset, c) in code[start + 1..].char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return start + 1 + offset + 1,
+            _ => {}
+        }
+    }
+    code.len()
+}
+
+/*
+ * `r"..."` y `r#"..."#`, `None` si después de la `r` no viene un `string`, como en `r#type`
+ */
+fn raw_string_end(code: &str, start: usize) -> Option<usize> {
+    let rest = &code[start..];
+    if code[..start].chars().next_back().is_some_and(is_ident) {
+        return None;
+    }
+    let after_r = rest
+        .strip_prefix('b')
+        .unwrap_or(rest)
+        .strip_prefix('r')?
+        .len();
+    let after_r = rest.len() - after_r;
+    let hashes = rest[after_r..].chars().take_while(|c| *c == '#').count();
+    if !rest[after_r + hashes..].starts_with('"') {
+        return None;
+    }
+    let close = format!("\"{}", "#".repeat(hashes));
+    let body = start + after_r + hashes + 1;
+    Some(
+        code[body..]
+            .find(&close)
+            .map_or(code.len(), |n| body + n + close.len()),
+    )
+}
+
+pub fn tokens(code: &str) -> Vec<(Token, &str)> {
+    let mut tokens: Vec<(Token, &str)> = Vec::new();
+    let mut plain: Option<usize> = None;
+    let mut i = 0;
+    while i < code.len() {
+        let rest = &code[i..];
+        let c = rest.chars().next().unwrap_or_default();
+        let previous = code[..i].chars().next_back();
+        let (token, end) = if rest.starts_with("//") {
+            (
+                Token::Comment,
+                rest.find('\n').map_or(code.len(), |n| i + n),
+            )
+        } else if rest.starts_with("/*") {
+            (Token::Comment, block_comment_end(code, i))
+        } else if rest.starts_with("#[") || rest.starts_with("#![") {
+            let end = rest.find(']').map_or(code.len(), |n| i + n + 1);
+            (Token::Attribute, end)
+        } else if let Some(end) = raw_string_end(code, i) {
+            (Token::String, end)
+        } else if c == '"' || (rest.starts_with("b\"") && !previous.is_some_and(is_ident)) {
+            let quote = if c == '"' { i } else { i + 1 };
+            (Token::String, string_end(code, quote))
+        } else if c == '\'' {
+            let mut chars = rest[1..].chars();
+            match (chars.next(), chars.next()) {
+                /*
+                 * El carácter después de `\` nunca cierra, `'\''` es una comilla
+                 */
+                (Some('\\'), Some(escaped)) => {
+                    let start = 2 + escaped.len_utf8();
+                    let end = rest[start..]
+                        .find('\'')
+                        .map_or(code.len(), |n| i + start + n + 1);
+                    (Token::Char, end)
+                }
+                (Some(c), Some('\'')) => (Token::Char, i + 1 + c.len_utf8() + 1),
+                _ => {
+                    let end = rest[1..]
+                        .find(|c| !is_ident(c))
+                        .map_or(code.len(), |n| i + 1 + n);
+                    (Token::Lifetime, end)
+                }
+            }
+        } else if c.is_ascii_digit() {
+            /*
+             * `1..10` es un rango, el primer `.` no es parte del número
+             */
+            let mut end = i;
+            for (offset, c) in rest.char_indices() {
+                let dot = c == '.' && !rest[offset + 1..].starts_with('.');
+                if !(is_ident(c) || dot) {
+                    break;
+                }
+                end = i + offset + c.len_utf8();
+            }
+            (Token::Number, end)
+        } else if is_ident(c) {
+            let end = rest.find(|c| !is_ident(c)).map_or(code.len(), |n| i + n);
+            let word = &code[i..end];
+            if code[end..].starts_with('!') && !code[end..].starts_with("!=") {
+                (Token::Macro, end + 1)
+            } else if KEYWORDS.contains(&word) {
+                (Token::Keyword, end)
+            } else if word.starts_with(|c: char| c.is_uppercase()) {
+                (Token::Type, end)
+            } else {
+                (Token::Plain, end)
+            }
+        } else {
+            (Token::Plain, i + c.len_utf8())
+        };
+        /*
+         * Los espacios y la puntuación seguidos se juntan en una sola pieza
+         */
+        match (token, plain) {
+            (Token::Plain, Some(start)) => {
+                tokens.pop();
+                tokens.push((Token::Plain, &code[start..end]));
+            }
+            (Token::Plain, None) => {
+                plain = Some(i);
+                tokens.push((Token::Plain, &code[i..end]));
+            }
+            (token, _) => {
+                plain = None;
+                tokens.push((token, &code[i..end]));
+            }
+        }
+        i = end;
+    }
+    tokens
+}
+
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+pub fn html(code: &str) -> String {
+    let mut out = String::with_capacity(code.len() * 2);
+    for (token, text) in tokens(code) {
+        match token.class() {
+            Some(class) => {
+                out.push_str(&format!("<span class=\"{class}\">{}</span>", escape(text)))
+            }
+            None => out.push_str(&escape(text)),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+     * Solo las piezas con color, los espacios y la puntuación no importan aquí
+     */
+    fn colored(code: &str) -> Vec<(Token, &str)> {
+        tokens(code)
+            .into_iter()
+            .filter(|(token, _)| *token != Token::Plain)
+            .collect()
+    }
+
+    #[test]
+    fn pieces_cover_the_whole_code() {
+        let code = "pub fn largest<T: PartialOrd>(list: &[T]) -> &T { /* é */ list[0] }";
+        let pieces: String = tokens(code).into_iter().map(|(_, text)| text).collect();
+        assert_eq!(pieces, code);
+        /*
+         * Los `Plain` seguidos son una sola pieza
+         */
+        assert_eq!(tokens("a + b"), [(Token::Plain, "a + b")]);
+    }
+
+    #[test]
+    fn keywords_types_and_macros() {
+        assert_eq!(
+            colored("pub fn largest<T: PartialOrd>(list: &[T]) -> Self { println!(\"\") }"),
+            [
+                (Token::Keyword, "pub"),
+                (Token::Keyword, "fn"),
+                (Token::Type, "T"),
+                (Token::Type, "PartialOrd"),
+                (Token::Type, "T"),
+                (Token::Keyword, "Self"),
+                (Token::Macro, "println!"),
+                (Token::String, "\"\""),
+            ]
+        );
+        /*
+         * Una palabra que empieza con una palabra reservada no es reservada
+         */
+        assert_eq!(colored("let format = fnord != iffy;"), [(Token::Keyword, "let")]);
+        assert_eq!(
+            colored("#[derive(Debug)] struct"),
+            [(Token::Attribute, "#[derive(Debug)]"), (Token::Keyword, "struct")]
+        );
+    }
+
+    #[test]
+    fn strings_and_chars() {
+        assert_eq!(
+            colored(r#"let s = "a \" b"; let t = "\\";"#),
+            [
+                (Token::Keyword, "let"),
+                (Token::String, r#""a \" b""#),
+                (Token::Keyword, "let"),
+                (Token::String, r#""\\""#),
+            ]
+        );
+        assert_eq!(
+            colored(r###"r#"dice "hola""# br"\" b"x""###),
+            [
+                (Token::String, r###"r#"dice "hola""#"###),
+                (Token::String, r#"br"\""#),
+                (Token::String, r#"b"x""#),
+            ]
+        );
+        /*
+         * `r#type` no abre un `string` crudo, tampoco `hr"`. El resaltador no conoce los
+         * identificadores crudos y pinta `type` como palabra reservada
+         */
+        assert_eq!(colored("r#type"), [(Token::Keyword, "type")]);
+        assert_eq!(colored("hr\"x\""), [(Token::String, "\"x\"")]);
+        assert_eq!(
+            colored(r"'a' '\n' 'é' '\''"),
+            [
+                (Token::Char, "'a'"),
+                (Token::Char, r"'\n'"),
+                (Token::Char, "'é'"),
+                (Token::Char, r"'\''"),
+            ]
+        );
+        assert_eq!(colored("\"sin cerrar"), [(Token::String, "\"sin cerrar")]);
+    }
+
+    #[test]
+    fn lifetimes_are_not_chars() {
+        assert_eq!(
+            colored("fn f<'a>(x: &'a str) -> &'static str"),
+            [
+                (Token::Keyword, "fn"),
+                (Token::Lifetime, "'a"),
+                (Token::Lifetime, "'a"),
+                (Token::Lifetime, "'static"),
+            ]
+        );
+        assert_eq!(
+            colored("'outer: loop { break 'outer; }"),
+            [
+                (Token::Lifetime, "'outer"),
+                (Token::Keyword, "loop"),
+                (Token::Keyword, "break"),
+                (Token::Lifetime, "'outer"),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_and_line_comments() {
+        assert_eq!(
+            colored("/* a /* b */ c */ fn"),
+            [(Token::Comment, "/* a /* b */ c */"), (Token::Keyword, "fn")]
+        );
+        assert_eq!(
+            colored("// fn \"x\"\nfn"),
+            [(Token::Comment, "// fn \"x\""), (Token::Keyword, "fn")]
+        );
+        assert_eq!(colored("/* /* */ sin cerrar"), [(Token::Comment, "/* /* */ sin cerrar")]);
+        assert_eq!(colored("\"/* no */\""), [(Token::String, "\"/* no */\"")]);
+    }
+
+    #[test]
+    fn numbers_and_ranges() {
+        assert_eq!(
+            colored("1..10 2.5 0xff_u8 1e3"),
+            [
+                (Token::Number, "1"),
+                (Token::Number, "10"),
+                (Token::Number, "2.5"),
+                (Token::Number, "0xff_u8"),
+                (Token::Number, "1e3"),
+            ]
+        );
+        assert_eq!(colored("x1"), []);
+    }
+
+    #[test]
+    fn escape_special_characters() {
+        assert_eq!(escape("<a href=\"x\">&</a>"), "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;");
+        assert_eq!(escape("sin nada"), "sin nada");
+        assert_eq!(
+            html("if a < b && s == \"<&>\" {}"),
+            "<span class=\"kw\">if</span> a &lt; b &amp;&amp; s == \
+             <span class=\"st\">&quot;&lt;&amp;&gt;&quot;</span> {}"
+        );
+        assert_eq!(
+            html("Vec<&'a T>"),
+            "<span class=\"ty\">Vec</span>&lt;&amp;<span class=\"lt\">'a</span> \
+             <span class=\"ty\">T</span>&gt;"
+        );
+    }
+}
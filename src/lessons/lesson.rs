@@ -0,0 +1,445 @@
+/*
+ * Una lección exportada es un archivo de texto con un paso por bloque de comentario. Cada paso
+ * lleva el texto en español, el espacio para la traducción al inglés y el código que explica
+ *
+ *  lesson = flow/loops.rs
+ *
+ *  == step 2
+ *  item = fn loop_flow
+ *  line = 9
+ *  hash = a40a1040
+ *  -- es
+ *  En `Rust` existe una estructura de control `loop` ...
+ *  -- en
+ *  `Rust` has a `loop` control structure ...
+ *  -- code
+ *  let mut counter = 0;
+ *
+ * `hash` identifica el texto en español. Al exportar de nuevo, una traducción se conserva si el
+ * texto no cambió, y si cambió se conserva marcada con `stale = true` hasta que alguien la
+ * revise. Una línea del contenido que empieza con `== `, `-- ` o `\` se escribe con un `\`
+ * adelante
+ */
+use std::fmt;
+
+use super::extract::{self, Source};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    /*
+     * El `item` al que pertenece el bloque, `fn loop_flow`
+     */
+    pub item: Option<String>,
+    pub line: usize,
+    pub hash: String,
+    pub es: String,
+    pub en: Option<String>,
+    /*
+     * La traducción se escribió para una versión anterior del texto en español
+     */
+    pub stale: bool,
+    pub code: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lesson {
+    pub path: String,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Spanish,
+    English,
+}
+
+impl Language {
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::Spanish => "es",
+            Language::English => "en",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Language> {
+        match code {
+            "es" => Some(Language::Spanish),
+            "en" => Some(Language::English),
+            _ => None,
+        }
+    }
+}
+
+/*
+ * `FNV-1a` de 32 `bits`, siempre da el mismo valor para el mismo texto en cualquier máquina, el
+ * `hasher` de `std` no lo garantiza entre versiones
+ */
+pub fn fingerprint(text: &str) -> String {
+    let hash = text.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    format!("{hash:08x}")
+}
+
+impl Step {
+    /*
+     * El texto en el idioma pedido, o en español cuando no hay traducción. El `bool` dice si se
+     * usó el respaldo
+     */
+    pub fn text(&self, language: Language) -> (&str, bool) {
+        match (language, &self.en) {
+            (Language::English, Some(en)) => (en, false),
+            (Language::English, None) => (&self.es, true),
+            (Language::Spanish, _) => (&self.es, false),
+        }
+    }
+}
+
+impl Lesson {
+    pub fn from_source(path: &str, source: &Source) -> Lesson {
+        let steps = source
+            .segments
+            .iter()
+            .map(|segment| Step {
+                item: source.item(segment).map(ToString::to_string),
+                line: segment.line,
+                hash: fingerprint(&segment.prose),
+                es: segment.prose.clone(),
+                en: None,
+                stale: false,
+                code: segment.code.clone(),
+            })
+            .collect();
+        Lesson {
+            path: path.to_string(),
+            steps,
+        }
+    }
+
+    pub fn extract(path: &str, text: &str) -> Lesson {
+        Lesson::from_source(path, &extract::extract(text))
+    }
+
+    /*
+     * El número del paso dentro de su `item`, `fn loop_flow` paso `0`, `1`, `2`...
+     */
+    fn ordinals(&self) -> Vec<usize> {
+        let mut ordinals = Vec::with_capacity(self.steps.len());
+        for (index, step) in self.steps.iter().enumerate() {
+            let before = self.steps[..index]
+                .iter()
+                .filter(|other| other.item == step.item)
+                .count();
+            ordinals.push(before);
+        }
+        ordinals
+    }
+
+    /*
+     * Copia las traducciones de una exportación anterior. Primero se busca el mismo texto en
+     * español, si no está se busca el mismo lugar, el mismo `item` y el mismo número de paso, y
+     * esa traducción queda como `stale`
+     */
+    pub fn merge(&mut self, previous: &Lesson) {
+        let ordinals = self.ordinals();
+        let previous_ordinals = previous.ordinals();
+        for (step, ordinal) in self.steps.iter_mut().zip(ordinals) {
+            let translated = |other: &&Step| other.en.is_some();
+            if let Some(same) = previous
+                .steps
+                .iter()
+                .filter(translated)
+                .find(|other| other.hash == step.hash)
+            {
+                step.en = same.en.clone();
+                step.stale = same.stale;
+                continue;
+            }
+            let moved = previous
+                .steps
+                .iter()
+                .zip(&previous_ordinals)
+                .filter(|(other, _)| translated(other))
+                .find(|(other, other_ordinal)| {
+                    other.item == step.item && **other_ordinal == ordinal
+                });
+            if let Some((other, _)) = moved {
+                step.en = other.en.clone();
+                step.stale = true;
+            }
+        }
+    }
+
+    pub fn translated(&self) -> usize {
+        self.steps.iter().filter(|step| step.en.is_some()).count()
+    }
+
+    pub fn stale(&self) -> usize {
+        self.steps.iter().filter(|step| step.stale).count()
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = format!("lesson = {}\n", self.path);
+        for (index, step) in self.steps.iter().enumerate() {
+            out.push_str(&format!("\n== step {}\n", index + 1));
+            if let Some(item) = &step.item {
+                out.push_str(&format!("item = {item}\n"));
+            }
+            out.push_str(&format!("line = {}\nhash = {}\n", step.line, step.hash));
+            if step.stale {
+                out.push_str("stale = true\n");
+            }
+            for (name, text) in [
+                ("es", step.es.as_str()),
+                ("en", step.en.as_deref().unwrap_or("")),
+                ("code", step.code.as_str()),
+            ] {
+                out.push_str(&format!("-- {name}\n"));
+                for line in text.lines() {
+                    if line.starts_with("== ") || line.starts_with("-- ") || line.starts_with('\\')
+                    {
+                        out.push('\\');
+                    }
+                    out.push_str(line);
+                    out.push('\n');
+                }
+            }
+        }
+        out
+    }
+
+    pub fn parse(text: &str) -> Result<Lesson, FormatError> {
+        let mut path = None;
+        let mut steps: Vec<Step> = Vec::new();
+        let mut block: Option<&str> = None;
+        for (index, raw) in text.lines().enumerate() {
+            let error = |message: String| FormatError {
+                line: index + 1,
+                message,
+            };
+            if raw.starts_with("== ") {
+                steps.push(Step {
+                    item: None,
+                    line: 0,
+                    hash: String::new(),
+                    es: String::new(),
+                    en: None,
+                    stale: false,
+                    code: String::new(),
+                });
+                block = None;
+                continue;
+            }
+            if let Some(name) = raw.strip_prefix("-- ") {
+                if steps.is_empty() {
+                    return Err(error(format!("`-- {name}` before the first step")));
+                }
+                block = match name.trim() {
+                    "es" => Some("es"),
+                    "en" => Some("en"),
+                    "code" => Some("code"),
+                    other => return Err(error(format!("unknown block `{other}`"))),
+                };
+                continue;
+            }
+            let line = raw.strip_prefix('\\').unwrap_or(raw);
+            match (block, steps.last_mut()) {
+                (Some(block), Some(step)) => {
+                    let target = match block {
+                        "es" => &mut step.es,
+                        "code" => &mut step.code,
+                        _ => step.en.get_or_insert_with(String::new),
+                    };
+                    if !target.is_empty() || !line.is_empty() {
+                        if !target.is_empty() {
+                            target.push('\n');
+                        }
+                        target.push_str(line);
+                    }
+                }
+                (_, step) => {
+                    if raw.trim().is_empty() {
+                        continue;
+                    }
+                    let (key, value) = raw
+                        .split_once('=')
+                        .ok_or_else(|| error(format!("expected `key = value`, found {raw:?}")))?;
+                    let (key, value) = (key.trim(), value.trim());
+                    match (key, step) {
+                        ("lesson", None) => path = Some(value.to_string()),
+                        ("item", Some(step)) => step.item = Some(value.to_string()),
+                        ("hash", Some(step)) => step.hash = value.to_string(),
+                        ("stale", Some(step)) => step.stale = value == "true",
+                        ("line", Some(step)) => {
+                            step.line = value
+                                .parse()
+                                .map_err(|_| error(format!("invalid line number {value:?}")))?;
+                        }
+                        (key, _) => return Err(error(format!("unknown key `{key}`"))),
+                    }
+                }
+            }
+        }
+        for step in &mut steps {
+            /*
+             * Las líneas vacías al final de un bloque separan los pasos, no son parte del texto
+             */
+            for text in [&mut step.es, &mut step.code] {
+                text.truncate(text.trim_end().len());
+            }
+            if let Some(en) = &mut step.en {
+                en.truncate(en.trim_end().len());
+            }
+            if step.en.as_deref() == Some("") {
+                step.en = None;
+            }
+        }
+        let path = path.ok_or(FormatError {
+            line: 1,
+            message: "missing `lesson = PATH`".to_string(),
+        })?;
+        Ok(Lesson { path, steps })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lessons::runner::LESSONS;
+
+    const V1: &str = "/*
+ * Cuenta hasta tres
+ */
+pub fn count() {
+    /*
+     * El rango no incluye el final
+     */
+    for n in 1..4 {
+        println!(\"{n}\");
+    }
+}
+
+/*
+ * Saluda
+ */
+pub fn greet() {}
+";
+
+    fn translate(lesson: &mut Lesson) {
+        for step in &mut lesson.steps {
+            step.en = Some(format!("en: {}", step.es));
+        }
+    }
+
+    #[test]
+    fn fingerprint_is_fnv1a() {
+        assert_eq!(fingerprint(""), "811c9dc5");
+        assert_eq!(fingerprint("a"), "e40c292c");
+        assert_ne!(fingerprint("Saluda"), fingerprint("Saluda."));
+    }
+
+    #[test]
+    fn bundled_lessons_round_trip() {
+        for entry in LESSONS {
+            let lesson = Lesson::parse(entry.translation).unwrap();
+            assert_eq!(lesson.path, entry.path);
+            let rendered = lesson.render();
+            assert_eq!(rendered, entry.translation, "{}", entry.path);
+            assert_eq!(Lesson::parse(&rendered).unwrap(), lesson);
+        }
+    }
+
+    #[test]
+    fn render_escapes_markers() {
+        let mut lesson = Lesson::extract("flow/count.rs", V1);
+        lesson.steps[0].es = "== no es un paso\n-- es\n\\ una barra".to_string();
+        lesson.steps[0].en = Some("-- code\n\n  sangría".to_string());
+        lesson.steps[1].code = "== code\n\nlet x = 1;".to_string();
+        let rendered = lesson.render();
+        assert!(rendered.contains("\n\\== no es un paso\n\\-- es\n\\\\ una barra\n"));
+        assert_eq!(Lesson::parse(&rendered).unwrap(), lesson);
+    }
+
+    #[test]
+    fn changed_text_is_stale() {
+        let mut previous = Lesson::extract("flow/count.rs", V1);
+        translate(&mut previous);
+        let previous = Lesson::parse(&previous.render()).unwrap();
+
+        /*
+         * El mismo texto conserva su traducción, el texto que cambió en el mismo lugar conserva la
+         * anterior marcada como `stale`, y un paso nuevo queda sin traducir
+         */
+        let v2 = V1
+            .replace("El rango no incluye el final", "El rango `1..4` no incluye el `4`")
+            .replace(
+                "pub fn greet() {}",
+                "pub fn greet() {}\n\n/*\n * Nuevo\n */\npub fn new() {}",
+            );
+        let mut lesson = Lesson::extract("flow/count.rs", &v2);
+        lesson.merge(&previous);
+        let summary: Vec<_> = lesson
+            .steps
+            .iter()
+            .map(|step| (step.item.as_deref(), step.en.as_deref(), step.stale))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (Some("fn count"), Some("en: Cuenta hasta tres"), false),
+                (Some("fn count"), Some("en: El rango no incluye el final"), true),
+                (Some("fn greet"), Some("en: Saluda"), false),
+                (Some("fn new"), None, false),
+            ]
+        );
+        assert_eq!((lesson.translated(), lesson.stale()), (3, 1));
+        assert_ne!(lesson.steps[1].hash, previous.steps[1].hash);
+
+        /*
+         * `stale` sobrevive a otra exportación aunque el texto ya no cambie
+         */
+        let rendered = lesson.render();
+        assert!(rendered.contains("stale = true\n"));
+        let mut again = Lesson::extract("flow/count.rs", &v2);
+        again.merge(&Lesson::parse(&rendered).unwrap());
+        assert_eq!(again, lesson);
+    }
+
+    #[test]
+    fn moved_text_keeps_its_translation() {
+        let mut previous = Lesson::extract("flow/count.rs", V1);
+        translate(&mut previous);
+        let moved = format!("use std::fmt;\n\n{V1}");
+        let mut lesson = Lesson::extract("flow/count.rs", &moved);
+        lesson.merge(&previous);
+        assert_eq!((lesson.translated(), lesson.stale()), (3, 0));
+        assert_eq!(lesson.steps[0].line, previous.steps[0].line + 2);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |text: &str| Lesson::parse(text).unwrap_err().to_string();
+        assert_eq!(error(""), "line 1: missing `lesson = PATH`");
+        assert_eq!(error("lesson = a.rs\n-- es\n"), "line 2: `-- es` before the first step");
+        assert_eq!(error("lesson = a.rs\n== step 1\n-- fr\n"), "line 3: unknown block `fr`");
+        assert_eq!(
+            error("lesson = a.rs\n== step 1\nline = x\n"),
+            "line 3: invalid line number \"x\""
+        );
+        assert_eq!(error("lesson = a.rs\nhash = 1\n"), "line 2: unknown key `hash`");
+    }
+}
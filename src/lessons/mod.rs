@@ -0,0 +1,5 @@
+pub mod book;
+pub mod extract;
+pub mod highlight;
+pub mod lesson;
+pub mod runner;
@@ -0,0 +1,228 @@
+/*
+ * Las lecciones que se pueden ejecutar. Cada una lleva su código fuente y su traducción dentro
+ * del binario con `include_str!`, así el `runner` funciona desde cualquier directorio
+ *
+ *  lessons/flow/loops.lesson  --include_str!-->  Entry { translation, .. }
+ *  src/flow/loops.rs          --include_str!-->  Entry { source, .. }
+ *
+ * El texto en español sale siempre del código fuente, el archivo `.lesson` solo aporta el
+ * inglés. Un comentario que cambió después de traducirlo se muestra con su traducción anterior y
+ * un aviso
+ */
+use std::fmt;
+
+use super::extract::{self, Item};
+use super::lesson::{FormatError, Language, Lesson};
+use crate::any::generics;
+use crate::collections::vector;
+use crate::flow::{if_expression, loops};
+use crate::func::i18n::{Locale, Translator};
+use crate::ownership::{borrowing, string_slices};
+
+pub struct Entry {
+    /*
+     * Relativo a `src`, `flow/loops.rs`
+     */
+    pub path: &'static str,
+    pub source: &'static str,
+    pub translation: &'static str,
+    /*
+     * Las funciones que se ejecutan, después de mostrar la explicación de cada una
+     */
+    pub steps: &'static [(&'static str, fn())],
+}
+
+/*
+ * En el orden del curso, primero los préstamos y los `slices`, después el control de flujo, los
+ * vectores y al final los genéricos
+ */
+pub const LESSONS: &[Entry] = &[
+    Entry {
+        path: "ownership/borrowing.rs",
+        source: include_str!("../ownership/borrowing.rs"),
+        translation: include_str!("../../lessons/ownership/borrowing.lesson"),
+        steps: &[("run", borrowing::run)],
+    },
+    Entry {
+        path: "ownership/string_slices.rs",
+        source: include_str!("../ownership/string_slices.rs"),
+        translation: include_str!("../../lessons/ownership/string_slices.lesson"),
+        steps: &[
+            ("run", string_slices::run),
+            ("are_same", string_slices::are_same),
+        ],
+    },
+    Entry {
+        path: "flow/if_expression.rs",
+        source: include_str!("../flow/if_expression.rs"),
+        translation: include_str!("../../lessons/flow/if_expression.lesson"),
+        steps: &[("run", if_expression::run)],
+    },
+    Entry {
+        path: "flow/loops.rs",
+        source: include_str!("../flow/loops.rs"),
+        translation: include_str!("../../lessons/flow/loops.lesson"),
+        steps: &[
+            ("loop_flow", loops::loop_flow),
+            ("while_flow", loops::while_flow),
+            ("for_flow", loops::for_flow),
+            ("adaptors_flow", loops::adaptors_flow),
+        ],
+    },
+    Entry {
+        path: "collections/vector.rs",
+        source: include_str!("../collections/vector.rs"),
+        translation: include_str!("../../lessons/collections/vector.lesson"),
+        steps: &[("run", vector::run)],
+    },
+    Entry {
+        path: "any/generics.rs",
+        source: include_str!("../any/generics.rs"),
+        translation: include_str!("../../lessons/any/generics.lesson"),
+        steps: &[("run", generics::run)],
+    },
+];
+
+/*
+ * Por la ruta completa o solo por el nombre del archivo, `flow/loops.rs` o `loops`
+ */
+pub fn find(name: &str) -> Option<&'static Entry> {
+    let name = name.trim_end_matches(".rs");
+    LESSONS.iter().find(|entry| {
+        let path = entry.path.trim_end_matches(".rs");
+        path == name || path.rsplit('/').next() == Some(name)
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunError {
+    pub path: &'static str,
+    pub error: FormatError,
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "lessons/{}: {}",
+            self.path.replace(".rs", ".lesson"),
+            self.error
+        )
+    }
+}
+
+impl std::error::Error for RunError {}
+
+impl Entry {
+    /*
+     * La lección del código actual con las traducciones del archivo `.lesson`
+     */
+    pub fn lesson(&self) -> Result<Lesson, RunError> {
+        let mut lesson = Lesson::extract(self.path, self.source);
+        let translation = Lesson::parse(self.translation).map_err(|error| RunError {
+            path: self.path,
+            error,
+        })?;
+        lesson.merge(&translation);
+        Ok(lesson)
+    }
+
+    fn step(&self, item: &Item) -> Option<fn()> {
+        match item.kind {
+            "fn" => self
+                .steps
+                .iter()
+                .find(|(name, _)| *name == item.name)
+                .map(|(_, run)| *run),
+            _ => None,
+        }
+    }
+}
+
+/*
+ * El primer idioma de la cadena de respaldo que tiene texto para las lecciones, `pt` usa `es` y
+ * cualquier otro termina en `en`
+ */
+pub fn language(translator: &Translator, locale: &Locale) -> Language {
+    translator
+        .chain(locale)
+        .iter()
+        .find_map(|locale| Language::from_code(locale.language()))
+        .unwrap_or(Language::Spanish)
+}
+
+/*
+ * Recorre los `items` en orden, muestra las explicaciones de cada uno y si es un paso lo ejecuta.
+ * Los mensajes del `runner` salen del catálogo de `func::i18n`
+ */
+pub fn run(entry: &Entry, translator: &Translator, locale: &Locale) -> Result<(), RunError> {
+    let language = language(translator, locale);
+    let lesson = entry.lesson()?;
+    let source = extract::extract(entry.source);
+    let message = |key: &str, args: &[(&str, &dyn fmt::Display)]| {
+        translator
+            .format(locale, key, args)
+            .unwrap_or_else(|error| error.to_string())
+    };
+    println!("{}", message("lesson.title", &[("path", &entry.path)]));
+    for (index, item) in source.items.iter().enumerate() {
+        let steps: Vec<_> = source
+            .segments
+            .iter()
+            .zip(&lesson.steps)
+            .filter(|(segment, _)| segment.item == Some(index))
+            .map(|(_, step)| step)
+            .collect();
+        let run = entry.step(item);
+        if steps.is_empty() && run.is_none() {
+            continue;
+        }
+        println!();
+        println!(
+            "--- {item} ({})",
+            message("lesson.line", &[("line", &item.line)])
+        );
+        for step in steps {
+            let (text, fallback) = step.text(language);
+            println!();
+            if fallback {
+                println!("{}", message("lesson.untranslated", &[]));
+            } else if step.stale && language == Language::English {
+                println!("{}", message("lesson.stale", &[]));
+            }
+            println!("{text}");
+        }
+        if let Some(run) = run {
+            println!();
+            println!("{}", message("lesson.running", &[("name", &item.name)]));
+            run();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+     * Si un comentario cambia sin exportar de nuevo, la traducción incluida queda `stale`
+     */
+    #[test]
+    fn bundled_translations_are_current() {
+        for entry in LESSONS {
+            let lesson = entry.lesson().unwrap();
+            assert_eq!(lesson.translated(), lesson.steps.len(), "{}", entry.path);
+            assert_eq!(lesson.stale(), 0, "{}", entry.path);
+        }
+    }
+
+    #[test]
+    fn find_by_path_or_name() {
+        for name in ["vector", "vector.rs", "collections/vector", "collections/vector.rs"] {
+            assert_eq!(find(name).map(|entry| entry.path), Some("collections/vector.rs"));
+        }
+        assert_eq!(find("borrowing").map(|entry| entry.path), Some("ownership/borrowing.rs"));
+        assert!(find("hashmap").is_none());
+    }
+}
@@ -0,0 +1,8 @@
+pub mod any;
+pub mod collections;
+pub mod custom_types;
+pub mod flow;
+pub mod func;
+pub mod lessons;
+pub mod ownership;
+pub mod types;
@@ -1,3 +1,543 @@
-fn main() {
-    println!("Hello, world!");
-}
\ No newline at end of file
+/*
+ * Cada subcomando ejecuta una de las herramientas de la biblioteca `the_basics`
+ *
+ *  * the_basics utf8 <TEXT>...
+ *  * the_basics utf8 --file <PATH>
+ *  * the_basics borrowck [FILE]
+ *  * the_basics memory [--svg]
+ *  * the_basics drops
+ *  * the_basics arena
+ *  * the_basics literal [LITERAL]...
+ *  * the_basics overflow <EXPR>
+ *  * the_basics casts [TYPE]
+ *  * the_basics float [--f32] <VALUE> [OTHER]
+ *  * the_basics expr [--ast|--tree] [FILE]
+ *  * the_basics countdown [N] [--every MS]
+ *  * the_basics classify [--first] [--max N] <NUMBER>...
+ *  * the_basics config [--config FILE] [--KEY VALUE]...
+ *  * the_basics greet [--lang LOCALE] [NAME]...
+ *  * the_basics lesson list
+ *  * the_basics lesson run [--lang LOCALE] <LESSON>
+ *  * the_basics lesson extract [--src DIR] [--out DIR] [FILE]...
+ */
+use std::env;
+use std::fs;
+use std::io::{self, BufRead};
+use std::process::ExitCode;
+
+use the_basics::flow::interpreter;
+use the_basics::flow::rules::{self, Mode};
+use the_basics::flow::timers::{Clock, Scheduler, SystemClock};
+use the_basics::func::i18n::{I18nError, Locale, Translator};
+use the_basics::lessons::lesson::Lesson;
+use the_basics::lessons::runner;
+use the_basics::ownership::{arena, borrowck, memory, traced};
+use the_basics::types::literal::{self, RadixFormat, Ty};
+use the_basics::types::float::{self, Precision};
+use the_basics::types::config::{self, Config};
+use the_basics::types::{casts, overflow};
+use the_basics::types::utf8;
+
+const USAGE: &str = "usage: the_basics <command> [args]
+
+commands:
+    utf8 <TEXT>...          inspect the code points and encodings of TEXT
+    utf8 --file <PATH>      inspect the raw bytes of a file, even if it is not UTF-8
+    borrowck [FILE]         trace ownership and check borrows in a small Rust-like program,
+                            uses a built-in example when FILE is missing
+    memory [--svg]          draw the stack and heap diagrams of the Vec lesson
+    drops                   print when values are created, moved, cloned and dropped
+    arena                   build a tree in an Arena and a graph in a SlotMap
+    literal [LITERAL]...    parse Rust numeric literals like 0xff_u8, 1e3f32 or b'A' and show
+                            them in every radix, reads one literal per line when none is given
+    overflow <EXPR>         evaluate EXPR in every integer type with checked, wrapping,
+                            saturating and overflowing arithmetic, e.g. \"200 + 100 * 2\"
+    casts [TYPE]            show which `as` conversions lose information, with an example of
+                            each one from TYPE
+    float [--f32] <VALUE> [OTHER]
+                            split a float into sign, exponent and mantissa, show its exact
+                            value and neighbours, and the ulp distance to OTHER
+    expr [--ast|--tree] [FILE]
+                            type check and evaluate a small expression language where blocks,
+                            `if` and `loop` have values, --ast prints the program back in a
+                            canonical form and --tree marks statements and expressions
+    countdown [N] [--every MS]
+                            count down from N (3 by default) with MS milliseconds (1000 by
+                            default) between numbers using the timer scheduler, then LIFTOFF!!
+    classify [--first] [--max N] <NUMBER>...
+                            classify each NUMBER by sign, parity, range 0..=N (100 by default),
+                            primality and divisor sum (NUMBER up to 10^12), and explain which
+                            rules matched, --first stops at the first rule like an if/else if
+                            chain
+    config [--config FILE] [--KEY VALUE]...
+                            print the effective configuration and where each value comes from,
+                            FILE overrides the defaults, THE_BASICS_KEY variables override FILE
+                            and flags like --max-points 5000 override everything
+    greet [--lang LOCALE] [NAME]...
+                            greet each NAME in LOCALE (es, en, pt, or LC_ALL/LANG by default)
+                            and count them, missing messages fall back to es and then en
+    lesson list             list the lessons that can be run and how much of each is translated
+    lesson run [--lang LOCALE] <LESSON>
+                            print the explanation of each step of LESSON (loops, flow/loops.rs)
+                            in Spanish or English and run it, LOCALE as in greet
+    lesson extract [--src DIR] [--out DIR] [FILE]...
+                            export the comment blocks of each FILE under DIR (src) with the code
+                            that follows them to OUT/FILE.lesson (lessons), keeping the English
+                            translations already there, every runnable lesson by default";
+
+fn utf8_command(args: &[String]) -> Result<(), String> {
+    match args {
+        [flag, path] if flag == "--file" => {
+            let bytes = fs::read(path).map_err(|error| format!("{path}: {error}"))?;
+            println!("{}", utf8::inspect_bytes(&bytes));
+        }
+        [] => return Err("utf8 needs a TEXT or --file <PATH>".to_string()),
+        words => println!("{}", utf8::inspect(&words.join(" "))),
+    }
+    Ok(())
+}
+
+fn borrowck_command(args: &[String]) -> Result<(), String> {
+    let (source, file) = match args {
+        [] => (borrowck::EXAMPLE.to_string(), "example.rs"),
+        [path] => (
+            fs::read_to_string(path).map_err(|error| format!("{path}: {error}"))?,
+            path.as_str(),
+        ),
+        _ => return Err("borrowck takes at most one FILE".to_string()),
+    };
+    let outcome = borrowck::check(&source);
+    print!("{}", outcome.render_trace(&source));
+    if outcome.is_ok() {
+        return Ok(());
+    }
+    println!();
+    Err(outcome.render_diagnostics(&source, file).trim_end().to_string())
+}
+
+fn memory_command(args: &[String]) -> Result<(), String> {
+    let svg = match args {
+        [] => false,
+        [flag] if flag == "--svg" => true,
+        _ => return Err("memory takes only --svg".to_string()),
+    };
+    let [before, after] = memory::multi_refs_example();
+    let diagrams = [
+        ("alloc_vec: Vec::<char>::with_capacity(4) after two pushes", memory::alloc_vec_example()),
+        ("multi_refs: let first = &names[0];", before),
+        ("multi_refs: names.push(4);", after),
+    ];
+    for (title, diagram) in diagrams {
+        if svg {
+            println!("<!-- {title} -->\n{}", diagram.to_svg());
+        } else {
+            println!("{title}\n\n{diagram}\n");
+        }
+    }
+    Ok(())
+}
+
+fn drops_command(args: &[String]) -> Result<(), String> {
+    if !args.is_empty() {
+        return Err("drops takes no arguments".to_string());
+    }
+    for (title, lesson) in traced::LESSONS {
+        println!("{title}");
+        for line in lesson() {
+            println!("    {line}");
+        }
+    }
+    Ok(())
+}
+
+fn arena_command(args: &[String]) -> Result<(), String> {
+    if !args.is_empty() {
+        return Err("arena takes no arguments".to_string());
+    }
+    for line in arena::demo() {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+fn describe_literal(text: &str) -> String {
+    let parsed = match literal::parse_literal(text) {
+        Ok(parsed) => parsed,
+        Err(error) => return format!("{text}: error: {error}"),
+    };
+    let value = parsed.value;
+    let ty = value.ty();
+    let inferred = if parsed.suffixed { "" } else { " (inferred)" };
+    let mut lines = vec![format!("{text}: {ty}{inferred} = {value}")];
+    let bits = match ty.name().trim_start_matches(['i', 'u', 'f']).parse() {
+        Ok(bits) => bits,
+        Err(_) => usize::BITS as usize,
+    };
+    if ty.is_float() {
+        let hex = RadixFormat::new(16).grouped().min_digits(bits / 4);
+        lines.push(format!("    bits {}", hex.format(&value)));
+        return lines.join("\n");
+    }
+    for (name, radix, digits) in [("dec", 10, 1), ("hex", 16, 1), ("oct", 8, 1), ("bin", 2, bits)] {
+        let format = RadixFormat::new(radix).grouped().min_digits(digits);
+        lines.push(format!("    {name}  {}", format.format(&value)));
+    }
+    lines.join("\n")
+}
+
+fn literal_command(args: &[String]) -> Result<(), String> {
+    if !args.is_empty() {
+        for text in args {
+            println!("{}", describe_literal(text));
+        }
+        return Ok(());
+    }
+    for line in io::stdin().lock().lines() {
+        let line = line.map_err(|error| format!("cannot read stdin: {error}"))?;
+        if !line.trim().is_empty() {
+            println!("{}", describe_literal(line.trim()));
+        }
+    }
+    Ok(())
+}
+
+fn overflow_command(args: &[String]) -> Result<(), String> {
+    if args.is_empty() {
+        return Err("overflow needs an EXPR".to_string());
+    }
+    let report = overflow::explore(&args.join(" ")).map_err(|error| error.to_string())?;
+    print!("{report}");
+    Ok(())
+}
+
+fn casts_command(args: &[String]) -> Result<(), String> {
+    match args {
+        [] => print!("{}", casts::matrix()),
+        [name] => {
+            let from = Ty::from_suffix(name).ok_or(format!("unknown type {name}"))?;
+            for conversion in casts::report(from) {
+                println!("{conversion}");
+            }
+        }
+        _ => return Err("casts takes at most one TYPE".to_string()),
+    }
+    Ok(())
+}
+
+fn float_command(args: &[String]) -> Result<(), String> {
+    let (precision, values) = match args {
+        [flag, rest @ ..] if flag == "--f32" => (Some(Precision::Single), rest),
+        rest => (None, rest),
+    };
+    let parse = |text: &String| float::parse(text, precision).map_err(|error| format!("{text}: {error}"));
+    let (value, other) = match values {
+        [value] => (parse(value)?, None),
+        [value, other] => (parse(value)?, Some(parse(other)?)),
+        _ => return Err("float needs a VALUE and at most one OTHER".to_string()),
+    };
+    for line in float::inspect(value) {
+        println!("{line}");
+    }
+    if value.precision() == Precision::Double {
+        println!("round trip {}", float::round_trip(value.to_f64()));
+    }
+    if let Some(other) = other {
+        println!();
+        for line in float::inspect(other) {
+            println!("{line}");
+        }
+        match value.ulps_between(other) {
+            Some(ulps) => println!("\n{value} and {other} are {ulps} ulps apart"),
+            None => println!("\n{value} and {other} have no ulp distance"),
+        }
+    }
+    Ok(())
+}
+
+fn expr_command(args: &[String]) -> Result<(), String> {
+    let (view, rest) = match args {
+        [flag, rest @ ..] if flag == "--ast" || flag == "--tree" => (Some(flag.as_str()), rest),
+        rest => (None, rest),
+    };
+    let (source, file) = match rest {
+        [] => (interpreter::EXAMPLE.to_string(), "example.rs"),
+        [path] => (
+            fs::read_to_string(path).map_err(|error| format!("{path}: {error}"))?,
+            path.as_str(),
+        ),
+        _ => return Err("expr takes at most one FILE".to_string()),
+    };
+    let outcome = interpreter::run(&source);
+    match (view, &outcome.program) {
+        (Some("--ast"), Some(program)) => println!("{}", interpreter::pretty(program)),
+        (Some(_), Some(program)) => println!("{}", interpreter::tree(program)),
+        _ => {}
+    }
+    if outcome.is_ok() {
+        print!("{}", outcome.render(&source, file));
+        return Ok(());
+    }
+    Err(outcome.render(&source, file).trim_end().to_string())
+}
+
+fn countdown_command(args: &[String]) -> Result<(), String> {
+    let mut from = 3;
+    let mut every = 1000;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--every" => {
+                let value = args.next().ok_or("--every needs a number of milliseconds")?;
+                every = value
+                    .parse()
+                    .ok()
+                    .filter(|every| *every > 0)
+                    .ok_or(format!("--every: {value:?} is not a positive number of milliseconds"))?;
+            }
+            value => {
+                from = value
+                    .parse()
+                    .map_err(|_| format!("countdown: {value:?} is not a number"))?;
+            }
+        }
+    }
+    let clock = SystemClock::new();
+    let mut scheduler = Scheduler::new(clock);
+    println!("Start");
+    scheduler.countdown(
+        from,
+        std::time::Duration::from_millis(every),
+        move |counter| {
+            println!("{:>7.3}s  Counting... {counter}", clock.now().as_secs_f64())
+        },
+        move || println!("{:>7.3}s  LIFTOFF!!", clock.now().as_secs_f64()),
+    );
+    scheduler.run_until_idle();
+    Ok(())
+}
+
+fn classify_command(args: &[String]) -> Result<(), String> {
+    let mut mode = Mode::AllMatches;
+    let mut max = 100;
+    let mut numbers = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--first" => mode = Mode::FirstMatch,
+            "--max" => {
+                let value = args.next().ok_or("--max needs a number")?;
+                max = value
+                    .parse()
+                    .map_err(|_| format!("--max: {value:?} is not a number"))?;
+            }
+            value => numbers.push(
+                value
+                    .parse::<i64>()
+                    .map_err(|_| format!("classify: {value:?} is not a number"))?,
+            ),
+        }
+    }
+    if numbers.is_empty() {
+        return Err("classify needs at least one NUMBER".to_string());
+    }
+    if let Some(number) = numbers.iter().find(|number| **number > rules::MAX_DIVISOR_SUM) {
+        if mode == Mode::AllMatches {
+            return Err(format!(
+                "classify: {number} is too large, the divisor sum only goes up to {}",
+                rules::MAX_DIVISOR_SUM
+            ));
+        }
+    }
+    let classifier = match mode {
+        Mode::FirstMatch => rules::limit_chain(max),
+        Mode::AllMatches => rules::classifier(max),
+    };
+    for (index, number) in numbers.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        let outcome = classifier.evaluate(number, mode);
+        println!("{number}: {}", outcome.labels.join(", "));
+        print!("{}", outcome.explain());
+    }
+    Ok(())
+}
+
+fn config_command(args: &[String]) -> Result<(), String> {
+    let mut config = Config::new();
+    let mut rest = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" => {
+                let path = args.next().ok_or("--config needs a FILE")?;
+                let text = fs::read_to_string(path).map_err(|error| format!("{path}: {error}"))?;
+                config
+                    .load_str(path, &text)
+                    .map_err(|error| error.to_string())?;
+            }
+            _ => rest.push(arg.clone()),
+        }
+    }
+    config.load_env("THE_BASICS_", config::SETTINGS, env::vars());
+    let unknown = config
+        .load_args(config::SETTINGS, &rest)
+        .map_err(|error| error.to_string())?;
+    if let Some(arg) = unknown.first() {
+        return Err(format!("config: unknown flag {arg}"));
+    }
+    if let Err(errors) = config.validate(config::SETTINGS) {
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        return Err(messages.join("\n"));
+    }
+    print!("{}", config.dump(config::SETTINGS));
+    Ok(())
+}
+
+fn greet_command(args: &[String]) -> Result<(), String> {
+    let translator = Translator::builtin();
+    let mut locale = None;
+    let mut names = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--lang" => {
+                let tag = args.next().ok_or("--lang needs a LOCALE like es or pt-BR")?;
+                locale = Some(tag.parse::<Locale>().map_err(|error| error.to_string())?);
+            }
+            flag if flag.starts_with("--") => return Err(format!("greet: unknown flag {flag}")),
+            name if name.trim().is_empty() => {}
+            name => names.push(name.trim()),
+        }
+    }
+    let locale = locale
+        .or_else(|| Locale::from_env(env::vars()))
+        .unwrap_or_else(|| translator.default_locale().clone());
+    let message = |result: Result<String, I18nError>| result.map_err(|error| error.to_string());
+    if names.is_empty() {
+        println!("{}", message(translator.greet(&locale, ""))?);
+    }
+    for name in &names {
+        println!("{}", message(translator.greet(&locale, name))?);
+    }
+    let count = names.len() as u64;
+    println!("{}", message(translator.format_plural(&locale, "people", count, &[]))?);
+    for name in &names {
+        println!("{}", message(translator.format(&locale, "farewell", &[("name", name)]))?);
+    }
+    Ok(())
+}
+
+fn lesson_command(args: &[String]) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("list") => {
+            for entry in runner::LESSONS {
+                let lesson = entry.lesson().map_err(|error| error.to_string())?;
+                println!(
+                    "{:<28} {:>3} steps {:>3} translated {:>3} stale",
+                    entry.path,
+                    lesson.steps.len(),
+                    lesson.translated(),
+                    lesson.stale()
+                );
+            }
+            Ok(())
+        }
+        Some("run") => {
+            let translator = Translator::builtin();
+            let mut locale = None;
+            let mut name = None;
+            let mut args = args[1..].iter();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--lang" => {
+                        let tag = args.next().ok_or("--lang needs a LOCALE like es or en")?;
+                        locale = Some(tag.parse::<Locale>().map_err(|error| error.to_string())?);
+                    }
+                    value => name = Some(value),
+                }
+            }
+            let name = name.ok_or("lesson run needs a LESSON, see `lesson list`")?;
+            let entry = runner::find(name)
+                .ok_or(format!("unknown lesson {name}, see `lesson list`"))?;
+            let locale = locale
+                .or_else(|| Locale::from_env(env::vars()))
+                .unwrap_or_else(|| translator.default_locale().clone());
+            runner::run(entry, &translator, &locale).map_err(|error| error.to_string())
+        }
+        Some("extract") => {
+            let (mut src, mut out) = ("src".to_string(), "lessons".to_string());
+            let mut files = Vec::new();
+            let mut args = args[1..].iter();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--src" => src = args.next().ok_or("--src needs a DIR")?.clone(),
+                    "--out" => out = args.next().ok_or("--out needs a DIR")?.clone(),
+                    file => files.push(file.to_string()),
+                }
+            }
+            if files.is_empty() {
+                files = runner::LESSONS.iter().map(|entry| entry.path.to_string()).collect();
+            }
+            for file in files {
+                let path = file
+                    .strip_prefix(&src)
+                    .map_or(file.as_str(), |path| path.trim_start_matches('/'));
+                let input = format!("{src}/{path}");
+                let text = fs::read_to_string(&input).map_err(|error| format!("{input}: {error}"))?;
+                let mut lesson = Lesson::extract(path, &text);
+                let output = format!("{out}/{}", path.replace(".rs", ".lesson"));
+                if let Ok(previous) = fs::read_to_string(&output) {
+                    let previous = Lesson::parse(&previous)
+                        .map_err(|error| format!("{output}: {error}"))?;
+                    lesson.merge(&previous);
+                }
+                if let Some(parent) = std::path::Path::new(&output).parent() {
+                    fs::create_dir_all(parent).map_err(|error| format!("{output}: {error}"))?;
+                }
+                fs::write(&output, lesson.render()).map_err(|error| format!("{output}: {error}"))?;
+                println!(
+                    "{input} -> {output}: {} steps, {} translated, {} stale",
+                    lesson.steps.len(),
+                    lesson.translated(),
+                    lesson.stale()
+                );
+            }
+            Ok(())
+        }
+        _ => Err(format!("lesson needs list, run or extract\n{USAGE}")),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("utf8") => utf8_command(&args[1..]),
+        Some("borrowck") => borrowck_command(&args[1..]),
+        Some("memory") => memory_command(&args[1..]),
+        Some("drops") => drops_command(&args[1..]),
+        Some("arena") => arena_command(&args[1..]),
+        Some("literal") => literal_command(&args[1..]),
+        Some("overflow") => overflow_command(&args[1..]),
+        Some("casts") => casts_command(&args[1..]),
+        Some("float") => float_command(&args[1..]),
+        Some("expr") => expr_command(&args[1..]),
+        Some("countdown") => countdown_command(&args[1..]),
+        Some("classify") => classify_command(&args[1..]),
+        Some("config") => config_command(&args[1..]),
+        Some("greet") => greet_command(&args[1..]),
+        Some("lesson") => lesson_command(&args[1..]),
+        Some("-h" | "--help") | None => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Some(command) => Err(format!("unknown command {command}\n{USAGE}")),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,209 @@
+/*
+ * Un grafo dirigido sobre dos `SlotMap`, uno de nodos y otro de aristas `edges`. Los nodos no se
+ * apuntan entre sí, guardan las llaves de sus aristas, el grafo es el único `owner` de todo y el
+ * `borrow checker` no tiene nada que reclamar
+ */
+use std::collections::{HashSet, VecDeque};
+
+use super::slotmap::{Key, SlotMap};
+
+pub type NodeKey<N, E> = Key<Node<N, E>>;
+pub type EdgeKey<N, E> = Key<Edge<N, E>>;
+
+pub struct Node<N, E> {
+    pub data: N,
+    outgoing: Vec<EdgeKey<N, E>>,
+    incoming: Vec<EdgeKey<N, E>>,
+}
+
+pub struct Edge<N, E> {
+    pub data: E,
+    pub from: NodeKey<N, E>,
+    pub to: NodeKey<N, E>,
+}
+
+pub struct Graph<N, E> {
+    nodes: SlotMap<Node<N, E>>,
+    edges: SlotMap<Edge<N, E>>,
+}
+
+impl<N, E> Default for Graph<N, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, E> Graph<N, E> {
+    pub fn new() -> Self {
+        Self {
+            nodes: SlotMap::new(),
+            edges: SlotMap::new(),
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn add_node(&mut self, data: N) -> NodeKey<N, E> {
+        self.nodes.insert(Node {
+            data,
+            outgoing: Vec::new(),
+            incoming: Vec::new(),
+        })
+    }
+
+    /*
+     * Devuelve `None` si alguno de los dos nodos ya no existe
+     */
+    pub fn add_edge(
+        &mut self,
+        from: NodeKey<N, E>,
+        to: NodeKey<N, E>,
+        data: E,
+    ) -> Option<EdgeKey<N, E>> {
+        if !self.nodes.contains_key(from) || !self.nodes.contains_key(to) {
+            return None;
+        }
+        let key = self.edges.insert(Edge { data, from, to });
+        self.nodes[from].outgoing.push(key);
+        self.nodes[to].incoming.push(key);
+        Some(key)
+    }
+
+    pub fn node(&self, key: NodeKey<N, E>) -> Option<&N> {
+        self.nodes.get(key).map(|node| &node.data)
+    }
+
+    pub fn node_mut(&mut self, key: NodeKey<N, E>) -> Option<&mut N> {
+        self.nodes.get_mut(key).map(|node| &mut node.data)
+    }
+
+    pub fn edge(&self, key: EdgeKey<N, E>) -> Option<&Edge<N, E>> {
+        self.edges.get(key)
+    }
+
+    pub fn remove_edge(&mut self, key: EdgeKey<N, E>) -> Option<E> {
+        let edge = self.edges.remove(key)?;
+        if let Some(node) = self.nodes.get_mut(edge.from) {
+            node.outgoing.retain(|&k| k != key);
+        }
+        if let Some(node) = self.nodes.get_mut(edge.to) {
+            node.incoming.retain(|&k| k != key);
+        }
+        Some(edge.data)
+    }
+
+    /*
+     * Al borrar un nodo también se borran sus aristas, las llaves que lo apuntaban quedan viejas
+     */
+    pub fn remove_node(&mut self, key: NodeKey<N, E>) -> Option<N> {
+        let node = self.nodes.get(key)?;
+        let attached: Vec<EdgeKey<N, E>> = node
+            .outgoing
+            .iter()
+            .chain(&node.incoming)
+            .copied()
+            .collect();
+        for edge in attached {
+            self.remove_edge(edge);
+        }
+        self.nodes.remove(key).map(|node| node.data)
+    }
+
+    pub fn neighbors(&self, key: NodeKey<N, E>) -> impl Iterator<Item = NodeKey<N, E>> + '_ {
+        self.nodes
+            .get(key)
+            .into_iter()
+            .flat_map(|node| node.outgoing.iter())
+            .filter_map(|&edge| self.edges.get(edge).map(|edge| edge.to))
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeKey<N, E>, &N)> {
+        self.nodes.iter().map(|(key, node)| (key, &node.data))
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (EdgeKey<N, E>, &Edge<N, E>)> {
+        self.edges.iter()
+    }
+
+    /*
+     * Recorrido en anchura `breadth-first search`, devuelve los nodos alcanzables desde `start`
+     */
+    pub fn bfs(&self, start: NodeKey<N, E>) -> Vec<NodeKey<N, E>> {
+        let mut order = Vec::new();
+        if !self.nodes.contains_key(start) {
+            return order;
+        }
+        let mut seen = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(key) = queue.pop_front() {
+            order.push(key);
+            for next in self.neighbors(key) {
+                if seen.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removing_a_node_removes_its_edges() {
+        let mut graph = Graph::new();
+        let home = graph.add_node("home");
+        let work = graph.add_node("work");
+        let gym = graph.add_node("gym");
+        let to_work = graph.add_edge(home, work, 12).unwrap();
+        let to_gym = graph.add_edge(work, gym, 3).unwrap();
+        let back = graph.add_edge(gym, home, 8).unwrap();
+        let own = graph.add_edge(work, work, 0).unwrap();
+        assert_eq!(graph.edge_count(), 4);
+
+        assert_eq!(graph.remove_node(work), Some("work"));
+        assert_eq!((graph.node_count(), graph.edge_count()), (2, 1));
+        for edge in [to_work, to_gym, own] {
+            assert!(graph.edge(edge).is_none());
+        }
+        assert_eq!(graph.edge(back).map(|edge| edge.data), Some(8));
+        assert_eq!(graph.neighbors(home).count(), 0);
+        assert_eq!(graph.bfs(gym), [gym, home]);
+        /*
+         * La llave vieja no sirve aunque su lugar se reutilice
+         */
+        let office = graph.add_node("office");
+        assert_eq!(office.index(), work.index());
+        assert_eq!(graph.node(work), None);
+        assert_eq!(graph.add_edge(home, work, 1), None);
+        assert_eq!(graph.remove_node(work), None);
+        assert!(graph.bfs(work).is_empty());
+    }
+
+    #[test]
+    fn removing_an_edge_updates_both_ends() {
+        let mut graph = Graph::new();
+        let a = graph.add_node('a');
+        let b = graph.add_node('b');
+        let ab = graph.add_edge(a, b, "ab").unwrap();
+        graph.add_edge(b, a, "ba").unwrap();
+        assert_eq!(graph.remove_edge(ab), Some("ab"));
+        assert_eq!(graph.remove_edge(ab), None);
+        assert_eq!(graph.neighbors(a).count(), 0);
+        assert_eq!(graph.neighbors(b).collect::<Vec<_>>(), [a]);
+        /*
+         * Sin la arista `b` ya no tiene entradas, borrarlo solo quita `ba`
+         */
+        graph.remove_node(b);
+        assert_eq!(graph.edge_count(), 0);
+        assert_eq!(graph.nodes().map(|(_, data)| *data).collect::<Vec<_>>(), ['a']);
+    }
+}
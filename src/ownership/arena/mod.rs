@@ -0,0 +1,250 @@
+/*
+ * Con un solo `owner` por valor es difícil construir un árbol o un grafo, cada nodo quiere apuntar
+ * a sus vecinos y ningún nodo es dueño de los demás. Hay dos formas de resolverlo sin `Rc`
+ *
+ *  1) Una `Arena` es dueña de todos los nodos y los libera juntos al final, los nodos se apuntan
+ *     con referencias `&'arena T` que viven lo mismo que la `Arena`
+ *  2) Un `SlotMap` es dueño de los nodos y los nodos se apuntan con llaves `Key<T>`, un índice y
+ *     una generación, si el nodo se borra la llave deja de funcionar en lugar de quedar colgando
+ */
+pub mod graph;
+pub mod slotmap;
+
+use std::cell::{Cell, OnceCell, RefCell};
+
+pub use graph::Graph;
+pub use slotmap::{Key, SlotMap};
+
+const FIRST_CHUNK: usize = 16;
+
+/*
+ * Cada bloque se reserva una sola vez y nunca se mueve, por eso las referencias a sus elementos
+ * siguen siendo válidas cuando la `Arena` crece. El siguiente bloque tiene el doble de capacidad
+ */
+struct Chunk<T> {
+    slots: Box<[OnceCell<T>]>,
+    used: Cell<usize>,
+    next: OnceCell<Box<Chunk<T>>>,
+}
+
+impl<T> Chunk<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: (0..capacity).map(|_| OnceCell::new()).collect(),
+            used: Cell::new(0),
+            next: OnceCell::new(),
+        }
+    }
+}
+
+/*
+ * Un `bump allocator`, reservar es avanzar un contador, no se puede liberar un elemento suelto,
+ * todos se liberan cuando la `Arena` sale del `scope`
+ */
+pub struct Arena<T> {
+    first: Chunk<T>,
+    len: Cell<usize>,
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(FIRST_CHUNK)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            first: Chunk::with_capacity(capacity.max(1)),
+            len: Cell::new(0),
+        }
+    }
+
+    /*
+     * Recibe `&self` y no `&mut self`, así se pueden reservar más elementos mientras existen
+     * referencias a los anteriores
+     */
+    pub fn alloc(&self, value: T) -> &T {
+        let mut chunk = &self.first;
+        while chunk.used.get() == chunk.slots.len() {
+            chunk = chunk
+                .next
+                .get_or_init(|| Box::new(Chunk::with_capacity(chunk.slots.len() * 2)));
+        }
+        let index = chunk.used.get();
+        chunk.used.set(index + 1);
+        self.len.set(self.len.get() + 1);
+        chunk.slots[index].get_or_init(|| value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /*
+     * Los elementos en el orden en que se reservaron
+     */
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        std::iter::successors(Some(&self.first), |chunk| {
+            chunk.next.get().map(|next| &**next)
+        })
+        .flat_map(|chunk| chunk.slots.iter().filter_map(OnceCell::get))
+    }
+}
+
+/*
+ * Un árbol dentro de una `Arena`, cada nodo conoce a su padre y a sus hijos. El padre se guarda en
+ * un `Cell` porque se asigna después de crear el nodo y todos los nodos son `&` compartidos
+ */
+pub struct TreeNode<'a> {
+    pub name: String,
+    pub parent: Cell<Option<&'a TreeNode<'a>>>,
+    pub children: RefCell<Vec<&'a TreeNode<'a>>>,
+}
+
+impl<'a> TreeNode<'a> {
+    pub fn new_in(arena: &'a Arena<TreeNode<'a>>, name: &str) -> &'a TreeNode<'a> {
+        arena.alloc(TreeNode {
+            name: name.to_string(),
+            parent: Cell::new(None),
+            children: RefCell::new(Vec::new()),
+        })
+    }
+
+    pub fn add_child(&'a self, child: &'a TreeNode<'a>) {
+        child.parent.set(Some(self));
+        self.children.borrow_mut().push(child);
+    }
+
+    /*
+     * La ruta desde la raíz, subiendo por los padres
+     */
+    pub fn path(&self) -> String {
+        let mut names = vec![self.name.as_str()];
+        let mut node = self.parent.get();
+        while let Some(parent) = node {
+            names.push(&parent.name);
+            node = parent.parent.get();
+        }
+        names.reverse();
+        names.join("/")
+    }
+}
+
+/*
+ * Las dos formas con el mismo ejemplo, devuelve las líneas que imprime la lección
+ */
+pub fn demo() -> Vec<String> {
+    let mut lines = Vec::new();
+
+    let arena = Arena::new();
+    let root = TreeNode::new_in(&arena, "src");
+    let ownership = TreeNode::new_in(&arena, "ownership");
+    let arena_mod = TreeNode::new_in(&arena, "arena");
+    root.add_child(ownership);
+    ownership.add_child(arena_mod);
+    ownership.add_child(TreeNode::new_in(&arena, "rope.rs"));
+    lines.push(format!("arena holds {} tree nodes", arena.len()));
+    for node in arena.iter() {
+        lines.push(format!(
+            "  {} ({} children)",
+            node.path(),
+            node.children.borrow().len()
+        ));
+    }
+
+    let mut graph = Graph::new();
+    let home = graph.add_node("home");
+    let work = graph.add_node("work");
+    let gym = graph.add_node("gym");
+    graph.add_edge(home, work, 12);
+    graph.add_edge(work, gym, 3);
+    graph.add_edge(gym, home, 8);
+    let route: Vec<&str> = graph
+        .bfs(home)
+        .into_iter()
+        .filter_map(|key| graph.node(key))
+        .copied()
+        .collect();
+    lines.push(format!("graph route from home: {}", route.join(" -> ")));
+    graph.remove_node(work);
+    lines.push(format!(
+        "after removing work: {} nodes, {} edges, stale key detected: {}",
+        graph.node_count(),
+        graph.edge_count(),
+        graph.node(work).is_none()
+    ));
+    let office = graph.add_node("office");
+    lines.push(format!(
+        "the slot of work is reused by office, but the old key still fails: {:?} vs {:?}",
+        work, office
+    ));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+     * Un tipo con una alineación mayor a la de cualquier entero
+     */
+    #[repr(align(64))]
+    struct Aligned(u8);
+
+    #[test]
+    fn references_survive_growth() {
+        let arena = Arena::with_capacity(2);
+        let first = arena.alloc(String::from("first"));
+        let address = first as *const String;
+        /*
+         * 2 + 4 + 8 + 16 lugares, el último elemento cae en el cuarto bloque
+         */
+        let all: Vec<&String> = (0..29).map(|n| arena.alloc(n.to_string())).collect();
+        assert_eq!(arena.len(), 30);
+        assert_eq!(first, "first");
+        assert!(std::ptr::eq(first, address));
+        assert_eq!(all[28], "28");
+        let values: Vec<&str> = arena.iter().map(String::as_str).collect();
+        assert_eq!(values.len(), 30);
+        assert_eq!(values[..3], ["first", "0", "1"]);
+        assert_eq!(values[29], "28");
+    }
+
+    #[test]
+    fn elements_are_aligned() {
+        let arena = Arena::with_capacity(1);
+        let values: Vec<&Aligned> = (0..10).map(|n| arena.alloc(Aligned(n))).collect();
+        for (n, value) in values.iter().enumerate() {
+            assert_eq!(*value as *const Aligned as usize % 64, 0);
+            assert_eq!(value.0 as usize, n);
+        }
+        let bytes = Arena::with_capacity(3);
+        let small: Vec<&u8> = (0..7).map(|n| bytes.alloc(n)).collect();
+        assert_eq!(small.iter().map(|b| **b).collect::<Vec<u8>>(), [0, 1, 2, 3, 4, 5, 6]);
+        assert!(Arena::<u8>::with_capacity(0).is_empty());
+        assert_eq!(*Arena::with_capacity(0).alloc(5u8), 5);
+    }
+
+    #[test]
+    fn tree_paths() {
+        let arena = Arena::new();
+        let root = TreeNode::new_in(&arena, "src");
+        let child = TreeNode::new_in(&arena, "ownership");
+        let leaf = TreeNode::new_in(&arena, "arena");
+        root.add_child(child);
+        child.add_child(leaf);
+        assert_eq!(leaf.path(), "src/ownership/arena");
+        assert_eq!(root.path(), "src");
+        assert_eq!(child.children.borrow().len(), 1);
+        assert!(std::ptr::eq(leaf.parent.get().unwrap(), child));
+    }
+}
@@ -0,0 +1,306 @@
+/*
+ * Un `SlotMap` guarda los valores en un `Vec` y entrega una llave por cada uno. Al borrar un valor
+ * su lugar se reutiliza, pero la generación del lugar aumenta, así una llave vieja `stale` ya no
+ * coincide y `get()` devuelve `None` en lugar de devolver otro valor
+ *
+ *   index       0       1       2
+ *           +-------+-------+-------+
+ *   gen     |   0   |   1   |   0   |
+ *   value   |  "a"  | vacío |  "c"  |
+ *           +-------+-------+-------+
+ *
+ * La llave `Key<T>` lleva el tipo del valor, una llave de un `SlotMap<Node>` no compila en un
+ * `SlotMap<Edge>`
+ */
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+
+pub struct Key<T> {
+    index: u32,
+    generation: u32,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> Key<T> {
+    fn new(index: u32, generation: u32) -> Self {
+        Self {
+            index,
+            generation,
+            _type: PhantomData,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+/*
+ * Las llaves se copian sin importar si `T` es `Copy`, por eso no se usa `#[derive]`
+ */
+impl<T> Clone for Key<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Key<T> {}
+
+impl<T> PartialEq for Key<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T> Eq for Key<T> {}
+
+impl<T> Hash for Key<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Key<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Key({}v{})", self.index, self.generation)
+    }
+}
+
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+pub struct SlotMap<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+impl<T> Default for SlotMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SlotMap<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn insert(&mut self, value: T) -> Key<T> {
+        self.len += 1;
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.value = Some(value);
+                Key::new(index, slot.generation)
+            }
+            None => {
+                let index = u32::try_from(self.slots.len()).expect("SlotMap is full");
+                self.slots.push(Slot {
+                    generation: 0,
+                    value: Some(value),
+                });
+                Key::new(index, 0)
+            }
+        }
+    }
+
+    fn slot(&self, key: Key<T>) -> Option<&Slot<T>> {
+        self.slots
+            .get(key.index())
+            .filter(|slot| slot.generation == key.generation && slot.value.is_some())
+    }
+
+    pub fn get(&self, key: Key<T>) -> Option<&T> {
+        self.slot(key).and_then(|slot| slot.value.as_ref())
+    }
+
+    pub fn get_mut(&mut self, key: Key<T>) -> Option<&mut T> {
+        self.slots
+            .get_mut(key.index())
+            .filter(|slot| slot.generation == key.generation)
+            .and_then(|slot| slot.value.as_mut())
+    }
+
+    pub fn contains_key(&self, key: Key<T>) -> bool {
+        self.slot(key).is_some()
+    }
+
+    /*
+     * Una llave es vieja si su lugar existió pero ya se borró o se reutilizó
+     */
+    pub fn is_stale(&self, key: Key<T>) -> bool {
+        key.index() < self.slots.len() && !self.contains_key(key)
+    }
+
+    pub fn remove(&mut self, key: Key<T>) -> Option<T> {
+        let slot = self.slots.get_mut(key.index())?;
+        if slot.generation != key.generation {
+            return None;
+        }
+        let value = slot.value.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(key.index);
+        self.len -= 1;
+        Some(value)
+    }
+
+    /*
+     * Conserva solo los valores para los que `keep` devuelve `true`
+     */
+    pub fn retain(&mut self, mut keep: impl FnMut(Key<T>, &mut T) -> bool) {
+        let keys: Vec<Key<T>> = self.keys().collect();
+        for key in keys {
+            let remove = self.get_mut(key).is_some_and(|value| !keep(key, value));
+            if remove {
+                self.remove(key);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        let keys: Vec<Key<T>> = self.keys().collect();
+        for key in keys {
+            self.remove(key);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Key<T>, &T)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            slot.value
+                .as_ref()
+                .map(|value| (Key::new(index as u32, slot.generation), value))
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Key<T>, &mut T)> {
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let generation = slot.generation;
+                slot.value
+                    .as_mut()
+                    .map(|value| (Key::new(index as u32, generation), value))
+            })
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = Key<T>> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+/*
+ * Como `HashMap`, indexar con una llave vieja lanza un `panic`, `get()` es la versión sin `panic`
+ */
+impl<T> Index<Key<T>> for SlotMap<T> {
+    type Output = T;
+
+    fn index(&self, key: Key<T>) -> &T {
+        match self.get(key) {
+            Some(value) => value,
+            None => panic!("stale or invalid key {key:?}"),
+        }
+    }
+}
+
+impl<T> IndexMut<Key<T>> for SlotMap<T> {
+    fn index_mut(&mut self, key: Key<T>) -> &mut T {
+        match self.get_mut(key) {
+            Some(value) => value,
+            None => panic!("stale or invalid key {key:?}"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SlotMap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_key_after_reinsert() {
+        let mut map = SlotMap::new();
+        let a = map.insert("a");
+        let b = map.insert("b");
+        assert_eq!(map.remove(a), Some("a"));
+        assert_eq!(map.get(a), None);
+        assert!(map.is_stale(a));
+        /*
+         * El lugar de `a` se reutiliza con la siguiente generación
+         */
+        let c = map.insert("c");
+        assert_eq!((c.index(), c.generation()), (a.index(), a.generation() + 1));
+        assert_eq!(map.get(a), None);
+        assert_eq!(map.get_mut(a), None);
+        assert_eq!(map.remove(a), None);
+        assert!(!map.contains_key(a));
+        assert_eq!(map[c], "c");
+        assert_eq!(map[b], "b");
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn remove_twice_and_unknown_keys() {
+        let mut map = SlotMap::new();
+        let a = map.insert(1);
+        assert_eq!(map.remove(a), Some(1));
+        assert_eq!(map.remove(a), None);
+        assert_eq!(map.len(), 0);
+        let mut other: SlotMap<i32> = SlotMap::new();
+        let far = (0..5).map(|n| other.insert(n)).last().unwrap();
+        assert_eq!(map.get(far), None);
+        assert!(!map.is_stale(far));
+    }
+
+    #[test]
+    fn retain_and_clear() {
+        let mut map = SlotMap::new();
+        let keys: Vec<Key<i32>> = (0..6).map(|n| map.insert(n)).collect();
+        map.retain(|_, value| {
+            *value *= 10;
+            *value % 20 == 0
+        });
+        assert_eq!(map.values().copied().collect::<Vec<_>>(), [0, 20, 40]);
+        assert!(map.is_stale(keys[1]));
+        map.clear();
+        assert!(map.is_empty());
+        assert!(keys.iter().all(|&key| map.get(key).is_none()));
+        let again = map.insert(7);
+        assert_eq!(again.generation(), 1);
+    }
+}
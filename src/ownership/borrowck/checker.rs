@@ -0,0 +1,1234 @@
+/*
+ * El `checker` recorre cada función instrucción por instrucción y lleva el estado de cada variable,
+ * si es dueña de su valor, si el valor se movió `move`, y qué préstamos `borrows` existen sobre ella.
+ *
+ * Como `rustc`, un préstamo sigue vivo mientras la referencia que lo guarda se vuelva a usar más
+ * adelante `non-lexical lifetimes`, por eso primero se recorre la función para saber dónde se usa
+ * cada variable y después se recorre otra vez para revisar las reglas
+ *
+ *  1) Cada valor tiene un solo `owner`
+ *  2) Puede haber muchas referencias `&T` o una sola `&mut T`, pero no ambas
+ *  3) Cuando el `owner` sale del `scope` el valor se libera y ninguna referencia puede sobrevivirlo
+ */
+use std::collections::HashMap;
+
+use super::diagnostic::{Diagnostic, Span};
+use super::parser::{Block, Expr, ExprKind, Function, Ident, Param, Program, Stmt, Type};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ty {
+    Unit,
+    Int,
+    Str,
+    String,
+    Ref { mutable: bool },
+    /*
+     * El tipo de una expresión con error, se acepta en cualquier lugar para no repetir errores
+     */
+    Unknown,
+}
+
+impl Ty {
+    fn from_type(ty: &Type) -> Ty {
+        match ty {
+            Type::Int => Ty::Int,
+            Type::Str => Ty::Str,
+            Type::String => Ty::String,
+            Type::Ref { mutable, .. } => Ty::Ref { mutable: *mutable },
+        }
+    }
+
+    /*
+     * Los tipos que implementan `Copy` se copian en lugar de moverse, una referencia `&T` también
+     * es `Copy`, pero una `&mut T` no
+     */
+    fn is_copy(self) -> bool {
+        !matches!(self, Ty::String | Ty::Ref { mutable: true })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Ty::Unit => "()",
+            Ty::Int => "i32",
+            Ty::Str => "&str",
+            Ty::String => "String",
+            Ty::Ref { mutable: true } => "&mut String",
+            Ty::Ref { mutable: false } => "&String",
+            Ty::Unknown => "_",
+        }
+    }
+}
+
+/*
+ * Si un valor de tipo `found` puede pasarse donde se espera `expected`. Un `&String` se convierte
+ * en `&str` y un `&mut String` en `&String`
+ */
+fn accepts(expected: &Type, found: Ty) -> bool {
+    match (expected, found) {
+        (_, Ty::Unknown) => true,
+        (Type::Int, Ty::Int) | (Type::String, Ty::String) => true,
+        (
+            Type::Ref {
+                mutable: false,
+                inner,
+            },
+            Ty::Str,
+        ) => **inner == Type::Str,
+        (Type::Ref { mutable: false, .. }, Ty::Ref { .. }) => true,
+        (Type::Ref { mutable: true, .. }, Ty::Ref { mutable: true }) => true,
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Uninit,
+    Init,
+    Moved(Span),
+}
+
+#[derive(Debug, Clone)]
+struct Binding {
+    name: String,
+    mutable: bool,
+    ty: Ty,
+    state: State,
+    decl: Span,
+    assigned: Option<Span>,
+}
+
+#[derive(Debug, Clone)]
+struct Borrow {
+    target: usize,
+    mutable: bool,
+    span: Span,
+    created: usize,
+    /*
+     * Las variables que guardan la referencia, el préstamo vive mientras alguna se siga usando
+     */
+    holders: Vec<usize>,
+    ended: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Value {
+    ty: Ty,
+    borrows: Vec<usize>,
+    moved_from: Option<usize>,
+}
+
+impl Value {
+    fn of(ty: Ty) -> Self {
+        Self {
+            ty,
+            borrows: Vec::new(),
+            moved_from: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /*
+     * El valor se consume, se mueve o se copia a otro lugar
+     */
+    Move,
+    /*
+     * El valor solo se lee, como hace `println!`, que toma una referencia
+     */
+    Read,
+}
+
+/*
+ * Un paso de la traza, una instrucción del programa y lo que le paso a cada variable
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub function: String,
+    pub span: Span,
+    pub events: Vec<String>,
+}
+
+struct Signature {
+    params: Vec<Type>,
+    ret: Option<Type>,
+}
+
+struct FnChecker<'p> {
+    signatures: &'p HashMap<String, Signature>,
+    function: String,
+    collecting: bool,
+    uses: Vec<Vec<(usize, Span)>>,
+    bindings: Vec<Binding>,
+    scopes: Vec<Vec<usize>>,
+    borrows: Vec<Borrow>,
+    pos: usize,
+    events: Vec<String>,
+    steps: Vec<Step>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+pub fn check_program(program: &Program) -> (Vec<Step>, Vec<Diagnostic>) {
+    let mut signatures = HashMap::new();
+    let mut diagnostics = Vec::new();
+    let mut steps = Vec::new();
+    let mut seen: HashMap<&str, Span> = HashMap::new();
+    for function in &program.functions {
+        if let Some(previous) = seen.insert(&function.name.name, function.name.span) {
+            diagnostics.push(
+                Diagnostic::error(
+                    "E0428",
+                    format!(
+                        "the name `{}` is defined multiple times",
+                        function.name.name
+                    ),
+                )
+                .secondary(
+                    previous,
+                    format!(
+                        "previous definition of the value `{}` here",
+                        function.name.name
+                    ),
+                )
+                .primary(
+                    function.name.span,
+                    format!("`{}` redefined here", function.name.name),
+                ),
+            );
+            continue;
+        }
+        signatures.insert(
+            function.name.name.clone(),
+            Signature {
+                params: function
+                    .params
+                    .iter()
+                    .map(|param| param.ty.clone())
+                    .collect(),
+                ret: function.ret.as_ref().map(|(ty, _)| ty.clone()),
+            },
+        );
+    }
+    let run = |name: &str,
+               params: &[Param],
+               stmts: &[Stmt],
+               tail: Option<&Expr>,
+               ret: Option<&(Type, Span)>,
+               close: Span| {
+        let mut collector = FnChecker::new(&signatures, name, true, Vec::new());
+        collector.body(params, stmts, tail, ret, close);
+        let mut checker = FnChecker::new(&signatures, name, false, collector.uses);
+        checker.body(params, stmts, tail, ret, close);
+        (checker.steps, checker.diagnostics)
+    };
+    if !program.body.is_empty() {
+        let end = Span::new(program.body_span.end, program.body_span.end);
+        let (main_steps, main_diagnostics) = run("main", &[], &program.body, None, None, end);
+        steps.extend(main_steps);
+        diagnostics.extend(main_diagnostics);
+    }
+    for function in &program.functions {
+        check_signature(function, &mut diagnostics);
+        let Function {
+            name,
+            params,
+            ret,
+            body,
+        } = function;
+        let (fn_steps, fn_diagnostics) = run(
+            &name.name,
+            params,
+            &body.stmts,
+            body.tail.as_deref(),
+            ret.as_ref(),
+            body.close_span(),
+        );
+        steps.extend(fn_steps);
+        diagnostics.extend(fn_diagnostics);
+    }
+    diagnostics.sort_by_key(|diagnostic| diagnostic.primary_span().start);
+    (steps, diagnostics)
+}
+
+/*
+ * Una función que devuelve una referencia necesita un parámetro del cual tomarla prestada, si hay
+ * exactamente un parámetro referencia `rustc` infiere el `lifetime`, si no, no sabe cuál usar
+ */
+fn check_signature(function: &Function, diagnostics: &mut Vec<Diagnostic>) {
+    let Some((ret, span)) = &function.ret else {
+        return;
+    };
+    if !ret.is_ref() {
+        return;
+    }
+    let refs = function
+        .params
+        .iter()
+        .filter(|param| param.ty.is_ref())
+        .count();
+    if refs == 1 {
+        return;
+    }
+    let help = if refs == 0 {
+        "help: this function's return type contains a borrowed value, but there is no value for it to be borrowed from".to_string()
+    } else {
+        format!(
+            "help: this function's return type contains a borrowed value, but the signature does not say which one of the {refs} parameters it is borrowed from"
+        )
+    };
+    diagnostics.push(
+        Diagnostic::error("E0106", "missing lifetime specifier")
+            .primary(
+                Span::new(span.start, span.start + 1),
+                "expected named lifetime parameter",
+            )
+            .note(help),
+    );
+}
+
+impl<'p> FnChecker<'p> {
+    fn new(
+        signatures: &'p HashMap<String, Signature>,
+        function: &str,
+        collecting: bool,
+        uses: Vec<Vec<(usize, Span)>>,
+    ) -> Self {
+        Self {
+            signatures,
+            function: function.to_string(),
+            collecting,
+            uses,
+            bindings: Vec::new(),
+            scopes: Vec::new(),
+            borrows: Vec::new(),
+            pos: 0,
+            events: Vec::new(),
+            steps: Vec::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn event(&mut self, text: String) {
+        if !self.collecting {
+            self.events.push(text);
+        }
+    }
+
+    fn error(&mut self, diagnostic: Diagnostic) {
+        if !self.collecting {
+            let code = diagnostic.code.unwrap_or("");
+            self.events
+                .push(format!("error[{code}]: {}", diagnostic.message));
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    fn flush(&mut self, span: Span) {
+        self.end_borrows();
+        let events = std::mem::take(&mut self.events);
+        if !self.collecting {
+            self.steps.push(Step {
+                function: self.function.clone(),
+                span,
+                events,
+            });
+        }
+    }
+
+    fn name(&self, id: usize) -> &str {
+        &self.bindings[id].name
+    }
+
+    fn body(
+        &mut self,
+        params: &[Param],
+        stmts: &[Stmt],
+        tail: Option<&Expr>,
+        ret: Option<&(Type, Span)>,
+        close: Span,
+    ) {
+        self.scopes.push(Vec::new());
+        if !params.is_empty() {
+            for param in params {
+                let ty = Ty::from_type(&param.ty);
+                let id = self.declare(&param.name, param.mutable, ty);
+                self.bindings[id].state = State::Init;
+                self.bindings[id].assigned = Some(param.name.span);
+                let text = match ty {
+                    Ty::String => format!(
+                        "`{}` owns the String moved in by the caller",
+                        param.name.name
+                    ),
+                    Ty::Ref { mutable: true } => format!(
+                        "`{}` is a mutable reference lent by the caller",
+                        param.name.name
+                    ),
+                    Ty::Ref { mutable: false } => format!(
+                        "`{}` is a shared reference lent by the caller",
+                        param.name.name
+                    ),
+                    _ => format!("`{}` holds a copy of the caller's value", param.name.name),
+                };
+                self.event(text);
+            }
+            let span = params[0].name.span.to(params[params.len() - 1].name.span);
+            self.flush(span);
+        }
+        for stmt in stmts {
+            self.stmt(stmt);
+        }
+        let mut escaping = Vec::new();
+        if let Some(tail) = tail {
+            self.pos += 1;
+            let value = self.expr(tail, Mode::Move);
+            if let Some(from) = value.moved_from {
+                let text = format!(
+                    "`{}` is returned, ownership moves to the caller",
+                    self.name(from)
+                );
+                self.event(text);
+            }
+            self.check_return(ret, &value, tail.span);
+            escaping = value.borrows.clone();
+            self.flush(tail.span);
+        } else if let Some((ty, span)) = ret {
+            self.error(
+                Diagnostic::error("E0308", "mismatched types")
+                    .primary(close, format!("expected `{ty}`, found `()`"))
+                    .secondary(*span, "expected because of this return type"),
+            );
+        }
+        if ret.is_some_and(|(ty, _)| ty.is_ref()) {
+            escaping.clear();
+        }
+        self.pop_scope(close, &escaping);
+        self.flush(close);
+    }
+
+    fn check_return(&mut self, ret: Option<&(Type, Span)>, value: &Value, span: Span) {
+        match ret {
+            Some((ty, ret_span)) => {
+                if !accepts(ty, value.ty) {
+                    self.error(
+                        Diagnostic::error("E0308", "mismatched types")
+                            .primary(
+                                span,
+                                format!("expected `{ty}`, found `{}`", value.ty.name()),
+                            )
+                            .secondary(*ret_span, "expected because of this return type"),
+                    );
+                }
+                if ty.is_ref() {
+                    if let Some(&borrow) = value.borrows.first() {
+                        let target = self.borrows[borrow].target;
+                        let name = self.name(target).to_string();
+                        self.error(
+                            Diagnostic::error(
+                                "E0515",
+                                format!("cannot return reference to local variable `{name}`"),
+                            )
+                            .primary(
+                                span,
+                                "returns a reference to data owned by the current function",
+                            ),
+                        );
+                    }
+                }
+            }
+            None if !matches!(value.ty, Ty::Unit | Ty::Unknown) => {
+                self.error(
+                    Diagnostic::error("E0308", "mismatched types")
+                        .primary(span, format!("expected `()`, found `{}`", value.ty.name())),
+                );
+            }
+            None => {}
+        }
+    }
+
+    fn declare(&mut self, name: &Ident, mutable: bool, ty: Ty) -> usize {
+        let id = self.bindings.len();
+        if self.lookup(&name.name).is_some() {
+            self.event(format!(
+                "`{}` shadows the previous `{}`",
+                name.name, name.name
+            ));
+        }
+        self.bindings.push(Binding {
+            name: name.name.clone(),
+            mutable,
+            ty,
+            state: State::Uninit,
+            decl: name.span,
+            assigned: None,
+        });
+        if self.collecting {
+            self.uses.push(Vec::new());
+        }
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(id);
+        }
+        id
+    }
+
+    fn lookup(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .copied()
+            .find(|&id| self.bindings[id].name == name)
+    }
+
+    fn resolve(&mut self, ident: &Ident) -> Option<usize> {
+        let found = self.lookup(&ident.name);
+        if found.is_none() {
+            self.error(
+                Diagnostic::error(
+                    "E0425",
+                    format!("cannot find value `{}` in this scope", ident.name),
+                )
+                .primary(ident.span, "not found in this scope"),
+            );
+        }
+        found
+    }
+
+    fn record_use(&mut self, id: usize, span: Span) {
+        if self.collecting {
+            self.uses[id].push((self.pos, span));
+        }
+    }
+
+    /*
+     * Un préstamo esta vivo si alguna variable que lo guarda se usa después de la instrucción
+     * actual, o si se creó en esta misma instrucción como argumento temporal
+     */
+    fn is_live(&self, borrow: usize) -> bool {
+        let borrow = &self.borrows[borrow];
+        if borrow.ended {
+            return false;
+        }
+        if borrow.created == self.pos {
+            return true;
+        }
+        borrow
+            .holders
+            .iter()
+            .any(|&holder| self.uses[holder].iter().any(|&(at, _)| at >= self.pos))
+    }
+
+    fn live_borrows_of(&self, target: usize) -> Vec<usize> {
+        (0..self.borrows.len())
+            .filter(|&borrow| self.borrows[borrow].target == target && self.is_live(borrow))
+            .collect()
+    }
+
+    fn later_use(&self, borrow: usize) -> Option<Span> {
+        self.borrows[borrow]
+            .holders
+            .iter()
+            .flat_map(|&holder| self.uses[holder].iter())
+            .filter(|&&(at, _)| at > self.pos)
+            .min_by_key(|&&(at, span)| (at, span.start))
+            .map(|&(_, span)| span)
+    }
+
+    fn with_later_use(&self, diagnostic: Diagnostic, borrow: usize, text: &str) -> Diagnostic {
+        match self.later_use(borrow) {
+            Some(span) => diagnostic.secondary(span, text),
+            None => diagnostic,
+        }
+    }
+
+    /*
+     * Después de cada instrucción se terminan los préstamos que ya no se usan más adelante
+     */
+    fn end_borrows(&mut self) {
+        for index in 0..self.borrows.len() {
+            let borrow = &self.borrows[index];
+            if borrow.ended {
+                continue;
+            }
+            let alive = borrow
+                .holders
+                .iter()
+                .any(|&holder| self.uses[holder].iter().any(|&(at, _)| at > self.pos));
+            if alive {
+                continue;
+            }
+            self.borrows[index].ended = true;
+            let borrow = &self.borrows[index];
+            if !borrow.holders.is_empty() {
+                let holders: Vec<String> = borrow
+                    .holders
+                    .iter()
+                    .map(|&h| format!("`{}`", self.name(h)))
+                    .collect();
+                let text = format!(
+                    "the borrow of `{}` held by {} ends, it is not used again",
+                    self.name(borrow.target),
+                    holders.join(", ")
+                );
+                self.event(text);
+            }
+        }
+    }
+
+    fn check_initialized(&mut self, id: usize, span: Span, borrow_verb: bool) -> bool {
+        let binding = &self.bindings[id];
+        match binding.state {
+            State::Init => true,
+            State::Uninit => {
+                let name = binding.name.clone();
+                let decl = binding.decl;
+                self.error(
+                    Diagnostic::error("E0381", format!("used binding `{name}` isn't initialized"))
+                        .secondary(decl, "binding declared here but left uninitialized")
+                        .primary(span, format!("`{name}` used here but it isn't initialized")),
+                );
+                false
+            }
+            State::Moved(at) => {
+                let name = binding.name.clone();
+                let ty = binding.ty;
+                let (message, label) = if borrow_verb {
+                    (
+                        format!("borrow of moved value: `{name}`"),
+                        "value borrowed here after move",
+                    )
+                } else {
+                    (
+                        format!("use of moved value: `{name}`"),
+                        "value used here after move",
+                    )
+                };
+                self.error(
+                    Diagnostic::error("E0382", message)
+                        .secondary(at, "value moved here")
+                        .primary(span, label)
+                        .note(format!(
+                            "note: move occurs because `{name}` has type `{}`, which does not implement the `Copy` trait",
+                            ty.name()
+                        )),
+                );
+                false
+            }
+        }
+    }
+
+    /*
+     * Revisa si se puede crear un préstamo nuevo, o leer la variable que es un préstamo implícito
+     */
+    fn check_conflicts(&mut self, id: usize, mutable: bool, span: Span) {
+        let name = self.name(id).to_string();
+        for borrow in self.live_borrows_of(id) {
+            let existing = self.borrows[borrow].mutable;
+            if !mutable && !existing {
+                continue;
+            }
+            let diagnostic = match (mutable, existing) {
+                (true, true) => Diagnostic::error(
+                    "E0499",
+                    format!("cannot borrow `{name}` as mutable more than once at a time"),
+                )
+                .secondary(self.borrows[borrow].span, "first mutable borrow occurs here")
+                .primary(span, "second mutable borrow occurs here"),
+                (true, false) => Diagnostic::error(
+                    "E0502",
+                    format!("cannot borrow `{name}` as mutable because it is also borrowed as immutable"),
+                )
+                .secondary(self.borrows[borrow].span, "immutable borrow occurs here")
+                .primary(span, "mutable borrow occurs here"),
+                _ => Diagnostic::error(
+                    "E0502",
+                    format!("cannot borrow `{name}` as immutable because it is also borrowed as mutable"),
+                )
+                .secondary(self.borrows[borrow].span, "mutable borrow occurs here")
+                .primary(span, "immutable borrow occurs here"),
+            };
+            let later = if existing {
+                "mutable borrow later used here"
+            } else {
+                "immutable borrow later used here"
+            };
+            let later = if mutable && existing {
+                "first borrow later used here"
+            } else {
+                later
+            };
+            let diagnostic = self.with_later_use(diagnostic, borrow, later);
+            self.error(diagnostic);
+            return;
+        }
+    }
+
+    fn check_mutable(&mut self, id: usize, span: Span) {
+        let binding = &self.bindings[id];
+        if binding.mutable {
+            return;
+        }
+        let name = binding.name.clone();
+        let decl = binding.decl;
+        self.error(
+            Diagnostic::error(
+                "E0596",
+                format!("cannot borrow `{name}` as mutable, as it is not declared as mutable"),
+            )
+            .primary(span, "cannot borrow as mutable")
+            .secondary(
+                decl,
+                format!("help: consider changing this to be mutable: `mut {name}`"),
+            ),
+        );
+    }
+
+    fn held_borrows(&self, id: usize) -> Vec<usize> {
+        (0..self.borrows.len())
+            .filter(|&borrow| {
+                !self.borrows[borrow].ended && self.borrows[borrow].holders.contains(&id)
+            })
+            .collect()
+    }
+
+    fn borrow(&mut self, target: &Ident, mutable: bool, span: Span) -> Value {
+        let Some(id) = self.resolve(target) else {
+            return Value::of(Ty::Unknown);
+        };
+        self.record_use(id, target.span);
+        if !self.check_initialized(id, span, true) {
+            return Value::of(Ty::Ref { mutable });
+        }
+        if mutable {
+            self.check_mutable(id, span);
+        }
+        self.check_conflicts(id, mutable, span);
+        self.borrows.push(Borrow {
+            target: id,
+            mutable,
+            span,
+            created: self.pos,
+            holders: Vec::new(),
+            ended: false,
+        });
+        Value {
+            ty: Ty::Ref { mutable },
+            borrows: vec![self.borrows.len() - 1],
+            moved_from: None,
+        }
+    }
+
+    fn var(&mut self, ident: &Ident, mode: Mode) -> Value {
+        let Some(id) = self.resolve(ident) else {
+            return Value::of(Ty::Unknown);
+        };
+        self.record_use(id, ident.span);
+        let ty = self.bindings[id].ty;
+        if !self.check_initialized(id, ident.span, mode == Mode::Read) {
+            return Value::of(ty);
+        }
+        let borrows = self.held_borrows(id);
+        if mode == Mode::Read || ty.is_copy() {
+            if !matches!(ty, Ty::Ref { .. }) {
+                self.check_conflicts(id, false, ident.span);
+            }
+            return Value {
+                ty,
+                borrows,
+                moved_from: None,
+            };
+        }
+        /*
+         * Mover un valor mientras existe una referencia a él la dejaría apuntando a memoria que ya no
+         * le pertenece a la variable
+         */
+        if let Some(&borrow) = self.live_borrows_of(id).first() {
+            let name = self.name(id).to_string();
+            let diagnostic = Diagnostic::error(
+                "E0505",
+                format!("cannot move out of `{name}` because it is borrowed"),
+            )
+            .secondary(
+                self.borrows[borrow].span,
+                format!("borrow of `{name}` occurs here"),
+            )
+            .primary(ident.span, format!("move out of `{name}` occurs here"));
+            let diagnostic = self.with_later_use(diagnostic, borrow, "borrow later used here");
+            self.error(diagnostic);
+        }
+        self.bindings[id].state = State::Moved(ident.span);
+        Value {
+            ty,
+            borrows,
+            moved_from: Some(id),
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr, mode: Mode) -> Value {
+        match &expr.kind {
+            ExprKind::Int(_) => Value::of(Ty::Int),
+            ExprKind::Str(_) => Value::of(Ty::Str),
+            ExprKind::NewString(_) => {
+                self.event("a new String is allocated on the heap".to_string());
+                Value::of(Ty::String)
+            }
+            ExprKind::Var(ident) => self.var(ident, mode),
+            ExprKind::Borrow { mutable, target } => self.borrow(target, *mutable, expr.span),
+            ExprKind::Call { name, args } => self.call(name, args, expr.span),
+            ExprKind::Method {
+                receiver,
+                method,
+                args,
+            } => self.method(receiver, method, args, expr.span),
+            ExprKind::Block(block) => self.block(block),
+        }
+    }
+
+    fn call(&mut self, name: &Ident, args: &[Expr], span: Span) -> Value {
+        match name.name.as_str() {
+            "print" | "println!" | "print!" => {
+                for arg in args {
+                    self.expr(arg, Mode::Read);
+                }
+                return Value::of(Ty::Unit);
+            }
+            "drop" => {
+                for arg in args {
+                    let value = self.expr(arg, Mode::Move);
+                    if let Some(from) = value.moved_from {
+                        let text = format!(
+                            "`{}` moves into `drop`, its heap memory is freed",
+                            self.name(from)
+                        );
+                        self.event(text);
+                    }
+                }
+                return Value::of(Ty::Unit);
+            }
+            _ => {}
+        }
+        let signatures = self.signatures;
+        let Some(signature) = signatures.get(&name.name) else {
+            self.error(
+                Diagnostic::error(
+                    "E0425",
+                    format!("cannot find function `{}` in this scope", name.name),
+                )
+                .primary(name.span, "not found in this scope"),
+            );
+            for arg in args {
+                self.expr(arg, Mode::Read);
+            }
+            return Value::of(Ty::Unknown);
+        };
+        if signature.params.len() != args.len() {
+            self.error(
+                Diagnostic::error(
+                    "E0061",
+                    format!(
+                        "this function takes {} argument{} but {} argument{} supplied",
+                        signature.params.len(),
+                        if signature.params.len() == 1 { "" } else { "s" },
+                        args.len(),
+                        if args.len() == 1 { " was" } else { "s were" }
+                    ),
+                )
+                .primary(
+                    span,
+                    format!("expected {} argument(s)", signature.params.len()),
+                ),
+            );
+        }
+        let mut ref_borrows = Vec::new();
+        for (arg, param) in args.iter().zip(&signature.params) {
+            let mode = if param.is_ref() {
+                Mode::Read
+            } else {
+                Mode::Move
+            };
+            let value = self.expr(arg, mode);
+            if !accepts(param, value.ty) {
+                let mut diagnostic = Diagnostic::error("E0308", "mismatched types").primary(
+                    arg.span,
+                    format!("expected `{param}`, found `{}`", value.ty.name()),
+                );
+                if param.is_ref() && value.ty == Ty::String {
+                    diagnostic = diagnostic.note("help: consider borrowing here: `&`");
+                }
+                self.error(diagnostic);
+            }
+            if param.is_ref() {
+                ref_borrows.extend(value.borrows.iter().copied());
+            }
+            let text = match (value.moved_from, value.ty) {
+                (Some(from), _) => {
+                    Some(format!("`{}` moves into `{}`", self.name(from), name.name))
+                }
+                (None, Ty::Ref { .. }) => Some(format!("`{}` receives a reference", name.name)),
+                (None, Ty::Int) => Some(format!("`{}` receives a copy", name.name)),
+                _ => None,
+            };
+            if let Some(text) = text {
+                self.event(text);
+            }
+        }
+        match &signature.ret {
+            None => Value::of(Ty::Unit),
+            Some(Type::String) => {
+                self.event(format!(
+                    "`{}` returns a String, ownership moves to the caller",
+                    name.name
+                ));
+                Value::of(Ty::String)
+            }
+            Some(ret) if ret.is_ref() => Value {
+                ty: Ty::from_type(ret),
+                borrows: ref_borrows,
+                moved_from: None,
+            },
+            Some(ret) => Value::of(Ty::from_type(ret)),
+        }
+    }
+
+    fn method(&mut self, receiver: &Ident, method: &Ident, args: &[Expr], span: Span) -> Value {
+        let Some(id) = self.resolve(receiver) else {
+            return Value::of(Ty::Unknown);
+        };
+        self.record_use(id, receiver.span);
+        let ty = self.bindings[id].ty;
+        let name = receiver.name.clone();
+        let needs_mut = match method.name.as_str() {
+            "len" | "is_empty" | "clone" | "to_string" | "to_owned" => false,
+            "push_str" | "push" | "clear" => true,
+            other => {
+                self.error(
+                    Diagnostic::error(
+                        "E0599",
+                        format!("no method named `{other}` found for `{}`", ty.name()),
+                    )
+                    .primary(method.span, "method not found"),
+                );
+                return Value::of(Ty::Unknown);
+            }
+        };
+        for arg in args {
+            self.expr(arg, Mode::Read);
+        }
+        if !self.check_initialized(id, receiver.span, true) {
+            return Value::of(Ty::Unknown);
+        }
+        match (ty, needs_mut) {
+            (Ty::Ref { mutable: false }, true) => self.error(
+                Diagnostic::error("E0596", format!("cannot borrow `*{name}` as mutable, as it is behind a `&` reference"))
+                    .primary(span, format!("`{name}` is a `&` reference, so the data it refers to cannot be borrowed as mutable")),
+            ),
+            (Ty::Ref { .. }, true) => self.event(format!("`{name}` changes the borrowed String through the reference")),
+            (Ty::Ref { .. }, false) => {}
+            (_, true) => {
+                self.check_mutable(id, receiver.span);
+                self.check_conflicts(id, true, span);
+                self.event(format!("`{name}` is borrowed mutably by `{}`", method.name));
+            }
+            (_, false) => self.check_conflicts(id, false, receiver.span),
+        }
+        match method.name.as_str() {
+            "len" => Value::of(Ty::Int),
+            "is_empty" => Value::of(Ty::Int),
+            "clone" | "to_string" | "to_owned" => {
+                self.event(format!(
+                    "`{name}` is cloned, a new String is allocated on the heap"
+                ));
+                Value::of(Ty::String)
+            }
+            _ => Value::of(Ty::Unit),
+        }
+    }
+
+    fn block(&mut self, block: &Block) -> Value {
+        let outer = std::mem::take(&mut self.events);
+        self.scopes.push(Vec::new());
+        for stmt in &block.stmts {
+            self.stmt(stmt);
+        }
+        let value = match &block.tail {
+            Some(tail) => {
+                self.pos += 1;
+                let value = self.expr(tail, Mode::Move);
+                self.flush(tail.span);
+                value
+            }
+            None => Value::of(Ty::Unit),
+        };
+        self.pop_scope(block.close_span(), &value.borrows);
+        self.flush(block.close_span());
+        self.events = outer;
+        value
+    }
+
+    /*
+     * Al salir del `scope` cada variable se libera en orden inverso a su declaración, si una
+     * referencia a ella se usa después, la referencia quedaría colgando `dangling`
+     */
+    fn pop_scope(&mut self, close: Span, escaping: &[usize]) {
+        let Some(scope) = self.scopes.pop() else {
+            return;
+        };
+        for &id in scope.iter().rev() {
+            for borrow in 0..self.borrows.len() {
+                if self.borrows[borrow].target != id || self.borrows[borrow].ended {
+                    continue;
+                }
+                let used_later = self.later_use(borrow).is_some();
+                if used_later || escaping.contains(&borrow) {
+                    let name = self.name(id).to_string();
+                    let diagnostic =
+                        Diagnostic::error("E0597", format!("`{name}` does not live long enough"))
+                            .secondary(
+                                self.bindings[id].decl,
+                                format!("binding `{name}` declared here"),
+                            )
+                            .secondary(
+                                self.borrows[borrow].span,
+                                "borrowed value does not live long enough",
+                            )
+                            .primary(close, format!("`{name}` dropped here while still borrowed"));
+                    let diagnostic =
+                        self.with_later_use(diagnostic, borrow, "borrow later used here");
+                    self.error(diagnostic);
+                }
+                self.borrows[borrow].ended = true;
+            }
+            let binding = &self.bindings[id];
+            let text = match (binding.state, binding.ty) {
+                (State::Uninit, _) => continue,
+                (State::Init, Ty::String) => format!(
+                    "`{}` goes out of scope, `drop` frees its heap memory",
+                    binding.name
+                ),
+                (State::Moved(_), Ty::String) => format!(
+                    "`{}` goes out of scope, it was moved so nothing is freed",
+                    binding.name
+                ),
+                _ => format!("`{}` goes out of scope", binding.name),
+            };
+            self.event(text);
+        }
+    }
+
+    fn bind(&mut self, id: usize, value: Value, span: Span) {
+        let name = self.name(id).to_string();
+        self.bindings[id].ty = value.ty;
+        self.bindings[id].state = State::Init;
+        self.bindings[id].assigned = Some(span);
+        for &borrow in &value.borrows {
+            self.borrows[borrow].holders.push(id);
+        }
+        let text = match (value.moved_from, value.ty) {
+            (Some(from), Ty::String) => {
+                format!("move `{}` -> `{name}`, `{name}` now owns the String and `{}` can no longer be used", self.name(from), self.name(from))
+            }
+            (Some(from), _) => format!("move `{}` -> `{name}`", self.name(from)),
+            (None, Ty::Ref { mutable }) => {
+                let targets: Vec<String> = value
+                    .borrows
+                    .iter()
+                    .map(|&borrow| format!("`{}`", self.name(self.borrows[borrow].target)))
+                    .collect();
+                let kind = if mutable { "mutably" } else { "immutably" };
+                if targets.is_empty() {
+                    format!("`{name}` holds a reference")
+                } else {
+                    format!("`{name}` borrows {} {kind}", targets.join(", "))
+                }
+            }
+            (None, Ty::String) => format!("`{name}` owns the String"),
+            (None, Ty::Int | Ty::Str) => format!("`{name}` holds a copy on the stack"),
+            (None, _) => format!("`{name}` is initialized"),
+        };
+        self.event(text);
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        self.pos += 1;
+        match stmt {
+            Stmt::Let {
+                name,
+                mutable,
+                ty,
+                init,
+                span,
+            } => {
+                let value = init.as_ref().map(|init| self.expr(init, Mode::Move));
+                let declared = ty.as_ref().map_or(Ty::Unknown, Ty::from_type);
+                let id = self.declare(name, *mutable, declared);
+                match value {
+                    Some(value) => {
+                        if let Some(ty) = ty {
+                            if !accepts(ty, value.ty) {
+                                let init_span = init.as_ref().map_or(*span, |init| init.span);
+                                self.error(Diagnostic::error("E0308", "mismatched types").primary(
+                                    init_span,
+                                    format!("expected `{ty}`, found `{}`", value.ty.name()),
+                                ));
+                            }
+                        }
+                        self.bind(id, value, *span);
+                    }
+                    None => self.event(format!("`{}` is declared but not initialized", name.name)),
+                }
+            }
+            Stmt::Assign {
+                target,
+                value,
+                span,
+            } => {
+                let value = self.expr(value, Mode::Move);
+                if let Some(id) = self.resolve(target) {
+                    self.assign(id, target, value, *span);
+                }
+            }
+            Stmt::Expr(expr, _) => {
+                let value = self.expr(expr, Mode::Move);
+                if value.ty == Ty::String && value.moved_from.is_none() {
+                    self.event("the String is not stored anywhere, it is dropped at the end of the statement".to_string());
+                }
+            }
+        }
+        self.flush(stmt.span());
+    }
+
+    fn assign(&mut self, id: usize, target: &Ident, value: Value, span: Span) {
+        let binding = &self.bindings[id];
+        let name = binding.name.clone();
+        if let (Some(first), false) = (binding.assigned, binding.mutable) {
+            self.error(
+                Diagnostic::error(
+                    "E0384",
+                    format!("cannot assign twice to immutable variable `{name}`"),
+                )
+                .secondary(first, format!("first assignment to `{name}`"))
+                .primary(span, "cannot assign twice to immutable variable")
+                .note(format!(
+                    "help: consider making this binding mutable: `mut {name}`"
+                )),
+            );
+        }
+        if let Some(&borrow) = self.live_borrows_of(id).first() {
+            let diagnostic = Diagnostic::error(
+                "E0506",
+                format!("cannot assign to `{name}` because it is borrowed"),
+            )
+            .secondary(
+                self.borrows[borrow].span,
+                format!("`{name}` is borrowed here"),
+            )
+            .primary(
+                target.span.to(span),
+                format!("`{name}` is assigned to here but it was already borrowed"),
+            );
+            let diagnostic = self.with_later_use(diagnostic, borrow, "borrow later used here");
+            self.error(diagnostic);
+        }
+        if self.bindings[id].state == State::Init && self.bindings[id].ty == Ty::String {
+            self.event(format!("the old String in `{name}` is dropped"));
+        }
+        /*
+         * La variable deja de guardar los préstamos anteriores
+         */
+        for borrow in &mut self.borrows {
+            borrow.holders.retain(|&holder| holder != id);
+        }
+        self.bind(id, value, span);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ownership::borrowck::{lexer, parser};
+
+    fn diagnostics(source: &str) -> Vec<Diagnostic> {
+        let program = parser::parse(&lexer::lex(source).unwrap()).unwrap();
+        check_program(&program).1
+    }
+
+    /*
+     * Cada etiqueta con el texto que señala, en el orden en que se agregaron
+     */
+    fn labels<'s>(source: &'s str, diagnostic: &Diagnostic) -> Vec<(bool, &'s str, String)> {
+        diagnostic
+            .labels
+            .iter()
+            .map(|label| {
+                let text = &source[label.span.start..label.span.end];
+                (label.primary, text, label.text.clone())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn mutable_borrow_of_an_immutable_binding() {
+        let source = "let s = String::new();\nlet r = &mut s;\n";
+        let found = diagnostics(source);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].code, Some("E0596"));
+        assert_eq!(
+            found[0].message,
+            "cannot borrow `s` as mutable, as it is not declared as mutable"
+        );
+        assert_eq!(
+            labels(source, &found[0]),
+            [
+                (true, "&mut s", "cannot borrow as mutable".to_string()),
+                (
+                    false,
+                    "s",
+                    "help: consider changing this to be mutable: `mut s`".to_string()
+                ),
+            ]
+        );
+        assert_eq!(found[0].labels[1].span, Span::new(4, 5));
+    }
+
+    #[test]
+    fn mutating_methods_need_mut_too() {
+        let source = "fn shout(s: String) {\n    s.push_str(\"!\");\n}\n";
+        let found = diagnostics(source);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].code, Some("E0596"));
+        assert_eq!(labels(source, &found[0])[0].1, "s");
+        /*
+         * Detrás de un `&` el mensaje es otro, el dueño sí es mutable
+         */
+        let source = "let mut s = String::new();\nlet r = &s;\nr.push_str(\"!\");\n";
+        let found = diagnostics(source);
+        assert_eq!(
+            found[0].message,
+            "cannot borrow `*r` as mutable, as it is behind a `&` reference"
+        );
+    }
+
+    #[test]
+    fn mut_bindings_and_params_are_accepted() {
+        let sources = [
+            "let mut s = String::new();\nlet r = &mut s;\nr.push_str(\"!\");\n",
+            "fn shout(mut s: String) {\n    let r = &mut s;\n    r.push_str(\"!\");\n}\n",
+            "fn shout(s: &mut String) {\n    s.push_str(\"!\");\n}\n",
+            "let s = String::new();\nlet n = s.len();\nlet r = &s;\nprintln!(r, n);\n",
+        ];
+        for source in sources {
+            assert!(diagnostics(source).is_empty(), "{source}");
+        }
+    }
+
+    #[test]
+    fn steps_follow_the_statements() {
+        let source = "let mut s = String::new();\nlet r = &mut s;\n";
+        let (steps, _) = check_program(&parser::parse(&lexer::lex(source).unwrap()).unwrap());
+        let spans: Vec<&str> = steps
+            .iter()
+            .map(|step| &source[step.span.start..step.span.end])
+            .collect();
+        assert_eq!(spans[..2], ["let mut s = String::new();", "let r = &mut s;"]);
+        assert!(steps.iter().all(|step| step.function == "main"));
+    }
+}
@@ -0,0 +1,167 @@
+/*
+ * Los errores se imprimen con la misma forma que usa `rustc`, el código del error, el mensaje, y
+ * las líneas del programa con etiquetas debajo de cada parte importante
+ *
+ *  error[E0382]: borrow of moved value: `s1`
+ *   --> lesson.rs:3:7
+ *    |
+ *  2 | let s2 = s1;
+ *    |          -- value moved here
+ *  3 | print(s1);
+ *    |       ^^ value borrowed here after move
+ */
+use std::fmt::Write;
+
+/*
+ * La posición en `bytes` dentro del programa, igual que un rango `start..end` de un `slice`
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    pub fn to(self, other: Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub span: Span,
+    pub text: String,
+    pub primary: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub code: Option<&'static str>,
+    pub message: String,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code: Some(code),
+            message: message.into(),
+            labels: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    /*
+     * Los errores de sintaxis no tienen código en `rustc`
+     */
+    pub fn syntax(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            code: None,
+            message: message.into(),
+            labels: Vec::new(),
+            notes: Vec::new(),
+        }
+        .primary(span, "")
+    }
+
+    pub fn primary(mut self, span: Span, text: impl Into<String>) -> Self {
+        self.labels.push(Label {
+            span,
+            text: text.into(),
+            primary: true,
+        });
+        self
+    }
+
+    pub fn secondary(mut self, span: Span, text: impl Into<String>) -> Self {
+        self.labels.push(Label {
+            span,
+            text: text.into(),
+            primary: false,
+        });
+        self
+    }
+
+    pub fn note(mut self, text: impl Into<String>) -> Self {
+        self.notes.push(text.into());
+        self
+    }
+
+    pub fn primary_span(&self) -> Span {
+        self.labels
+            .iter()
+            .find(|label| label.primary)
+            .map(|label| label.span)
+            .unwrap_or_default()
+    }
+
+    pub fn render(&self, source: &str, file: &str) -> String {
+        let mut out = String::new();
+        let _ = match self.code {
+            Some(code) => writeln!(out, "error[{code}]: {}", self.message),
+            None => writeln!(out, "error: {}", self.message),
+        };
+        let (line, column) = line_col(source, self.primary_span().start);
+        let mut labels: Vec<&Label> = self.labels.iter().collect();
+        labels.sort_by_key(|label| label.span.start);
+        let last_line = labels
+            .iter()
+            .map(|label| line_col(source, label.span.start).0)
+            .max()
+            .unwrap_or(line);
+        let gutter = last_line.to_string().len();
+        let pad = " ".repeat(gutter);
+        let _ = writeln!(out, "{pad}--> {file}:{line}:{column}");
+        let _ = writeln!(out, "{pad} |");
+        let mut previous_line = 0;
+        for label in labels {
+            let (line, column) = line_col(source, label.span.start);
+            if line != previous_line {
+                if previous_line != 0 && line > previous_line + 1 {
+                    let _ = writeln!(out, "{}", ".".repeat(gutter));
+                }
+                let _ = writeln!(out, "{line:>gutter$} | {}", line_text(source, line));
+                previous_line = line;
+            }
+            let width = label.span.end.saturating_sub(label.span.start);
+            let width = source
+                .get(label.span.start..label.span.start + width)
+                .map_or(1, |text| {
+                    text.lines().next().unwrap_or("").chars().count().max(1)
+                });
+            let marker = if label.primary { "^" } else { "-" };
+            let _ = writeln!(
+                out,
+                "{pad} | {}{} {}",
+                " ".repeat(column - 1),
+                marker.repeat(width),
+                label.text
+            );
+        }
+        let _ = writeln!(out, "{pad} |");
+        for note in &self.notes {
+            let _ = writeln!(out, "{pad} = {note}");
+        }
+        out
+    }
+}
+
+/*
+ * La línea y la columna comienzan en uno, la columna se cuenta en `chars`
+ */
+pub fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let offset = offset.min(source.len());
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+pub fn line_text(source: &str, line: usize) -> &str {
+    source.lines().nth(line - 1).unwrap_or("")
+}
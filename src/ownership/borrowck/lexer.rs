@@ -0,0 +1,274 @@
+/*
+ * El `lexer` convierte el texto del programa en una lista de `tokens`, cada uno con su `Span`
+ * para poder señalarlo en los mensajes de error
+ */
+use super::diagnostic::{Diagnostic, Span};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenKind {
+    Ident(String),
+    Int(i64),
+    Str(String),
+    Let,
+    Mut,
+    Fn,
+    Amp,
+    Colon,
+    PathSep,
+    Comma,
+    Semi,
+    Eq,
+    Dot,
+    Arrow,
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    Eof,
+}
+
+impl TokenKind {
+    pub fn describe(&self) -> String {
+        match self {
+            TokenKind::Ident(name) => format!("`{name}`"),
+            TokenKind::Int(value) => format!("`{value}`"),
+            TokenKind::Str(_) => "string literal".to_string(),
+            TokenKind::Let => "`let`".to_string(),
+            TokenKind::Mut => "`mut`".to_string(),
+            TokenKind::Fn => "`fn`".to_string(),
+            TokenKind::Amp => "`&`".to_string(),
+            TokenKind::Colon => "`:`".to_string(),
+            TokenKind::PathSep => "`::`".to_string(),
+            TokenKind::Comma => "`,`".to_string(),
+            TokenKind::Semi => "`;`".to_string(),
+            TokenKind::Eq => "`=`".to_string(),
+            TokenKind::Dot => "`.`".to_string(),
+            TokenKind::Arrow => "`->`".to_string(),
+            TokenKind::LParen => "`(`".to_string(),
+            TokenKind::RParen => "`)`".to_string(),
+            TokenKind::LBrace => "`{`".to_string(),
+            TokenKind::RBrace => "`}`".to_string(),
+            TokenKind::Eof => "end of input".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+pub fn lex(source: &str) -> Result<Vec<Token>, Diagnostic> {
+    let mut tokens = Vec::new();
+    let bytes = source.as_bytes();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let start = pos;
+        let c = bytes[pos];
+        if c.is_ascii_whitespace() {
+            pos += 1;
+            continue;
+        }
+        /*
+         * Los comentarios de una línea `//` se ignoran
+         */
+        if source[pos..].starts_with("//") {
+            pos = source[pos..]
+                .find('\n')
+                .map_or(bytes.len(), |index| pos + index);
+            continue;
+        }
+        let kind = match c {
+            b'&' => TokenKind::Amp,
+            b',' => TokenKind::Comma,
+            b';' => TokenKind::Semi,
+            b'=' => TokenKind::Eq,
+            b'.' => TokenKind::Dot,
+            b'(' => TokenKind::LParen,
+            b')' => TokenKind::RParen,
+            b'{' => TokenKind::LBrace,
+            b'}' => TokenKind::RBrace,
+            b':' if bytes.get(pos + 1) == Some(&b':') => {
+                pos += 1;
+                TokenKind::PathSep
+            }
+            b':' => TokenKind::Colon,
+            b'-' if bytes.get(pos + 1) == Some(&b'>') => {
+                pos += 1;
+                TokenKind::Arrow
+            }
+            b'"' => {
+                let end = source[pos + 1..].find('"').ok_or_else(|| {
+                    Diagnostic::syntax(
+                        "unterminated double quote string",
+                        Span::new(start, bytes.len()),
+                    )
+                })?;
+                let text = source[pos + 1..pos + 1 + end].to_string();
+                pos += end + 1;
+                TokenKind::Str(text)
+            }
+            b'0'..=b'9' => {
+                while pos + 1 < bytes.len()
+                    && (bytes[pos + 1].is_ascii_digit() || bytes[pos + 1] == b'_')
+                {
+                    pos += 1;
+                }
+                let digits = source[start..=pos].replace('_', "");
+                let value = digits.parse().map_err(|_| {
+                    Diagnostic::syntax("integer literal is too large", Span::new(start, pos + 1))
+                })?;
+                TokenKind::Int(value)
+            }
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                while pos + 1 < bytes.len()
+                    && (bytes[pos + 1].is_ascii_alphanumeric() || bytes[pos + 1] == b'_')
+                {
+                    pos += 1;
+                }
+                /*
+                 * Los `macros` como `println!` se leen como un solo identificador
+                 */
+                if bytes.get(pos + 1) == Some(&b'!') {
+                    pos += 1;
+                }
+                match &source[start..=pos] {
+                    "let" => TokenKind::Let,
+                    "mut" => TokenKind::Mut,
+                    "fn" => TokenKind::Fn,
+                    name => TokenKind::Ident(name.to_string()),
+                }
+            }
+            _ => {
+                let c = source[pos..].chars().next().unwrap_or('?');
+                return Err(Diagnostic::syntax(
+                    format!("unknown start of token: {c}"),
+                    Span::new(start, start + c.len_utf8()),
+                ));
+            }
+        };
+        pos += 1;
+        tokens.push(Token {
+            kind,
+            span: Span::new(start, pos),
+        });
+    }
+    tokens.push(Token {
+        kind: TokenKind::Eof,
+        span: Span::new(bytes.len(), bytes.len()),
+    });
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        lex(source)
+            .unwrap()
+            .into_iter()
+            .map(|token| token.kind)
+            .collect()
+    }
+
+    #[test]
+    fn punctuation_and_keywords() {
+        assert_eq!(
+            kinds("fn f(s: &mut String) -> usize { s.len() } // fin"),
+            [
+                TokenKind::Fn,
+                TokenKind::Ident("f".to_string()),
+                TokenKind::LParen,
+                TokenKind::Ident("s".to_string()),
+                TokenKind::Colon,
+                TokenKind::Amp,
+                TokenKind::Mut,
+                TokenKind::Ident("String".to_string()),
+                TokenKind::RParen,
+                TokenKind::Arrow,
+                TokenKind::Ident("usize".to_string()),
+                TokenKind::LBrace,
+                TokenKind::Ident("s".to_string()),
+                TokenKind::Dot,
+                TokenKind::Ident("len".to_string()),
+                TokenKind::LParen,
+                TokenKind::RParen,
+                TokenKind::RBrace,
+                TokenKind::Eof,
+            ]
+        );
+        /*
+         * `println!` es un solo identificador, `letter` no es `let`
+         */
+        assert_eq!(
+            kinds("println!(letter, String::from(\"a b\"), 1_000);"),
+            [
+                TokenKind::Ident("println!".to_string()),
+                TokenKind::LParen,
+                TokenKind::Ident("letter".to_string()),
+                TokenKind::Comma,
+                TokenKind::Ident("String".to_string()),
+                TokenKind::PathSep,
+                TokenKind::Ident("from".to_string()),
+                TokenKind::LParen,
+                TokenKind::Str("a b".to_string()),
+                TokenKind::RParen,
+                TokenKind::Comma,
+                TokenKind::Int(1000),
+                TokenKind::RParen,
+                TokenKind::Semi,
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn spans_are_byte_ranges() {
+        let tokens = lex("let s = \"é\";\n").unwrap();
+        let spans: Vec<Span> = tokens.iter().map(|token| token.span).collect();
+        assert_eq!(
+            spans,
+            [
+                Span::new(0, 3),
+                Span::new(4, 5),
+                Span::new(6, 7),
+                Span::new(8, 12),
+                Span::new(12, 13),
+                Span::new(14, 14),
+            ]
+        );
+        assert_eq!(kinds(""), [TokenKind::Eof]);
+        assert_eq!(kinds("// solo un comentario"), [TokenKind::Eof]);
+    }
+
+    #[test]
+    fn unterminated_string() {
+        let error = lex("let s = \"hola;\nlet t = 1;").unwrap_err();
+        assert_eq!(error.message, "unterminated double quote string");
+        assert_eq!(error.code, None);
+        assert_eq!(error.primary_span(), Span::new(8, 25));
+    }
+
+    #[test]
+    fn unknown_start_of_token() {
+        let error = lex("let ñ = 1;").unwrap_err();
+        assert_eq!(error.message, "unknown start of token: ñ");
+        assert_eq!(error.primary_span(), Span::new(4, 6));
+        /*
+         * Un `-` solo no es una flecha
+         */
+        let error = lex("x - 1").unwrap_err();
+        assert_eq!(error.message, "unknown start of token: -");
+        assert_eq!(error.primary_span(), Span::new(2, 3));
+    }
+
+    #[test]
+    fn integer_too_large() {
+        let error = lex("let x = 99_999_999_999_999_999_999;").unwrap_err();
+        assert_eq!(error.message, "integer literal is too large");
+        assert_eq!(error.primary_span(), Span::new(8, 34));
+        assert_eq!(kinds("9223372036854775807")[0], TokenKind::Int(i64::MAX));
+    }
+}
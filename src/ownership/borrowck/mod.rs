@@ -0,0 +1,285 @@
+/*
+ * Un simulador de las reglas de `ownership` y `borrowing` para un lenguaje pequeño que se parece a
+ * `Rust`, con `let`, `let mut`, `String::from()`, referencias `&` y `&mut`, funciones y bloques
+ *
+ *  let s1 = String::from("hello");
+ *  let s2 = s1;
+ *  println!(s1);
+ *
+ * El simulador imprime paso a paso qué variable es dueña de cada valor, cuándo se mueve, cuándo se
+ * presta y cuándo se libera, y reporta los errores con los mismos códigos que `rustc`
+ */
+pub mod checker;
+pub mod diagnostic;
+pub mod lexer;
+pub mod parser;
+
+use std::fmt::Write;
+
+pub use checker::Step;
+pub use diagnostic::{Diagnostic, Span};
+
+/*
+ * El programa de ejemplo sigue las lecciones de `ownership`, el último `println!` es un error
+ */
+pub const EXAMPLE: &str = r#"fn calculate_length(s: &String) -> i32 {
+    s.len()
+}
+
+fn change(some_string: &mut String) {
+    some_string.push_str(", world");
+}
+
+fn takes_ownership(some_string: String) {
+    println!(some_string);
+}
+
+let mut s = String::from("hello");
+let len = calculate_length(&s);
+change(&mut s);
+let r1 = &s;
+let r2 = &s;
+println!(r1, r2);
+let r3 = &mut s;
+println!(r3);
+takes_ownership(s);
+println!(s);
+"#;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub steps: Vec<Step>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Outcome {
+    pub fn is_ok(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /*
+     * Cada paso muestra la línea del programa y debajo lo que paso con cada variable
+     */
+    pub fn render_trace(&self, source: &str) -> String {
+        let mut out = String::new();
+        let mut function = "";
+        for step in &self.steps {
+            if step.events.is_empty() {
+                continue;
+            }
+            if step.function != function {
+                function = &step.function;
+                let _ = writeln!(out, "fn {function}");
+            }
+            /*
+             * El `main` implícito no tiene llave de cierre, su final es el final del programa
+             */
+            if step.span.start >= source.trim_end().len() {
+                let _ = writeln!(out, "     | end of `{function}`");
+                for event in &step.events {
+                    let _ = writeln!(out, "     |   {event}");
+                }
+                continue;
+            }
+            let (line, _) = diagnostic::line_col(source, step.span.start);
+            let _ = writeln!(
+                out,
+                "{line:>4} | {}",
+                diagnostic::line_text(source, line).trim()
+            );
+            for event in &step.events {
+                let _ = writeln!(out, "     |   {event}");
+            }
+        }
+        out
+    }
+
+    pub fn render_diagnostics(&self, source: &str, file: &str) -> String {
+        let mut out = String::new();
+        for diagnostic in &self.diagnostics {
+            let _ = writeln!(out, "{}", diagnostic.render(source, file));
+        }
+        match self.diagnostics.len() {
+            0 => {}
+            1 => out.push_str("error: aborting due to 1 previous error\n"),
+            count => {
+                let _ = writeln!(out, "error: aborting due to {count} previous errors");
+            }
+        }
+        out
+    }
+}
+
+/*
+ * Los errores de sintaxis detienen el análisis, igual que en `rustc`
+ */
+pub fn check(source: &str) -> Outcome {
+    let program = lexer::lex(source).and_then(|tokens| parser::parse(&tokens));
+    match program {
+        Ok(program) => {
+            let (steps, diagnostics) = checker::check_program(&program);
+            Outcome { steps, diagnostics }
+        }
+        Err(diagnostic) => Outcome {
+            steps: Vec::new(),
+            diagnostics: vec![diagnostic],
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(source: &str) -> Vec<&'static str> {
+        check(source)
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.code.unwrap_or("syntax"))
+            .collect()
+    }
+
+    #[test]
+    fn example_ends_with_a_use_after_move() {
+        let outcome = check(EXAMPLE);
+        assert_eq!(codes(EXAMPLE), ["E0382"]);
+        let rendered = outcome.render_diagnostics(EXAMPLE, "example.rs");
+        assert!(rendered.starts_with("error[E0382]: borrow of moved value: `s`"), "{rendered}");
+        assert!(rendered.contains("value moved here"), "{rendered}");
+        assert!(rendered.ends_with("error: aborting due to 1 previous error\n"), "{rendered}");
+    }
+
+    #[test]
+    fn use_after_move_e0382() {
+        let source = "let s1 = String::from(\"hello\");\nlet s2 = s1;\nprintln!(s1);\n";
+        assert_eq!(codes(source), ["E0382"]);
+    }
+
+    #[test]
+    fn uninitialized_e0381() {
+        assert_eq!(codes("let s: String;\nprintln!(s);\n"), ["E0381"]);
+    }
+
+    #[test]
+    fn two_mutable_borrows_e0499() {
+        let source = "let mut s = String::new();\nlet r1 = &mut s;\nlet r2 = &mut s;\nprintln!(r1, r2);\n";
+        assert_eq!(codes(source), ["E0499"]);
+    }
+
+    #[test]
+    fn mutable_and_shared_borrow_e0502() {
+        let source = "let mut s = String::new();\nlet r1 = &s;\nlet r2 = &mut s;\nprintln!(r1, r2);\n";
+        assert_eq!(codes(source), ["E0502"]);
+        let source = "let mut s = String::new();\nlet r1 = &mut s;\nprintln!(s);\nprintln!(r1);\n";
+        assert_eq!(codes(source), ["E0502"]);
+    }
+
+    #[test]
+    fn move_while_borrowed_e0505() {
+        let source = "let s = String::new();\nlet r = &s;\nlet t = s;\nprintln!(r);\n";
+        assert_eq!(codes(source), ["E0505"]);
+    }
+
+    #[test]
+    fn assign_while_borrowed_e0506() {
+        let source = "let mut s = String::new();\nlet r = &s;\ns = String::new();\nprintln!(r);\n";
+        assert_eq!(codes(source), ["E0506"]);
+    }
+
+    #[test]
+    fn dropped_while_borrowed_e0597() {
+        let source = "let r;\n{\n    let s = String::from(\"hi\");\n    r = &s;\n}\nprintln!(r);\n";
+        assert_eq!(codes(source), ["E0597"]);
+    }
+
+    #[test]
+    fn mutable_borrow_of_immutable_e0596() {
+        assert_eq!(codes("let s = String::new();\nlet r = &mut s;\n"), ["E0596"]);
+        let source = "fn change(s: &String) {\n    s.push_str(\"!\");\n}\n";
+        assert_eq!(codes(source), ["E0596"]);
+    }
+
+    #[test]
+    fn assign_twice_e0384() {
+        assert_eq!(codes("let x = 1;\nx = 2;\n"), ["E0384"]);
+    }
+
+    #[test]
+    fn return_local_reference_e0515() {
+        let source = "fn dangle(s: &String) -> &String {\n    let t = String::new();\n    &t\n}\n";
+        assert_eq!(codes(source), ["E0515"]);
+    }
+
+    #[test]
+    fn missing_lifetime_e0106() {
+        assert_eq!(codes("fn dangle() -> &String {\n    String::new()\n}\n")[0], "E0106");
+    }
+
+    #[test]
+    fn mismatched_types_e0308() {
+        assert_eq!(codes("let x: i32 = String::new();\n"), ["E0308"]);
+        assert_eq!(codes("fn five() -> i32 {\n}\n"), ["E0308"]);
+    }
+
+    #[test]
+    fn unknown_names_e0425() {
+        assert_eq!(codes("println!(nothing);\n"), ["E0425"]);
+        assert_eq!(codes("missing(1);\n"), ["E0425"]);
+    }
+
+    #[test]
+    fn wrong_argument_count_e0061() {
+        assert_eq!(codes("fn one(x: i32) {\n}\none(1, 2);\n"), ["E0061"]);
+    }
+
+    #[test]
+    fn unknown_method_e0599() {
+        assert_eq!(codes("let s = String::new();\ns.shout();\n"), ["E0599"]);
+    }
+
+    #[test]
+    fn duplicate_function_e0428() {
+        assert_eq!(codes("fn f() {\n}\nfn f() {\n}\n"), ["E0428"]);
+    }
+
+    #[test]
+    fn syntax_errors_stop_the_check() {
+        let outcome = check("let = 5;\n");
+        assert_eq!(codes("let = 5;\n"), ["syntax"]);
+        assert!(outcome.steps.is_empty());
+    }
+
+    /*
+     * Con `NLL` un préstamo termina en su último uso y no al final del `scope`
+     */
+    #[test]
+    fn borrow_ends_before_mutation() {
+        let source = "let mut s = String::from(\"hello\");\nlet r1 = &s;\nlet r2 = &s;\nprintln!(r1, r2);\nlet r3 = &mut s;\nprintln!(r3);\n";
+        assert!(check(source).is_ok(), "{:?}", codes(source));
+    }
+
+    #[test]
+    fn accepted_programs() {
+        let sources = [
+            "let mut s = String::new();\nlet r = &s;\nprintln!(r);\ns.push_str(\"x\");\n",
+            "let mut s = String::new();\nlet r = &s;\ns = String::from(\"new\");\nprintln!(s);\n",
+            "let s = String::new();\nlet r = &s;\nprintln!(r);\nlet t = s;\n",
+            "let x = 5;\nlet y = x;\nprintln!(x, y);\n",
+            "let s1 = String::from(\"a\");\nlet s2 = s1.clone();\nprintln!(s1, s2);\n",
+            "fn first(s: &String) -> &String {\n    s\n}\nlet s = String::new();\nlet f = first(&s);\nprintln!(f);\n",
+            "let s;\ns = String::new();\nprintln!(s);\n",
+        ];
+        for source in sources {
+            assert!(check(source).is_ok(), "{source}\n{:?}", codes(source));
+        }
+    }
+
+    #[test]
+    fn trace_reports_moves_and_drops() {
+        let source = "let s1 = String::from(\"hello\");\nlet s2 = s1;\n";
+        let trace = check(source).render_trace(source);
+        assert!(trace.contains("move `s1` -> `s2`"), "{trace}");
+        assert!(trace.contains("`s2` goes out of scope, `drop` frees its heap memory"), "{trace}");
+        assert!(trace.contains("`s1` goes out of scope, it was moved so nothing is freed"), "{trace}");
+    }
+}
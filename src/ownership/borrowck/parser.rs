@@ -0,0 +1,589 @@
+/*
+ * El `parser` construye el árbol de sintaxis `AST` del mini lenguaje con descenso recursivo, cada
+ * regla de la gramática es una función
+ *
+ *  program := (function | stmt)*
+ *  function := "fn" ident "(" params ")" ("->" type)? block
+ *  block := "{" stmt* expr? "}"
+ *  stmt := "let" "mut"? ident (":" type)? ("=" expr)? ";" | ident "=" expr ";" | expr ";"
+ *  expr := "&" "mut"? ident | int | string | block | ident "::" ident "(" args ")"
+ *        | ident "(" args ")" | ident "." ident "(" args ")" | ident
+ */
+use super::diagnostic::{Diagnostic, Span};
+use super::lexer::{Token, TokenKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ident {
+    pub name: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    Int,
+    Str,
+    String,
+    Ref { mutable: bool, inner: Box<Type> },
+}
+
+impl Type {
+    pub fn is_ref(&self) -> bool {
+        matches!(self, Type::Ref { .. })
+    }
+}
+
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Int => write!(f, "i32"),
+            Type::Str => write!(f, "str"),
+            Type::String => write!(f, "String"),
+            Type::Ref {
+                mutable: true,
+                inner,
+            } => write!(f, "&mut {inner}"),
+            Type::Ref {
+                mutable: false,
+                inner,
+            } => write!(f, "&{inner}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    pub name: Ident,
+    pub mutable: bool,
+    pub ty: Type,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Function {
+    pub name: Ident,
+    pub params: Vec<Param>,
+    pub ret: Option<(Type, Span)>,
+    pub body: Block,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub stmts: Vec<Stmt>,
+    /*
+     * La última expresión sin `;` es el valor del bloque
+     */
+    pub tail: Option<Box<Expr>>,
+    pub span: Span,
+}
+
+impl Block {
+    pub fn close_span(&self) -> Span {
+        Span::new(self.span.end - 1, self.span.end)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stmt {
+    Let {
+        name: Ident,
+        mutable: bool,
+        ty: Option<Type>,
+        init: Option<Expr>,
+        span: Span,
+    },
+    Assign {
+        target: Ident,
+        value: Expr,
+        span: Span,
+    },
+    Expr(Expr, Span),
+}
+
+impl Stmt {
+    pub fn span(&self) -> Span {
+        match self {
+            Stmt::Let { span, .. } | Stmt::Assign { span, .. } | Stmt::Expr(_, span) => *span,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprKind {
+    Int(i64),
+    Str(String),
+    /*
+     * `String::from("...")` y `String::new()` crean un `String` en el `heap`
+     */
+    NewString(String),
+    Var(Ident),
+    Borrow {
+        mutable: bool,
+        target: Ident,
+    },
+    Call {
+        name: Ident,
+        args: Vec<Expr>,
+    },
+    Method {
+        receiver: Ident,
+        method: Ident,
+        args: Vec<Expr>,
+    },
+    Block(Block),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Program {
+    pub functions: Vec<Function>,
+    /*
+     * Las instrucciones fuera de una función forman un `main` implícito
+     */
+    pub body: Vec<Stmt>,
+    pub body_span: Span,
+}
+
+struct Parser<'t> {
+    tokens: &'t [Token],
+    pos: usize,
+}
+
+pub fn parse(tokens: &[Token]) -> Result<Program, Diagnostic> {
+    let mut parser = Parser { tokens, pos: 0 };
+    let mut program = Program::default();
+    while !parser.at(&TokenKind::Eof) {
+        if parser.at(&TokenKind::Fn) {
+            program.functions.push(parser.function()?);
+        } else {
+            let stmt = parser.stmt()?;
+            program.body_span = if program.body.is_empty() {
+                stmt.span()
+            } else {
+                program.body_span.to(stmt.span())
+            };
+            program.body.push(stmt);
+        }
+    }
+    Ok(program)
+}
+
+impl<'t> Parser<'t> {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos.min(self.tokens.len() - 1)]
+    }
+
+    fn peek_at(&self, offset: usize) -> &TokenKind {
+        &self.tokens[(self.pos + offset).min(self.tokens.len() - 1)].kind
+    }
+
+    fn at(&self, kind: &TokenKind) -> bool {
+        &self.peek().kind == kind
+    }
+
+    fn bump(&mut self) -> Token {
+        let token = self.peek().clone();
+        if token.kind != TokenKind::Eof {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, kind: &TokenKind) -> bool {
+        if self.at(kind) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, kind: TokenKind) -> Result<Span, Diagnostic> {
+        if self.at(&kind) {
+            Ok(self.bump().span)
+        } else {
+            let found = self.peek();
+            Err(Diagnostic::syntax(
+                format!(
+                    "expected {}, found {}",
+                    kind.describe(),
+                    found.kind.describe()
+                ),
+                found.span,
+            ))
+        }
+    }
+
+    fn ident(&mut self) -> Result<Ident, Diagnostic> {
+        let token = self.bump();
+        match token.kind {
+            TokenKind::Ident(name) => Ok(Ident {
+                name,
+                span: token.span,
+            }),
+            other => Err(Diagnostic::syntax(
+                format!("expected identifier, found {}", other.describe()),
+                token.span,
+            )),
+        }
+    }
+
+    fn ty(&mut self) -> Result<Type, Diagnostic> {
+        if self.eat(&TokenKind::Amp) {
+            let mutable = self.eat(&TokenKind::Mut);
+            let inner = Box::new(self.ty()?);
+            return Ok(Type::Ref { mutable, inner });
+        }
+        let name = self.ident()?;
+        match name.name.as_str() {
+            "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "usize" | "isize" => {
+                Ok(Type::Int)
+            }
+            "str" => Ok(Type::Str),
+            "String" => Ok(Type::String),
+            other => Err(Diagnostic::error(
+                "E0412",
+                format!("cannot find type `{other}` in this scope"),
+            )
+            .primary(name.span, "not found in this scope")),
+        }
+    }
+
+    fn function(&mut self) -> Result<Function, Diagnostic> {
+        self.expect(TokenKind::Fn)?;
+        let name = self.ident()?;
+        self.expect(TokenKind::LParen)?;
+        let mut params = Vec::new();
+        while !self.at(&TokenKind::RParen) {
+            let mutable = self.eat(&TokenKind::Mut);
+            let name = self.ident()?;
+            self.expect(TokenKind::Colon)?;
+            let ty = self.ty()?;
+            params.push(Param { name, mutable, ty });
+            if !self.eat(&TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(TokenKind::RParen)?;
+        let ret = if self.eat(&TokenKind::Arrow) {
+            let start = self.peek().span;
+            let ty = self.ty()?;
+            let end = self.tokens[self.pos - 1].span;
+            Some((ty, start.to(end)))
+        } else {
+            None
+        };
+        let body = self.block()?;
+        Ok(Function {
+            name,
+            params,
+            ret,
+            body,
+        })
+    }
+
+    fn block(&mut self) -> Result<Block, Diagnostic> {
+        let open = self.expect(TokenKind::LBrace)?;
+        let mut stmts = Vec::new();
+        let mut tail = None;
+        while !self.at(&TokenKind::RBrace) {
+            if self.at(&TokenKind::Eof) {
+                return Err(Diagnostic::syntax(
+                    "this file contains an unclosed delimiter",
+                    open,
+                ));
+            }
+            if self.at(&TokenKind::Let) || self.is_assignment() {
+                stmts.push(self.stmt()?);
+                continue;
+            }
+            let expr = self.expr()?;
+            if self.at(&TokenKind::RBrace) {
+                tail = Some(Box::new(expr));
+            } else if let ExprKind::Block(_) = expr.kind {
+                let span = expr.span;
+                self.eat(&TokenKind::Semi);
+                stmts.push(Stmt::Expr(expr, span));
+            } else {
+                let semi = self.expect(TokenKind::Semi)?;
+                let span = expr.span.to(semi);
+                stmts.push(Stmt::Expr(expr, span));
+            }
+        }
+        let close = self.expect(TokenKind::RBrace)?;
+        Ok(Block {
+            stmts,
+            tail,
+            span: open.to(close),
+        })
+    }
+
+    fn is_assignment(&self) -> bool {
+        matches!(self.peek_at(0), TokenKind::Ident(_)) && self.peek_at(1) == &TokenKind::Eq
+    }
+
+    fn stmt(&mut self) -> Result<Stmt, Diagnostic> {
+        let start = self.peek().span;
+        if self.eat(&TokenKind::Let) {
+            let mutable = self.eat(&TokenKind::Mut);
+            let name = self.ident()?;
+            let ty = if self.eat(&TokenKind::Colon) {
+                Some(self.ty()?)
+            } else {
+                None
+            };
+            let init = if self.eat(&TokenKind::Eq) {
+                Some(self.expr()?)
+            } else {
+                None
+            };
+            let end = self.expect(TokenKind::Semi)?;
+            return Ok(Stmt::Let {
+                name,
+                mutable,
+                ty,
+                init,
+                span: start.to(end),
+            });
+        }
+        if self.is_assignment() {
+            let target = self.ident()?;
+            self.expect(TokenKind::Eq)?;
+            let value = self.expr()?;
+            let end = self.expect(TokenKind::Semi)?;
+            return Ok(Stmt::Assign {
+                target,
+                value,
+                span: start.to(end),
+            });
+        }
+        let expr = self.expr()?;
+        if let ExprKind::Block(_) = expr.kind {
+            let span = expr.span;
+            self.eat(&TokenKind::Semi);
+            return Ok(Stmt::Expr(expr, span));
+        }
+        let end = self.expect(TokenKind::Semi)?;
+        Ok(Stmt::Expr(expr, start.to(end)))
+    }
+
+    fn args(&mut self) -> Result<(Vec<Expr>, Span), Diagnostic> {
+        self.expect(TokenKind::LParen)?;
+        let mut args = Vec::new();
+        while !self.at(&TokenKind::RParen) {
+            args.push(self.expr()?);
+            if !self.eat(&TokenKind::Comma) {
+                break;
+            }
+        }
+        let close = self.expect(TokenKind::RParen)?;
+        Ok((args, close))
+    }
+
+    fn expr(&mut self) -> Result<Expr, Diagnostic> {
+        let token = self.peek().clone();
+        match token.kind {
+            TokenKind::Amp => {
+                self.bump();
+                let mutable = self.eat(&TokenKind::Mut);
+                let target = self.ident()?;
+                Ok(Expr {
+                    span: token.span.to(target.span),
+                    kind: ExprKind::Borrow { mutable, target },
+                })
+            }
+            TokenKind::Int(value) => {
+                self.bump();
+                Ok(Expr {
+                    kind: ExprKind::Int(value),
+                    span: token.span,
+                })
+            }
+            TokenKind::Str(text) => {
+                self.bump();
+                Ok(Expr {
+                    kind: ExprKind::Str(text),
+                    span: token.span,
+                })
+            }
+            TokenKind::LBrace => {
+                let block = self.block()?;
+                Ok(Expr {
+                    span: block.span,
+                    kind: ExprKind::Block(block),
+                })
+            }
+            TokenKind::Ident(_) => self.path_expr(),
+            other => Err(Diagnostic::syntax(
+                format!("expected expression, found {}", other.describe()),
+                token.span,
+            )),
+        }
+    }
+
+    fn path_expr(&mut self) -> Result<Expr, Diagnostic> {
+        let name = self.ident()?;
+        if self.eat(&TokenKind::PathSep) {
+            let item = self.ident()?;
+            let (args, close) = self.args()?;
+            let span = name.span.to(close);
+            return match (name.name.as_str(), item.name.as_str(), args.as_slice()) {
+                (
+                    "String",
+                    "from",
+                    [Expr {
+                        kind: ExprKind::Str(text),
+                        ..
+                    }],
+                ) => Ok(Expr {
+                    kind: ExprKind::NewString(text.clone()),
+                    span,
+                }),
+                ("String", "new", []) => Ok(Expr {
+                    kind: ExprKind::NewString(String::new()),
+                    span,
+                }),
+                _ => Err(Diagnostic::error(
+                    "E0433",
+                    format!(
+                        "failed to resolve: `{}::{}` is not supported",
+                        name.name, item.name
+                    ),
+                )
+                .primary(
+                    span,
+                    "only `String::from(\"...\")` and `String::new()` are supported",
+                )),
+            };
+        }
+        if self.at(&TokenKind::LParen) {
+            let (args, close) = self.args()?;
+            return Ok(Expr {
+                span: name.span.to(close),
+                kind: ExprKind::Call { name, args },
+            });
+        }
+        if self.eat(&TokenKind::Dot) {
+            let method = self.ident()?;
+            let (args, close) = self.args()?;
+            return Ok(Expr {
+                span: name.span.to(close),
+                kind: ExprKind::Method {
+                    receiver: name,
+                    method,
+                    args,
+                },
+            });
+        }
+        Ok(Expr {
+            span: name.span,
+            kind: ExprKind::Var(name),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ownership::borrowck::lexer::lex;
+
+    fn parse_source(source: &str) -> Result<Program, Diagnostic> {
+        parse(&lex(source)?)
+    }
+
+    /*
+     * El mensaje y el texto que señala el error
+     */
+    fn error(source: &str) -> (String, &str) {
+        let error = parse_source(source).unwrap_err();
+        let span = error.primary_span();
+        (error.message, &source[span.start..span.end])
+    }
+
+    #[test]
+    fn functions_blocks_and_tails() {
+        let source = "fn first(mut s: &String, n: i32) -> &str {\n    let x;\n    { n }\n    s\n}\n\
+                      let r = &mut v;\nv = String::from(\"a\");\n";
+        let program = parse_source(source).unwrap();
+        let function = &program.functions[0];
+        assert_eq!(function.name.name, "first");
+        assert_eq!(
+            function.params.iter().map(|param| param.mutable).collect::<Vec<_>>(),
+            [true, false]
+        );
+        assert_eq!(function.params[0].ty.to_string(), "&String");
+        let (ret, span) = function.ret.as_ref().unwrap();
+        assert_eq!((ret.to_string(), &source[span.start..span.end]), ("&str".to_string(), "&str"));
+        /*
+         * Un bloque como instrucción no necesita `;`, la última expresión es la cola
+         */
+        assert_eq!(function.body.stmts.len(), 2);
+        assert!(matches!(
+            function.body.tail.as_deref(),
+            Some(Expr { kind: ExprKind::Var(Ident { name, .. }), .. }) if name == "s"
+        ));
+        assert_eq!(&source[function.body.close_span().start..][..1], "}");
+
+        assert_eq!(program.body.len(), 2);
+        assert!(matches!(
+            &program.body[0],
+            Stmt::Let {
+                init: Some(Expr { kind: ExprKind::Borrow { mutable: true, .. }, .. }),
+                ..
+            }
+        ));
+        assert!(matches!(
+            &program.body[1],
+            Stmt::Assign { value: Expr { kind: ExprKind::NewString(text), .. }, .. } if text == "a"
+        ));
+        let body = &source[program.body_span.start..program.body_span.end];
+        assert_eq!(body, "let r = &mut v;\nv = String::from(\"a\");");
+    }
+
+    #[test]
+    fn unterminated_block_points_at_the_open_brace() {
+        let source = "fn f() {\n    let x = 1;\n    {\n        x\n    }\n";
+        assert_eq!(
+            error(source),
+            ("this file contains an unclosed delimiter".to_string(), "{")
+        );
+        assert_eq!(parse_source(source).unwrap_err().primary_span().start, 7);
+        /*
+         * El bloque interior está cerrado, el que falta es el de la función
+         */
+        assert_eq!(error("{ {\n}").1, "{");
+        assert_eq!(parse_source("{ {\n}").unwrap_err().primary_span().start, 0);
+        assert_eq!(error("fn f(").0, "expected identifier, found end of input");
+    }
+
+    #[test]
+    fn unexpected_tokens() {
+        assert_eq!(error("let x = ;"), ("expected expression, found `;`".to_string(), ";"));
+        assert_eq!(error("let 5 = x;"), ("expected identifier, found `5`".to_string(), "5"));
+        assert_eq!(error("let x = 1\nlet y = 2;").0, "expected `;`, found `let`");
+        assert_eq!(error("fn f) {}"), ("expected `(`, found `)`".to_string(), ")"));
+        assert_eq!(error("f(1 2);"), ("expected `)`, found `2`".to_string(), "2"));
+        assert_eq!(error("s.;").0, "expected identifier, found `;`");
+        /*
+         * Al final del programa el error señala la posición después del último `token`
+         */
+        let (message, text) = error("let x = 1");
+        assert_eq!((message.as_str(), text), ("expected `;`, found end of input", ""));
+    }
+
+    #[test]
+    fn unsupported_types_and_paths() {
+        let error = parse_source("fn f(x: Vec) {}").unwrap_err();
+        assert_eq!(error.code, Some("E0412"));
+        assert_eq!(error.message, "cannot find type `Vec` in this scope");
+        assert_eq!(error.primary_span(), Span::new(8, 11));
+        let error = parse_source("let v = Vec::new();").unwrap_err();
+        assert_eq!(error.code, Some("E0433"));
+        assert_eq!(error.message, "failed to resolve: `Vec::new` is not supported");
+        let error = parse_source("let s = String::from(1);").unwrap_err();
+        assert_eq!(error.message, "failed to resolve: `String::from` is not supported");
+    }
+}
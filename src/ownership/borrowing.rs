@@ -4,7 +4,7 @@
  * Una referencia permite que multiples partes del código puedan acceder al recurso sin la necesidad
  * de tener la propiedad
  */
-fn run() {
+pub fn run() {
     let greeting = String::from("Hi!");
     let (g_simple, transform) = takes_and_back_ownwership(greeting); // -> El valor de `greeting` se mueve dentro de la función
     /*
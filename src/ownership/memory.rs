@@ -0,0 +1,1070 @@
+/*
+ * Un simulador de la memoria de un programa, las variables viven en el `stack` y los datos de un
+ * `String`, un `Vec` o un `Box` viven en un bloque del `heap`. En el `stack` un `String` o un `Vec`
+ * solo guarda tres números, el puntero `ptr` al bloque, la longitud `len` y la capacidad `cap`
+ *
+ *                   STACK                                     HEAP
+ *     first                       names
+ *  +---------+                  +---------+                  +-------+
+ *  |   ptr   | ---------------> |   ptr   | ---------------> | 0 | 1 |
+ *  +---------+                  +---------+                  | 1 | 2 |
+ *
+ * Los diagramas de las lecciones se dibujan a partir del estado de la simulación, en texto `ASCII`
+ * o en `SVG`, cuando un bloque se libera las referencias que apuntaban a él quedan colgando
+ * `dangling` y se dibujan con una `x`
+ */
+use std::fmt;
+
+const STACK_BASE: usize = 0x7ffc_0000;
+const HEAP_BASE: usize = 0x5600_1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemoryError {
+    UnknownVariable(String),
+    Moved(String),
+    /*
+     * La operación no existe para el tipo de la variable, como `.push()` sobre un número
+     */
+    Unsupported {
+        name: String,
+        operation: &'static str,
+    },
+    OutOfBounds {
+        name: String,
+        index: usize,
+        len: usize,
+    },
+}
+
+impl fmt::Display for MemoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MemoryError::UnknownVariable(name) => write!(f, "there is no variable `{name}`"),
+            MemoryError::Moved(name) => write!(f, "`{name}` was moved or dropped"),
+            MemoryError::Unsupported { name, operation } => {
+                write!(f, "`{name}` does not support `{operation}`")
+            }
+            MemoryError::OutOfBounds { name, index, len } => {
+                write!(
+                    f,
+                    "index {index} is out of bounds for `{name}` with len {len}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for MemoryError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Var(usize),
+    Element { block: usize, index: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Scalar(String),
+    String {
+        block: usize,
+        len: usize,
+        cap: usize,
+    },
+    Vec {
+        block: usize,
+        len: usize,
+        cap: usize,
+    },
+    Box {
+        block: usize,
+    },
+    Ref {
+        target: Target,
+        mutable: bool,
+    },
+    Moved,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Var {
+    pub name: String,
+    pub addr: usize,
+    pub value: Value,
+    pub dropped: bool,
+}
+
+/*
+ * Un bloque del `heap`, las celdas después de `len` existen pero no están inicializadas
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub addr: usize,
+    pub cells: Vec<Option<String>>,
+    pub elem_size: usize,
+    pub freed: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Memory {
+    vars: Vec<Var>,
+    blocks: Vec<Block>,
+    next_heap: usize,
+}
+
+/*
+ * Los `bytes` de un `String` que no son `ASCII` se muestran en hexadecimal, igual que en la
+ * lección de `UTF-8`
+ */
+fn byte_cells(text: &str) -> Vec<Option<String>> {
+    text.bytes()
+        .map(|byte| {
+            Some(if byte.is_ascii_graphic() || byte == b' ' {
+                format!("'{}'", byte as char)
+            } else {
+                format!("{byte:#04X}")
+            })
+        })
+        .collect()
+}
+
+impl Memory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn vars(&self) -> &[Var] {
+        &self.vars
+    }
+
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    fn alloc(&mut self, mut cells: Vec<Option<String>>, cap: usize, elem_size: usize) -> usize {
+        cells.resize(cap.max(cells.len()), None);
+        let addr = HEAP_BASE + self.next_heap;
+        self.next_heap += 0x100.max((cells.len() * elem_size).next_power_of_two());
+        self.blocks.push(Block {
+            addr,
+            cells,
+            elem_size,
+            freed: false,
+        });
+        self.blocks.len() - 1
+    }
+
+    fn declare(&mut self, name: &str, value: Value) -> usize {
+        let addr = STACK_BASE - 0x18 * (self.vars.len() + 1);
+        self.vars.push(Var {
+            name: name.to_string(),
+            addr,
+            value,
+            dropped: false,
+        });
+        self.vars.len() - 1
+    }
+
+    /*
+     * Las variables pueden reutilizar un nombre `shadowing`, se busca la última declarada
+     */
+    fn find(&self, name: &str) -> Result<usize, MemoryError> {
+        let id = self
+            .vars
+            .iter()
+            .rposition(|var| var.name == name)
+            .ok_or_else(|| MemoryError::UnknownVariable(name.to_string()))?;
+        if self.vars[id].dropped || self.vars[id].value == Value::Moved {
+            return Err(MemoryError::Moved(name.to_string()));
+        }
+        Ok(id)
+    }
+
+    pub fn scalar(&mut self, name: &str, value: impl fmt::Display) {
+        self.declare(name, Value::Scalar(value.to_string()));
+    }
+
+    /*
+     * `String::from()` reserva exactamente los `bytes` del texto
+     */
+    pub fn string(&mut self, name: &str, text: &str) {
+        let block = self.alloc(byte_cells(text), text.len(), 1);
+        self.declare(
+            name,
+            Value::String {
+                block,
+                len: text.len(),
+                cap: text.len(),
+            },
+        );
+    }
+
+    pub fn vec<T: fmt::Display>(&mut self, name: &str, items: &[T]) {
+        self.vec_with_capacity(name, items, items.len());
+    }
+
+    pub fn vec_with_capacity<T: fmt::Display>(&mut self, name: &str, items: &[T], cap: usize) {
+        let cells = items.iter().map(|item| Some(item.to_string())).collect();
+        let block = self.alloc(cells, cap, std::mem::size_of::<T>());
+        self.declare(
+            name,
+            Value::Vec {
+                block,
+                len: items.len(),
+                cap: cap.max(items.len()),
+            },
+        );
+    }
+
+    /*
+     * Copia el estado real de un `Vec`, su dirección, su longitud y su capacidad
+     */
+    pub fn track_vec<T: fmt::Display>(&mut self, name: &str, vec: &Vec<T>) {
+        self.vec_with_capacity(name, vec, vec.capacity());
+        self.set_real_addr(vec.as_ptr() as usize);
+    }
+
+    pub fn track_string(&mut self, name: &str, text: &String) {
+        let block = self.alloc(byte_cells(text), text.capacity(), 1);
+        self.declare(
+            name,
+            Value::String {
+                block,
+                len: text.len(),
+                cap: text.capacity(),
+            },
+        );
+        self.set_real_addr(text.as_ptr() as usize);
+    }
+
+    fn set_real_addr(&mut self, addr: usize) {
+        if let Some(block) = self.blocks.last_mut() {
+            block.addr = addr;
+        }
+    }
+
+    pub fn boxed<T: fmt::Display>(&mut self, name: &str, value: T) {
+        let block = self.alloc(vec![Some(value.to_string())], 1, std::mem::size_of::<T>());
+        self.declare(name, Value::Box { block });
+    }
+
+    pub fn borrow(&mut self, name: &str, target: &str, mutable: bool) -> Result<(), MemoryError> {
+        let target = Target::Var(self.find(target)?);
+        self.declare(name, Value::Ref { target, mutable });
+        Ok(())
+    }
+
+    /*
+     * Una referencia a un elemento, como `&names[0]`, apunta directo al bloque del `heap`
+     */
+    pub fn borrow_element(
+        &mut self,
+        name: &str,
+        target: &str,
+        index: usize,
+    ) -> Result<(), MemoryError> {
+        let id = self.find(target)?;
+        let (block, len) = match self.vars[id].value {
+            Value::Vec { block, len, .. } | Value::String { block, len, .. } => (block, len),
+            Value::Box { block } => (block, 1),
+            _ => {
+                return Err(MemoryError::Unsupported {
+                    name: target.to_string(),
+                    operation: "index",
+                })
+            }
+        };
+        if index >= len {
+            return Err(MemoryError::OutOfBounds {
+                name: target.to_string(),
+                index,
+                len,
+            });
+        }
+        self.declare(
+            name,
+            Value::Ref {
+                target: Target::Element { block, index },
+                mutable: false,
+            },
+        );
+        Ok(())
+    }
+
+    /*
+     * Cuando ya no hay espacio, `Rust` reserva un bloque nuevo con el doble de capacidad, copia los
+     * elementos y libera el bloque anterior, las referencias al bloque anterior quedan colgando
+     */
+    fn reserve(&mut self, id: usize, additional: usize, min_cap: usize) {
+        let (block, len, cap) = match self.vars[id].value {
+            Value::Vec { block, len, cap } | Value::String { block, len, cap } => (block, len, cap),
+            _ => return,
+        };
+        if len + additional <= cap {
+            return;
+        }
+        let new_cap = (cap * 2).max(len + additional).max(min_cap);
+        let cells = self.blocks[block].cells[..len].to_vec();
+        let elem_size = self.blocks[block].elem_size;
+        self.blocks[block].freed = true;
+        let new_block = self.alloc(cells, new_cap, elem_size);
+        match &mut self.vars[id].value {
+            Value::Vec { block, cap, .. } | Value::String { block, cap, .. } => {
+                *block = new_block;
+                *cap = new_cap;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn push(&mut self, name: &str, value: impl fmt::Display) -> Result<(), MemoryError> {
+        let id = self.find(name)?;
+        if !matches!(self.vars[id].value, Value::Vec { .. }) {
+            return Err(MemoryError::Unsupported {
+                name: name.to_string(),
+                operation: "push",
+            });
+        }
+        self.reserve(id, 1, 4);
+        if let Value::Vec { block, len, .. } = &mut self.vars[id].value {
+            self.blocks[*block].cells[*len] = Some(value.to_string());
+            *len += 1;
+        }
+        Ok(())
+    }
+
+    pub fn push_str(&mut self, name: &str, text: &str) -> Result<(), MemoryError> {
+        let id = self.find(name)?;
+        if !matches!(self.vars[id].value, Value::String { .. }) {
+            return Err(MemoryError::Unsupported {
+                name: name.to_string(),
+                operation: "push_str",
+            });
+        }
+        self.reserve(id, text.len(), 8);
+        if let Value::String { block, len, .. } = &mut self.vars[id].value {
+            for (offset, cell) in byte_cells(text).into_iter().enumerate() {
+                self.blocks[*block].cells[*len + offset] = cell;
+            }
+            *len += text.len();
+        }
+        Ok(())
+    }
+
+    /*
+     * `let to = from;` copia los tres números del `stack`, el bloque del `heap` no se copia y
+     * `from` deja de ser válida
+     */
+    pub fn move_to(&mut self, from: &str, to: &str) -> Result<(), MemoryError> {
+        let id = self.find(from)?;
+        let value = std::mem::replace(&mut self.vars[id].value, Value::Moved);
+        self.declare(to, value);
+        Ok(())
+    }
+
+    pub fn drop_var(&mut self, name: &str) -> Result<(), MemoryError> {
+        let id = self.find(name)?;
+        if let Value::String { block, .. } | Value::Vec { block, .. } | Value::Box { block } =
+            self.vars[id].value
+        {
+            self.blocks[block].freed = true;
+        }
+        self.vars[id].dropped = true;
+        Ok(())
+    }
+
+    fn target_is_dead(&self, target: Target) -> bool {
+        match target {
+            Target::Var(id) => self.vars[id].dropped || self.vars[id].value == Value::Moved,
+            Target::Element { block, .. } => self.blocks[block].freed,
+        }
+    }
+
+    /*
+     * Las referencias que apuntan a memoria liberada, en `Rust` el `borrow checker` no permite
+     * que existan
+     */
+    pub fn dangling(&self) -> Vec<&str> {
+        self.vars
+            .iter()
+            .filter(|var| !var.dropped)
+            .filter_map(|var| match var.value {
+                Value::Ref { target, .. } if self.target_is_dead(target) => Some(var.name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn target_addr(&self, target: Target) -> usize {
+        match target {
+            Target::Var(id) => self.vars[id].addr,
+            Target::Element { block, index } => {
+                self.blocks[block].addr + index * self.blocks[block].elem_size
+            }
+        }
+    }
+
+    fn rows(&self, var: &Var) -> Vec<(String, String)> {
+        let ptr = |block: usize| format!("{:#x}", self.blocks[block].addr);
+        match &var.value {
+            Value::Scalar(value) => vec![("val".to_string(), value.clone())],
+            Value::String { block, len, cap } | Value::Vec { block, len, cap } => vec![
+                ("ptr".to_string(), ptr(*block)),
+                ("len".to_string(), len.to_string()),
+                ("cap".to_string(), cap.to_string()),
+            ],
+            Value::Box { block } => vec![("ptr".to_string(), ptr(*block))],
+            Value::Ref { target, .. } => {
+                vec![(
+                    "ptr".to_string(),
+                    format!("{:#x}", self.target_addr(*target)),
+                )]
+            }
+            Value::Moved => vec![("-".to_string(), "moved".to_string())],
+        }
+    }
+
+    fn pointee(&self, var: &Var) -> Option<(Target, bool)> {
+        match var.value {
+            Value::String { block, .. } | Value::Vec { block, .. } | Value::Box { block } => {
+                Some((Target::Element { block, index: 0 }, false))
+            }
+            Value::Ref { target, .. } => Some((target, self.target_is_dead(target))),
+            _ => None,
+        }
+    }
+
+    /*
+     * Los elementos del diagrama se acomodan en una cuadrícula de caracteres, el `stack` a la
+     * izquierda, el `heap` a la derecha y entre ellos un carril vertical para cada flecha
+     */
+    fn layout(&self) -> Layout {
+        let arrows_to = |target: Target| {
+            self.vars.iter().filter(|var| !var.dropped).any(|var| {
+                self.pointee(var)
+                    .is_some_and(|(to, _)| same_place(to, target))
+            })
+        };
+        let visible_vars: Vec<usize> = (0..self.vars.len())
+            .filter(|&id| !self.vars[id].dropped || arrows_to(Target::Var(id)))
+            .collect();
+        let visible_blocks: Vec<usize> = (0..self.blocks.len())
+            .filter(|&block| {
+                !self.blocks[block].freed || arrows_to(Target::Element { block, index: 0 })
+            })
+            .collect();
+
+        let stack_rows: Vec<Vec<(String, String)>> = visible_vars
+            .iter()
+            .map(|&id| self.rows(&self.vars[id]))
+            .collect();
+        let stack_inner = column_widths(
+            stack_rows.iter().flatten(),
+            visible_vars.iter().map(|&id| self.vars[id].name.as_str()),
+        );
+        let stack_width = stack_inner.0 + stack_inner.1 + 8;
+
+        let mut boxes = Vec::new();
+        let mut var_rows = vec![0; self.vars.len()];
+        let mut y = 2;
+        for (&id, rows) in visible_vars.iter().zip(stack_rows) {
+            let var = &self.vars[id];
+            var_rows[id] = y + 2;
+            let height = rows.len();
+            boxes.push(Shape {
+                x: 1,
+                y,
+                columns: stack_inner,
+                title: var.name.clone(),
+                rows,
+                dead: var.dropped,
+            });
+            y += height + 4;
+        }
+        let stack_height = y;
+
+        let mut arrows = Vec::new();
+        for &id in &visible_vars {
+            let var = &self.vars[id];
+            if var.dropped {
+                continue;
+            }
+            if let Some((target, dangling)) = self.pointee(var) {
+                arrows.push((var_rows[id], target, dangling));
+            }
+        }
+        /*
+         * Las flechas entre variables del `stack` usan los carriles más cercanos
+         */
+        arrows.sort_by_key(|(_, target, _)| matches!(target, Target::Element { .. }));
+        let lanes = arrows.len();
+        let heap_x = stack_width + 2 * lanes + 8;
+
+        let heap_rows: Vec<Vec<(String, String)>> = visible_blocks
+            .iter()
+            .map(|&block| {
+                self.blocks[block]
+                    .cells
+                    .iter()
+                    .enumerate()
+                    .map(|(index, cell)| {
+                        (
+                            index.to_string(),
+                            cell.clone().unwrap_or_else(|| "uninit".to_string()),
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+        let heap_titles: Vec<String> = visible_blocks
+            .iter()
+            .map(|&block| {
+                let block = &self.blocks[block];
+                if block.freed {
+                    format!("{:#x} freed", block.addr)
+                } else {
+                    format!("{:#x}", block.addr)
+                }
+            })
+            .collect();
+        let heap_inner = column_widths(
+            heap_rows.iter().flatten(),
+            heap_titles.iter().map(String::as_str),
+        );
+        let heap_width = heap_inner.0 + heap_inner.1 + 7;
+
+        /*
+         * Cada bloque se coloca a la altura de la primera variable que apunta a él, si todavía
+         * hay espacio, para que la flecha sea recta
+         */
+        let mut order = visible_blocks.clone();
+        let first_source = |block: usize| {
+            arrows
+                .iter()
+                .filter(|(_, target, _)| matches!(target, Target::Element { block: b, .. } if *b == block))
+                .map(|(row, _, _)| *row)
+                .min()
+                .unwrap_or(usize::MAX)
+        };
+        order.sort_by_key(|&block| first_source(block));
+        let mut block_rows = vec![0; self.blocks.len()];
+        let mut y = 2;
+        for &block in &order {
+            let index = visible_blocks.iter().position(|&b| b == block).unwrap_or(0);
+            let source = first_source(block);
+            if source != usize::MAX {
+                y = y.max(source.saturating_sub(2));
+            }
+            block_rows[block] = y + 2;
+            let rows = heap_rows[index].clone();
+            let height = rows.len();
+            boxes.push(Shape {
+                x: heap_x,
+                y,
+                columns: heap_inner,
+                title: heap_titles[index].clone(),
+                rows,
+                dead: self.blocks[block].freed,
+            });
+            y += height + 4;
+        }
+        let height = stack_height.max(y);
+
+        let arrows = arrows
+            .into_iter()
+            .enumerate()
+            .map(|(lane, (from, target, dangling))| {
+                let lane_x = stack_width + 4 + 2 * lane;
+                let (to, end_x, head) = match target {
+                    Target::Var(id) => (var_rows[id], stack_width + 1, '<'),
+                    Target::Element { block, index } => {
+                        (block_rows[block] + index, heap_x - 2, '>')
+                    }
+                };
+                let points = if from == to && head == '>' {
+                    vec![(stack_width + 1, from), (end_x, to)]
+                } else {
+                    vec![
+                        (stack_width + 1, from),
+                        (lane_x, from),
+                        (lane_x, to),
+                        (end_x, to),
+                    ]
+                };
+                Arrow {
+                    points,
+                    head,
+                    dangling,
+                }
+            })
+            .collect();
+
+        Layout {
+            width: heap_x + heap_width + 2,
+            height,
+            heap_x,
+            boxes,
+            arrows,
+        }
+    }
+
+    pub fn to_ascii(&self) -> String {
+        self.layout().to_ascii()
+    }
+
+    pub fn to_svg(&self) -> String {
+        self.layout().to_svg()
+    }
+}
+
+impl fmt::Display for Memory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_ascii())
+    }
+}
+
+fn same_place(a: Target, b: Target) -> bool {
+    match (a, b) {
+        (Target::Var(a), Target::Var(b)) => a == b,
+        (Target::Element { block: a, .. }, Target::Element { block: b, .. }) => a == b,
+        _ => false,
+    }
+}
+
+/*
+ * El ancho de la columna de etiquetas y de valores, el título también debe caber sobre la caja
+ */
+fn column_widths<'a>(
+    rows: impl Iterator<Item = &'a (String, String)>,
+    titles: impl Iterator<Item = &'a str>,
+) -> (usize, usize) {
+    let (mut label, mut value) = (1, 1);
+    for (l, v) in rows {
+        label = label.max(l.chars().count());
+        value = value.max(v.chars().count());
+    }
+    let title = titles.map(|title| title.chars().count()).max().unwrap_or(0);
+    if label + value + 7 < title {
+        value = title - label - 7;
+    }
+    (label, value)
+}
+
+struct Shape {
+    x: usize,
+    y: usize,
+    columns: (usize, usize),
+    title: String,
+    rows: Vec<(String, String)>,
+    dead: bool,
+}
+
+impl Shape {
+    fn width(&self) -> usize {
+        self.columns.0 + self.columns.1 + 7
+    }
+}
+
+struct Arrow {
+    points: Vec<(usize, usize)>,
+    head: char,
+    dangling: bool,
+}
+
+struct Layout {
+    width: usize,
+    height: usize,
+    heap_x: usize,
+    boxes: Vec<Shape>,
+    arrows: Vec<Arrow>,
+}
+
+struct Canvas {
+    cells: Vec<Vec<char>>,
+}
+
+impl Canvas {
+    fn put(&mut self, x: usize, y: usize, c: char) {
+        if let Some(cell) = self.cells.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *cell = c;
+        }
+    }
+
+    fn get(&self, x: usize, y: usize) -> char {
+        self.cells
+            .get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .unwrap_or(' ')
+    }
+
+    fn text(&mut self, x: usize, y: usize, text: &str) {
+        for (offset, c) in text.chars().enumerate() {
+            self.put(x + offset, y, c);
+        }
+    }
+
+    /*
+     * Cuando dos flechas se cruzan se dibuja un `+`
+     */
+    fn line(&mut self, x: usize, y: usize, c: char) {
+        let crossing = matches!((self.get(x, y), c), ('|', '-') | ('-', '|') | ('+', _));
+        self.put(x, y, if crossing { '+' } else { c });
+    }
+}
+
+impl Layout {
+    fn to_ascii(&self) -> String {
+        let mut canvas = Canvas {
+            cells: vec![vec![' '; self.width]; self.height],
+        };
+        canvas.text(0, 0, "STACK");
+        canvas.text(self.heap_x, 0, "HEAP");
+        for shape in &self.boxes {
+            let (label, value) = shape.columns;
+            let border = format!("+{}+{}+", "-".repeat(label + 2), "-".repeat(value + 2));
+            canvas.text(shape.x, shape.y, &shape.title);
+            canvas.text(shape.x, shape.y + 1, &border);
+            for (row, (l, v)) in shape.rows.iter().enumerate() {
+                let y = shape.y + 2 + row;
+                canvas.text(shape.x, y, &format!("| {l:<label$} | {v:>value$} |"));
+                /*
+                 * La memoria liberada se encierra entre paréntesis como en la lección de `Vec`
+                 */
+                if shape.dead {
+                    canvas.put(shape.x.wrapping_sub(1), y, '(');
+                    canvas.put(shape.x + shape.width(), y, ')');
+                }
+            }
+            canvas.text(shape.x, shape.y + 2 + shape.rows.len(), &border);
+        }
+        for arrow in &self.arrows {
+            for pair in arrow.points.windows(2) {
+                let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+                if y0 == y1 {
+                    for x in x0.min(x1)..=x0.max(x1) {
+                        canvas.line(x, y0, '-');
+                    }
+                } else {
+                    for y in y0.min(y1)..=y0.max(y1) {
+                        canvas.line(x0, y, '|');
+                    }
+                }
+            }
+            for &(x, y) in &arrow.points[1..arrow.points.len() - 1] {
+                canvas.put(x, y, '+');
+            }
+            if let Some(&(x, y)) = arrow.points.last() {
+                canvas.put(x, y, arrow.head);
+            }
+            if arrow.dangling {
+                let ((x0, y), (x1, _)) = (arrow.points[0], arrow.points[1]);
+                canvas.put((x0 + x1) / 2, y, 'x');
+            }
+        }
+        let mut out: Vec<String> = canvas
+            .cells
+            .into_iter()
+            .map(|row| row.into_iter().collect::<String>().trim_end().to_string())
+            .collect();
+        while out.last().is_some_and(String::is_empty) {
+            out.pop();
+        }
+        out.join("\n")
+    }
+
+    /*
+     * El `SVG` usa la misma cuadrícula, cada caracter mide `CELL_W` por `CELL_H` pixeles
+     */
+    fn to_svg(&self) -> String {
+        const CELL_W: usize = 9;
+        const CELL_H: usize = 18;
+        let px = |x: usize| x * CELL_W;
+        let py = |y: usize| y * CELL_H + CELL_H / 2;
+        let mut out = format!(
+            concat!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" ",
+                "font-family=\"monospace\" font-size=\"14\">\n",
+                "<defs><marker id=\"head\" markerWidth=\"8\" markerHeight=\"8\" refX=\"7\" refY=\"4\" ",
+                "orient=\"auto\"><path d=\"M0,0 L8,4 L0,8 z\"/></marker></defs>\n",
+                "<text x=\"0\" y=\"{}\">STACK</text>\n<text x=\"{}\" y=\"{}\">HEAP</text>\n"
+            ),
+            px(self.width),
+            py(self.height),
+            py(0) + 5,
+            px(self.heap_x),
+            py(0) + 5
+        );
+        for shape in &self.boxes {
+            let (label, _) = shape.columns;
+            let (x, top, bottom) = (
+                px(shape.x),
+                py(shape.y + 1),
+                py(shape.y + 2 + shape.rows.len()),
+            );
+            let style = if shape.dead {
+                "fill=\"#eee\" stroke=\"#999\" stroke-dasharray=\"4\""
+            } else {
+                "fill=\"#fff\" stroke=\"#000\""
+            };
+            out.push_str(&format!(
+                "<text x=\"{x}\" y=\"{}\">{}</text>\n<rect x=\"{x}\" y=\"{top}\" width=\"{}\" height=\"{}\" {style}/>\n",
+                py(shape.y) + 5,
+                escape(&shape.title),
+                px(shape.width() - 1),
+                bottom - top
+            ));
+            let divider = px(shape.x + label + 3);
+            out.push_str(&format!(
+                "<line x1=\"{divider}\" y1=\"{top}\" x2=\"{divider}\" y2=\"{bottom}\" stroke=\"#999\"/>\n"
+            ));
+            for (row, (l, v)) in shape.rows.iter().enumerate() {
+                let y = py(shape.y + 2 + row) + 5;
+                out.push_str(&format!(
+                    "<text x=\"{}\" y=\"{y}\">{}</text><text x=\"{}\" y=\"{y}\" text-anchor=\"end\">{}</text>\n",
+                    px(shape.x + 2),
+                    escape(l),
+                    px(shape.x + shape.width() - 2),
+                    escape(v)
+                ));
+            }
+        }
+        for arrow in &self.arrows {
+            let points: Vec<String> = arrow
+                .points
+                .iter()
+                .map(|&(x, y)| format!("{},{}", px(x), py(y)))
+                .collect();
+            let color = if arrow.dangling {
+                "#c00\" stroke-dasharray=\"6 3"
+            } else {
+                "#000"
+            };
+            out.push_str(&format!(
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{color}\" marker-end=\"url(#head)\"/>\n",
+                points.join(" ")
+            ));
+            if arrow.dangling {
+                let ((x0, y), (x1, _)) = (arrow.points[0], arrow.points[1]);
+                out.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\" fill=\"#c00\" text-anchor=\"middle\">x</text>\n",
+                    px((x0 + x1) / 2),
+                    py(y) + 5
+                ));
+            }
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/*
+ * Los diagramas de la lección de `Vec`, `alloc_vec()` y `multi_refs()` antes y después de
+ * `names.push(4)`
+ */
+pub fn alloc_vec_example() -> Memory {
+    let mut memory = Memory::new();
+    memory.vec_with_capacity("chars", &['a', 'b'], 4);
+    memory
+}
+
+pub fn multi_refs_example() -> [Memory; 2] {
+    let mut memory = Memory::new();
+    memory.vec("names", &[1, 2, 3]);
+    let _ = memory.borrow_element("first", "names", 0);
+    let before = memory.clone();
+    let _ = memory.push("names", 4);
+    [before, memory]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VECTOR_LESSON: &str = include_str!("../collections/vector.rs");
+
+    /*
+     * Las parejas `| índice | valor |` de las celdas del `heap` dibujadas en un texto
+     */
+    fn heap_rows(text: &str) -> Vec<(String, String)> {
+        let mut rows = Vec::new();
+        for line in text.lines() {
+            let cells: Vec<&str> = line.split('|').map(str::trim).collect();
+            let mut i = 0;
+            while i + 1 < cells.len() {
+                let digits =
+                    |cell: &str| !cell.is_empty() && cell.chars().all(|c| c.is_ascii_digit());
+                if digits(cells[i]) && digits(cells[i + 1]) {
+                    rows.push((cells[i].to_string(), cells[i + 1].to_string()));
+                    i += 2;
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        rows
+    }
+
+    fn block_rows(block: &Block) -> Vec<(String, String)> {
+        block
+            .cells
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell)| Some((index.to_string(), cell.clone()?)))
+            .collect()
+    }
+
+    /*
+     * El texto de `vector.rs` entre dos marcas
+     */
+    fn lesson_between<'a>(start: &str, end: &str) -> &'a str {
+        let from = VECTOR_LESSON.find(start).unwrap() + start.len();
+        let to = from + VECTOR_LESSON[from..].find(end).unwrap();
+        &VECTOR_LESSON[from..to]
+    }
+
+    #[test]
+    fn alloc_vec_golden() {
+        let expected = [
+            "STACK                          HEAP",
+            "",
+            " chars                         0x56001000",
+            " +-----+------------+          +---+--------+",
+            " | ptr | 0x56001000 | -------> | 0 |      a |",
+            " | len |          2 |          | 1 |      b |",
+            " | cap |          4 |          | 2 | uninit |",
+            " +-----+------------+          | 3 | uninit |",
+            "                               +---+--------+"
+        ];
+        assert_eq!(alloc_vec_example().to_ascii(), expected.join("\n"));
+    }
+
+    #[test]
+    fn multi_refs_golden() {
+        let [before, after] = multi_refs_example();
+        let expected_before = [
+            "STACK                            HEAP",
+            "",
+            " names                           0x56001000",
+            " +-----+------------+            +---+----+",
+            " | ptr | 0x56001000 | -----+---> | 0 |  1 |",
+            " | len |          3 |      |     | 1 |  2 |",
+            " | cap |          3 |      |     | 2 |  3 |",
+            " +-----+------------+      |     +---+----+",
+            "                           |",
+            " first                     |",
+            " +-----+------------+      |",
+            " | ptr | 0x56001000 | -----+",
+            " +-----+------------+"
+        ];
+        let expected_after = [
+            "STACK                            HEAP",
+            "",
+            " names                           0x56001100",
+            " +-----+------------+            +---+----------+",
+            " | ptr | 0x56001100 | ---------> | 0 |        1 |",
+            " | len |          4 |            | 1 |        2 |",
+            " | cap |          6 |            | 2 |        3 |",
+            " +-----+------------+            | 3 |        4 |",
+            "                                 | 4 |   uninit |",
+            " first                           | 5 |   uninit |",
+            " +-----+------------+            +---+----------+",
+            " | ptr | 0x56001000 | --x--+",
+            " +-----+------------+      |     0x56001000 freed",
+            "                           |     +---+----------+",
+            "                           +--->(| 0 |        1 |)",
+            "                                (| 1 |        2 |)",
+            "                                (| 2 |        3 |)",
+            "                                 +---+----------+"
+        ];
+        assert_eq!(before.to_ascii(), expected_before.join("\n"));
+        assert_eq!(after.to_ascii(), expected_after.join("\n"));
+    }
+
+    #[test]
+    fn alloc_vec_matches_the_lesson() {
+        let diagram = lesson_between("fn alloc_vec()", "*/");
+        let heap: Vec<String> = diagram
+            .lines()
+            .find(|line| line.contains("'a'"))
+            .unwrap()
+            .split('|')
+            .map(|cell| cell.trim().trim_matches('\'').to_string())
+            .filter(|cell| !cell.is_empty() && !cell.starts_with('*'))
+            .collect();
+        let memory = alloc_vec_example();
+        let cells: Vec<String> = memory.blocks()[0]
+            .cells
+            .iter()
+            .map(|cell| cell.clone().unwrap_or_else(|| "uninit".to_string()))
+            .collect();
+        assert_eq!(cells, heap);
+        assert!(diagram.contains("|      2 |      4 |"));
+        assert_eq!(memory.vars()[0].value, Value::Vec { block: 0, len: 2, cap: 4 });
+    }
+
+    #[test]
+    fn multi_refs_matches_the_lesson() {
+        let before_text = lesson_between("cargo run -- memory", "names.push(4);");
+        let after_text = lesson_between("names.push(4);", "*/");
+        let [before, after] = multi_refs_example();
+        assert_eq!(heap_rows(before_text), block_rows(&before.blocks()[0]));
+        /*
+         * Después del `push` se dibujan el bloque liberado y el nuevo, en ese orden
+         */
+        let mut blocks = block_rows(&after.blocks()[0]);
+        blocks.extend(block_rows(&after.blocks()[1]));
+        assert_eq!(heap_rows(after_text), blocks);
+        assert!(after.blocks()[0].freed && !after.blocks()[1].freed);
+        assert!(after_text.contains("---x") && after.to_ascii().contains("--x--"));
+        assert!(!before_text.contains("-x-") && !before.to_ascii().contains("-x-"));
+    }
+
+    #[test]
+    fn push_that_reallocates_leaves_a_dangling_reference() {
+        let [before, after] = multi_refs_example();
+        assert!(before.dangling().is_empty());
+        assert_eq!(after.dangling(), ["first"]);
+        /*
+         * Con capacidad de sobra el bloque no se mueve y la referencia sigue siendo válida
+         */
+        let mut memory = Memory::new();
+        memory.vec_with_capacity("names", &[1, 2, 3], 4);
+        memory.borrow_element("first", "names", 0).unwrap();
+        memory.push("names", 4).unwrap();
+        assert!(memory.dangling().is_empty());
+        memory.push("names", 5).unwrap();
+        assert_eq!(memory.dangling(), ["first"]);
+        assert_eq!(
+            memory.vars()[0].value,
+            Value::Vec { block: 1, len: 5, cap: 8 }
+        );
+        /*
+         * Una referencia a una variable liberada también queda colgando
+         */
+        let mut memory = Memory::new();
+        memory.string("s", "hola");
+        memory.borrow("r", "s", false).unwrap();
+        memory.drop_var("s").unwrap();
+        assert_eq!(memory.dangling(), ["r"]);
+    }
+
+    #[test]
+    fn svg_escapes_string_contents() {
+        let mut memory = Memory::new();
+        memory.string("html", "<a&b>");
+        let svg = memory.to_svg();
+        for escaped in ["'&lt;'", "'&amp;'", "'&gt;'"] {
+            assert!(svg.contains(escaped), "{escaped}");
+        }
+        for raw in ["'<'", "'&'", "'>'"] {
+            assert!(!svg.contains(raw), "{raw}");
+        }
+        assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>\n"));
+    }
+}
@@ -0,0 +1,9 @@
+pub mod borrowing;
+pub mod string_slices;
+pub mod tokenizer;
+pub mod search;
+pub mod rope;
+pub mod memory;
+pub mod traced;
+pub mod arena;
+pub mod borrowck;
@@ -0,0 +1,637 @@
+/*
+ * Un `String` guarda todo su contenido en un solo bloque contiguo del `heap`, cuando `.push_str()`
+ * o el operador `+` superan la capacidad, `Rust` reserva un bloque más grande y copia todos los
+ * `bytes`. Insertar en medio de un `String` también mueve todo lo que esta después.
+ *
+ * Un `Rope` divide el texto en pedazos `chunks` pequeños guardados en las hojas de un árbol
+ * balanceado, insertar o borrar solo reconstruye el camino de la raíz a la hoja, `O(log n)`
+ *
+ *                   (branch)
+ *                  /        \
+ *           (branch)        "world!"
+ *           /      \
+ *      "Hello"    ", "
+ */
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+
+/*
+ * El tamaño máximo en `bytes` de cada hoja, las hojas pequeñas se unen cuando caben juntas
+ */
+const MAX_LEAF: usize = 1024;
+
+#[derive(Debug, Clone)]
+enum Node {
+    Leaf(String),
+    Branch {
+        left: Box<Node>,
+        right: Box<Node>,
+        /*
+         * Cada rama guarda las medidas de todo su subárbol para poder bajar sin recorrer las hojas
+         */
+        chars: usize,
+        bytes: usize,
+        newlines: usize,
+        height: usize,
+    },
+}
+
+impl Default for Node {
+    fn default() -> Self {
+        Node::Leaf(String::new())
+    }
+}
+
+impl Node {
+    fn chars(&self) -> usize {
+        match self {
+            Node::Leaf(text) => text.chars().count(),
+            Node::Branch { chars, .. } => *chars,
+        }
+    }
+
+    fn bytes(&self) -> usize {
+        match self {
+            Node::Leaf(text) => text.len(),
+            Node::Branch { bytes, .. } => *bytes,
+        }
+    }
+
+    fn newlines(&self) -> usize {
+        match self {
+            Node::Leaf(text) => text.bytes().filter(|&b| b == b'\n').count(),
+            Node::Branch { newlines, .. } => *newlines,
+        }
+    }
+
+    fn height(&self) -> usize {
+        match self {
+            Node::Leaf(_) => 0,
+            Node::Branch { height, .. } => *height,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bytes() == 0
+    }
+
+    fn branch(left: Node, right: Node) -> Node {
+        Node::Branch {
+            chars: left.chars() + right.chars(),
+            bytes: left.bytes() + right.bytes(),
+            newlines: left.newlines() + right.newlines(),
+            height: left.height().max(right.height()) + 1,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    fn into_children(self) -> (Node, Node) {
+        match self {
+            Node::Branch { left, right, .. } => (*left, *right),
+            leaf => (leaf, Node::default()),
+        }
+    }
+
+    /*
+     * Construye un árbol balanceado a partir del texto, partiendo en `chunks` de a lo más
+     * `MAX_LEAF` `bytes` sin cortar ningún `char`
+     */
+    fn from_text(text: &str) -> Node {
+        let mut leaves = Vec::new();
+        let mut rest = text;
+        while rest.len() > MAX_LEAF {
+            let mut cut = MAX_LEAF;
+            while !rest.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            leaves.push(Node::Leaf(rest[..cut].to_string()));
+            rest = &rest[cut..];
+        }
+        leaves.push(Node::Leaf(rest.to_string()));
+        Node::from_leaves(leaves)
+    }
+
+    fn from_leaves(mut leaves: Vec<Node>) -> Node {
+        if leaves.len() == 1 {
+            return leaves.pop().unwrap_or_default();
+        }
+        let right = leaves.split_off(leaves.len() / 2);
+        Node::branch(Node::from_leaves(leaves), Node::from_leaves(right))
+    }
+
+    fn rotate_left(self) -> Node {
+        let (left, right) = self.into_children();
+        let (right_left, right_right) = right.into_children();
+        Node::branch(Node::branch(left, right_left), right_right)
+    }
+
+    fn rotate_right(self) -> Node {
+        let (left, right) = self.into_children();
+        let (left_left, left_right) = left.into_children();
+        Node::branch(left_left, Node::branch(left_right, right))
+    }
+
+    /*
+     * Las rotaciones de un árbol `AVL`, después de unir dos subárboles la diferencia de alturas es
+     * a lo más de dos, y una o dos rotaciones la regresan a uno
+     */
+    fn balance(left: Node, right: Node) -> Node {
+        if left.height() > right.height() + 1 {
+            let (left_left, left_right) = left.into_children();
+            let left = if left_left.height() >= left_right.height() {
+                Node::branch(left_left, left_right)
+            } else {
+                Node::branch(left_left, left_right).rotate_left()
+            };
+            Node::branch(left, right).rotate_right()
+        } else if right.height() > left.height() + 1 {
+            let (right_left, right_right) = right.into_children();
+            let right = if right_right.height() >= right_left.height() {
+                Node::branch(right_left, right_right)
+            } else {
+                Node::branch(right_left, right_right).rotate_right()
+            };
+            Node::branch(left, right).rotate_left()
+        } else {
+            Node::branch(left, right)
+        }
+    }
+
+    /*
+     * Unir dos árboles baja por el lado del más alto hasta encontrar un subárbol de la misma
+     * altura, en el camino de regreso se balancea cada nivel
+     */
+    fn join(left: Node, right: Node) -> Node {
+        if left.is_empty() {
+            return right;
+        }
+        if right.is_empty() {
+            return left;
+        }
+        match (left, right) {
+            (Node::Leaf(mut a), Node::Leaf(b)) if a.len() + b.len() <= MAX_LEAF => {
+                a.push_str(&b);
+                Node::Leaf(a)
+            }
+            (left, right) if left.height() > right.height() + 1 => {
+                let (left_left, left_right) = left.into_children();
+                Node::balance(left_left, Node::join(left_right, right))
+            }
+            (left, right) if right.height() > left.height() + 1 => {
+                let (right_left, right_right) = right.into_children();
+                Node::balance(Node::join(left, right_left), right_right)
+            }
+            (left, right) => Node::branch(left, right),
+        }
+    }
+
+    /*
+     * Divide el árbol en dos, el izquierdo con los primeros `at` `chars`
+     */
+    fn split(self, at: usize) -> (Node, Node) {
+        match self {
+            Node::Leaf(mut text) => {
+                let index = char_to_byte(&text, at);
+                let right = text.split_off(index);
+                (Node::Leaf(text), Node::Leaf(right))
+            }
+            Node::Branch { left, right, .. } => {
+                let left_chars = left.chars();
+                if at <= left_chars {
+                    let (a, b) = left.split(at);
+                    (a, Node::join(b, *right))
+                } else {
+                    let (a, b) = right.split(at - left_chars);
+                    (Node::join(*left, a), b)
+                }
+            }
+        }
+    }
+
+    /*
+     * Devuelve la hoja que contiene el `char` `at` y la posición del `char` dentro de la hoja
+     */
+    fn leaf_at(&self, at: usize) -> (&str, usize) {
+        match self {
+            Node::Leaf(text) => (text, at),
+            Node::Branch { left, right, .. } => {
+                let left_chars = left.chars();
+                if at < left_chars {
+                    left.leaf_at(at)
+                } else {
+                    right.leaf_at(at - left_chars)
+                }
+            }
+        }
+    }
+
+    /*
+     * Copia a `out` los `chars` del rango, solo baja por los subárboles que tocan el rango, por eso
+     * no recorre las hojas que están antes o después
+     */
+    fn push_range(&self, range: Range<usize>, out: &mut String) {
+        match self {
+            Node::Leaf(text) => {
+                let start = char_to_byte(text, range.start);
+                let end = start + char_to_byte(&text[start..], range.len());
+                out.push_str(&text[start..end]);
+            }
+            Node::Branch { left, right, .. } => {
+                let left_chars = left.chars();
+                if range.start < left_chars {
+                    left.push_range(range.start..range.end.min(left_chars), out);
+                }
+                if range.end > left_chars {
+                    right.push_range(range.start.saturating_sub(left_chars)..range.end - left_chars, out);
+                }
+            }
+        }
+    }
+
+    /*
+     * Cuántos `chars` hay antes del salto de línea número `n`
+     */
+    fn newline_to_char(&self, n: usize) -> usize {
+        match self {
+            Node::Leaf(text) => {
+                let (index, _) = text
+                    .char_indices()
+                    .filter(|&(_, c)| c == '\n')
+                    .nth(n)
+                    .unwrap_or((text.len(), '\n'));
+                text[..index].chars().count()
+            }
+            Node::Branch { left, right, .. } => {
+                let left_newlines = left.newlines();
+                if n < left_newlines {
+                    left.newline_to_char(n)
+                } else {
+                    left.chars() + right.newline_to_char(n - left_newlines)
+                }
+            }
+        }
+    }
+
+    /*
+     * Cuántos saltos de línea hay en los primeros `at` `chars`
+     */
+    fn newlines_before(&self, at: usize) -> usize {
+        match self {
+            Node::Leaf(text) => text.chars().take(at).filter(|&c| c == '\n').count(),
+            Node::Branch { left, right, .. } => {
+                let left_chars = left.chars();
+                if at <= left_chars {
+                    left.newlines_before(at)
+                } else {
+                    left.newlines() + right.newlines_before(at - left_chars)
+                }
+            }
+        }
+    }
+}
+
+fn char_to_byte(text: &str, at: usize) -> usize {
+    text.char_indices().nth(at).map_or(text.len(), |(index, _)| index)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Rope {
+    root: Node,
+}
+
+impl Rope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len_chars(&self) -> usize {
+        self.root.chars()
+    }
+
+    pub fn len_bytes(&self) -> usize {
+        self.root.bytes()
+    }
+
+    /*
+     * Un texto sin saltos de línea tiene una línea, y cada `'\n'` agrega una más
+     */
+    pub fn len_lines(&self) -> usize {
+        self.root.newlines() + 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_empty()
+    }
+
+    pub fn height(&self) -> usize {
+        self.root.height()
+    }
+
+    /*
+     * Como `String::insert_str()`, la posición se mide en `chars` y lanza un `panic` si esta fuera
+     * del texto
+     */
+    pub fn insert(&mut self, at: usize, text: &str) {
+        assert!(at <= self.len_chars(), "insert position {at} out of bounds");
+        if text.is_empty() {
+            return;
+        }
+        let (left, right) = std::mem::take(&mut self.root).split(at);
+        self.root = Node::join(Node::join(left, Node::from_text(text)), right);
+    }
+
+    pub fn insert_char(&mut self, at: usize, c: char) {
+        self.insert(at, c.encode_utf8(&mut [0; 4]));
+    }
+
+    pub fn push_str(&mut self, text: &str) {
+        self.insert(self.len_chars(), text);
+    }
+
+    pub fn remove(&mut self, range: Range<usize>) {
+        assert!(
+            range.start <= range.end && range.end <= self.len_chars(),
+            "remove range {range:?} out of bounds"
+        );
+        let (left, rest) = std::mem::take(&mut self.root).split(range.start);
+        let (_, right) = rest.split(range.end - range.start);
+        self.root = Node::join(left, right);
+    }
+
+    /*
+     * Mueve los `chars` desde `at` hasta el final a un nuevo `Rope`
+     */
+    pub fn split_off(&mut self, at: usize) -> Rope {
+        assert!(at <= self.len_chars(), "split position {at} out of bounds");
+        let (left, right) = std::mem::take(&mut self.root).split(at);
+        self.root = left;
+        Rope { root: right }
+    }
+
+    /*
+     * Toma la propiedad de `other` y lo agrega al final
+     */
+    pub fn append(&mut self, other: Rope) {
+        self.root = Node::join(std::mem::take(&mut self.root), other.root);
+    }
+
+    pub fn char_at(&self, at: usize) -> Option<char> {
+        if at >= self.len_chars() {
+            return None;
+        }
+        let (leaf, index) = self.root.leaf_at(at);
+        leaf.chars().nth(index)
+    }
+
+    /*
+     * Si el rango cabe en una sola hoja se devuelve un `slice` prestado `Cow::Borrowed`, si no,
+     * se copian solo las hojas que cubre el rango a un nuevo `String` `Cow::Owned`
+     */
+    pub fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        assert!(
+            range.start <= range.end && range.end <= self.len_chars(),
+            "slice range {range:?} out of bounds"
+        );
+        if range.is_empty() {
+            return Cow::Borrowed("");
+        }
+        let (leaf, index) = self.root.leaf_at(range.start);
+        let start = char_to_byte(leaf, index);
+        let end = char_to_byte(leaf, index + range.len());
+        if leaf[start..].chars().count() >= range.len() {
+            return Cow::Borrowed(&leaf[start..end]);
+        }
+        let mut text = String::new();
+        self.root.push_range(range, &mut text);
+        Cow::Owned(text)
+    }
+
+    /*
+     * La posición en `chars` donde comienza la línea `line`, comenzando desde cero
+     */
+    pub fn line_to_char(&self, line: usize) -> usize {
+        assert!(line < self.len_lines(), "line {line} out of bounds");
+        if line == 0 {
+            0
+        } else {
+            self.root.newline_to_char(line - 1) + 1
+        }
+    }
+
+    pub fn char_to_line(&self, at: usize) -> usize {
+        assert!(at <= self.len_chars(), "char position {at} out of bounds");
+        self.root.newlines_before(at)
+    }
+
+    /*
+     * El contenido de la línea `line` sin el salto de línea final
+     */
+    pub fn line(&self, line: usize) -> Cow<'_, str> {
+        let start = self.line_to_char(line);
+        let end = if line + 1 < self.len_lines() {
+            self.line_to_char(line + 1) - 1
+        } else {
+            self.len_chars()
+        };
+        self.slice(start..end)
+    }
+
+    pub fn chunks(&self) -> Chunks<'_> {
+        Chunks {
+            stack: vec![&self.root],
+        }
+    }
+
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.chunks().flat_map(str::chars)
+    }
+}
+
+/*
+ * Recorre las hojas de izquierda a derecha con una pila, sin recursión
+ */
+pub struct Chunks<'a> {
+    stack: Vec<&'a Node>,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            match node {
+                Node::Leaf(text) if text.is_empty() => continue,
+                Node::Leaf(text) => return Some(text),
+                Node::Branch { left, right, .. } => {
+                    self.stack.push(right);
+                    self.stack.push(left);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl From<&str> for Rope {
+    fn from(text: &str) -> Self {
+        Rope {
+            root: Node::from_text(text),
+        }
+    }
+}
+
+impl From<String> for Rope {
+    fn from(text: String) -> Self {
+        if text.len() <= MAX_LEAF {
+            return Rope {
+                root: Node::Leaf(text),
+            };
+        }
+        Rope::from(text.as_str())
+    }
+}
+
+impl From<&Rope> for String {
+    fn from(rope: &Rope) -> Self {
+        let mut text = String::with_capacity(rope.len_bytes());
+        for chunk in rope.chunks() {
+            text.push_str(chunk);
+        }
+        text
+    }
+}
+
+impl From<Rope> for String {
+    fn from(rope: Rope) -> Self {
+        String::from(&rope)
+    }
+}
+
+impl fmt::Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in self.chunks() {
+            f.write_str(chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for Rope {
+    fn eq(&self, other: &Rope) -> bool {
+        self.len_bytes() == other.len_bytes() && self.chars().eq(other.chars())
+    }
+}
+
+impl Eq for Rope {}
+
+impl PartialEq<str> for Rope {
+    fn eq(&self, other: &str) -> bool {
+        self.len_bytes() == other.len() && self.chars().eq(other.chars())
+    }
+}
+
+impl PartialEq<&str> for Rope {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+     * Un generador `xorshift` para que las pruebas no dependan de un `crate` externo y siempre
+     * hagan las mismas operaciones
+     */
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn below(&mut self, n: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % n as u64) as usize
+        }
+
+        fn text(&mut self, max: usize) -> String {
+            const ALPHABET: [char; 8] = ['a', 'b', ' ', '\n', 'é', 'ñ', '日', '😊'];
+            (0..self.below(max + 1)).map(|_| ALPHABET[self.below(ALPHABET.len())]).collect()
+        }
+    }
+
+    fn char_slice(text: &str, range: Range<usize>) -> String {
+        text.chars().skip(range.start).take(range.len()).collect()
+    }
+
+    /*
+     * Cada operación se aplica al `Rope` y a un `String`, y los dos deben tener siempre el mismo
+     * texto. Los textos de hasta `3000` `chars` obligan a partir y unir hojas de `MAX_LEAF` `bytes`
+     */
+    #[test]
+    fn fuzz_against_string() {
+        let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+        let mut rope = Rope::new();
+        let mut expected = String::new();
+        for _ in 0..500 {
+            let len = expected.chars().count();
+            match rng.below(5) {
+                0 | 1 => {
+                    let at = rng.below(len + 1);
+                    let max = if rng.below(10) == 0 { 3000 } else { 20 };
+                    let text = rng.text(max);
+                    rope.insert(at, &text);
+                    expected.insert_str(char_to_byte(&expected, at), &text);
+                }
+                2 => {
+                    let start = rng.below(len + 1);
+                    let end = start + rng.below(len - start + 1).min(200);
+                    rope.remove(start..end);
+                    let range = char_to_byte(&expected, start)..char_to_byte(&expected, end);
+                    expected.replace_range(range, "");
+                }
+                3 => {
+                    let at = rng.below(len + 1);
+                    let mut tail = rope.split_off(at);
+                    assert_eq!(tail, char_slice(&expected, at..len).as_str());
+                    tail.push_str("");
+                    rope.append(tail);
+                }
+                _ => {
+                    let start = rng.below(len + 1);
+                    let end = start + rng.below(len - start + 1);
+                    assert_eq!(rope.slice(start..end), char_slice(&expected, start..end));
+                }
+            }
+            assert_eq!(rope, expected.as_str());
+            assert_eq!(rope.len_chars(), expected.chars().count());
+            assert_eq!(rope.len_lines(), expected.split('\n').count());
+        }
+        assert!(rope.height() < 20, "height {}", rope.height());
+    }
+
+    #[test]
+    fn lines_and_char_positions() {
+        let text = "uno\ndós\n\ntres 😊";
+        let rope = Rope::from(text);
+        for (index, line) in text.split('\n').enumerate() {
+            assert_eq!(rope.line(index), line);
+        }
+        assert_eq!(rope.line_to_char(1), 4);
+        assert_eq!(rope.char_to_line(8), 2);
+        assert_eq!(rope.char_at(5), Some('ó'));
+        assert_eq!(rope.char_at(100), None);
+    }
+
+    #[test]
+    fn slice_borrows_inside_one_leaf() {
+        let text = "ab".repeat(MAX_LEAF);
+        let rope = Rope::from(text.as_str());
+        assert!(matches!(rope.slice(10..20), Cow::Borrowed("ababababab")));
+        let across = rope.slice(MAX_LEAF - 3..MAX_LEAF + 3);
+        assert!(matches!(across, Cow::Owned(_)));
+        assert_eq!(across, char_slice(&text, MAX_LEAF - 3..MAX_LEAF + 3));
+    }
+}
@@ -0,0 +1,444 @@
+/*
+ * Buscar un patrón dentro de un texto es la operación más común sobre los `string slices`. Cada
+ * algoritmo devuelve un `Match` que contiene el `slice` prestado del texto y su rango en `bytes`,
+ * no se copia el texto encontrado.
+ *
+ * Los algoritmos comparan `bytes` y no `chars`, como el patrón y el texto son `UTF-8` válido, un
+ * patrón solo puede coincidir en el inicio de un `char`, por lo que el `slice` siempre es válido
+ */
+use std::collections::VecDeque;
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match<'t> {
+    pub text: &'t str,
+    pub start: usize,
+    /*
+     * El número del patrón que coincidio, siempre es `0` para los algoritmos de un solo patrón
+     */
+    pub pattern: usize,
+}
+
+impl<'t> Match<'t> {
+    pub fn end(&self) -> usize {
+        self.start + self.text.len()
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end()
+    }
+}
+
+/*
+ * Todos los algoritmos implementan el `trait` `Matcher`, solo es necesario escribir `find_at` y el
+ * resto de los métodos tienen una implementación por defecto
+ */
+pub trait Matcher {
+    /*
+     * Busca la primera coincidencia que comienza en `start` o después, los patrones vacíos nunca
+     * coinciden
+     */
+    fn find_at<'t>(&self, haystack: &'t str, start: usize) -> Option<Match<'t>>;
+
+    fn find<'t>(&self, haystack: &'t str) -> Option<Match<'t>> {
+        self.find_at(haystack, 0)
+    }
+
+    fn is_match(&self, haystack: &str) -> bool {
+        self.find(haystack).is_some()
+    }
+
+    /*
+     * Las coincidencias no se traslapan, la búsqueda continúa donde terminó la anterior
+     */
+    fn find_iter<'m, 't>(&'m self, haystack: &'t str) -> Matches<'m, 't, Self>
+    where
+        Self: Sized,
+    {
+        Matches {
+            matcher: self,
+            haystack,
+            pos: 0,
+        }
+    }
+
+    fn ranges(&self, haystack: &str) -> Vec<Range<usize>>
+    where
+        Self: Sized,
+    {
+        self.find_iter(haystack).map(|m| m.range()).collect()
+    }
+
+    fn count(&self, haystack: &str) -> usize
+    where
+        Self: Sized,
+    {
+        self.find_iter(haystack).count()
+    }
+}
+
+pub struct Matches<'m, 't, M> {
+    matcher: &'m M,
+    haystack: &'t str,
+    pos: usize,
+}
+
+impl<'m, 't, M: Matcher> Iterator for Matches<'m, 't, M> {
+    type Item = Match<'t>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let found = self.matcher.find_at(self.haystack, self.pos)?;
+        self.pos = found.end();
+        Some(found)
+    }
+}
+
+fn single<'t>(haystack: &'t str, start: usize, len: usize) -> Match<'t> {
+    Match {
+        text: &haystack[start..start + len],
+        start,
+        pattern: 0,
+    }
+}
+
+/*
+ * El algoritmo ingenuo compara el patrón en cada posición del texto, en el peor caso hace
+ * `O(n * m)` comparaciones
+ */
+#[derive(Debug, Clone)]
+pub struct Naive {
+    pattern: Vec<u8>,
+}
+
+impl Naive {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl Matcher for Naive {
+    fn find_at<'t>(&self, haystack: &'t str, start: usize) -> Option<Match<'t>> {
+        let m = self.pattern.len();
+        if m == 0 || start > haystack.len() {
+            return None;
+        }
+        haystack.as_bytes()[start..]
+            .windows(m)
+            .position(|window| window == self.pattern.as_slice())
+            .map(|index| single(haystack, start + index, m))
+    }
+}
+
+/*
+ * El algoritmo `Knuth–Morris–Pratt` nunca regresa en el texto, la tabla `failure` indica cuánto del
+ * patrón ya coincide cuando falla una comparación. Hace `O(n + m)` comparaciones
+ */
+#[derive(Debug, Clone)]
+pub struct Kmp {
+    pattern: Vec<u8>,
+    /*
+     * `failure[i]` es la longitud del prefijo más largo de `pattern[..=i]` que también es sufijo
+     */
+    failure: Vec<usize>,
+}
+
+impl Kmp {
+    pub fn new(pattern: &str) -> Self {
+        let pattern = pattern.as_bytes().to_vec();
+        let mut failure = vec![0; pattern.len()];
+        let mut len = 0;
+        for i in 1..pattern.len() {
+            while len > 0 && pattern[i] != pattern[len] {
+                len = failure[len - 1];
+            }
+            if pattern[i] == pattern[len] {
+                len += 1;
+            }
+            failure[i] = len;
+        }
+        Self { pattern, failure }
+    }
+}
+
+impl Matcher for Kmp {
+    fn find_at<'t>(&self, haystack: &'t str, start: usize) -> Option<Match<'t>> {
+        let m = self.pattern.len();
+        if m == 0 || start > haystack.len() {
+            return None;
+        }
+        let mut matched = 0;
+        for (i, &byte) in haystack.as_bytes().iter().enumerate().skip(start) {
+            while matched > 0 && byte != self.pattern[matched] {
+                matched = self.failure[matched - 1];
+            }
+            if byte == self.pattern[matched] {
+                matched += 1;
+            }
+            if matched == m {
+                return Some(single(haystack, i + 1 - m, m));
+            }
+        }
+        None
+    }
+}
+
+/*
+ * El algoritmo `Boyer–Moore–Horspool` compara el patrón de derecha a izquierda, y cuando falla salta
+ * tantas posiciones como indique el último `byte` de la ventana. En textos normales revisa solo una
+ * fracción de los `bytes`
+ */
+#[derive(Debug, Clone)]
+pub struct Horspool {
+    pattern: Vec<u8>,
+    shift: [usize; 256],
+}
+
+impl Horspool {
+    pub fn new(pattern: &str) -> Self {
+        let pattern = pattern.as_bytes().to_vec();
+        let m = pattern.len();
+        let mut shift = [m; 256];
+        for (i, &byte) in pattern.iter().enumerate().take(m.saturating_sub(1)) {
+            shift[byte as usize] = m - 1 - i;
+        }
+        Self { pattern, shift }
+    }
+}
+
+impl Matcher for Horspool {
+    fn find_at<'t>(&self, haystack: &'t str, start: usize) -> Option<Match<'t>> {
+        let m = self.pattern.len();
+        let text = haystack.as_bytes();
+        if m == 0 {
+            return None;
+        }
+        let mut pos = start;
+        while pos + m <= text.len() {
+            let last = text[pos + m - 1];
+            if last == self.pattern[m - 1] && text[pos..pos + m - 1] == self.pattern[..m - 1] {
+                return Some(single(haystack, pos, m));
+            }
+            pos += self.shift[last as usize];
+        }
+        None
+    }
+}
+
+/*
+ * Cada estado del autómata es un prefijo de alguno de los patrones
+ */
+#[derive(Debug, Clone)]
+struct State {
+    next: [usize; 256],
+    depth: usize,
+    /*
+     * Los patrones que terminan en este estado, incluidos los que son sufijos del prefijo
+     */
+    outputs: Vec<usize>,
+}
+
+impl State {
+    fn new(depth: usize) -> Self {
+        Self {
+            next: [0; 256],
+            depth,
+            outputs: Vec::new(),
+        }
+    }
+}
+
+/*
+ * El algoritmo `Aho–Corasick` busca varios patrones a la vez, construye un autómata con todos los
+ * patrones y recorre el texto una sola vez sin importar cuántos patrones existan.
+ * Cuando dos patrones coinciden se prefiere el que comienza primero y después el más largo
+ */
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    patterns: Vec<usize>,
+    states: Vec<State>,
+}
+
+impl AhoCorasick {
+    pub fn new<P: AsRef<str>>(patterns: &[P]) -> Self {
+        /*
+         * Primero se construye el `trie` con los prefijos de todos los patrones, el estado `0` es la
+         * raíz y un `0` en la tabla `next` significa que todavía no existe la transición
+         */
+        let mut states = vec![State::new(0)];
+        let mut lengths = Vec::with_capacity(patterns.len());
+        for (id, pattern) in patterns.iter().enumerate() {
+            let bytes = pattern.as_ref().as_bytes();
+            lengths.push(bytes.len());
+            if bytes.is_empty() {
+                continue;
+            }
+            let mut current = 0;
+            for &byte in bytes {
+                if states[current].next[byte as usize] == 0 {
+                    states.push(State::new(states[current].depth + 1));
+                    let created = states.len() - 1;
+                    states[current].next[byte as usize] = created;
+                }
+                current = states[current].next[byte as usize];
+            }
+            states[current].outputs.push(id);
+        }
+        /*
+         * Después se recorre el `trie` por niveles `BFS` y cada transición que falta apunta a donde
+         * apuntaría el enlace de falla, así el autómata nunca tiene que retroceder
+         */
+        let mut fail = vec![0; states.len()];
+        let mut queue: VecDeque<usize> = states[0].next.iter().copied().filter(|&s| s != 0).collect();
+        while let Some(current) = queue.pop_front() {
+            let inherited = states[fail[current]].outputs.clone();
+            states[current].outputs.extend(inherited);
+            for byte in 0..256 {
+                let child = states[current].next[byte];
+                let fallback = states[fail[current]].next[byte];
+                if child != 0 {
+                    fail[child] = fallback;
+                    queue.push_back(child);
+                } else {
+                    states[current].next[byte] = fallback;
+                }
+            }
+        }
+        Self {
+            patterns: lengths,
+            states,
+        }
+    }
+
+    pub fn pattern_count(&self) -> usize {
+        self.patterns.len()
+    }
+}
+
+impl Matcher for AhoCorasick {
+    fn find_at<'t>(&self, haystack: &'t str, start: usize) -> Option<Match<'t>> {
+        let mut state = 0;
+        let mut best: Option<(usize, usize, usize)> = None;
+        for (i, &byte) in haystack.as_bytes().iter().enumerate().skip(start) {
+            state = self.states[state].next[byte as usize];
+            let current = &self.states[state];
+            /*
+             * Si ya hay una coincidencia y el prefijo actual comienza después de ella, ninguna
+             * coincidencia futura puede comenzar antes
+             */
+            if let Some((best_start, _, _)) = best {
+                if i + 1 - current.depth > best_start {
+                    break;
+                }
+            }
+            for &id in &current.outputs {
+                let len = self.patterns[id];
+                let found = (i + 1 - len, len, id);
+                let better = match best {
+                    None => true,
+                    Some((s, l, _)) => found.0 < s || (found.0 == s && len > l),
+                };
+                if better {
+                    best = Some(found);
+                }
+            }
+        }
+        best.map(|(start, len, pattern)| Match {
+            text: &haystack[start..start + len],
+            start,
+            pattern,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type FindAll = Box<dyn Fn(&str) -> Vec<(usize, String)>>;
+
+    fn matchers(pattern: &str) -> Vec<(&'static str, FindAll)> {
+        fn boxed<M: Matcher + 'static>(matcher: M) -> FindAll {
+            Box::new(move |haystack| {
+                matcher
+                    .find_iter(haystack)
+                    .map(|m| (m.start, m.text.to_string()))
+                    .collect()
+            })
+        }
+        vec![
+            ("naive", boxed(Naive::new(pattern))),
+            ("kmp", boxed(Kmp::new(pattern))),
+            ("horspool", boxed(Horspool::new(pattern))),
+            ("aho-corasick", boxed(AhoCorasick::new(&[pattern]))),
+        ]
+    }
+
+    /*
+     * `match_indices` tampoco traslapa las coincidencias, en `aaaa` encuentra `aa` en `0` y `2`
+     */
+    #[test]
+    fn matchers_agree_with_match_indices() {
+        let cases = [
+            ("aaaa", "aa"),
+            ("aaaaa", "aaa"),
+            ("abababab", "abab"),
+            ("abcabcabd", "abcabd"),
+            ("mississippi", "issi"),
+            ("mississippi", "ss"),
+            ("hello world", "xyz"),
+            ("ab", "abc"),
+            ("", "a"),
+            ("añoaño año", "año"),
+            ("ñññ", "ññ"),
+            ("日本語の日本", "日本"),
+        ];
+        for (haystack, pattern) in cases {
+            let expected: Vec<(usize, String)> = haystack
+                .match_indices(pattern)
+                .map(|(start, text)| (start, text.to_string()))
+                .collect();
+            for (name, find_all) in matchers(pattern) {
+                assert_eq!(find_all(haystack), expected, "{name}: {pattern:?} in {haystack:?}");
+            }
+        }
+    }
+
+    /*
+     * A diferencia de `match_indices`, que encuentra el patrón vacío en cada límite de `char`, los
+     * patrones vacíos nunca coinciden
+     */
+    #[test]
+    fn empty_pattern_never_matches() {
+        for haystack in ["", "abc", "año"] {
+            for (name, find_all) in matchers("") {
+                assert_eq!(find_all(haystack), Vec::new(), "{name}: in {haystack:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn find_at_resumes_after_start() {
+        let haystack = "abcabcabc";
+        for (name, find_all) in matchers("abc") {
+            assert_eq!(find_all(haystack).len(), 3, "{name}");
+        }
+        assert_eq!(Kmp::new("abc").find_at(haystack, 1).map(|m| m.start), Some(3));
+        assert_eq!(Naive::new("abc").find_at(haystack, 10), None);
+        assert_eq!(Horspool::new("abc").find_at(haystack, 7), None);
+    }
+
+    #[test]
+    fn aho_corasick_prefers_leftmost_then_longest() {
+        let matcher = AhoCorasick::new(&["he", "she", "hers", "his"]);
+        let found: Vec<(usize, &str, usize)> = matcher
+            .find_iter("ushers his")
+            .map(|m| (m.start, m.text, m.pattern))
+            .collect();
+        assert_eq!(found, [(1, "she", 1), (7, "his", 3)]);
+        let matcher = AhoCorasick::new(&["a", "ab", "abc", ""]);
+        assert_eq!(matcher.find("xabcd").map(|m| m.text), Some("abc"));
+        assert_eq!(matcher.pattern_count(), 4);
+    }
+}
@@ -1,76 +1,82 @@
+use crate::custom_types::result::{ExampleResult, LessonError};
+use crate::ownership::tokenizer;
+
 /*
  * Los `String slices` son un tipo de `String` que en lugar de tener una referencia a todo el
  * `String` solo hacen referencia a una porción de el
  */
-fn run() {
+pub fn run() {
     /*
      * Los `Strings literals` son un `slice` y el hecho de que sean muy eficientes es porque `Rust`
      * los `harcodea` en el `binary`. Es un `slice` que apunta a esa parte específica del `binary`
      */
     let s: &str = "Hello, world!";
-    get_first(s);
+    println!("First word: {:?}", get_first(s));
     /*
      * Se pueden crear `String slices` usando el `range operator` con corchetes
      * `[starting_index..ending_index]` donde `starting_index` es la primera posición del `slice`
      * y en `ending_index` es la última posición más uno
      */
     let phrase = String::from("Hello, world! From Rust lang");
-    get_first(&phrase);
+    println!("First word: {:?}", get_first(&phrase));
     /*
      * Internamente, el `slice` guarda la posición de inicio y su longitud `length` que corresponde
      * a `ending_index-starting_index`
      */
     let slice = &phrase[0..5];
     println!("Portion of String: {}", slice);
-    let word_found = get_word(&phrase, 3);
-    println!("Word: {}", word_found);
-
+    if let ExampleResult::Ok(word_found) = get_word(&phrase, 3) {
+        println!("Word: {}", word_found);
+    }
+    /*
+     * La última palabra ya no lanza un `panic`, y una posición que no existe devuelve un error que
+     * dice cuántas palabras hay. `context` agrega lo que se estaba haciendo sin perder la causa
+     */
+    println!("Last word: {:?}", get_word(&phrase, 4).ok());
+    if let ExampleResult::Err(error) = get_word(&phrase, 10).context("reading the missing word") {
+        println!("{}", error.report());
+    }
 }
 
 /*
  * La notación `&str` de la firma de la función `get_first()` permite recibir como parámetros tipos
- * `&String` y `&str`
+ * `&String` y `&str`. El `slice` que se devuelve apunta al mismo texto, por eso se devuelve un
+ * `Option<&str>`, si el texto esta vacío o solo tiene espacios no hay una primera palabra
  */
-fn get_first(phrase: &str) -> &str {
-    let chars = phrase.trim().as_bytes();
-    for (index, &char) in chars.iter().enumerate() {
-        if char == b' ' {
-            return &phrase[0..index];
-        }
-    }
-    &phrase[..]
+fn get_first(phrase: &str) -> Option<&str> {
+    tokenizer::first_word(phrase)
 }
 
-fn get_word(phrase: &String, word: i32) -> &str {
-    let chars = phrase.trim().as_bytes();
-    let mut total_spaces_pos = Vec::<i32>::new();
-    for (index, &char) in chars.iter().enumerate() {
-        if char == b' ' {
-            total_spaces_pos.push(i32::try_from(index).unwrap());
-        }
-    }
-    if total_spaces_pos.len() > 0 {
-        for _ in chars {
-            let starting_index = if word == 0 {
-                0
-            } else {
-                total_spaces_pos
-                    .get((word as usize) - 1)
-                    .unwrap()
-                    .clone() as usize
-            };
-            let ending_index = total_spaces_pos
-                .get(word as usize)
-                .unwrap()
-                .clone() as usize;
-            return &phrase[starting_index..ending_index];
-        }
+/*
+ * El `index` de la palabra es un `usize` porque no existen posiciones negativas, y si la palabra no
+ * existe se devuelve un `Err` en lugar de hacer `unwrap()` y lanzar un `panic`
+ */
+fn get_word(phrase: &str, word: usize) -> ExampleResult<&str, LessonError> {
+    match tokenizer::nth_word(phrase, word) {
+        Some(found) => ExampleResult::Ok(found),
+        None => ExampleResult::Err(LessonError::WordNotFound {
+            index: word,
+            words: tokenizer::word_count(phrase),
+        }),
     }
+}
+
+/*
+ * Cada palabra como un `slice` junto con la posición en `bytes` donde empieza, así quien la recibe
+ * puede volver a cortar el texto original alrededor de ella con `&phrase[..start]`
+ */
+pub fn get_words(phrase: &str) -> impl Iterator<Item = (usize, &str)> {
+    tokenizer::words(phrase).map(|token| (token.byte_start, token.text))
+}
 
-    &phrase[..].trim()
+/*
+ * Las líneas también son `slices` del mismo texto, sin el `\n` ni el `\r\n` del final
+ */
+pub fn get_lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines()
 }
 
-fn are_same() {
+pub fn are_same() {
     /*
      * La variable `left` apunta a un `literal string` que es almacenado en la parte de la memoria
      * estática
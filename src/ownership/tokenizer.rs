@@ -0,0 +1,335 @@
+/*
+ * Un `tokenizer` divide un texto en palabras `words` y oraciones `sentences` sin copiar nada, cada
+ * `Token` es un `&str` prestado del texto original junto con su posición en `bytes` y en `chars`.
+ * A diferencia de buscar `b' '` en los `bytes`, aquí se toma en cuenta cualquier espacio `Unicode`
+ * (`'\t'`, `'\n'`, `'\u{3000}'`, ...) y los signos de puntuación (`,`, `¿`, `«`, `—`, `。`, ...)
+ */
+use std::ops::Range;
+
+/*
+ * Los caracteres que pueden quedar dentro de una palabra si están rodeados de letras, como en
+ * `don't` o `well-known`
+ */
+const JOINERS: [char; 4] = ['\'', '’', '-', '‐'];
+
+/*
+ * Los caracteres que terminan una oración, seguidos de un espacio o del final del texto
+ */
+const TERMINATORS: [char; 7] = ['.', '!', '?', '…', '。', '！', '？'];
+
+/*
+ * Los caracteres de cierre que todavía pertenecen a la oración después del terminador, como en
+ * `"¡Hola!"` o `(fin.)`
+ */
+const CLOSERS: [char; 8] = ['"', '\'', '”', '’', '»', ')', ']', '」'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    /*
+     * El `slice` apunta a la misma memoria que el texto original, por eso el `Token` no puede vivir
+     * más que el texto `'a`
+     */
+    pub text: &'a str,
+    pub byte_start: usize,
+    pub char_start: usize,
+}
+
+impl<'a> Token<'a> {
+    pub fn byte_range(&self) -> Range<usize> {
+        self.byte_start..self.byte_start + self.text.len()
+    }
+
+    /*
+     * La longitud en `chars` no es la misma que en `bytes`, `'é'` ocupa dos `bytes` y `'😊'` cuatro
+     */
+    pub fn char_range(&self) -> Range<usize> {
+        self.char_start..self.char_start + self.text.chars().count()
+    }
+
+    /*
+     * Las palabras de un `Token` conservan las posiciones relativas al texto original, así se
+     * pueden recorrer las palabras de una oración sin perder las posiciones
+     */
+    pub fn words(&self) -> Words<'a> {
+        Words {
+            source: self.text,
+            pos: 0,
+            char_pos: 0,
+            base_byte: self.byte_start,
+            base_char: self.char_start,
+        }
+    }
+}
+
+/*
+ * Un espacio en blanco o un signo de puntuación separan palabras
+ */
+pub fn is_separator(c: char) -> bool {
+    c.is_whitespace() || is_punctuation(c)
+}
+
+/*
+ * La biblioteca estándar solo conoce la puntuación `ASCII`, el resto son los bloques `Unicode` de
+ * puntuación más comunes
+ */
+pub fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation()
+        || matches!(c,
+            '¡' | '§' | '«' | '¶' | '·' | '»' | '¿'
+            | '\u{2010}'..='\u{2027}'
+            | '\u{2030}'..='\u{205E}'
+            | '\u{3001}'..='\u{3003}'
+            | '\u{3008}'..='\u{3011}'
+            | '\u{FF01}'..='\u{FF0F}'
+            | '\u{FF1A}'..='\u{FF1F}'
+        )
+}
+
+fn is_joiner(c: char) -> bool {
+    JOINERS.contains(&c)
+}
+
+/*
+ * Iterador sobre las palabras de un texto, cada llamada a `.next()` avanza sobre el texto sin
+ * reservar memoria en el `heap`
+ */
+#[derive(Debug, Clone)]
+pub struct Words<'a> {
+    source: &'a str,
+    pos: usize,
+    char_pos: usize,
+    base_byte: usize,
+    base_char: usize,
+}
+
+impl<'a> Iterator for Words<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.source[self.pos..];
+        let mut chars = rest.char_indices().peekable();
+        let mut skipped = 0;
+        /*
+         * Primero se saltan los separadores, si se termina el texto no hay más palabras
+         */
+        let start = loop {
+            let &(index, c) = chars.peek()?;
+            if !is_separator(c) {
+                break index;
+            }
+            chars.next();
+            skipped += 1;
+        };
+        let mut end = start;
+        let mut len = 0;
+        while let Some((index, c)) = chars.next() {
+            let inside = !is_separator(c)
+                || (is_joiner(c) && chars.peek().is_some_and(|&(_, next)| !is_separator(next)));
+            if !inside {
+                break;
+            }
+            end = index + c.len_utf8();
+            len += 1;
+        }
+        let token = Token {
+            text: &rest[start..end],
+            byte_start: self.base_byte + self.pos + start,
+            char_start: self.base_char + self.char_pos + skipped,
+        };
+        self.pos += end;
+        self.char_pos += skipped + len;
+        Some(token)
+    }
+}
+
+pub fn words(text: &str) -> Words<'_> {
+    Words {
+        source: text,
+        pos: 0,
+        char_pos: 0,
+        base_byte: 0,
+        base_char: 0,
+    }
+}
+
+/*
+ * Iterador sobre las oraciones de un texto, cada oración se devuelve sin los espacios de los
+ * extremos
+ */
+#[derive(Debug, Clone)]
+pub struct Sentences<'a> {
+    source: &'a str,
+    pos: usize,
+    char_pos: usize,
+}
+
+impl<'a> Iterator for Sentences<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = &self.source[self.pos..];
+            if rest.is_empty() {
+                return None;
+            }
+            let mut end = rest.len();
+            let mut seen_terminator = false;
+            for (index, c) in rest.char_indices() {
+                if TERMINATORS.contains(&c) {
+                    seen_terminator = true;
+                } else if seen_terminator && CLOSERS.contains(&c) {
+                    continue;
+                } else if seen_terminator && c.is_whitespace() {
+                    end = index;
+                    break;
+                } else {
+                    seen_terminator = false;
+                }
+            }
+            let raw = &rest[..end];
+            let leading = raw.len() - raw.trim_start().len();
+            let text = raw.trim();
+            let token = Token {
+                text,
+                byte_start: self.pos + leading,
+                char_start: self.char_pos + raw[..leading].chars().count(),
+            };
+            self.pos += end;
+            self.char_pos += raw.chars().count();
+            /*
+             * Las oraciones vacías (solo espacios) se ignoran y se busca la siguiente
+             */
+            if !text.is_empty() {
+                return Some(token);
+            }
+        }
+    }
+}
+
+pub fn sentences(text: &str) -> Sentences<'_> {
+    Sentences {
+        source: text,
+        pos: 0,
+        char_pos: 0,
+    }
+}
+
+/*
+ * Los accesores devuelven `Option<&str>`, si no existe la palabra se devuelve `None` en lugar de
+ * lanzar un `panic`
+ */
+pub fn first_word(text: &str) -> Option<&str> {
+    nth_word(text, 0)
+}
+
+pub fn nth_word(text: &str, n: usize) -> Option<&str> {
+    words(text).nth(n).map(|token| token.text)
+}
+
+pub fn last_word(text: &str) -> Option<&str> {
+    words(text).last().map(|token| token.text)
+}
+
+pub fn word_count(text: &str) -> usize {
+    words(text).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts<'a>(tokens: impl Iterator<Item = Token<'a>>) -> Vec<&'a str> {
+        tokens.map(|token| token.text).collect()
+    }
+
+    #[test]
+    fn unicode_whitespace_splits_words() {
+        let text = "uno\u{00A0}dos\u{3000}tres\tcuatro\u{2003}cinco\r\nseis";
+        assert_eq!(texts(words(text)), ["uno", "dos", "tres", "cuatro", "cinco", "seis"]);
+        assert_eq!(texts(words("«Hola», dijo—¿sí?")), ["Hola", "dijo", "sí"]);
+        assert_eq!(texts(words("東京、大阪。")), ["東京", "大阪"]);
+    }
+
+    #[test]
+    fn joiners_stay_inside_words() {
+        assert_eq!(
+            texts(words("don't well-known rock’n’roll")),
+            ["don't", "well-known", "rock’n’roll"]
+        );
+        /*
+         * Un `joiner` al borde o junto a otro separador no une nada
+         */
+        assert_eq!(
+            texts(words("'hola' fin- -x a--b a - b")),
+            ["hola", "fin", "x", "a", "b", "a", "b"]
+        );
+    }
+
+    #[test]
+    fn sentences_split_on_terminators() {
+        let text = "¿Qué pasó? ¡Nada! Bueno… sigue.  Y luego";
+        assert_eq!(
+            texts(sentences(text)),
+            ["¿Qué pasó?", "¡Nada!", "Bueno…", "sigue.", "Y luego"]
+        );
+        assert_eq!(
+            texts(sentences("Dijo \"¡Hola!\" y se fue. (Fin.) Otra")),
+            ["Dijo \"¡Hola!\"", "y se fue.", "(Fin.)", "Otra"]
+        );
+        /*
+         * Un punto sin espacio después, como en `3.14` o `v1.2`, no termina la oración
+         */
+        assert_eq!(texts(sentences("Pi es 3.14 aprox. Ok")), ["Pi es 3.14 aprox.", "Ok"]);
+        assert_eq!(texts(sentences("これはペン。あれは本。")), ["これはペン。あれは本。"]);
+    }
+
+    #[test]
+    fn empty_input() {
+        for text in ["", "   ", "\u{3000}\n", "¡¿...?!"] {
+            assert_eq!(words(text).next(), None, "{text:?}");
+            assert_eq!(first_word(text), None);
+            assert_eq!(last_word(text), None);
+            assert_eq!(word_count(text), 0);
+        }
+        assert_eq!(sentences("").next(), None);
+        assert_eq!(sentences(" \n\t ").next(), None);
+    }
+
+    #[test]
+    fn word_accessors() {
+        let text = "  Hello, world! From Rust lang ";
+        assert_eq!(first_word(text), Some("Hello"));
+        assert_eq!(nth_word(text, 3), Some("Rust"));
+        assert_eq!(nth_word(text, 5), None);
+        assert_eq!(last_word(text), Some("lang"));
+        assert_eq!(word_count(text), 5);
+    }
+
+    #[test]
+    fn byte_and_char_offsets() {
+        let text = "año 😊 niño";
+        let tokens: Vec<Token> = words(text).collect();
+        let offsets: Vec<(usize, usize)> =
+            tokens.iter().map(|token| (token.byte_start, token.char_start)).collect();
+        assert_eq!(offsets, [(0, 0), (5, 4), (10, 6)]);
+        for token in &tokens {
+            assert_eq!(&text[token.byte_range()], token.text);
+            let chars: String = text
+                .chars()
+                .skip(token.char_range().start)
+                .take(token.char_range().len())
+                .collect();
+            assert_eq!(chars, token.text);
+        }
+        /*
+         * Las palabras de una oración conservan las posiciones del texto completo
+         */
+        let text = "¿Sí? Él comió piña.";
+        let second = sentences(text).nth(1).unwrap();
+        assert_eq!((second.byte_start, second.char_start), (7, 5));
+        let pina = second.words().last().unwrap();
+        assert_eq!(pina.text, "piña");
+        assert_eq!(&text[pina.byte_range()], "piña");
+        assert_eq!(pina.char_start, text.chars().position(|c| c == 'p').unwrap());
+    }
+}
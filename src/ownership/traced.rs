@@ -0,0 +1,517 @@
+/*
+ * Un `move` en `Rust` solo copia los `bytes` del valor a otro lugar, no ejecuta código, por eso no
+ * se puede observar directamente. `Traced<T>` envuelve un valor y anota en un diario `journal` de
+ * cada `thread` cuando se crea, cuando se clona y cuando se libera con `Drop`, los movimientos se
+ * anotan llamando a `.move_into()` y `.move_out()`
+ *
+ *  let s1 = Traced::new("s1", String::from("hello"));
+ *  takes_ownership(s1.move_into("takes_ownership"));
+ *
+ *  create s1
+ *  move s1 into takes_ownership
+ *  drop s1
+ *
+ * Las variables locales se liberan en orden inverso a su declaración, pero los campos de un
+ * `struct`, los elementos de una tupla y los de un `Vec` se liberan en orden
+ */
+use std::cell::RefCell;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventKind {
+    Create,
+    MoveIn(String),
+    MoveOut(String),
+    Clone { from: usize },
+    Drop,
+    /*
+     * Una marca escrita por la lección, como el final de un `scope`
+     */
+    Mark,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub kind: EventKind,
+    pub id: usize,
+    pub label: String,
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            EventKind::Create => write!(f, "create {}", self.label),
+            EventKind::MoveIn(place) => write!(f, "move {} into {place}", self.label),
+            EventKind::MoveOut(place) => write!(f, "move {} out of {place}", self.label),
+            EventKind::Clone { from } => write!(f, "clone #{from} as {}", self.label),
+            EventKind::Drop => write!(f, "drop {}", self.label),
+            EventKind::Mark => write!(f, "-- {}", self.label),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Journal {
+    events: Vec<Event>,
+    next_id: usize,
+}
+
+thread_local! {
+    static JOURNAL: RefCell<Journal> = RefCell::new(Journal::default());
+}
+
+fn record(kind: EventKind, id: usize, label: &str) {
+    JOURNAL.with(|journal| {
+        journal.borrow_mut().events.push(Event {
+            kind,
+            id,
+            label: label.to_string(),
+        })
+    });
+}
+
+fn next_id() -> usize {
+    JOURNAL.with(|journal| {
+        let mut journal = journal.borrow_mut();
+        journal.next_id += 1;
+        journal.next_id
+    })
+}
+
+/*
+ * Las funciones del diario solo ven los eventos del `thread` actual, así las pruebas que corren en
+ * paralelo no se mezclan
+ */
+pub mod journal {
+    use super::{record, Event, EventKind, JOURNAL};
+
+    pub fn events() -> Vec<Event> {
+        JOURNAL.with(|journal| journal.borrow().events.clone())
+    }
+
+    /*
+     * Devuelve los eventos y vacía el diario
+     */
+    pub fn take() -> Vec<Event> {
+        JOURNAL.with(|journal| std::mem::take(&mut journal.borrow_mut().events))
+    }
+
+    pub fn clear() {
+        JOURNAL.with(|journal| journal.borrow_mut().events.clear());
+    }
+
+    pub fn mark(text: &str) {
+        record(EventKind::Mark, 0, text);
+    }
+
+    /*
+     * Las etiquetas de los valores en el orden en que se liberaron
+     */
+    pub fn drop_order() -> Vec<String> {
+        JOURNAL.with(|journal| {
+            journal
+                .borrow()
+                .events
+                .iter()
+                .filter(|event| event.kind == EventKind::Drop)
+                .map(|event| event.label.clone())
+                .collect()
+        })
+    }
+
+    /*
+     * Todos los eventos como texto, una línea por evento
+     */
+    pub fn lines() -> Vec<String> {
+        JOURNAL.with(|journal| {
+            journal
+                .borrow()
+                .events
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        })
+    }
+}
+
+pub struct Traced<T> {
+    value: T,
+    id: usize,
+    label: String,
+}
+
+impl<T> Traced<T> {
+    pub fn new(label: &str, value: T) -> Self {
+        let id = next_id();
+        record(EventKind::Create, id, label);
+        Self {
+            value,
+            id,
+            label: label.to_string(),
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /*
+     * Anota que el valor entra a `place`, una función o una variable, y lo devuelve sin cambios
+     */
+    pub fn move_into(self, place: &str) -> Self {
+        record(EventKind::MoveIn(place.to_string()), self.id, &self.label);
+        self
+    }
+
+    pub fn move_out(self, place: &str) -> Self {
+        record(EventKind::MoveOut(place.to_string()), self.id, &self.label);
+        self
+    }
+}
+
+/*
+ * El clon es un valor nuevo con su propio `id`, su etiqueta lleva una comilla `s1'`
+ */
+impl<T: Clone> Clone for Traced<T> {
+    fn clone(&self) -> Self {
+        let id = next_id();
+        let label = format!("{}'", self.label);
+        record(EventKind::Clone { from: self.id }, id, &label);
+        Self {
+            value: self.value.clone(),
+            id,
+            label,
+        }
+    }
+}
+
+impl<T> Drop for Traced<T> {
+    fn drop(&mut self) {
+        record(EventKind::Drop, self.id, &self.label);
+    }
+}
+
+impl<T> Deref for Traced<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Traced<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Traced<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}({:?})", self.label, self.id, self.value)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Traced<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+/*
+ * Las lecciones de `ownership`, cada una devuelve el diario de lo que paso
+ */
+pub type Lesson = fn() -> Vec<String>;
+
+pub const LESSONS: [(&str, Lesson); 4] = [
+    ("string", string_lesson),
+    ("scope", scope_lesson),
+    ("containers", containers_lesson),
+    ("early return", early_return_lesson),
+];
+
+fn takes_ownership(some_string: Traced<String>) {
+    journal::mark(&format!("takes_ownership prints {some_string}"));
+}
+
+fn gives_ownership() -> Traced<String> {
+    Traced::new("some_string", String::from("yours")).move_out("gives_ownership")
+}
+
+fn takes_and_gives_back(a_string: Traced<String>) -> Traced<String> {
+    a_string.move_out("takes_and_gives_back")
+}
+
+pub fn string_lesson() -> Vec<String> {
+    journal::clear();
+    {
+        let s1 = gives_ownership();
+        let s2 = Traced::new("s2", String::from("hello"));
+        let s3 = takes_and_gives_back(s2.move_into("takes_and_gives_back"));
+        let s4 = s3.clone();
+        takes_ownership(s4.move_into("takes_ownership"));
+        journal::mark(&format!("s1 = {s1}, s3 = {s3}, end of scope"));
+    }
+    journal::lines()
+}
+
+pub fn scope_lesson() -> Vec<String> {
+    journal::clear();
+    {
+        let _outer = Traced::new("outer", 1);
+        {
+            let _inner = Traced::new("inner", 2);
+            journal::mark("end of inner scope");
+        }
+        let _last = Traced::new("last", 3);
+        journal::mark("end of outer scope");
+    }
+    journal::lines()
+}
+
+struct Pair {
+    _first: Traced<i32>,
+    _second: Traced<i32>,
+}
+
+pub fn containers_lesson() -> Vec<String> {
+    journal::clear();
+    {
+        let _pair = Pair {
+            _first: Traced::new("pair.first", 1),
+            _second: Traced::new("pair.second", 2),
+        };
+        let _tuple = (Traced::new("tuple.0", 'a'), Traced::new("tuple.1", 'b'));
+        let _items: Vec<Traced<usize>> = (0..2)
+            .map(|index| Traced::new(&format!("items[{index}]"), index))
+            .collect();
+        journal::mark("end of scope, locals in reverse, contents in order");
+    }
+    journal::lines()
+}
+
+/*
+ * Un `return` anticipado libera las variables que ya existen, las que no se alcanzaron a crear no
+ * aparecen en el diario
+ */
+fn early(stop: bool) -> usize {
+    let _a = Traced::new("a", 1);
+    if stop {
+        journal::mark("early return");
+        return 1;
+    }
+    let _b = Traced::new("b", 2);
+    2
+}
+
+pub fn early_return_lesson() -> Vec<String> {
+    journal::clear();
+    early(true);
+    early(false);
+    journal::lines()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+     * Los eventos como texto, sin el `id` del original en los clones porque depende de las pruebas
+     * que corrieron antes en el mismo `thread`
+     */
+    fn sequence() -> Vec<String> {
+        journal::take()
+            .iter()
+            .map(|event| match event.kind {
+                EventKind::Clone { .. } => format!("clone as {}", event.label),
+                _ => event.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn moves_into_and_out_of_functions() {
+        string_lesson();
+        assert_eq!(
+            sequence(),
+            [
+                "create some_string",
+                "move some_string out of gives_ownership",
+                "create s2",
+                "move s2 into takes_and_gives_back",
+                "move s2 out of takes_and_gives_back",
+                "clone as s2'",
+                "move s2' into takes_ownership",
+                "-- takes_ownership prints hello",
+                "drop s2'",
+                "-- s1 = yours, s3 = hello, end of scope",
+                "drop s2",
+                "drop some_string",
+            ]
+        );
+    }
+
+    #[test]
+    fn moved_values_are_dropped_once() {
+        journal::clear();
+        {
+            let s1 = Traced::new("s1", String::from("hello"));
+            let _s2 = s1.move_into("s2");
+            journal::mark("end of scope");
+        }
+        assert_eq!(
+            sequence(),
+            ["create s1", "move s1 into s2", "-- end of scope", "drop s1"]
+        );
+    }
+
+    #[test]
+    fn clones_are_new_values_dropped_on_their_own() {
+        journal::clear();
+        let original = Traced::new("v", vec![1, 2]);
+        let copy = original.clone();
+        assert_ne!(copy.id(), original.id());
+        assert_eq!(*copy, *original);
+        assert!(journal::events().iter().any(|event| {
+            event.id == copy.id()
+                && event.kind
+                    == EventKind::Clone {
+                        from: original.id(),
+                    }
+        }));
+        drop(original);
+        journal::mark("original dropped");
+        drop(copy);
+        assert_eq!(
+            sequence(),
+            [
+                "create v",
+                "clone as v'",
+                "drop v",
+                "-- original dropped",
+                "drop v'"
+            ]
+        );
+    }
+
+    #[test]
+    fn shadowing_keeps_the_hidden_value_until_the_end_of_scope() {
+        journal::clear();
+        {
+            let x = Traced::new("x1", 1);
+            let x = Traced::new("x2", *x + 1);
+            journal::mark(&format!("x = {x}"));
+        }
+        assert_eq!(
+            sequence(),
+            ["create x1", "create x2", "-- x = 2", "drop x2", "drop x1"]
+        );
+    }
+
+    #[test]
+    fn assignment_drops_the_previous_value() {
+        journal::clear();
+        let mut value = Traced::new("old", 1);
+        journal::mark("before");
+        value = Traced::new("new", *value + 1);
+        journal::mark("after");
+        drop(value);
+        assert_eq!(
+            sequence(),
+            [
+                "create old",
+                "-- before",
+                "create new",
+                "drop old",
+                "-- after",
+                "drop new"
+            ]
+        );
+    }
+
+    #[test]
+    fn early_drop_runs_before_the_end_of_scope() {
+        journal::clear();
+        {
+            let a = Traced::new("a", 1);
+            let _b = Traced::new("b", 2);
+            drop(a);
+            journal::mark("end of scope");
+        }
+        assert_eq!(
+            sequence(),
+            [
+                "create a",
+                "create b",
+                "drop a",
+                "-- end of scope",
+                "drop b"
+            ]
+        );
+    }
+
+    #[test]
+    fn locals_drop_in_reverse_and_contents_in_order() {
+        containers_lesson();
+        assert_eq!(
+            journal::drop_order(),
+            [
+                "items[0]",
+                "items[1]",
+                "tuple.0",
+                "tuple.1",
+                "pair.first",
+                "pair.second"
+            ]
+        );
+    }
+
+    #[test]
+    fn early_return_drops_only_what_was_created() {
+        early_return_lesson();
+        assert_eq!(
+            sequence(),
+            [
+                "create a",
+                "-- early return",
+                "drop a",
+                "create a",
+                "create b",
+                "drop b",
+                "drop a"
+            ]
+        );
+    }
+
+    #[test]
+    fn vector_removal_drops_the_element_when_it_is_discarded() {
+        journal::clear();
+        let mut items: Vec<_> = ["a", "b", "c"]
+            .into_iter()
+            .map(|label| Traced::new(label, ()))
+            .collect();
+        let b = items.remove(1);
+        journal::mark("removed b");
+        items.truncate(1);
+        journal::mark("truncated");
+        drop(b);
+        drop(items);
+        assert_eq!(
+            sequence(),
+            [
+                "create a",
+                "create b",
+                "create c",
+                "-- removed b",
+                "drop c",
+                "-- truncated",
+                "drop b",
+                "drop a"
+            ]
+        );
+    }
+}
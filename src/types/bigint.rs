@@ -0,0 +1,1403 @@
+/*
+ * Los enteros primitivos tienen un tamaño fijo, el más grande es `u128` y `20!` ya casi no cabe en
+ * un `u64`. Un entero de precisión arbitraria guarda sus dígitos en un `Vec` y crece lo necesario,
+ * cada dígito `limb` es un `u32`, así el número está en base 2^32 y el producto de dos dígitos cabe
+ * en un `u64`
+ *
+ *      12345678901234567890123  =  [0x7142_44CB, 0x42B6_4E76, 0x0000_029D]
+ *
+ * El primer `limb` es el menos significativo
+ *
+ * `BigUint` solo representa números positivos y el cero, `BigInt` agrega el signo
+ */
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Shl, Shr, Sub, SubAssign,
+};
+use std::str::FromStr;
+
+/*
+ * Debajo de este número de `limbs` la multiplicación de escuela es más rápida que `Karatsuba`
+ */
+const KARATSUBA_THRESHOLD: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBigIntError {
+    Empty,
+    InvalidDigit { index: usize, ch: char },
+    InvalidRadix(u32),
+    /*
+     * Un número negativo no cabe en un `BigUint`
+     */
+    Negative,
+}
+
+impl fmt::Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBigIntError::Empty => write!(f, "cannot parse integer from empty string"),
+            ParseBigIntError::InvalidDigit { index, ch } => {
+                write!(f, "invalid digit {ch:?} found at position {index}")
+            }
+            ParseBigIntError::InvalidRadix(radix) => write!(f, "radix {radix} is not in 2..=36"),
+            ParseBigIntError::Negative => write!(f, "a BigUint cannot be negative"),
+        }
+    }
+}
+
+impl std::error::Error for ParseBigIntError {}
+
+/*
+ * Las operaciones sobre `slices` de `limbs`, los resultados pueden tener ceros de sobra al final y
+ * `trim()` los quita
+ */
+fn trim(mut limbs: Vec<u32>) -> Vec<u32> {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+    limbs
+}
+
+fn cmp_limbs(a: &[u32], b: &[u32]) -> Ordering {
+    let a = &a[..a.iter().rposition(|&limb| limb != 0).map_or(0, |i| i + 1)];
+    let b = &b[..b.iter().rposition(|&limb| limb != 0).map_or(0, |i| i + 1)];
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut out = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, &limb) in long.iter().enumerate() {
+        let sum = limb as u64 + short.get(i).copied().unwrap_or(0) as u64 + carry;
+        out.push(sum as u32);
+        carry = sum >> 32;
+    }
+    if carry > 0 {
+        out.push(carry as u32);
+    }
+    out
+}
+
+/*
+ * `a - b` con `a >= b`
+ */
+fn sub_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut out = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &limb) in a.iter().enumerate() {
+        let diff = limb as i64 - b.get(i).copied().unwrap_or(0) as i64 - borrow;
+        out.push(diff as u32);
+        borrow = i64::from(diff < 0);
+    }
+    trim(out)
+}
+
+/*
+ * Suma `b` dentro de `a` a partir del `limb` `offset`, como sumar en columnas desplazadas
+ */
+fn add_at(a: &mut Vec<u32>, b: &[u32], offset: usize) {
+    if a.len() < offset + b.len() {
+        a.resize(offset + b.len(), 0);
+    }
+    let mut carry = 0u64;
+    let mut i = 0;
+    while i < b.len() || carry > 0 {
+        if offset + i == a.len() {
+            a.push(0);
+        }
+        let sum = a[offset + i] as u64 + b.get(i).copied().unwrap_or(0) as u64 + carry;
+        a[offset + i] = sum as u32;
+        carry = sum >> 32;
+        i += 1;
+    }
+}
+
+fn schoolbook(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut out = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let cur = out[i + j] as u64 + x as u64 * y as u64 + carry;
+            out[i + j] = cur as u32;
+            carry = cur >> 32;
+        }
+        out[i + b.len()] = carry as u32;
+    }
+    out
+}
+
+/*
+ * `Karatsuba` parte cada número en mitad alta y baja, `a = a1·B + a0`, y usa tres productos en
+ * lugar de cuatro
+ *
+ *      a·b = z2·B² + z1·B + z0,  z0 = a0·b0,  z2 = a1·b1,  z1 = (a0 + a1)(b0 + b1) - z0 - z2
+ */
+fn mul_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return trim(schoolbook(a, b));
+    }
+    let half = a.len().max(b.len()) / 2;
+    let split = |x: &'_ [u32]| -> (Vec<u32>, Vec<u32>) {
+        let (low, high) = x.split_at(half.min(x.len()));
+        (trim(low.to_vec()), high.to_vec())
+    };
+    let (a0, a1) = split(a);
+    let (b0, b1) = split(b);
+    let z0 = mul_limbs(&a0, &b0);
+    let z2 = mul_limbs(&a1, &b1);
+    let z1 = mul_limbs(&add_limbs(&a0, &a1), &add_limbs(&b0, &b1));
+    let z1 = sub_limbs(&sub_limbs(&z1, &z0), &z2);
+    let mut out = z0;
+    add_at(&mut out, &z1, half);
+    add_at(&mut out, &z2, 2 * half);
+    trim(out)
+}
+
+fn div_small(a: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut rem = 0u64;
+    for i in (0..a.len()).rev() {
+        let cur = (rem << 32) | a[i] as u64;
+        quotient[i] = (cur / divisor as u64) as u32;
+        rem = cur % divisor as u64;
+    }
+    (trim(quotient), rem as u32)
+}
+
+fn shl_limbs(a: &[u32], bits: usize) -> Vec<u32> {
+    if a.is_empty() {
+        return Vec::new();
+    }
+    let (limbs, bits) = (bits / 32, bits % 32);
+    let mut out = vec![0u32; limbs];
+    if bits == 0 {
+        out.extend_from_slice(a);
+    } else {
+        let mut carry = 0u32;
+        for &limb in a {
+            out.push((limb << bits) | carry);
+            carry = limb >> (32 - bits);
+        }
+        out.push(carry);
+    }
+    trim(out)
+}
+
+fn shr_limbs(a: &[u32], bits: usize) -> Vec<u32> {
+    let (limbs, bits) = (bits / 32, bits % 32);
+    if limbs >= a.len() {
+        return Vec::new();
+    }
+    let a = &a[limbs..];
+    if bits == 0 {
+        return a.to_vec();
+    }
+    let out = (0..a.len())
+        .map(|i| (a[i] >> bits) | a.get(i + 1).map_or(0, |&next| next << (32 - bits)))
+        .collect();
+    trim(out)
+}
+
+/*
+ * La división larga del algoritmo D de `Knuth`, se estima cada dígito del cociente con los dos
+ * primeros dígitos del residuo y el primero del divisor, el estimado se pasa a lo más por dos.
+ * Primero se desplazan los dos números para que el divisor empiece con un bit en uno
+ */
+fn divmod_limbs(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if cmp_limbs(u, v) == Ordering::Less {
+        return (Vec::new(), trim(u.to_vec()));
+    }
+    if v.len() == 1 {
+        let (quotient, rem) = div_small(u, v[0]);
+        return (quotient, trim(vec![rem]));
+    }
+    let shift = v[v.len() - 1].leading_zeros() as usize;
+    let vn = shl_limbs(v, shift);
+    let mut un = shl_limbs(u, shift);
+    un.resize(u.len() + 1, 0);
+    let n = vn.len();
+    let m = u.len() - n;
+    let base = 1u64 << 32;
+    let mut quotient = vec![0u32; m + 1];
+    for j in (0..=m).rev() {
+        let num = ((un[j + n] as u64) << 32) | un[j + n - 1] as u64;
+        let mut qhat = num / vn[n - 1] as u64;
+        let mut rhat = num % vn[n - 1] as u64;
+        while qhat >= base || qhat * vn[n - 2] as u64 > ((rhat << 32) | un[j + n - 2] as u64) {
+            qhat -= 1;
+            rhat += vn[n - 1] as u64;
+            if rhat >= base {
+                break;
+            }
+        }
+        let mut borrow = 0i64;
+        let mut carry = 0u64;
+        for i in 0..n {
+            let product = qhat * vn[i] as u64 + carry;
+            carry = product >> 32;
+            let diff = un[i + j] as i64 - borrow - (product & 0xFFFF_FFFF) as i64;
+            un[i + j] = diff as u32;
+            borrow = i64::from(diff < 0);
+        }
+        let diff = un[j + n] as i64 - borrow - carry as i64;
+        un[j + n] = diff as u32;
+        /*
+         * Si el estimado era uno de más el residuo quedó negativo, se regresa el divisor
+         */
+        if diff < 0 {
+            qhat -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let sum = un[i + j] as u64 + vn[i] as u64 + carry;
+                un[i + j] = sum as u32;
+                carry = sum >> 32;
+            }
+            un[j + n] = un[j + n].wrapping_add(carry as u32);
+        }
+        quotient[j] = qhat as u32;
+    }
+    (trim(quotient), shr_limbs(&un[..n], shift))
+}
+
+/*
+ * El número de dígitos en `radix` que siempre cabe en un `u32`, y `radix` elevado a ese número
+ */
+fn chunk(radix: u32) -> (usize, u32) {
+    let mut digits = 0;
+    let mut power = 1u32;
+    while let Some(next) = power.checked_mul(radix) {
+        power = next;
+        digits += 1;
+    }
+    (digits, power)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn one() -> Self {
+        Self { limbs: vec![1] }
+    }
+
+    fn from_limbs(limbs: Vec<u32>) -> Self {
+        Self { limbs: trim(limbs) }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_even(&self) -> bool {
+        self.limbs.first().is_none_or(|limb| limb % 2 == 0)
+    }
+
+    /*
+     * El número de bits necesarios, el cero usa cero bits
+     */
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(last) => self.limbs.len() * 32 - last.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    pub fn bit(&self, index: usize) -> bool {
+        self.limbs
+            .get(index / 32)
+            .is_some_and(|limb| limb >> (index % 32) & 1 == 1)
+    }
+
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        match cmp_limbs(&self.limbs, &other.limbs) {
+            Ordering::Less => None,
+            _ => Some(Self::from_limbs(sub_limbs(&self.limbs, &other.limbs))),
+        }
+    }
+
+    pub fn checked_div(&self, other: &BigUint) -> Option<BigUint> {
+        self.checked_div_rem(other).map(|(quotient, _)| quotient)
+    }
+
+    pub fn checked_div_rem(&self, other: &BigUint) -> Option<(BigUint, BigUint)> {
+        if other.is_zero() {
+            return None;
+        }
+        let (quotient, rem) = divmod_limbs(&self.limbs, &other.limbs);
+        Some((Self::from_limbs(quotient), Self::from_limbs(rem)))
+    }
+
+    /*
+     * Igual que con los primitivos, dividir entre cero lanza un `panic`
+     */
+    pub fn div_rem(&self, other: &BigUint) -> (BigUint, BigUint) {
+        self.checked_div_rem(other)
+            .unwrap_or_else(|| panic!("attempt to divide by zero"))
+    }
+
+    /*
+     * Exponenciación rápida, se eleva al cuadrado por cada bit del exponente
+     */
+    pub fn pow(&self, mut exp: u32) -> BigUint {
+        let mut base = self.clone();
+        let mut result = BigUint::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    /*
+     * `self^exp mod modulus` sin calcular la potencia completa, se reduce después de cada producto
+     */
+    pub fn modpow(&self, exp: &BigUint, modulus: &BigUint) -> BigUint {
+        if modulus.is_zero() {
+            panic!("attempt to calculate the remainder with a divisor of zero");
+        }
+        let mut result = BigUint::one() % modulus;
+        let base = self % modulus;
+        for bit in (0..exp.bits()).rev() {
+            result = &(&result * &result) % modulus;
+            if exp.bit(bit) {
+                result = &(&result * &base) % modulus;
+            }
+        }
+        result
+    }
+
+    /*
+     * El máximo común divisor con el algoritmo de `Euclides`, `gcd(a, 0) = a`
+     */
+    pub fn gcd(&self, other: &BigUint) -> BigUint {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let rem = &a % &b;
+            a = b;
+            b = rem;
+        }
+        a
+    }
+
+    /*
+     * Acepta `_` entre los dígitos, igual que los literales de `Rust`
+     */
+    pub fn from_str_radix(text: &str, radix: u32) -> Result<BigUint, ParseBigIntError> {
+        Self::parse_digits(text, radix, 0, false)
+    }
+
+    /*
+     * Como en los literales de `Rust`, después de un prefijo `0x`, `0o` o `0b` el primer dígito ya
+     * puede ser un `_`, `0x_ff`, pero sin prefijo el número no puede comenzar con `_`
+     */
+    fn parse_digits(
+        text: &str,
+        radix: u32,
+        offset: usize,
+        prefixed: bool,
+    ) -> Result<BigUint, ParseBigIntError> {
+        if !(2..=36).contains(&radix) {
+            return Err(ParseBigIntError::InvalidRadix(radix));
+        }
+        let mut digits = Vec::with_capacity(text.len());
+        for (index, ch) in text.char_indices() {
+            if ch == '_' && (index > 0 || prefixed) {
+                continue;
+            }
+            match ch.to_digit(radix) {
+                Some(digit) => digits.push(digit),
+                None => {
+                    return Err(ParseBigIntError::InvalidDigit {
+                        index: offset + index,
+                        ch,
+                    })
+                }
+            }
+        }
+        if digits.is_empty() {
+            return Err(ParseBigIntError::Empty);
+        }
+        let (size, _) = chunk(radix);
+        let mut limbs: Vec<u32> = Vec::new();
+        for group in digits.chunks(size) {
+            let multiplier = radix.pow(group.len() as u32);
+            let value = group.iter().fold(0u32, |acc, &digit| acc * radix + digit);
+            let mut carry = value as u64;
+            for limb in &mut limbs {
+                let cur = *limb as u64 * multiplier as u64 + carry;
+                *limb = cur as u32;
+                carry = cur >> 32;
+            }
+            if carry > 0 {
+                limbs.push(carry as u32);
+            }
+        }
+        Ok(Self::from_limbs(limbs))
+    }
+
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix {radix} is not in 2..=36");
+        if self.is_zero() {
+            return "0".to_string();
+        }
+        let (size, power) = chunk(radix);
+        let mut groups = Vec::new();
+        let mut rest = self.limbs.clone();
+        while !rest.is_empty() {
+            let (quotient, rem) = div_small(&rest, power);
+            groups.push(rem);
+            rest = quotient;
+        }
+        let mut out = String::new();
+        for (i, group) in groups.iter().rev().enumerate() {
+            let mut digits = Vec::with_capacity(size);
+            let mut value = *group;
+            while value > 0 {
+                digits.push(std::char::from_digit(value % radix, radix).unwrap_or('?'));
+                value /= radix;
+            }
+            if i > 0 {
+                digits.resize(size, '0');
+            }
+            out.extend(digits.iter().rev());
+        }
+        out
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        self.to_u128().and_then(|value| u64::try_from(value).ok())
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        Some(
+            self.limbs
+                .iter()
+                .rev()
+                .fold(0u128, |acc, &limb| (acc << 32) | limb as u128),
+        )
+    }
+}
+
+/*
+ * `n!`, el ejemplo clásico que desborda cualquier primitivo, `35!` ya no cabe en un `u128`
+ */
+pub fn factorial(n: u32) -> BigUint {
+    (1..=n).map(BigUint::from).product()
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_limbs(&self.limbs, &other.limbs)
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! biguint_from_unsigned {
+    ($($t:ty),*) => {$(
+        impl From<$t> for BigUint {
+            fn from(value: $t) -> Self {
+                let mut value = value as u128;
+                let mut limbs = Vec::new();
+                while value > 0 {
+                    limbs.push(value as u32);
+                    value >>= 32;
+                }
+                Self { limbs }
+            }
+        }
+    )*};
+}
+
+biguint_from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! biguint_try_from_signed {
+    ($($t:ty),*) => {$(
+        impl TryFrom<$t> for BigUint {
+            type Error = ParseBigIntError;
+
+            fn try_from(value: $t) -> Result<Self, Self::Error> {
+                if value < 0 {
+                    return Err(ParseBigIntError::Negative);
+                }
+                Ok(BigUint::from(value as u128))
+            }
+        }
+    )*};
+}
+
+biguint_try_from_signed!(i8, i16, i32, i64, i128, isize);
+
+/*
+ * Los operadores se implementan entre referencias `&a + &b` para no mover los números, las demás
+ * combinaciones con valores se apoyan en esa
+ */
+macro_rules! forward_binop {
+    ($t:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign:ident) => {
+        impl $trait<$t> for $t {
+            type Output = $t;
+
+            fn $method(self, other: $t) -> $t {
+                (&self).$method(&other)
+            }
+        }
+
+        impl $trait<&$t> for $t {
+            type Output = $t;
+
+            fn $method(self, other: &$t) -> $t {
+                (&self).$method(other)
+            }
+        }
+
+        impl $trait<$t> for &$t {
+            type Output = $t;
+
+            fn $method(self, other: $t) -> $t {
+                self.$method(&other)
+            }
+        }
+
+        impl $assign_trait<$t> for $t {
+            fn $assign(&mut self, other: $t) {
+                *self = (&*self).$method(&other);
+            }
+        }
+
+        impl $assign_trait<&$t> for $t {
+            fn $assign(&mut self, other: &$t) {
+                *self = (&*self).$method(other);
+            }
+        }
+    };
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        BigUint::from_limbs(add_limbs(&self.limbs, &other.limbs))
+    }
+}
+
+/*
+ * Como con `u64`, restar un número más grande lanza un `panic`, `checked_sub()` no
+ */
+impl Sub<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn sub(self, other: &BigUint) -> BigUint {
+        self.checked_sub(other)
+            .unwrap_or_else(|| panic!("attempt to subtract with overflow"))
+    }
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        BigUint::from_limbs(mul_limbs(&self.limbs, &other.limbs))
+    }
+}
+
+impl Div<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn div(self, other: &BigUint) -> BigUint {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn rem(self, other: &BigUint) -> BigUint {
+        self.div_rem(other).1
+    }
+}
+
+forward_binop!(BigUint, Add, add, AddAssign, add_assign);
+forward_binop!(BigUint, Sub, sub, SubAssign, sub_assign);
+forward_binop!(BigUint, Mul, mul, MulAssign, mul_assign);
+forward_binop!(BigUint, Div, div, DivAssign, div_assign);
+forward_binop!(BigUint, Rem, rem, RemAssign, rem_assign);
+
+impl Shl<usize> for &BigUint {
+    type Output = BigUint;
+
+    fn shl(self, bits: usize) -> BigUint {
+        BigUint::from_limbs(shl_limbs(&self.limbs, bits))
+    }
+}
+
+impl Shl<usize> for BigUint {
+    type Output = BigUint;
+
+    fn shl(self, bits: usize) -> BigUint {
+        &self << bits
+    }
+}
+
+impl Shr<usize> for &BigUint {
+    type Output = BigUint;
+
+    fn shr(self, bits: usize) -> BigUint {
+        BigUint::from_limbs(shr_limbs(&self.limbs, bits))
+    }
+}
+
+impl Shr<usize> for BigUint {
+    type Output = BigUint;
+
+    fn shr(self, bits: usize) -> BigUint {
+        &self >> bits
+    }
+}
+
+impl Sum for BigUint {
+    fn sum<I: Iterator<Item = BigUint>>(iter: I) -> Self {
+        iter.fold(BigUint::zero(), |acc, value| acc + value)
+    }
+}
+
+impl Product for BigUint {
+    fn product<I: Iterator<Item = BigUint>>(iter: I) -> Self {
+        iter.fold(BigUint::one(), |acc, value| acc * value)
+    }
+}
+
+/*
+ * Los prefijos `0x`, `0o` y `0b` eligen la base, sin prefijo se lee en decimal
+ */
+fn split_radix(text: &str) -> (&str, u32, usize) {
+    for (prefix, radix) in [
+        ("0x", 16),
+        ("0X", 16),
+        ("0o", 8),
+        ("0O", 8),
+        ("0b", 2),
+        ("0B", 2),
+    ] {
+        if let Some(rest) = text.strip_prefix(prefix) {
+            return (rest, radix, prefix.len());
+        }
+    }
+    (text, 10, 0)
+}
+
+impl FromStr for BigUint {
+    type Err = ParseBigIntError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.parse::<BigInt>()? {
+            value if value.is_negative() => Err(ParseBigIntError::Negative),
+            value => Ok(value.magnitude),
+        }
+    }
+}
+
+/*
+ * Se usa `pad_integral()` para respetar el ancho, el relleno con ceros y el prefijo `#` igual que
+ * los primitivos, `{:>10}`, `{:08}`, `{:#x}`
+ */
+macro_rules! radix_fmt {
+    ($t:ty, $trait:ident, $radix:expr, $prefix:expr, $upper:expr) => {
+        impl fmt::$trait for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let (negative, magnitude) = self.parts();
+                let digits = magnitude.to_str_radix($radix);
+                let digits = if $upper {
+                    digits.to_uppercase()
+                } else {
+                    digits
+                };
+                f.pad_integral(!negative, $prefix, &digits)
+            }
+        }
+    };
+}
+
+impl BigUint {
+    fn parts(&self) -> (bool, &BigUint) {
+        (false, self)
+    }
+}
+
+radix_fmt!(BigUint, Display, 10, "", false);
+radix_fmt!(BigUint, Binary, 2, "0b", false);
+radix_fmt!(BigUint, Octal, 8, "0o", false);
+radix_fmt!(BigUint, LowerHex, 16, "0x", false);
+radix_fmt!(BigUint, UpperHex, 16, "0x", true);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    /*
+     * El cero nunca es negativo, así dos ceros siempre son iguales
+     */
+    negative: bool,
+    magnitude: BigUint,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn one() -> Self {
+        BigUint::one().into()
+    }
+
+    fn from_parts(negative: bool, magnitude: BigUint) -> Self {
+        Self {
+            negative: negative && !magnitude.is_zero(),
+            magnitude,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_positive(&self) -> bool {
+        !self.negative && !self.is_zero()
+    }
+
+    pub fn signum(&self) -> i32 {
+        match (self.negative, self.is_zero()) {
+            (true, _) => -1,
+            (false, true) => 0,
+            (false, false) => 1,
+        }
+    }
+
+    pub fn abs(&self) -> BigInt {
+        Self::from_parts(false, self.magnitude.clone())
+    }
+
+    pub fn unsigned_abs(&self) -> BigUint {
+        self.magnitude.clone()
+    }
+
+    fn parts(&self) -> (bool, &BigUint) {
+        (self.negative, &self.magnitude)
+    }
+
+    /*
+     * Como los primitivos, el cociente se trunca hacia el cero y el residuo lleva el signo del
+     * dividendo, `-7 / 2 = -3` y `-7 % 2 = -1`
+     */
+    pub fn checked_div_rem(&self, other: &BigInt) -> Option<(BigInt, BigInt)> {
+        let (quotient, rem) = self.magnitude.checked_div_rem(&other.magnitude)?;
+        Some((
+            Self::from_parts(self.negative != other.negative, quotient),
+            Self::from_parts(self.negative, rem),
+        ))
+    }
+
+    pub fn div_rem(&self, other: &BigInt) -> (BigInt, BigInt) {
+        self.checked_div_rem(other)
+            .unwrap_or_else(|| panic!("attempt to divide by zero"))
+    }
+
+    /*
+     * El residuo siempre positivo, como `i64::rem_euclid()`
+     */
+    pub fn rem_euclid(&self, other: &BigInt) -> BigInt {
+        let rem = self % other;
+        if rem.is_negative() {
+            rem + other.abs()
+        } else {
+            rem
+        }
+    }
+
+    pub fn pow(&self, exp: u32) -> BigInt {
+        Self::from_parts(self.negative && exp % 2 == 1, self.magnitude.pow(exp))
+    }
+
+    /*
+     * El resultado queda en `0..|modulus|`, el exponente no puede ser negativo
+     */
+    pub fn modpow(&self, exp: &BigInt, modulus: &BigInt) -> BigInt {
+        assert!(!exp.is_negative(), "negative exponent in modpow");
+        let base = self.rem_euclid(modulus).magnitude;
+        base.modpow(&exp.magnitude, &modulus.magnitude).into()
+    }
+
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        self.magnitude.gcd(&other.magnitude).into()
+    }
+
+    pub fn from_str_radix(text: &str, radix: u32) -> Result<BigInt, ParseBigIntError> {
+        let (negative, digits, offset) = split_sign(text);
+        let magnitude = BigUint::parse_digits(digits, radix, offset, false)?;
+        Ok(Self::from_parts(negative, magnitude))
+    }
+
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let digits = self.magnitude.to_str_radix(radix);
+        if self.negative {
+            format!("-{digits}")
+        } else {
+            digits
+        }
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        self.to_i128().and_then(|value| i64::try_from(value).ok())
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        let magnitude = self.magnitude.to_u128()?;
+        if self.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+}
+
+fn split_sign(text: &str) -> (bool, &str, usize) {
+    match text.as_bytes().first() {
+        Some(b'-') => (true, &text[1..], 1),
+        Some(b'+') => (false, &text[1..], 1),
+        _ => (false, text, 0),
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (negative, rest, sign_len) = split_sign(text);
+        let (digits, radix, prefix_len) = split_radix(rest);
+        let magnitude = BigUint::parse_digits(digits, radix, sign_len + prefix_len, prefix_len > 0)?;
+        Ok(Self::from_parts(negative, magnitude))
+    }
+}
+
+impl From<BigUint> for BigInt {
+    fn from(magnitude: BigUint) -> Self {
+        Self::from_parts(false, magnitude)
+    }
+}
+
+/*
+ * Un `BigInt` negativo no se puede convertir, se devuelve el mismo número en el error
+ */
+impl TryFrom<BigInt> for BigUint {
+    type Error = BigInt;
+
+    fn try_from(value: BigInt) -> Result<Self, Self::Error> {
+        if value.negative {
+            Err(value)
+        } else {
+            Ok(value.magnitude)
+        }
+    }
+}
+
+macro_rules! bigint_from {
+    ($($t:ty),*) => {$(
+        impl From<$t> for BigInt {
+            fn from(value: $t) -> Self {
+                Self::from_parts(value < 0, BigUint::from((value as i128).unsigned_abs()))
+            }
+        }
+    )*};
+}
+
+bigint_from!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! bigint_from_unsigned {
+    ($($t:ty),*) => {$(
+        impl From<$t> for BigInt {
+            fn from(value: $t) -> Self {
+                BigUint::from(value).into()
+            }
+        }
+    )*};
+}
+
+bigint_from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude)
+    }
+}
+
+/*
+ * Con signos iguales se suman las magnitudes, con signos distintos se resta la menor de la mayor y
+ * el resultado lleva el signo de la mayor
+ */
+impl Add<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(self.negative, &self.magnitude + &other.magnitude);
+        }
+        match self.magnitude.cmp(&other.magnitude) {
+            Ordering::Less => {
+                BigInt::from_parts(other.negative, &other.magnitude - &self.magnitude)
+            }
+            _ => BigInt::from_parts(self.negative, &self.magnitude - &other.magnitude),
+        }
+    }
+}
+
+impl Sub<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::from_parts(
+            self.negative != other.negative,
+            &self.magnitude * &other.magnitude,
+        )
+    }
+}
+
+impl Div<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).1
+    }
+}
+
+forward_binop!(BigInt, Add, add, AddAssign, add_assign);
+forward_binop!(BigInt, Sub, sub, SubAssign, sub_assign);
+forward_binop!(BigInt, Mul, mul, MulAssign, mul_assign);
+forward_binop!(BigInt, Div, div, DivAssign, div_assign);
+forward_binop!(BigInt, Rem, rem, RemAssign, rem_assign);
+
+impl Sum for BigInt {
+    fn sum<I: Iterator<Item = BigInt>>(iter: I) -> Self {
+        iter.fold(BigInt::zero(), |acc, value| acc + value)
+    }
+}
+
+impl Product for BigInt {
+    fn product<I: Iterator<Item = BigInt>>(iter: I) -> Self {
+        iter.fold(BigInt::one(), |acc, value| acc * value)
+    }
+}
+
+radix_fmt!(BigInt, Display, 10, "", false);
+radix_fmt!(BigInt, Binary, 2, "0b", false);
+radix_fmt!(BigInt, Octal, 8, "0o", false);
+radix_fmt!(BigInt, LowerHex, 16, "0x", false);
+radix_fmt!(BigInt, UpperHex, 16, "0x", true);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(text: &str) -> BigUint {
+        text.parse().unwrap()
+    }
+
+    /*
+     * `(a, b, a * b, a / b, a % b, gcd(a, b))` calculados con los enteros de `Python`, los
+     * divisores están cerca de los límites de un `limb`, `2^32 - 1`, `2^32`, `2^64 + 1`, ..., y
+     * hay dos casos donde el paso de `Knuth D` estima un dígito de más y tiene que regresar a sumar
+     * el divisor
+     */
+    const CASES: [(&str, &str, &str, &str, &str, &str); 27] = [
+        (
+            "0xc9276fbc83dd7398f15728e6bebf4f7e6021b8c26bc02373ab",
+            "0x1",
+            "0xc9276fbc83dd7398f15728e6bebf4f7e6021b8c26bc02373ab",
+            "0xc9276fbc83dd7398f15728e6bebf4f7e6021b8c26bc02373ab",
+            "0x0",
+            "0x1",
+        ),
+        (
+            "0xcccede003f4dce05de7c14104148697ca55bf54e44e0fd2dcec9115dfe4408ccec5f72fc1dd",
+            "0x2",
+            "0x1999dbc007e9b9c0bbcf828208290d2f94ab7ea9c89c1fa5b9d9222bbfc881199d8bee5f83ba",
+            "0x66676f001fa6e702ef3e0a0820a434be52adfaa722707e96e76488aeff220466762fb97e0ee",
+            "0x1",
+            "0x1",
+        ),
+        (
+            "0xee95d6007feb60fe56b7a3e3b4ce2f7ecc5ddaed5ba7244dd0",
+            "0xffffffff",
+            "0xee95d5ff91558afdd6cc42e55e168b9b178fab6e8f4949607458dbb230",
+            "0xee95d6016e8136ffc538dae37a070a624664e54fa2",
+            "0xc099d72",
+            "0x1",
+        ),
+        (
+            "0x8234f9b45d4226e6",
+            "0x100000000",
+            "0x8234f9b45d4226e600000000",
+            "0x8234f9b4",
+            "0x5d4226e6",
+            "0x2",
+        ),
+        (
+            "0xb8c0252718bf1a3c",
+            "0x100000001",
+            "0xb8c02527d17f3f6318bf1a3c",
+            "0xb8c02526",
+            "0x5ffef516",
+            "0x1",
+        ),
+        (
+            "0xfad3c12204f05fb98350d3431717f0532ebacd24c965747290b0674c72abb25279a5fe25436",
+            "0xffffffffffffffff",
+            "0xfad3c12204f05fb8887d122112279099ab69f9e1b24d841f61f59a27a9463ddfe8f596d8d0b44dad865a01dabca",
+            "0xfad3c12204f05fba7e2494651c08500dacdf6189e56dc4803d8fc8d6581",
+            "0x976d2b735c6fb9b7",
+            "0x1",
+        ),
+        (
+            "0xfa12d86d0b90ae56082a054026573d2a2f2834074814232a6e",
+            "0x10000000000000000",
+            "0xfa12d86d0b90ae56082a054026573d2a2f2834074814232a6e0000000000000000",
+            "0xfa12d86d0b90ae56082a054026573d2a2f",
+            "0x2834074814232a6e",
+            "0x2",
+        ),
+        (
+            "0xe8ac985c8613b626c293989599292ad51a4b40fc2b15435e0d",
+            "0x10000000000000001",
+            "0xe8ac985c8613b627ab4030f21f3ce0fbdcded991c43e6e33274b40fc2b15435e0d",
+            "0xe8ac985c8613b625d9e70039131574af40",
+            "0x6440c317ffceaecd",
+            "0x1",
+        ),
+        (
+            "0xf0c40b54d28e4081bb2c63f88bbd8dec",
+            "0x800000000000000000000000",
+            "0x786205aa69472040dd9631fc45dec6f6000000000000000000000000",
+            "0x1e18816a9",
+            "0x528e4081bb2c63f88bbd8dec",
+            "0x4",
+        ),
+        (
+            "0xf75c507378c9841f703c6af00ce43bfc2e5091f97bb63904f3b9e6d4ed6651a2dd852adef3b",
+            "0xffffffffffffffffffffffff",
+            "0xf75c507378c9841f703c6aef1587eb88b5870dda0b79ce14e6d5aad8bf15bfa961cef1d9fff6192b1299ae5d227ad5210c5",
+            "0xf75c507378c9841f703c6af104408c6fa71a1618ebf2a3f5f7f",
+            "0xa7344948067bbc977ced4eba",
+            "0x7",
+        ),
+        (
+            "0xd63abb2e2d51d53c5a5d480a02dd3816a78e3937dec0b843b31c1b3aa2d8e05bace80de5e87",
+            "0x80000000000000000000000000000001",
+            "0x6b1d5d9716a8ea9e2d2ea405016e9c0c2a01d7ca1cb2315e33eb55a75449a8447e02402ad2f8b843b31c1b3aa2d8e05bace80de5e87",
+            "0x1ac75765c5aa3aa78b4ba901405ba7029f63185b7083",
+            "0x50dcafe618b269d1e7031b6b6882ee04",
+            "0x1",
+        ),
+        (
+            "0x81fae47eec06318870b7132ba5332a8685ada68a63a67faf561609b2f0f12cb0206190d6c88",
+            "0x800000000000000000000001",
+            "0x40fd723f760318c4385b8996549479c22edd04cda28a5303503e2f5ffe263ce273d7481aba5609b2f0f12cb0206190d6c88",
+            "0x103f5c8fdd80c6310e16e2655427ac3115b4286f30470b2b4273",
+            "0x346a195aea5bd1bf0de22a15",
+            "0x1",
+        ),
+        (
+            "0x94c92fbd213f1e8fb7381fb202b8f305659543f4cce9011ac2",
+            "0xffffffff0000000000000001",
+            "0x94c92fbc8c75eed295f90122e04a0310841b6f7f1e8bdcd7f7cff1eaa39543f4cce9011ac2",
+            "0x94c92fbdb6084e4d6d406dfedb",
+            "0x3031468a8cf5a75fa8931be7",
+            "0x1",
+        ),
+        (
+            "0x7fffffff800000000000000000000000",
+            "0x800000000000000000000001",
+            "0x3fffffffc0000000000000007fffffff800000000000000000000000",
+            "0xfffffffe",
+            "0x7fffffffffffffff00000002",
+            "0x3",
+        ),
+        (
+            "0x7fffffffffffffffffffffff0000000000000000",
+            "0x8000000000000000ffffffff",
+            "0x40000000000000007ffffffeffffffffffffffff000000010000000000000000",
+            "0xfffffffffffffffe",
+            "0x1fffffffe",
+            "0x1",
+        ),
+        (
+            "0xffffffffffffffffffffffffffffffff",
+            "0xffffffffffffffff",
+            "0xfffffffffffffffeffffffffffffffff0000000000000001",
+            "0x10000000000000001",
+            "0x0",
+            "0xffffffffffffffff",
+        ),
+        (
+            "0x75bcd15",
+            "0xdb4da5f7ef412b1",
+            "0x64dc3d2f1f2fc458e84585",
+            "0x0",
+            "0x75bcd15",
+            "0x9",
+        ),
+        (
+            "0x1549d16f566c27b049defdd",
+            "0x30692",
+            "0x4069535010ca49ada1e2548fa0a",
+            "0x70932563403784bf79",
+            "0xe6db",
+            "0x1",
+        ),
+        (
+            "0xb31b6b2f2fa1a9f",
+            "0x41f0639d513",
+            "0x2e22259d2265f11f02c81d44cd",
+            "0x2b75c",
+            "0x1a5b4baf2cb",
+            "0x1",
+        ),
+        (
+            "0x18012b8d435f4fe80f0e6b67746a666abe4aa3e02c6b6239de910769d5b89aa",
+            "0x605d4c6f70f",
+            "0x9092fec979c0dfe30e846eb0f84e5fc4098e7e6fb6f10e73bcda247584d9b97e5cbac16f6",
+            "0x3fc5250c0bb10a4f282e7f671089717d5c48266c1c2631c7ca9b",
+            "0x4dbde491d95",
+            "0xd",
+        ),
+        (
+            "0xc78bb279a4f90dba384fcfbd39f6654b0028bd5f558b7124e5418bd351004acfdec4",
+            "0x208a2e85bc469929b69e67a9a6bc7e62f5",
+            "0x195d2be0c29943774715020617c4a20eaa69753ebc6ba84fd2f8c68c7931a5ae2c992204173f61876860cb2109f2eac0af3994",
+            "0x621e28889c73570a58aab535c25823f2e80",
+            "0x20c31a2fe01ab5ed5e57e9cff918b5e44",
+            "0x1",
+        ),
+        (
+            "0x3de9519b0a16ce57da2dd6",
+            "0x575e66aa4917f0ad442",
+            "0x15211f3bcc7cbde9973956c9e5bae7b6c4891092c",
+            "0xb56",
+            "0x2cf9e248d78331809aa",
+            "0x6",
+        ),
+        (
+            "0x63680581a2db6",
+            "0x59ed7834d039026a4b59978c42d15dc981f",
+            "0x22eb5fe46f3293b66e1e8f6b3c723b1bc98c0b176cb7990a",
+            "0x0",
+            "0x63680581a2db6",
+            "0x1",
+        ),
+        (
+            "0xe24739b759000efc6e461497e97cc4d4e63f41bda13aff33624fb5ab754",
+            "0x16499b323beb3bc71a66f6e4759fcf97a6d537f9",
+            "0x13b32e72bdc00186065e4fd606225fb89173c56f08a8d2c7910952ec751314121a000acdd7836eddb6b49de9fdff9835cb4",
+            "0xa271664e8c3b0e7490fd",
+            "0x126e90099767afb6d3f276ffdec86b5d3052563f",
+            "0x1",
+        ),
+        (
+            "0x3a5060a4f4a858fb297ffebc89949d0ae0d86c5dac32b871",
+            "0x3e4051d",
+            "0xe2e1e22ec60a7131fc723e4900d03e7b39caa54f14104f16fd19cd",
+            "0xefcef8d44d2818f9f9e7c49ceaa7714e1a8437964",
+            "0x21f041d",
+            "0x1",
+        ),
+        (
+            "0x1ea69c8ffb840a96c837cad3fd223451ec83faa",
+            "0x2ce8fce008376d373ba59d56be3",
+            "0x560882d769932d141690f7a4327823ae4f9d54233c8fd0cd5f37f046b109e81be",
+            "0xaeb7d9637998",
+            "0x345f947750c27748e8c448e5e2",
+            "0x1",
+        ),
+        (
+            "0xb79",
+            "0x7e4f44c1a6fa997b0916916e89106d4cf44ae57",
+            "0x5a91b6bd1b4b10ad48b43ea7d227d75f7deaf4241f",
+            "0x0",
+            "0xb79",
+            "0x1",
+        ),
+    ];
+
+    #[test]
+    fn arithmetic_matches_python() {
+        for (a, b, product, quotient, rem, gcd) in CASES {
+            let (a, b) = (big(a), big(b));
+            assert_eq!(&a * &b, big(product), "{a:#x} * {b:#x}");
+            assert_eq!(a.div_rem(&b), (big(quotient), big(rem)), "{a:#x} / {b:#x}");
+            assert_eq!(a.gcd(&b), big(gcd), "gcd({a:#x}, {b:#x})");
+            assert_eq!(&big(quotient) * &b + big(rem), a);
+        }
+    }
+
+    /*
+     * Los números de más de `KARATSUBA_THRESHOLD` `limbs` se multiplican con `Karatsuba`, el
+     * resultado tiene que ser el mismo que el de la multiplicación de escuela
+     */
+    #[test]
+    fn karatsuba_matches_schoolbook() {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut limbs = |len: usize| -> Vec<u32> {
+            (0..len)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    state as u32
+                })
+                .collect()
+        };
+        for (a_len, b_len) in [(32, 32), (33, 64), (100, 40), (150, 149), (257, 300)] {
+            let a = limbs(a_len);
+            let b = limbs(b_len);
+            assert_eq!(mul_limbs(&a, &b), trim(schoolbook(&a, &b)), "{a_len}x{b_len}");
+        }
+        let all_ones = vec![u32::MAX; 80];
+        assert_eq!(mul_limbs(&all_ones, &all_ones), trim(schoolbook(&all_ones, &all_ones)));
+    }
+
+    #[test]
+    fn division_round_trips_large_numbers() {
+        let a = factorial(200);
+        let b = BigUint::from(3u32).pow(150) + BigUint::one();
+        let (quotient, rem) = a.div_rem(&b);
+        assert!(rem < b);
+        assert_eq!(quotient * &b + rem, a);
+        assert_eq!((&a * &b) / &b, a);
+        assert_eq!(BigUint::from(5u32).checked_div(&BigUint::zero()), None);
+    }
+
+    #[test]
+    fn signed_division_truncates_like_i128() {
+        for a in [-7i128, 7, -1, 0, 170141183460469231731687303715884105727] {
+            for b in [2i128, -2, 3, -1, 1 << 40] {
+                let (big_a, big_b) = (BigInt::from(a), BigInt::from(b));
+                let (quotient, rem) = big_a.div_rem(&big_b);
+                assert_eq!(quotient.to_i128(), Some(a / b), "{a} / {b}");
+                assert_eq!(rem.to_i128(), Some(a % b), "{a} % {b}");
+                assert_eq!(big_a.rem_euclid(&big_b).to_i128(), Some(a.rem_euclid(b)));
+            }
+        }
+    }
+
+    #[test]
+    fn factorial_and_decimal_text() {
+        assert_eq!(factorial(25).to_string(), "15511210043330985984000000");
+        assert_eq!(factorial(35).to_u128(), None);
+        assert_eq!(factorial(34).to_u128(), Some(295232799039604140847618609643520000000));
+        let text = "-123456789012345678901234567890";
+        assert_eq!(text.parse::<BigInt>().unwrap().to_string(), text);
+    }
+
+    #[test]
+    fn parse_radix_and_underscores() {
+        assert_eq!(big("0xff"), BigUint::from(255u32));
+        assert_eq!(big("0x_ff"), BigUint::from(255u32));
+        assert_eq!(big("0b_1010_1010"), BigUint::from(0b1010_1010u32));
+        assert_eq!(big("0o7_7"), BigUint::from(0o77u32));
+        assert_eq!(big("1_000_000"), BigUint::from(1_000_000u32));
+        assert_eq!("-0x_10".parse::<BigInt>(), Ok(BigInt::from(-16)));
+        assert_eq!(BigUint::from_str_radix("zz", 36), Ok(BigUint::from(36u32 * 36 - 1)));
+        assert_eq!(
+            "_1".parse::<BigUint>(),
+            Err(ParseBigIntError::InvalidDigit { index: 0, ch: '_' })
+        );
+        assert_eq!(
+            BigUint::from_str_radix("_ff", 16),
+            Err(ParseBigIntError::InvalidDigit { index: 0, ch: '_' })
+        );
+        assert_eq!("0x".parse::<BigUint>(), Err(ParseBigIntError::Empty));
+        assert_eq!("0x_".parse::<BigUint>(), Err(ParseBigIntError::Empty));
+        assert_eq!("0xfg".parse::<BigUint>(), Err(ParseBigIntError::InvalidDigit { index: 3, ch: 'g' }));
+        assert_eq!("-5".parse::<BigUint>(), Err(ParseBigIntError::Negative));
+        assert_eq!(BigUint::from_str_radix("1", 37), Err(ParseBigIntError::InvalidRadix(37)));
+    }
+
+    #[test]
+    fn radix_text_round_trips() {
+        let value = factorial(40);
+        for radix in [2, 8, 10, 16, 36] {
+            let text = value.to_str_radix(radix);
+            assert_eq!(BigUint::from_str_radix(&text, radix), Ok(value.clone()), "radix {radix}");
+        }
+        assert_eq!(format!("{:#x}", BigUint::from(255u32)), "0xff");
+        assert_eq!(format!("{:08}", BigUint::from(42u32)), "00000042");
+    }
+}
@@ -0,0 +1,359 @@
+/*
+ * `as` nunca falla, siempre entrega un valor del tipo destino aunque no sea el mismo número. Estas
+ * son todas las formas en que pierde información
+ *
+ *  300i32 as u8          44          trunca los `bits` altos
+ *  -1i32 as u32          4294967295  reinterpreta el `bit` de signo
+ *  -1i8 as u16           65535       extiende el signo a un tipo sin signo
+ *  16777217i32 as f32    16777216.0  redondea, `f32` solo tiene 24 `bits` de mantisa
+ *  -1.5f64 as u8         0           trunca decimales y satura, `NaN` es 0
+ *  0.1f64 as f32         0.1         redondea, los valores enormes son `inf`
+ *
+ * `From` solo existe para las conversiones sin pérdida, `TryFrom` para las demás entre enteros
+ */
+use std::fmt;
+
+use super::literal::{Number, Ty};
+
+const TYPES: [Ty; 14] = [
+    Ty::I8,
+    Ty::I16,
+    Ty::I32,
+    Ty::I64,
+    Ty::I128,
+    Ty::Isize,
+    Ty::U8,
+    Ty::U16,
+    Ty::U32,
+    Ty::U64,
+    Ty::U128,
+    Ty::Usize,
+    Ty::F32,
+    Ty::F64,
+];
+
+/*
+ * Los enteros pasan por `i128` o `u128` y se convierten al destino con `as`, truncar un `u128` da
+ * los mismos `bits` que truncar directo desde el tipo original. Lo mismo con `f64` para `f32`
+ */
+macro_rules! cast_to {
+    ($value:expr, $to:expr) => {
+        match $value {
+            Number::F32(v) => cast_to!(@into v as f64, $to),
+            Number::F64(v) => cast_to!(@into v, $to),
+            Number::U128(v) => cast_to!(@into v, $to),
+            other => {
+                let v = other.as_i128().unwrap_or_default();
+                cast_to!(@into v, $to)
+            }
+        }
+    };
+    (@into $v:expr, $to:expr) => {
+        match $to {
+            Ty::I8 => Number::I8($v as i8),
+            Ty::I16 => Number::I16($v as i16),
+            Ty::I32 => Number::I32($v as i32),
+            Ty::I64 => Number::I64($v as i64),
+            Ty::I128 => Number::I128($v as i128),
+            Ty::Isize => Number::Isize($v as isize),
+            Ty::U8 => Number::U8($v as u8),
+            Ty::U16 => Number::U16($v as u16),
+            Ty::U32 => Number::U32($v as u32),
+            Ty::U64 => Number::U64($v as u64),
+            Ty::U128 => Number::U128($v as u128),
+            Ty::Usize => Number::Usize($v as usize),
+            Ty::F32 => Number::F32($v as f32),
+            Ty::F64 => Number::F64($v as f64),
+        }
+    };
+}
+
+/*
+ * `value as to` con las mismas reglas que el compilador
+ */
+pub fn cast(value: Number, to: Ty) -> Number {
+    cast_to!(value, to)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Loss {
+    Lossless,
+    Truncates,
+    ReinterpretsSign,
+    SignExtends,
+    Rounds,
+    Saturates,
+}
+
+impl Loss {
+    pub fn symbol(self) -> char {
+        match self {
+            Loss::Lossless => '.',
+            Loss::Truncates => 'T',
+            Loss::ReinterpretsSign => 'S',
+            Loss::SignExtends => 'E',
+            Loss::Rounds => 'R',
+            Loss::Saturates => 'Z',
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            Loss::Lossless => "lossless",
+            Loss::Truncates => "truncates high bits",
+            Loss::ReinterpretsSign => "reinterprets the sign bit",
+            Loss::SignExtends => "sign-extends negatives into an unsigned type",
+            Loss::Rounds => "rounds to the nearest float",
+            Loss::Saturates => "drops the fraction and saturates, NaN becomes 0",
+        }
+    }
+}
+
+fn bits(ty: Ty) -> u32 {
+    match ty {
+        Ty::I8 | Ty::U8 => 8,
+        Ty::I16 | Ty::U16 => 16,
+        Ty::I32 | Ty::U32 | Ty::F32 => 32,
+        Ty::I64 | Ty::U64 | Ty::F64 => 64,
+        Ty::I128 | Ty::U128 => 128,
+        Ty::Isize | Ty::Usize => usize::BITS,
+    }
+}
+
+fn int_value(value: Number) -> (bool, u128) {
+    match value {
+        Number::U128(v) => (false, v),
+        other => {
+            let v = other.as_i128().unwrap_or_default();
+            (v < 0, v.unsigned_abs())
+        }
+    }
+}
+
+/*
+ * Compara los valores matemáticos, no los `bits`, `-1i8` y `255u8` no son el mismo número pero
+ * `NaN` sí es igual a `NaN`
+ */
+fn same(a: Number, b: Number) -> bool {
+    match (a.ty().is_float(), b.ty().is_float()) {
+        (false, false) => int_value(a) == int_value(b),
+        (true, true) => a.as_f64() == b.as_f64() || (a.as_f64().is_nan() && b.as_f64().is_nan()),
+        (true, false) => same(b, a),
+        (false, true) => {
+            let float = b.as_f64();
+            let wide = cast(b, if float < 0.0 { Ty::I128 } else { Ty::U128 });
+            int_value(wide) == int_value(a) && cast(wide, Ty::F64).as_f64() == float
+        }
+    }
+}
+
+/*
+ * Valores que suelen romper una conversión, cada uno se usa solo si cabe exacto en el tipo origen
+ */
+fn candidates(from: Ty) -> Vec<Number> {
+    let mut values = vec![
+        Number::F64(-1.5),
+        Number::I128(-1),
+        Number::U128(300),
+        Number::I128(-129),
+        Number::U128((1 << 24) + 1),
+        Number::U128((1 << 53) + 1),
+        Number::F64(0.1),
+        Number::F64(1e39),
+        Number::F64(f64::NAN),
+    ];
+    for ty in TYPES {
+        if !ty.is_float() {
+            values.push(Number::U128(cast(Number::I128(-1), ty).to_bits() >> 1));
+            values.push(Number::U128(
+                (cast(Number::I128(-1), ty).to_bits() >> 1) + 1,
+            ));
+            values.push(cast(Number::I128(i128::MIN), ty));
+            values.push(Number::U128(cast(Number::I128(-1), ty).to_bits()));
+        }
+    }
+    values
+        .into_iter()
+        .map(|value| (value, cast(value, from)))
+        .filter(|&(value, typed)| same(typed, value))
+        .map(|(_, typed)| typed)
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Conversion {
+    pub from: Ty,
+    pub to: Ty,
+    pub loss: Loss,
+    /*
+     * Un valor que no sobrevive la ida y vuelta, con su resultado
+     */
+    pub example: Option<(Number, Number)>,
+}
+
+pub fn conversion(from: Ty, to: Ty) -> Conversion {
+    let example = candidates(from)
+        .into_iter()
+        .map(|value| (value, cast(value, to)))
+        .find(|&(value, result)| !same(result, value));
+    let loss = match (from.is_float(), to.is_float()) {
+        _ if example.is_none() => Loss::Lossless,
+        (true, false) => Loss::Saturates,
+        (_, true) => Loss::Rounds,
+        _ if bits(to) < bits(from) => Loss::Truncates,
+        _ if bits(to) == bits(from) => Loss::ReinterpretsSign,
+        _ => Loss::SignExtends,
+    };
+    Conversion {
+        from,
+        to,
+        loss,
+        example,
+    }
+}
+
+fn show(value: Number) -> String {
+    match value {
+        Number::F32(v) if !v.is_finite() => format!("f32::{}", special(v as f64)),
+        Number::F64(v) if !v.is_finite() => format!("f64::{}", special(v)),
+        other => format!("{other}{}", other.ty()),
+    }
+}
+
+fn special(value: f64) -> &'static str {
+    match value {
+        v if v.is_nan() => "NAN",
+        v if v > 0.0 => "INFINITY",
+        _ => "NEG_INFINITY",
+    }
+}
+
+impl fmt::Display for Conversion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pair = format!("{} as {}", self.from, self.to);
+        match self.example {
+            Some((value, result)) => write!(
+                f,
+                "{pair:<14}{:<50}{} as {} = {}",
+                self.loss.describe(),
+                show(value),
+                self.to,
+                result
+            ),
+            None => write!(f, "{pair:<14}{}", self.loss.describe()),
+        }
+    }
+}
+
+/*
+ * La matriz de todas las parejas, filas origen y columnas destino
+ */
+pub fn matrix() -> String {
+    let mut out = String::from("from\\to");
+    for to in TYPES {
+        out.push_str(&format!("{:>6}", to.name()));
+    }
+    out.push('\n');
+    for from in TYPES {
+        out.push_str(&format!("{:<7}", from.name()));
+        for to in TYPES {
+            out.push_str(&format!("{:>6}", conversion(from, to).loss.symbol()));
+        }
+        out.push('\n');
+    }
+    out.push('\n');
+    for loss in [
+        Loss::Lossless,
+        Loss::Truncates,
+        Loss::ReinterpretsSign,
+        Loss::SignExtends,
+        Loss::Rounds,
+        Loss::Saturates,
+    ] {
+        out.push_str(&format!("  {}  {}\n", loss.symbol(), loss.describe()));
+    }
+    out
+}
+
+/*
+ * Todas las conversiones desde `from`, con un ejemplo de cada pérdida
+ */
+pub fn report(from: Ty) -> Vec<Conversion> {
+    TYPES
+        .into_iter()
+        .filter(|&to| to != from)
+        .map(|to| conversion(from, to))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn floats_saturate_into_integers() {
+        let values = [-1.5, 0.0, 0.99, 254.5, 255.0, 256.0, 1e300, f64::INFINITY, -f64::INFINITY];
+        for value in values {
+            assert_eq!(cast(Number::F64(value), Ty::U8), Number::U8(value as u8), "{value}");
+        }
+        assert_eq!(cast(Number::F64(300.7), Ty::U8), Number::U8(255));
+        assert_eq!(cast(Number::F64(-300.7), Ty::I8), Number::I8(-128));
+        assert_eq!(cast(Number::F32(-0.9), Ty::U32), Number::U32(0));
+        assert_eq!(cast(Number::F64(1e39), Ty::F32), Number::F32(f32::INFINITY));
+        let f64_to_u8 = conversion(Ty::F64, Ty::U8);
+        assert_eq!(f64_to_u8.loss, Loss::Saturates);
+        assert_eq!(f64_to_u8.example, Some((Number::F64(-1.5), Number::U8(0))));
+    }
+
+    #[test]
+    fn nan_becomes_zero() {
+        for ty in TYPES.into_iter().filter(|ty| !ty.is_float()) {
+            let zero = cast(Number::F64(f64::NAN), ty);
+            assert_eq!(zero.ty(), ty);
+            assert_eq!(zero.to_bits(), 0, "NaN as {ty}");
+            assert_eq!(cast(Number::F32(f32::NAN), ty).to_bits(), 0);
+        }
+        assert!(cast(Number::F64(f64::NAN), Ty::F32).as_f64().is_nan());
+        assert!(same(Number::F64(f64::NAN), Number::F32(f32::NAN)));
+    }
+
+    #[test]
+    fn sign_is_reinterpreted() {
+        assert_eq!(cast(Number::I32(-1), Ty::U32), Number::U32(u32::MAX));
+        assert_eq!(cast(Number::U8(200), Ty::I8), Number::I8(200u8 as i8));
+        assert_eq!(cast(Number::I8(-1), Ty::U16), Number::U16(65535));
+        assert_eq!(cast(Number::I64(i64::MIN), Ty::U64), Number::U64(1 << 63));
+        assert_eq!(cast(Number::U128(u128::MAX), Ty::I128), Number::I128(-1));
+        assert_eq!(cast(Number::I32(300), Ty::U8), Number::U8(44));
+        assert_eq!(conversion(Ty::I32, Ty::U32).loss, Loss::ReinterpretsSign);
+        assert_eq!(conversion(Ty::U8, Ty::I8).loss, Loss::ReinterpretsSign);
+        assert_eq!(conversion(Ty::I8, Ty::U16).loss, Loss::SignExtends);
+        assert_eq!(conversion(Ty::I32, Ty::U8).loss, Loss::Truncates);
+    }
+
+    #[test]
+    fn lossless_matches_from() {
+        let lossless = [
+            (Ty::U8, Ty::I16),
+            (Ty::I8, Ty::I64),
+            (Ty::U32, Ty::F64),
+            (Ty::I16, Ty::F32),
+            (Ty::F32, Ty::F64),
+        ];
+        for (from, to) in lossless {
+            let expected = Conversion {
+                from,
+                to,
+                loss: Loss::Lossless,
+                example: None,
+            };
+            assert_eq!(conversion(from, to), expected);
+        }
+        let i32_to_f32 = conversion(Ty::I32, Ty::F32);
+        assert_eq!(i32_to_f32.loss, Loss::Rounds);
+        assert_eq!(
+            i32_to_f32.example,
+            Some((Number::I32((1 << 24) + 1), Number::F32(16_777_216.0)))
+        );
+    }
+}
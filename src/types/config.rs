@@ -0,0 +1,694 @@
+/*
+ * Una constante como `const MAX_POINTS: u32 = 100_000;` de `types/variables_const.rs` se conoce en
+ * tiempo de compilación y no se puede cambiar sin compilar de nuevo. Aquí la constante sigue siendo
+ * el valor por defecto, pero se puede reemplazar en capas, cada capa tapa a las anteriores
+ *
+ *  flag      --max-points 5000                 <- gana
+ *  env       THE_BASICS_MAX_POINTS=20000
+ *  archivo   max_points = 200_000
+ *  const     MAX_POINTS = 100_000              <- solo si nadie más lo define
+ *
+ * Cada valor recuerda de dónde vino, así un error dice qué archivo, variable o `flag` hay que
+ * corregir
+ */
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+
+use super::literal::{self, FromLiteral};
+
+/*
+ * Las constantes de la lección, ahora son el valor por defecto de cada `Key`
+ */
+pub use super::variables_const::{DIMENSIONS, MAX_POINTS};
+
+/*
+ * Un tipo que se puede leer de un texto de configuración. Los números usan el mismo `parser` que
+ * los literales de `Rust`, `200_000` y `0xff` son válidos, `1e3` solo para una llave `f32` o `f64`
+ * porque igual que en `Rust` un literal con exponente es flotante
+ */
+pub trait ConfigValue: Sized + Clone + PartialOrd + fmt::Display {
+    fn type_name() -> &'static str;
+    fn parse(text: &str) -> Result<Self, String>;
+}
+
+impl<T: FromLiteral + Clone + PartialOrd + fmt::Display> ConfigValue for T {
+    fn type_name() -> &'static str {
+        T::TY.name()
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        literal::parse_as::<T>(text).map_err(|error| error.to_string())
+    }
+}
+
+impl ConfigValue for bool {
+    fn type_name() -> &'static str {
+        "bool"
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        match text {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => Err("expected `true` or `false`".to_string()),
+        }
+    }
+}
+
+/*
+ * Una llave con tipo, su valor por defecto y un rango opcional. Se declara como `const`, igual que
+ * la constante que reemplaza
+ *
+ *  pub const MAX_POINTS_KEY: Key<u32> = Key::bounded("max_points", MAX_POINTS, 1, 1_000_000, "...");
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct Key<T> {
+    pub name: &'static str,
+    pub default: T,
+    pub min: Option<T>,
+    pub max: Option<T>,
+    pub description: &'static str,
+}
+
+impl<T> Key<T> {
+    pub const fn new(name: &'static str, default: T, description: &'static str) -> Self {
+        Self {
+            name,
+            default,
+            min: None,
+            max: None,
+            description,
+        }
+    }
+
+    pub const fn bounded(
+        name: &'static str,
+        default: T,
+        min: T,
+        max: T,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            default,
+            min: Some(min),
+            max: Some(max),
+            description,
+        }
+    }
+}
+
+impl<T: ConfigValue> Key<T> {
+    pub fn validate(&self, value: &T) -> Result<(), String> {
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) if value < min || value > max => {
+                Err(format!("{value} is outside the range {min}..={max}"))
+            }
+            (Some(min), None) if value < min => Err(format!("{value} is less than {min}")),
+            (None, Some(max)) if value > max => Err(format!("{value} is greater than {max}")),
+            _ => Ok(()),
+        }
+    }
+}
+
+pub const MAX_POINTS_KEY: Key<u32> = Key::bounded(
+    "max_points",
+    MAX_POINTS,
+    1,
+    1_000_000,
+    "maximum number of points",
+);
+pub const DIMENSIONS_KEY: Key<u8> = Key::bounded(
+    "dimensions",
+    DIMENSIONS,
+    1,
+    4,
+    "number of dimensions of each point",
+);
+pub const VERBOSE_KEY: Key<bool> = Key::new("verbose", false, "print every step");
+
+/*
+ * Las llaves sin su tipo, para recorrerlas todas al validar o al imprimir la configuración
+ */
+pub trait Setting {
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn type_name(&self) -> &'static str;
+    fn default_text(&self) -> String;
+    /*
+     * Revisa el texto y lo devuelve normalizado, `200_000` se convierte en `200000`
+     */
+    fn check(&self, text: &str) -> Result<String, String>;
+}
+
+impl<T: ConfigValue> Setting for Key<T> {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn description(&self) -> &'static str {
+        self.description
+    }
+
+    fn type_name(&self) -> &'static str {
+        T::type_name()
+    }
+
+    fn default_text(&self) -> String {
+        self.default.to_string()
+    }
+
+    fn check(&self, text: &str) -> Result<String, String> {
+        let value = T::parse(text)?;
+        self.validate(&value)?;
+        Ok(value.to_string())
+    }
+}
+
+pub const SETTINGS: &[&dyn Setting] = &[&MAX_POINTS_KEY, &DIMENSIONS_KEY, &VERBOSE_KEY];
+
+/*
+ * De dónde vino un valor
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File { path: String, line: usize },
+    Env { var: String },
+    Flag { flag: String },
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File { path, line } => write!(f, "{path}:{line}"),
+            Source::Env { var } => write!(f, "env {var}"),
+            Source::Flag { flag } => write!(f, "flag {flag}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    Syntax {
+        source: Source,
+        message: String,
+    },
+    UnknownKey {
+        key: String,
+        source: Source,
+    },
+    Invalid {
+        key: String,
+        value: String,
+        source: Source,
+        message: String,
+    },
+    MissingValue {
+        flag: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Syntax { source, message } => write!(f, "{source}: {message}"),
+            ConfigError::UnknownKey { key, source } => write!(f, "{source}: unknown key `{key}`"),
+            ConfigError::Invalid {
+                key,
+                value,
+                source,
+                message,
+            } => write!(
+                f,
+                "{source}: invalid value {value:?} for `{key}`: {message}"
+            ),
+            ConfigError::MissingValue { flag } => write!(f, "flag {flag} needs a value"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    text: String,
+    source: Source,
+}
+
+/*
+ * Las capas en el orden en que se cargaron, la última que define una llave es la que se usa
+ */
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    layers: Vec<HashMap<String, Entry>>,
+}
+
+/*
+ * `max_points` en el archivo, `THE_BASICS_MAX_POINTS` en el entorno y `--max-points` como `flag`
+ */
+pub fn env_var(prefix: &str, key: &str) -> String {
+    format!("{prefix}{}", key.replace('.', "_").to_uppercase())
+}
+
+pub fn flag(key: &str) -> String {
+    format!("--{}", key.replace(['_', '.'], "-"))
+}
+
+impl Config {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /*
+     * Un formato parecido a `TOML`, una llave por línea, comentarios con `#` y secciones que se
+     * agregan como prefijo de las llaves
+     *
+     *  # limits
+     *  max_points = 200_000      ->  max_points
+     *  [render]
+     *  verbose = true            ->  render.verbose
+     */
+    pub fn load_str(&mut self, path: &str, text: &str) -> Result<(), ConfigError> {
+        let mut layer = HashMap::new();
+        let mut section = String::new();
+        for (index, line) in text.lines().enumerate() {
+            let source = Source::File {
+                path: path.to_string(),
+                line: index + 1,
+            };
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[') {
+                let name = name.strip_suffix(']').ok_or_else(|| ConfigError::Syntax {
+                    source: source.clone(),
+                    message: "missing `]` after the section name".to_string(),
+                })?;
+                section = name.trim().to_string();
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| ConfigError::Syntax {
+                source: source.clone(),
+                message: format!("expected `key = value`, found {line:?}"),
+            })?;
+            let key = match section.as_str() {
+                "" => key.trim().to_string(),
+                section => format!("{section}.{}", key.trim()),
+            };
+            let value = value.trim();
+            let text = match value.strip_prefix('"') {
+                Some(rest) => rest
+                    .strip_suffix('"')
+                    .ok_or_else(|| ConfigError::Syntax {
+                        source: source.clone(),
+                        message: "unterminated string".to_string(),
+                    })?
+                    .to_string(),
+                None => value.to_string(),
+            };
+            layer.insert(key, Entry { text, source });
+        }
+        self.layers.push(layer);
+        Ok(())
+    }
+
+    /*
+     * Solo se toman las variables que empiezan con `prefix` y corresponden a una llave conocida
+     */
+    pub fn load_env(
+        &mut self,
+        prefix: &str,
+        settings: &[&dyn Setting],
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) {
+        let vars: HashMap<String, String> = vars
+            .into_iter()
+            .filter(|(var, _)| var.starts_with(prefix))
+            .collect();
+        let mut layer = HashMap::new();
+        for setting in settings {
+            let var = env_var(prefix, setting.name());
+            if let Some(text) = vars.get(&var) {
+                let entry = Entry {
+                    text: text.clone(),
+                    source: Source::Env { var },
+                };
+                layer.insert(setting.name().to_string(), entry);
+            }
+        }
+        self.layers.push(layer);
+    }
+
+    /*
+     * Acepta `--max-points 5000` y `--max-points=5000`, un `bool` sin valor como `--verbose` es
+     * `true`. Devuelve los argumentos que no son de configuración
+     */
+    pub fn load_args(
+        &mut self,
+        settings: &[&dyn Setting],
+        args: &[String],
+    ) -> Result<Vec<String>, ConfigError> {
+        let mut layer = HashMap::new();
+        let mut rest = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            let Some(setting) = settings.iter().find(|setting| flag(setting.name()) == name) else {
+                rest.push(arg.clone());
+                continue;
+            };
+            let text = match inline {
+                Some(text) => text,
+                None if setting.type_name() == "bool" => "true".to_string(),
+                None => args
+                    .next()
+                    .cloned()
+                    .ok_or_else(|| ConfigError::MissingValue {
+                        flag: name.to_string(),
+                    })?,
+            };
+            let entry = Entry {
+                text,
+                source: Source::Flag {
+                    flag: name.to_string(),
+                },
+            };
+            layer.insert(setting.name().to_string(), entry);
+        }
+        self.layers.push(layer);
+        Ok(rest)
+    }
+
+    fn entry(&self, name: &str) -> Option<&Entry> {
+        self.layers.iter().rev().find_map(|layer| layer.get(name))
+    }
+
+    /*
+     * El valor con su tipo, el de la capa más alta que lo define o el valor por defecto
+     */
+    pub fn get<T: ConfigValue>(&self, key: &Key<T>) -> Result<T, ConfigError> {
+        let Some(entry) = self.entry(key.name) else {
+            return Ok(key.default.clone());
+        };
+        let invalid = |message: String| ConfigError::Invalid {
+            key: key.name.to_string(),
+            value: entry.text.clone(),
+            source: entry.source.clone(),
+            message,
+        };
+        let value = T::parse(&entry.text).map_err(invalid)?;
+        key.validate(&value).map_err(invalid)?;
+        Ok(value)
+    }
+
+    pub fn source(&self, name: &str) -> Source {
+        self.entry(name)
+            .map_or(Source::Default, |entry| entry.source.clone())
+    }
+
+    /*
+     * Revisa todas las capas, no solo la que gana, un valor inválido en el archivo es un error
+     * aunque un `flag` lo tape
+     */
+    pub fn validate(&self, settings: &[&dyn Setting]) -> Result<(), Vec<ConfigError>> {
+        let mut errors = Vec::new();
+        for layer in &self.layers {
+            let mut entries: Vec<_> = layer.iter().collect();
+            entries.sort_by_key(|(key, entry)| (entry.source.to_string(), key.to_string()));
+            for (key, entry) in entries {
+                match settings.iter().find(|setting| setting.name() == key) {
+                    None => errors.push(ConfigError::UnknownKey {
+                        key: key.clone(),
+                        source: entry.source.clone(),
+                    }),
+                    Some(setting) => {
+                        if let Err(message) = setting.check(&entry.text) {
+                            errors.push(ConfigError::Invalid {
+                                key: key.clone(),
+                                value: entry.text.clone(),
+                                source: entry.source.clone(),
+                                message,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /*
+     * La configuración efectiva en el mismo formato del archivo, con el origen de cada valor
+     *
+     *  max_points = 5000     # u32, flag --max-points
+     *  dimensions = 3        # u8, default
+     */
+    pub fn dump(&self, settings: &[&dyn Setting]) -> String {
+        let lines: Vec<(String, String)> = settings
+            .iter()
+            .map(|setting| {
+                let value = match self.entry(setting.name()) {
+                    Some(entry) => setting
+                        .check(&entry.text)
+                        .unwrap_or_else(|_| entry.text.clone()),
+                    None => setting.default_text(),
+                };
+                let comment = format!("{}, {}", setting.type_name(), self.source(setting.name()));
+                (format!("{} = {value}", setting.name()), comment)
+            })
+            .collect();
+        let width = lines.iter().map(|(line, _)| line.len()).max().unwrap_or(0);
+        let mut out = String::new();
+        for (line, comment) in lines {
+            let _ = writeln!(out, "{line:<width$}  # {comment}");
+        }
+        out
+    }
+}
+
+/*
+ * Un `#` dentro de un texto entre comillas no es un comentario
+ */
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn file(line: usize) -> Source {
+        Source::File {
+            path: "basics.conf".to_string(),
+            line,
+        }
+    }
+
+    #[test]
+    fn flag_over_env_over_file() {
+        let mut config = Config::new();
+        assert_eq!(config.get(&MAX_POINTS_KEY), Ok(MAX_POINTS));
+        assert_eq!(config.source("max_points"), Source::Default);
+
+        config
+            .load_str("basics.conf", "max_points = 200_000\ndimensions = 2\n")
+            .unwrap();
+        assert_eq!(config.get(&MAX_POINTS_KEY), Ok(200_000));
+        assert_eq!(config.source("max_points"), file(1));
+
+        let env = vars(&[("THE_BASICS_MAX_POINTS", "20000"), ("MAX_POINTS", "7")]);
+        config.load_env("THE_BASICS_", SETTINGS, env);
+        assert_eq!(config.get(&MAX_POINTS_KEY), Ok(20_000));
+        assert_eq!(
+            config.source("max_points"),
+            Source::Env {
+                var: "THE_BASICS_MAX_POINTS".to_string()
+            }
+        );
+
+        let rest = config
+            .load_args(SETTINGS, &args(&["--max-points", "5000", "input.txt"]))
+            .unwrap();
+        assert_eq!(rest, ["input.txt"]);
+        assert_eq!(config.get(&MAX_POINTS_KEY), Ok(5_000));
+        assert_eq!(
+            config.source("max_points"),
+            Source::Flag {
+                flag: "--max-points".to_string()
+            }
+        );
+        /*
+         * Una llave que solo define el archivo sigue saliendo del archivo
+         */
+        assert_eq!(config.get(&DIMENSIONS_KEY), Ok(2));
+        assert_eq!(config.source("dimensions"), file(2));
+    }
+
+    #[test]
+    fn sections_prefix_keys() {
+        let mut config = Config::new();
+        config
+            .load_str("basics.conf", "verbose = true\n[render]\nverbose = false\n[ ]\nx = 1\n")
+            .unwrap();
+        assert_eq!(config.source("verbose"), file(1));
+        assert_eq!(config.source("render.verbose"), file(3));
+        assert_eq!(config.source("x"), file(5));
+        assert_eq!(env_var("THE_BASICS_", "render.verbose"), "THE_BASICS_RENDER_VERBOSE");
+        assert_eq!(flag("render.max_points"), "--render-max-points");
+    }
+
+    #[test]
+    fn flag_values_inline_or_next() {
+        let mut inline = Config::new();
+        inline.load_args(SETTINGS, &args(&["--dimensions=4"])).unwrap();
+        let mut separate = Config::new();
+        separate.load_args(SETTINGS, &args(&["--dimensions", "4"])).unwrap();
+        assert_eq!(inline.get(&DIMENSIONS_KEY), Ok(4));
+        assert_eq!(separate.get(&DIMENSIONS_KEY), Ok(4));
+        /*
+         * `--dimensions=` es un valor vacío, no toma el siguiente argumento
+         */
+        let mut empty = Config::new();
+        let rest = empty.load_args(SETTINGS, &args(&["--dimensions=", "4"])).unwrap();
+        assert_eq!(rest, ["4"]);
+        assert!(empty.get(&DIMENSIONS_KEY).is_err());
+        assert_eq!(
+            Config::new().load_args(SETTINGS, &args(&["--max-points"])),
+            Err(ConfigError::MissingValue {
+                flag: "--max-points".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn bare_bool_flag_is_true() {
+        let mut config = Config::new();
+        let rest = config
+            .load_args(SETTINGS, &args(&["--verbose", "file.txt"]))
+            .unwrap();
+        assert_eq!(rest, ["file.txt"]);
+        assert_eq!(config.get(&VERBOSE_KEY), Ok(true));
+        config.load_args(SETTINGS, &args(&["--verbose=false"])).unwrap();
+        assert_eq!(config.get(&VERBOSE_KEY), Ok(false));
+    }
+
+    #[test]
+    fn comments_outside_quotes() {
+        assert_eq!(strip_comment("a = 1 # uno"), "a = 1 ");
+        assert_eq!(strip_comment("a = \"#1\" # uno"), "a = \"#1\" ");
+        assert_eq!(strip_comment("# todo"), "");
+        assert_eq!(strip_comment("a = \"sin # cerrar"), "a = \"sin # cerrar");
+        let mut config = Config::new();
+        config
+            .load_str("basics.conf", "max_points = \"#5\"  # comentario\n")
+            .unwrap();
+        let error = config.get(&MAX_POINTS_KEY).unwrap_err();
+        assert!(matches!(error, ConfigError::Invalid { ref value, .. } if value == "#5"));
+    }
+
+    #[test]
+    fn syntax_errors_name_the_line() {
+        let load = |text| Config::new().load_str("basics.conf", text);
+        assert_eq!(
+            load("\n[render\n"),
+            Err(ConfigError::Syntax {
+                source: file(2),
+                message: "missing `]` after the section name".to_string(),
+            })
+        );
+        assert_eq!(
+            load("max_points 5").unwrap_err().to_string(),
+            "basics.conf:1: expected `key = value`, found \"max_points 5\""
+        );
+        assert_eq!(
+            load("a = \"abc").unwrap_err().to_string(),
+            "basics.conf:1: unterminated string"
+        );
+    }
+
+    #[test]
+    fn validation_errors_name_their_source() {
+        let mut config = Config::new();
+        config
+            .load_str("basics.conf", "max_points = 0\ncolour = red\n")
+            .unwrap();
+        config.load_env("THE_BASICS_", SETTINGS, vars(&[("THE_BASICS_DIMENSIONS", "1e3")]));
+        config.load_args(SETTINGS, &args(&["--max-points", "10"])).unwrap();
+        /*
+         * El `flag` tapa el `0` del archivo, pero el archivo sigue siendo inválido
+         */
+        assert_eq!(config.get(&MAX_POINTS_KEY), Ok(10));
+        let errors: Vec<String> = config
+            .validate(SETTINGS)
+            .unwrap_err()
+            .iter()
+            .map(ConfigError::to_string)
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                "basics.conf:1: invalid value \"0\" for `max_points`: \
+                 0 is outside the range 1..=1000000",
+                "basics.conf:2: unknown key `colour`",
+                "env THE_BASICS_DIMENSIONS: invalid value \"1e3\" for `dimensions`: \
+                 expected `u8`, found floating-point number",
+            ]
+        );
+        let mut flags = Config::new();
+        flags.load_args(SETTINGS, &args(&["--dimensions=9"])).unwrap();
+        assert_eq!(
+            flags.get(&DIMENSIONS_KEY),
+            Err(ConfigError::Invalid {
+                key: "dimensions".to_string(),
+                value: "9".to_string(),
+                source: Source::Flag {
+                    flag: "--dimensions".to_string()
+                },
+                message: "9 is outside the range 1..=4".to_string(),
+            })
+        );
+        assert_eq!(Config::new().validate(SETTINGS), Ok(()));
+    }
+
+    #[test]
+    fn dump_shows_values_and_sources() {
+        let mut config = Config::new();
+        config.load_str("basics.conf", "max_points = 200_000\n").unwrap();
+        config.load_args(SETTINGS, &args(&["--verbose"])).unwrap();
+        assert_eq!(
+            config.dump(SETTINGS),
+            "max_points = 200000  # u32, basics.conf:1\n\
+             dimensions = 3       # u8, default\n\
+             verbose = true       # bool, flag --verbose\n"
+        );
+    }
+}
@@ -0,0 +1,451 @@
+/*
+ * Un flotante `IEEE-754` guarda tres campos en sus `bits`
+ *
+ *          sign  exponent       mantissa
+ *  f32     1     8  (bias 127)  23
+ *  f64     1     11 (bias 1023) 52
+ *
+ *  value = (-1)^sign * 1.mantissa * 2^(exponent - bias)
+ *
+ * Con el exponente en ceros el `1.` implícito se vuelve `0.` y el número es `subnormal`, con el
+ * exponente en unos el número es `inf` si la mantisa es cero o `NaN` si no lo es. Los flotantes no
+ * tienen precisión decimal, `0.1` no existe y se guarda el binario más cercano, por eso
+ * `exact_decimal()` muestra el valor real que se guardó
+ */
+use std::fmt;
+
+use super::bigint::BigUint;
+use super::literal::{self, LiteralError, Number};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    Single,
+    Double,
+}
+
+impl Precision {
+    pub fn name(self) -> &'static str {
+        match self {
+            Precision::Single => "f32",
+            Precision::Double => "f64",
+        }
+    }
+
+    pub fn bits(self) -> u32 {
+        match self {
+            Precision::Single => 32,
+            Precision::Double => 64,
+        }
+    }
+
+    pub fn exponent_bits(self) -> u32 {
+        match self {
+            Precision::Single => 8,
+            Precision::Double => 11,
+        }
+    }
+
+    pub fn mantissa_bits(self) -> u32 {
+        self.bits() - self.exponent_bits() - 1
+    }
+
+    pub fn bias(self) -> i32 {
+        (1 << (self.exponent_bits() - 1)) - 1
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Class {
+    Zero,
+    Subnormal,
+    Normal,
+    Infinite,
+    /*
+     * Un `NaN` `quiet` tiene el `bit` más alto de la mantisa encendido, el resto es el `payload`
+     */
+    Nan { quiet: bool, payload: u64 },
+}
+
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Class::Zero => write!(f, "zero"),
+            Class::Subnormal => write!(f, "subnormal"),
+            Class::Normal => write!(f, "normal"),
+            Class::Infinite => write!(f, "infinite"),
+            Class::Nan { quiet, payload } => {
+                let kind = if *quiet { "quiet" } else { "signaling" };
+                write!(f, "NaN ({kind}, payload {payload:#x})")
+            }
+        }
+    }
+}
+
+/*
+ * Los `bits` de un `f32` o un `f64`, los de `f32` ocupan la parte baja del `u64`
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Float {
+    precision: Precision,
+    bits: u64,
+}
+
+impl Float {
+    pub fn from_f32(value: f32) -> Self {
+        Self::from_bits(Precision::Single, value.to_bits() as u64)
+    }
+
+    pub fn from_f64(value: f64) -> Self {
+        Self::from_bits(Precision::Double, value.to_bits())
+    }
+
+    pub fn from_bits(precision: Precision, bits: u64) -> Self {
+        let mask = u64::MAX >> (64 - precision.bits());
+        Self {
+            precision,
+            bits: bits & mask,
+        }
+    }
+
+    pub fn precision(self) -> Precision {
+        self.precision
+    }
+
+    pub fn to_bits(self) -> u64 {
+        self.bits
+    }
+
+    /*
+     * Ensanchar un `f32` a `f64` siempre es exacto
+     */
+    pub fn to_f64(self) -> f64 {
+        match self.precision {
+            Precision::Single => f32::from_bits(self.bits as u32) as f64,
+            Precision::Double => f64::from_bits(self.bits),
+        }
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.bits >> (self.precision.bits() - 1) == 1
+    }
+
+    pub fn biased_exponent(self) -> u64 {
+        let mask = (1 << self.precision.exponent_bits()) - 1;
+        (self.bits >> self.precision.mantissa_bits()) & mask
+    }
+
+    pub fn mantissa(self) -> u64 {
+        self.bits & ((1 << self.precision.mantissa_bits()) - 1)
+    }
+
+    pub fn class(self) -> Class {
+        let max_exponent = (1 << self.precision.exponent_bits()) - 1;
+        let quiet_bit = 1 << (self.precision.mantissa_bits() - 1);
+        match (self.biased_exponent(), self.mantissa()) {
+            (0, 0) => Class::Zero,
+            (0, _) => Class::Subnormal,
+            (e, 0) if e == max_exponent => Class::Infinite,
+            (e, m) if e == max_exponent => Class::Nan {
+                quiet: m & quiet_bit != 0,
+                payload: m & !quiet_bit,
+            },
+            _ => Class::Normal,
+        }
+    }
+
+    pub fn is_finite(self) -> bool {
+        !matches!(self.class(), Class::Infinite | Class::Nan { .. })
+    }
+
+    /*
+     * El exponente sin `bias`, los `subnormal` usan el mismo que el `normal` más pequeño
+     */
+    pub fn exponent(self) -> Option<i32> {
+        match self.class() {
+            Class::Infinite | Class::Nan { .. } => None,
+            Class::Zero | Class::Subnormal => Some(1 - self.precision.bias()),
+            Class::Normal => Some(self.biased_exponent() as i32 - self.precision.bias()),
+        }
+    }
+
+    /*
+     * Sin el `bit` de signo los `bits` de los positivos ya están en orden, del cero a `inf`. Con
+     * el signo como negativo se obtiene una recta donde vecinos difieren en 1, un `ULP`
+     */
+    fn ordered(self) -> i64 {
+        let magnitude = (self.bits & (u64::MAX >> (65 - self.precision.bits()))) as i64;
+        if self.is_negative() {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    fn from_ordered(precision: Precision, ordered: i64) -> Self {
+        let sign = if ordered < 0 {
+            1 << (precision.bits() - 1)
+        } else {
+            0
+        };
+        Self::from_bits(precision, ordered.unsigned_abs() | sign)
+    }
+
+    /*
+     * El siguiente flotante hacia `inf`, `next_up(-0.0)` es el `subnormal` positivo más pequeño
+     */
+    pub fn next_up(self) -> Self {
+        match self.class() {
+            Class::Nan { .. } => self,
+            Class::Infinite if !self.is_negative() => self,
+            _ => Self::from_ordered(self.precision, self.ordered() + 1),
+        }
+    }
+
+    pub fn next_down(self) -> Self {
+        match self.class() {
+            Class::Nan { .. } => self,
+            Class::Infinite if self.is_negative() => self,
+            _ => Self::from_ordered(self.precision, self.ordered() - 1),
+        }
+    }
+
+    /*
+     * La distancia al siguiente flotante en magnitud, cuánto vale el último `bit` de la mantisa
+     */
+    pub fn ulp(self) -> Option<f64> {
+        if !self.is_finite() {
+            return None;
+        }
+        let magnitude = Self::from_bits(
+            self.precision,
+            self.bits & !(1 << (self.precision.bits() - 1)),
+        );
+        let up = magnitude.next_up();
+        Some(match up.is_finite() {
+            true => up.to_f64() - magnitude.to_f64(),
+            false => magnitude.to_f64() - magnitude.next_down().to_f64(),
+        })
+    }
+
+    /*
+     * Cuántos flotantes hay entre los dos, `None` si alguno es `NaN` o las precisiones no coinciden
+     */
+    pub fn ulps_between(self, other: Float) -> Option<u64> {
+        let comparable = self.precision == other.precision
+            && !matches!(self.class(), Class::Nan { .. })
+            && !matches!(other.class(), Class::Nan { .. });
+        comparable.then(|| self.ordered().abs_diff(other.ordered()))
+    }
+
+    /*
+     * Todo flotante finito es `m * 2^e` con `m` entero, si `e` es negativo se multiplica por
+     * `5^-e` y se recorre el punto `-e` lugares, el resultado siempre termina
+     */
+    pub fn exact_decimal(self) -> String {
+        let sign = if self.is_negative() { "-" } else { "" };
+        let exponent = match self.class() {
+            Class::Nan { .. } => return "NaN".to_string(),
+            Class::Infinite => return format!("{sign}inf"),
+            _ => self.exponent().unwrap_or_default() - self.precision.mantissa_bits() as i32,
+        };
+        let significand = match self.class() {
+            Class::Normal => self.mantissa() | (1 << self.precision.mantissa_bits()),
+            _ => self.mantissa(),
+        };
+        let significand = BigUint::from(significand);
+        if exponent >= 0 {
+            return format!("{sign}{}", significand << exponent as usize);
+        }
+        let places = exponent.unsigned_abs() as usize;
+        let digits = (significand * BigUint::from(5u32).pow(places as u32)).to_string();
+        let digits = format!("{digits:0>width$}", width = places + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - places);
+        let fraction = fraction.trim_end_matches('0');
+        match fraction {
+            "" => format!("{sign}{whole}"),
+            _ => format!("{sign}{whole}.{fraction}"),
+        }
+    }
+
+    /*
+     * Los tres campos separados, con el valor de cada uno
+     */
+    pub fn layout(self) -> Vec<String> {
+        let sign = if self.is_negative() { "1 (-)" } else { "0 (+)" };
+        let exponent_bits = self.precision.exponent_bits() as usize;
+        let mantissa_bits = self.precision.mantissa_bits() as usize;
+        let exponent = match self.exponent() {
+            Some(unbiased) => format!(
+                "{:0exponent_bits$b}  {} - {} = {unbiased}",
+                self.biased_exponent(),
+                self.biased_exponent(),
+                self.precision.bias()
+            ),
+            None => format!("{:0exponent_bits$b}  all ones", self.biased_exponent()),
+        };
+        let implicit = match self.class() {
+            Class::Normal => "1.",
+            _ => "0.",
+        };
+        vec![
+            format!("sign      {sign}"),
+            format!("exponent  {exponent}"),
+            format!("mantissa  {:0mantissa_bits$b}  {implicit}", self.mantissa()),
+        ]
+    }
+}
+
+impl fmt::Display for Float {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.precision {
+            Precision::Single => write!(f, "{:?}", f32::from_bits(self.bits as u32)),
+            Precision::Double => write!(f, "{:?}", f64::from_bits(self.bits)),
+        }
+    }
+}
+
+/*
+ * Acepta cualquier literal de `Rust` más `NaN`, `inf` y `-inf`. Sin `precision` el sufijo decide y
+ * sin sufijo es `f64`
+ */
+pub fn parse(text: &str, precision: Option<Precision>) -> Result<Float, LiteralError> {
+    let special = match text.trim().to_ascii_lowercase().as_str() {
+        "nan" => Some(f64::NAN),
+        "inf" | "+inf" | "infinity" => Some(f64::INFINITY),
+        "-inf" | "-infinity" => Some(f64::NEG_INFINITY),
+        _ => None,
+    };
+    let value = match (special, precision) {
+        (Some(value), Some(Precision::Single)) => return Ok(Float::from_f32(value as f32)),
+        (Some(value), _) => return Ok(Float::from_f64(value)),
+        (None, Some(Precision::Single)) => {
+            return literal::parse_as::<f32>(text).map(Float::from_f32)
+        }
+        (None, Some(Precision::Double)) => {
+            return literal::parse_as::<f64>(text).map(Float::from_f64)
+        }
+        (None, None) => literal::parse_literal(text)?.value,
+    };
+    Ok(match value {
+        Number::F32(value) => Float::from_f32(value),
+        Number::F64(value) => Float::from_f64(value),
+        other => Float::from_f64(other.as_f64()),
+    })
+}
+
+pub fn inspect(value: Float) -> Vec<String> {
+    let hex_digits = value.precision.bits() as usize / 4;
+    let mut lines = vec![
+        format!("{} {value}", value.precision.name()),
+        format!("bits      {:#0width$x}", value.bits, width = hex_digits + 2),
+    ];
+    lines.extend(value.layout());
+    lines.push(format!("class     {}", value.class()));
+    lines.push(format!("exact     {}", value.exact_decimal()));
+    lines.push(format!("next up   {}", value.next_up()));
+    lines.push(format!("next down {}", value.next_down()));
+    if let Some(ulp) = value.ulp() {
+        lines.push(format!("ulp       {ulp:e}"));
+    }
+    lines
+}
+
+/*
+ * `f64 -> f32 -> f64`, el viaje de vuelta solo es exacto si el valor cabe en los 24 `bits` de
+ * mantisa de `f32` y en su rango. El otro sentido `f32 -> f64 -> f32` siempre es exacto
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoundTrip {
+    pub original: f64,
+    pub narrowed: f32,
+    pub widened: f64,
+}
+
+impl RoundTrip {
+    pub fn is_exact(&self) -> bool {
+        self.original.to_bits() == self.widened.to_bits()
+    }
+
+    /*
+     * Cuántos `f64` separan al original del que regresó
+     */
+    pub fn ulps_lost(&self) -> Option<u64> {
+        Float::from_f64(self.original).ulps_between(Float::from_f64(self.widened))
+    }
+
+    pub fn relative_error(&self) -> f64 {
+        match self.original {
+            0.0 => 0.0,
+            original => ((self.widened - original) / original).abs(),
+        }
+    }
+}
+
+impl fmt::Display for RoundTrip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} as f32 = {:?}, back as f64 = {:?}",
+            self.original, self.narrowed, self.widened
+        )?;
+        match (self.is_exact(), self.ulps_lost()) {
+            (true, _) => write!(f, ", exact"),
+            (false, Some(ulps)) => write!(
+                f,
+                ", off by {ulps} f64 ulps (relative error {:e})",
+                self.relative_error()
+            ),
+            (false, None) => write!(f, ", not comparable"),
+        }
+    }
+}
+
+pub fn round_trip(value: f64) -> RoundTrip {
+    let narrowed = value as f32;
+    RoundTrip {
+        original: value,
+        narrowed,
+        widened: narrowed as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+     * Leído como `f64` y convertido a `f32` daba `1.0`, `0x3f800000`
+     */
+    #[test]
+    fn f32_is_read_without_double_rounding() {
+        let text = "1.0000000596046447753906250000000001";
+        let value = parse(text, Some(Precision::Single)).unwrap();
+        assert_eq!(value.to_bits(), 0x3f80_0001);
+        assert_eq!(value.next_down().to_bits(), 0x3f80_0000);
+        assert_eq!(value.next_up().to_bits(), 0x3f80_0002);
+        assert_eq!(value.exact_decimal(), "1.00000011920928955078125");
+        assert_eq!(parse(&format!("{text}f32"), None), Ok(value));
+        assert_eq!(value.ulps_between(Float::from_f32(1.0)), Some(1));
+    }
+
+    #[test]
+    fn parse_matches_std() {
+        for text in ["0.1", "1e-45", "3.4028235e38", "16777217", "-2.5e-10"] {
+            let single = parse(text, Some(Precision::Single)).unwrap();
+            assert_eq!(single, Float::from_f32(text.parse().unwrap()), "{text}");
+            let double = parse(text, Some(Precision::Double)).unwrap();
+            assert_eq!(double, Float::from_f64(text.parse().unwrap()), "{text}");
+        }
+    }
+
+    #[test]
+    fn special_values() {
+        let nan = parse("NaN", Some(Precision::Single)).unwrap();
+        assert!(matches!(nan.class(), Class::Nan { .. }));
+        assert_eq!(parse("-inf", None), Ok(Float::from_f64(f64::NEG_INFINITY)));
+        assert_eq!(
+            parse("1e39", Some(Precision::Single)).map_err(|error| error.to_string()),
+            Err("value 1e39 out of range for f32".to_string())
+        );
+    }
+}
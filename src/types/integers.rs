@@ -1,8 +1,10 @@
+use crate::types::bigint;
+
 /*
  * Los tipos de datos `scalar` representan un solo valor y son indivisibles sin subestructuras
  * internas. `Rust` maneja dos subsets de tipos los `scalar` y los `compound`
  */
-fn run() {
+pub fn run() {
     /*
      * Los tipos `Integer` son números enteros sin parte decimal, y pueden ser con signo
      * (positivos o negativos) o sin signo (solo positivos). Los tamaños varían en función de
@@ -31,9 +33,14 @@ fn run() {
     println!("Long: Unsigned {long}, signed {min_long} to {long_sg}");
     
     let biglong: u128 = 340_282_366_920_938_463_463_374_607_431_768_211_455; // -> 0 .. 340,282,366,920,938,463,463,374,607,431,768,211,455
-    let biglong = i128::MIN;
+    let min_biglong = i128::MIN;
     let biglong_sg: i128 = 170_141_183_460_469_231_731_687_303_715_884_105_727; // -> -170,141,183,460,469,231,731,687,303,715,884,105,728 .. 170,141,183,460,469,231,731,687,303,715,884,105,727
-    println!("BigLong: Unsigned {biglong}, signed {biglong} to {biglong_sg}");
+    println!("BigLong: Unsigned {biglong}, signed {min_biglong} to {biglong_sg}");
+    /*
+     * Para números más grandes que `u128`, como `35!`, se usa `types::bigint::BigUint` que crece
+     * lo necesario
+     */
+    println!("35! = {}", bigint::factorial(35));
 
     /*
      * Los `scalar types` también tienen representación en formato decimal, hexadecimal, octal y binario.
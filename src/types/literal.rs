@@ -0,0 +1,881 @@
+/*
+ * Un literal numérico de `Rust` tiene tres partes, un prefijo opcional que elige la base, los
+ * dígitos con `_` como separador y un sufijo opcional con el tipo
+ *
+ *      0x  ff_ff  u16        1_000.5  e-3  f32        b'A'
+ *      |   |      |          |        |    |          |
+ *      |   |      sufijo     |        |    sufijo     byte literal, un `u8`
+ *      |   dígitos           |        exponente
+ *      prefijo               dígitos
+ *
+ * Sin sufijo un entero es `i32` y un flotante es `f64`. El `parser` devuelve el valor con su tipo o
+ * un error preciso, y `RadixFormat` hace el camino inverso, escribe un número en cualquier base
+ */
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ty {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+    F32,
+    F64,
+}
+
+impl Ty {
+    const ALL: [Ty; 14] = [
+        Ty::I8,
+        Ty::I16,
+        Ty::I32,
+        Ty::I64,
+        Ty::I128,
+        Ty::Isize,
+        Ty::U8,
+        Ty::U16,
+        Ty::U32,
+        Ty::U64,
+        Ty::U128,
+        Ty::Usize,
+        Ty::F32,
+        Ty::F64,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Ty::I8 => "i8",
+            Ty::I16 => "i16",
+            Ty::I32 => "i32",
+            Ty::I64 => "i64",
+            Ty::I128 => "i128",
+            Ty::Isize => "isize",
+            Ty::U8 => "u8",
+            Ty::U16 => "u16",
+            Ty::U32 => "u32",
+            Ty::U64 => "u64",
+            Ty::U128 => "u128",
+            Ty::Usize => "usize",
+            Ty::F32 => "f32",
+            Ty::F64 => "f64",
+        }
+    }
+
+    pub fn from_suffix(suffix: &str) -> Option<Ty> {
+        Ty::ALL.into_iter().find(|ty| ty.name() == suffix)
+    }
+
+    pub fn is_float(self) -> bool {
+        matches!(self, Ty::F32 | Ty::F64)
+    }
+
+    pub fn is_signed(self) -> bool {
+        !matches!(
+            self,
+            Ty::U8 | Ty::U16 | Ty::U32 | Ty::U64 | Ty::U128 | Ty::Usize
+        )
+    }
+
+    /*
+     * El rango de los enteros como `i128` y `u128`, los dos extremos son inclusivos
+     */
+    fn int_range(self) -> (i128, u128) {
+        match self {
+            Ty::I8 => (i8::MIN as i128, i8::MAX as u128),
+            Ty::I16 => (i16::MIN as i128, i16::MAX as u128),
+            Ty::I32 => (i32::MIN as i128, i32::MAX as u128),
+            Ty::I64 => (i64::MIN as i128, i64::MAX as u128),
+            Ty::I128 => (i128::MIN, i128::MAX as u128),
+            Ty::Isize => (isize::MIN as i128, isize::MAX as u128),
+            Ty::U8 => (0, u8::MAX as u128),
+            Ty::U16 => (0, u16::MAX as u128),
+            Ty::U32 => (0, u32::MAX as u128),
+            Ty::U64 => (0, u64::MAX as u128),
+            Ty::U128 => (0, u128::MAX),
+            Ty::Usize => (0, usize::MAX as u128),
+            Ty::F32 | Ty::F64 => (0, 0),
+        }
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    Isize(isize),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Usize(usize),
+    F32(f32),
+    F64(f64),
+}
+
+impl Number {
+    pub fn ty(&self) -> Ty {
+        match self {
+            Number::I8(_) => Ty::I8,
+            Number::I16(_) => Ty::I16,
+            Number::I32(_) => Ty::I32,
+            Number::I64(_) => Ty::I64,
+            Number::I128(_) => Ty::I128,
+            Number::Isize(_) => Ty::Isize,
+            Number::U8(_) => Ty::U8,
+            Number::U16(_) => Ty::U16,
+            Number::U32(_) => Ty::U32,
+            Number::U64(_) => Ty::U64,
+            Number::U128(_) => Ty::U128,
+            Number::Usize(_) => Ty::Usize,
+            Number::F32(_) => Ty::F32,
+            Number::F64(_) => Ty::F64,
+        }
+    }
+
+    /*
+     * Los enteros con signo caben en `i128`, los `u128` más grandes que `i128::MAX` no
+     */
+    pub fn as_i128(&self) -> Option<i128> {
+        match *self {
+            Number::I8(v) => Some(v as i128),
+            Number::I16(v) => Some(v as i128),
+            Number::I32(v) => Some(v as i128),
+            Number::I64(v) => Some(v as i128),
+            Number::I128(v) => Some(v),
+            Number::Isize(v) => Some(v as i128),
+            Number::U8(v) => Some(v as i128),
+            Number::U16(v) => Some(v as i128),
+            Number::U32(v) => Some(v as i128),
+            Number::U64(v) => Some(v as i128),
+            Number::U128(v) => i128::try_from(v).ok(),
+            Number::Usize(v) => Some(v as i128),
+            Number::F32(_) | Number::F64(_) => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Number::F32(v) => v as f64,
+            Number::F64(v) => v,
+            Number::U128(v) => v as f64,
+            _ => self.as_i128().unwrap_or_default() as f64,
+        }
+    }
+
+    /*
+     * Los `bits` del valor como los guarda la memoria, los negativos en complemento a dos y los
+     * flotantes en `IEEE-754`, es lo que imprimen `{:x}` y `{:b}`
+     */
+    pub fn to_bits(&self) -> u128 {
+        match *self {
+            Number::I8(v) => v as u8 as u128,
+            Number::I16(v) => v as u16 as u128,
+            Number::I32(v) => v as u32 as u128,
+            Number::I64(v) => v as u64 as u128,
+            Number::I128(v) => v as u128,
+            Number::Isize(v) => v as usize as u128,
+            Number::U8(v) => v as u128,
+            Number::U16(v) => v as u128,
+            Number::U32(v) => v as u128,
+            Number::U64(v) => v as u128,
+            Number::U128(v) => v,
+            Number::Usize(v) => v as u128,
+            Number::F32(v) => v.to_bits() as u128,
+            Number::F64(v) => v.to_bits() as u128,
+        }
+    }
+
+    fn from_int(ty: Ty, value: i128, magnitude: u128) -> Number {
+        match ty {
+            Ty::I8 => Number::I8(value as i8),
+            Ty::I16 => Number::I16(value as i16),
+            Ty::I32 => Number::I32(value as i32),
+            Ty::I64 => Number::I64(value as i64),
+            Ty::I128 => Number::I128(value),
+            Ty::Isize => Number::Isize(value as isize),
+            Ty::U8 => Number::U8(magnitude as u8),
+            Ty::U16 => Number::U16(magnitude as u16),
+            Ty::U32 => Number::U32(magnitude as u32),
+            Ty::U64 => Number::U64(magnitude as u64),
+            Ty::U128 => Number::U128(magnitude),
+            Ty::Usize => Number::Usize(magnitude as usize),
+            Ty::F32 | Ty::F64 => unreachable!("floats are parsed from their digits"),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::F32(v) => write!(f, "{v:?}"),
+            Number::F64(v) => write!(f, "{v:?}"),
+            Number::U128(v) => write!(f, "{v}"),
+            other => write!(f, "{}", other.as_i128().unwrap_or_default()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiteralError {
+    Empty,
+    /*
+     * La posición es el `byte` dentro del texto original
+     */
+    InvalidDigit {
+        ch: char,
+        index: usize,
+        radix: u32,
+    },
+    MissingDigits,
+    /*
+     * `.5`, en `Rust` un flotante siempre empieza con un dígito
+     */
+    MissingIntegerPart,
+    InvalidSuffix(String),
+    OutOfRange {
+        value: String,
+        ty: Ty,
+        suffixed: bool,
+    },
+    /*
+     * `-1u8`, el operador `-` no existe para los enteros sin signo
+     */
+    NegativeUnsigned(Ty),
+    FloatRadix(u32),
+    InvalidFloat(String),
+    InvalidByte(String),
+    MismatchedSuffix {
+        expected: Ty,
+        found: Ty,
+    },
+    /*
+     * `1e3` donde se espera un entero, sin sufijo el tipo viene de fuera y no del texto
+     */
+    ExpectedInteger(Ty),
+}
+
+impl fmt::Display for LiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LiteralError::Empty => write!(f, "empty literal"),
+            LiteralError::InvalidDigit { ch, index, radix } => {
+                write!(
+                    f,
+                    "invalid digit {ch:?} for a base {radix} literal at position {index}"
+                )
+            }
+            LiteralError::MissingDigits => write!(f, "no valid digits found for number"),
+            LiteralError::MissingIntegerPart => {
+                write!(f, "float literals must have an integer part")
+            }
+            LiteralError::InvalidSuffix(suffix) => {
+                write!(f, "invalid suffix `{suffix}` for number literal")
+            }
+            LiteralError::OutOfRange {
+                value,
+                ty,
+                suffixed: true,
+            } => {
+                write!(f, "suffix {ty} but value {value} out of range")
+            }
+            LiteralError::OutOfRange {
+                value,
+                ty,
+                suffixed: false,
+            } => {
+                write!(f, "value {value} out of range for {ty}")
+            }
+            LiteralError::NegativeUnsigned(ty) => {
+                write!(f, "cannot apply unary operator `-` to type `{ty}`")
+            }
+            LiteralError::FloatRadix(radix) => {
+                write!(f, "base {radix} float literal is not supported")
+            }
+            LiteralError::InvalidFloat(text) => write!(f, "invalid float literal `{text}`"),
+            LiteralError::InvalidByte(text) => write!(f, "invalid byte literal `{text}`"),
+            LiteralError::MismatchedSuffix { expected, found } => {
+                write!(
+                    f,
+                    "expected `{expected}`, found literal with suffix `{found}`"
+                )
+            }
+            LiteralError::ExpectedInteger(ty) => {
+                write!(f, "expected `{ty}`, found floating-point number")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LiteralError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Literal {
+    pub value: Number,
+    pub radix: u32,
+    /*
+     * `false` cuando el tipo se eligió por defecto, `i32` o `f64`
+     */
+    pub suffixed: bool,
+}
+
+/*
+ * `b'A'`, `b'\n'`, `b'\x7f'`, solo `ASCII`, igual que en `Rust`
+ */
+fn parse_byte(text: &str) -> Result<u8, LiteralError> {
+    let invalid = || LiteralError::InvalidByte(text.to_string());
+    let inner = text
+        .strip_prefix("b'")
+        .and_then(|rest| rest.strip_suffix('\''))
+        .ok_or_else(invalid)?;
+    let byte = match inner.as_bytes() {
+        [b'\\', b'n'] => b'\n',
+        [b'\\', b'r'] => b'\r',
+        [b'\\', b't'] => b'\t',
+        [b'\\', b'0'] => 0,
+        [b'\\', b'\\'] => b'\\',
+        [b'\\', b'\''] => b'\'',
+        [b'\\', b'"'] => b'"',
+        [b'\\', b'x', hi, lo] => {
+            let hex = std::str::from_utf8(&[*hi, *lo])
+                .map_err(|_| invalid())?
+                .to_string();
+            u8::from_str_radix(&hex, 16).map_err(|_| invalid())?
+        }
+        [byte] if byte.is_ascii() && *byte != b'\'' && *byte != b'\\' => *byte,
+        _ => return Err(invalid()),
+    };
+    Ok(byte)
+}
+
+/*
+ * Separa prefijo, dígitos y sufijo. Los dígitos siguen hasta el primer caracter que no es un
+ * dígito de la base, un `_` o, en decimal, parte de un flotante. Por eso `0x1f32` es un entero
+ * hexadecimal y no un `f32`, igual que en `Rust`
+ */
+fn split(text: &str) -> (u32, usize, usize) {
+    let (radix, start) = match text.get(..2) {
+        Some("0x") => (16, 2),
+        Some("0o") => (8, 2),
+        Some("0b") => (2, 2),
+        _ => (10, 0),
+    };
+    let bytes = text.as_bytes();
+    let mut end = start;
+    while end < bytes.len() {
+        let c = bytes[end] as char;
+        let is_digit = c == '_' || c.is_digit(if radix == 2 || radix == 8 { 10 } else { radix });
+        let is_float = radix == 10
+            && match c {
+                '.' => bytes.get(end + 1).is_none_or(|next| next.is_ascii_digit()),
+                'e' | 'E' => bytes.get(end + 1).is_some_and(|&next| {
+                    next.is_ascii_digit() || next == b'+' || next == b'-' || next == b'_'
+                }),
+                '+' | '-' => end > start && matches!(bytes[end - 1], b'e' | b'E'),
+                _ => false,
+            };
+        if !is_digit && !is_float {
+            break;
+        }
+        end += 1;
+    }
+    (radix, start, end)
+}
+
+fn parse_int(digits: &str, radix: u32, offset: usize) -> Result<Option<u128>, LiteralError> {
+    let mut value: Option<u128> = Some(0);
+    let mut any = false;
+    for (index, c) in digits.char_indices() {
+        if c == '_' {
+            continue;
+        }
+        let digit = c.to_digit(radix).ok_or(LiteralError::InvalidDigit {
+            ch: c,
+            index: offset + index,
+            radix,
+        })?;
+        any = true;
+        value = value
+            .and_then(|v| v.checked_mul(radix as u128))
+            .and_then(|v| v.checked_add(digit as u128));
+    }
+    if !any {
+        return Err(LiteralError::MissingDigits);
+    }
+    Ok(value)
+}
+
+/*
+ * Acepta un `-` al inicio, `Rust` lo trata como el operador de negación aplicado al literal
+ */
+pub fn parse_literal(text: &str) -> Result<Literal, LiteralError> {
+    parse_inferred(text, None)
+}
+
+fn parse_inferred(text: &str, expected: Option<Ty>) -> Result<Literal, LiteralError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(LiteralError::Empty);
+    }
+    let (negative, body, offset) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest, 1),
+        None => (false, text, 0),
+    };
+    if body.starts_with("b'") {
+        let byte = parse_byte(body)?;
+        if negative {
+            return Err(LiteralError::NegativeUnsigned(Ty::U8));
+        }
+        let ty = expected.unwrap_or(Ty::U8);
+        if ty.is_float() {
+            return Err(LiteralError::MismatchedSuffix {
+                expected: ty,
+                found: Ty::U8,
+            });
+        }
+        return check_int(ty, false, byte as u128, body, expected.is_none()).map(|value| Literal {
+            value,
+            radix: 10,
+            suffixed: true,
+        });
+    }
+    let (radix, start, end) = split(body);
+    let digits = &body[start..end];
+    if digits.starts_with('.') {
+        return Err(LiteralError::MissingIntegerPart);
+    }
+    let suffix = &body[end..];
+    let suffix_ty = match suffix {
+        "" => None,
+        _ => Some(
+            Ty::from_suffix(suffix)
+                .ok_or_else(|| LiteralError::InvalidSuffix(suffix.to_string()))?,
+        ),
+    };
+    if let (Some(expected), Some(found)) = (expected, suffix_ty) {
+        if expected != found {
+            return Err(LiteralError::MismatchedSuffix { expected, found });
+        }
+    }
+    let is_float = radix == 10 && digits.contains(['.', 'e', 'E']);
+    let ty = suffix_ty
+        .or(expected)
+        .unwrap_or(if is_float { Ty::F64 } else { Ty::I32 });
+    let shown = &body[..end];
+    let value = if ty.is_float() {
+        if radix != 10 {
+            return Err(LiteralError::FloatRadix(radix));
+        }
+        if digits.is_empty() || digits.starts_with('_') {
+            return Err(LiteralError::MissingDigits);
+        }
+        /*
+         * El texto se convierte directo al tipo final, leer un `f32` como `f64` y después
+         * convertirlo redondea dos veces y puede dar el `f32` vecino
+         */
+        let clean: String = digits.chars().filter(|&c| c != '_').collect();
+        let clean = format!("{}{clean}", if negative { "-" } else { "" });
+        let invalid = || LiteralError::InvalidFloat(shown.to_string());
+        let value = match ty {
+            Ty::F32 => Number::F32(clean.parse().map_err(|_| invalid())?),
+            _ => Number::F64(clean.parse().map_err(|_| invalid())?),
+        };
+        if !value.as_f64().is_finite() {
+            return Err(LiteralError::OutOfRange {
+                value: format!("{}{shown}", if negative { "-" } else { "" }),
+                ty,
+                suffixed: suffix_ty.is_some(),
+            });
+        }
+        value
+    } else {
+        if is_float && suffix_ty.is_none() {
+            return Err(LiteralError::ExpectedInteger(ty));
+        }
+        if is_float {
+            return Err(LiteralError::InvalidSuffix(ty.name().to_string()));
+        }
+        /*
+         * `_1` es un identificador, pero después de un prefijo `0x_ff` es válido
+         */
+        if start == 0 && digits.starts_with('_') {
+            return Err(LiteralError::InvalidDigit {
+                ch: '_',
+                index: offset + start,
+                radix,
+            });
+        }
+        let magnitude = parse_int(digits, radix, offset + start)?;
+        let shown = format!("{}{shown}", if negative { "-" } else { "" });
+        match magnitude {
+            Some(magnitude) => check_int(ty, negative, magnitude, &shown, suffix_ty.is_some())?,
+            None => {
+                return Err(LiteralError::OutOfRange {
+                    value: shown,
+                    ty,
+                    suffixed: suffix_ty.is_some(),
+                })
+            }
+        }
+    };
+    Ok(Literal {
+        value,
+        radix,
+        suffixed: suffix_ty.is_some(),
+    })
+}
+
+fn check_int(
+    ty: Ty,
+    negative: bool,
+    magnitude: u128,
+    shown: &str,
+    suffixed: bool,
+) -> Result<Number, LiteralError> {
+    if negative && !ty.is_signed() {
+        return Err(LiteralError::NegativeUnsigned(ty));
+    }
+    let (min, max) = ty.int_range();
+    let in_range = if negative {
+        magnitude <= min.unsigned_abs()
+    } else {
+        magnitude <= max
+    };
+    if !in_range {
+        return Err(LiteralError::OutOfRange {
+            value: shown.to_string(),
+            ty,
+            suffixed,
+        });
+    }
+    let value = if negative {
+        0i128.wrapping_sub_unsigned(magnitude)
+    } else {
+        magnitude as i128
+    };
+    Ok(Number::from_int(ty, value, magnitude))
+}
+
+/*
+ * Los tipos que se pueden leer de un literal con `parse_as::<T>()`, sin sufijo el literal toma el
+ * tipo `T` en lugar de `i32`, con sufijo el sufijo debe coincidir
+ */
+pub trait FromLiteral: Sized {
+    const TY: Ty;
+
+    fn from_number(number: Number) -> Option<Self>;
+}
+
+macro_rules! from_literal {
+    ($($t:ty => $variant:ident),*) => {$(
+        impl FromLiteral for $t {
+            const TY: Ty = Ty::$variant;
+
+            fn from_number(number: Number) -> Option<Self> {
+                match number {
+                    Number::$variant(value) => Some(value),
+                    _ => None,
+                }
+            }
+        }
+    )*};
+}
+
+from_literal!(
+    i8 => I8, i16 => I16, i32 => I32, i64 => I64, i128 => I128, isize => Isize,
+    u8 => U8, u16 => U16, u32 => U32, u64 => U64, u128 => U128, usize => Usize,
+    f32 => F32, f64 => F64
+);
+
+pub fn parse_as<T: FromLiteral>(text: &str) -> Result<T, LiteralError> {
+    let literal = parse_inferred(text, Some(T::TY))?;
+    T::from_number(literal.value).ok_or(LiteralError::MismatchedSuffix {
+        expected: T::TY,
+        found: literal.value.ty(),
+    })
+}
+
+/*
+ * Escribe números en cualquier base de 2 a 36, con prefijo y dígitos agrupados
+ *
+ *  RadixFormat::new(2).grouped().format_u128(255)  ->  0b1111_1111
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RadixFormat {
+    radix: u32,
+    prefix: bool,
+    group: usize,
+    separator: char,
+    uppercase: bool,
+    min_digits: usize,
+}
+
+impl RadixFormat {
+    pub fn new(radix: u32) -> Self {
+        assert!((2..=36).contains(&radix), "radix {radix} is not in 2..=36");
+        Self {
+            radix,
+            prefix: true,
+            group: 0,
+            separator: '_',
+            uppercase: false,
+            min_digits: 1,
+        }
+    }
+
+    /*
+     * Agrupa como se suele leer cada base, de 4 en binario y hexadecimal, de 3 en las demás
+     */
+    pub fn grouped(self) -> Self {
+        let group = if matches!(self.radix, 2 | 16) { 4 } else { 3 };
+        self.group(group)
+    }
+
+    pub fn group(mut self, size: usize) -> Self {
+        self.group = size;
+        self
+    }
+
+    pub fn separator(mut self, separator: char) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    pub fn uppercase(mut self) -> Self {
+        self.uppercase = true;
+        self
+    }
+
+    pub fn no_prefix(mut self) -> Self {
+        self.prefix = false;
+        self
+    }
+
+    /*
+     * Rellena con ceros a la izquierda, útil para mostrar todos los `bits` de un tipo
+     */
+    pub fn min_digits(mut self, digits: usize) -> Self {
+        self.min_digits = digits.max(1);
+        self
+    }
+
+    fn prefix(&self) -> &'static str {
+        match (self.prefix, self.radix) {
+            (true, 2) => "0b",
+            (true, 8) => "0o",
+            (true, 16) => "0x",
+            _ => "",
+        }
+    }
+
+    pub fn format_u128(&self, mut value: u128) -> String {
+        let mut digits = Vec::new();
+        while value > 0 || digits.is_empty() {
+            let digit = std::char::from_digit((value % self.radix as u128) as u32, self.radix)
+                .unwrap_or('?');
+            digits.push(if self.uppercase {
+                digit.to_ascii_uppercase()
+            } else {
+                digit
+            });
+            value /= self.radix as u128;
+        }
+        while digits.len() < self.min_digits {
+            digits.push('0');
+        }
+        let mut out = String::from(self.prefix());
+        for (index, digit) in digits.iter().enumerate().rev() {
+            out.push(*digit);
+            if self.group > 0 && index > 0 && index % self.group == 0 {
+                out.push(self.separator);
+            }
+        }
+        out
+    }
+
+    pub fn format_i128(&self, value: i128) -> String {
+        let digits = self.format_u128(value.unsigned_abs());
+        if value < 0 {
+            format!("-{digits}")
+        } else {
+            digits
+        }
+    }
+
+    /*
+     * En decimal se escribe el valor con signo, en las demás bases los `bits` del tipo como hace
+     * `{:x}`, `-1i8` es `0xff`
+     */
+    pub fn format(&self, number: &Number) -> String {
+        match (self.radix, number) {
+            (10, Number::F32(_) | Number::F64(_)) => number.to_string(),
+            (10, Number::U128(value)) => self.format_u128(*value),
+            (10, other) => self.format_i128(other.as_i128().unwrap_or_default()),
+            (_, other) => self.format_u128(other.to_bits()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn f32_literals_round_once() {
+        let literal = parse_literal("1.0000000596046447753906250000000001f32").unwrap();
+        assert_eq!(literal.value.to_bits(), 0x3f80_0001);
+        /*
+         * `2^24 + 1` queda justo a la mitad entre dos `f32`, se redondea al par
+         */
+        assert_eq!(parse_as::<f32>("16777217"), Ok(16_777_216.0));
+    }
+
+    #[test]
+    fn floats_match_std_parse() {
+        for text in [
+            "0.1",
+            "3.4028235e38",
+            "1.17549435e-38",
+            "1e-45",
+            "0.30000000000000004",
+            "2.5",
+            "9007199254740993",
+            "1.0000000596046447753906250000000001",
+        ] {
+            let f32_bits = parse_as::<f32>(text).map(f32::to_bits);
+            assert_eq!(
+                f32_bits,
+                Ok(text.parse::<f32>().unwrap().to_bits()),
+                "{text}"
+            );
+            let f64_bits = parse_as::<f64>(text).map(f64::to_bits);
+            assert_eq!(
+                f64_bits,
+                Ok(text.parse::<f64>().unwrap().to_bits()),
+                "{text}"
+            );
+            let negative = format!("-{text}");
+            assert_eq!(
+                parse_as::<f32>(&negative).map(f32::to_bits),
+                Ok(negative.parse::<f32>().unwrap().to_bits()),
+                "{negative}"
+            );
+        }
+    }
+
+    #[test]
+    fn floats_need_an_integer_part() {
+        assert_eq!(parse_literal(".5"), Err(LiteralError::MissingIntegerPart));
+        assert_eq!(parse_literal("-.5"), Err(LiteralError::MissingIntegerPart));
+        assert_eq!(parse_as::<f64>("1."), Ok(1.0));
+        assert_eq!(
+            parse_literal("1.e3"),
+            Err(LiteralError::InvalidSuffix(".e3".to_string()))
+        );
+    }
+
+    /*
+     * `Rust` no tiene un operador `+` unario, y el signo solo puede seguir a un `e` del exponente,
+     * antes un `+` al inicio restaba uno a la posición `0` y lanzaba un `panic`
+     */
+    #[test]
+    fn leading_sign_is_not_an_exponent() {
+        for text in ["+5", "+", "-+1", "--1", "+e5"] {
+            assert!(parse_literal(text).is_err(), "{text}");
+        }
+        assert_eq!(
+            parse_literal("+5"),
+            Err(LiteralError::InvalidSuffix("+5".to_string()))
+        );
+        assert_eq!(
+            parse_literal("-+1"),
+            Err(LiteralError::InvalidSuffix("+1".to_string()))
+        );
+        assert_eq!(parse_as::<u32>("+5"), Err(LiteralError::InvalidSuffix("+5".to_string())));
+        assert_eq!(parse_as::<f64>("1e+5"), Ok(1e5));
+        assert_eq!(parse_as::<f64>("-1e-5"), Ok(-1e-5));
+    }
+
+    #[test]
+    fn out_of_range_floats() {
+        assert!(matches!(
+            parse_literal("1e39f32"),
+            Err(LiteralError::OutOfRange { ty: Ty::F32, .. })
+        ));
+        assert_eq!(parse_as::<f64>("1e39"), Ok(1e39));
+    }
+
+    #[test]
+    fn byte_literals_are_not_floats() {
+        assert_eq!(
+            parse_as::<f32>("b'A'"),
+            Err(LiteralError::MismatchedSuffix {
+                expected: Ty::F32,
+                found: Ty::U8
+            })
+        );
+        assert_eq!(parse_as::<u32>("b'A'"), Ok(65));
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(parse_literal("0x1f32").unwrap().value, Number::I32(0x1f32));
+        assert_eq!(parse_as::<u8>("255"), Ok(255));
+        assert_eq!(parse_as::<i8>("-128"), Ok(-128));
+        assert_eq!(
+            parse_literal("-1u8"),
+            Err(LiteralError::NegativeUnsigned(Ty::U8))
+        );
+        assert!(matches!(
+            parse_literal("256u8"),
+            Err(LiteralError::OutOfRange { ty: Ty::U8, .. })
+        ));
+    }
+
+    #[test]
+    fn exponent_is_not_an_integer() {
+        assert_eq!(parse_as::<u32>("1e3"), Err(LiteralError::ExpectedInteger(Ty::U32)));
+        assert_eq!(
+            parse_as::<u32>("1e3").unwrap_err().to_string(),
+            "expected `u32`, found floating-point number"
+        );
+        assert_eq!(
+            parse_literal("1e3u32"),
+            Err(LiteralError::InvalidSuffix("u32".to_string()))
+        );
+        assert_eq!(parse_as::<f64>("1e3"), Ok(1000.0));
+    }
+
+    #[test]
+    fn underscore_after_prefix() {
+        assert_eq!(parse_literal("0x_ff").unwrap().value, Number::I32(0xff));
+        assert_eq!(parse_literal("0o_7").unwrap().value, Number::I32(0o7));
+        assert_eq!(parse_literal("0b_1010").unwrap().value, Number::I32(0b1010));
+        assert_eq!(parse_as::<u8>("0x_f_f"), Ok(255));
+        assert_eq!(
+            parse_literal("_1"),
+            Err(LiteralError::InvalidDigit {
+                ch: '_',
+                index: 0,
+                radix: 10
+            })
+        );
+        assert_eq!(parse_literal("0x_"), Err(LiteralError::MissingDigits));
+    }
+}
@@ -0,0 +1,520 @@
+/*
+ * Una matriz de tamaño fijo es un `array` de `arrays`, `[[T; C]; R]`, vive completa en el `stack`
+ * igual que los `arrays` de `compound_arrays()`. Con `const generics` las dimensiones son parte del
+ * tipo y el compilador revisa que la multiplicación tenga sentido
+ *
+ *  Matrix<T, 2, 3> * Matrix<T, 3, 4>  ->  Matrix<T, 2, 4>
+ *  Matrix<T, 2, 3> * Matrix<T, 2, 3>  ->  error[E0308]: mismatched types
+ *
+ * El determinante existe solo para las matrices cuadradas de 1 a 4, pedirlo para otro tamaño
+ * tampoco compila, y la inversa solo para las cuadradas de `f32` y `f64`
+ */
+use std::fmt;
+use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
+
+use crate::any::generics::Point;
+
+/*
+ * Los números con los que se puede operar, con su cero y su uno
+ */
+pub trait Scalar:
+    Copy
+    + PartialEq
+    + fmt::Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+/*
+ * La inversa divide, solo tiene sentido con flotantes
+ */
+pub trait Float: Scalar + PartialOrd + std::ops::Div<Output = Self> {
+    const EPSILON: Self;
+    fn abs(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+}
+
+macro_rules! scalar {
+    ($zero:literal, $one:literal, $($t:ty),*) => {$(
+        impl Scalar for $t {
+            const ZERO: Self = $zero;
+            const ONE: Self = $one;
+        }
+    )*};
+}
+
+scalar!(0, 1, i8, i16, i32, i64, i128, isize);
+scalar!(0.0, 1.0, f32, f64);
+
+macro_rules! float {
+    ($($t:ident),*) => {$(
+        impl Float for $t {
+            const EPSILON: Self = $t::EPSILON;
+
+            fn abs(self) -> Self {
+                $t::abs(self)
+            }
+
+            fn sin(self) -> Self {
+                $t::sin(self)
+            }
+
+            fn cos(self) -> Self {
+                $t::cos(self)
+            }
+        }
+    )*};
+}
+
+float!(f32, f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix<T, const R: usize, const C: usize> {
+    rows: [[T; C]; R],
+}
+
+/*
+ * Las transformaciones usan coordenadas homogéneas, un punto 2D `(x, y)` es `(x, y, 1)` y cabe en
+ * una matriz de 3x3, un punto 3D en una de 4x4. La última columna es la traslación
+ */
+pub type Transform2<T> = Matrix<T, 3, 3>;
+pub type Transform3<T> = Matrix<T, 4, 4>;
+
+impl<T: Scalar, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn new(rows: [[T; C]; R]) -> Self {
+        Self { rows }
+    }
+
+    pub fn zeros() -> Self {
+        Self::new([[T::ZERO; C]; R])
+    }
+
+    pub fn from_fn(mut f: impl FnMut(usize, usize) -> T) -> Self {
+        Self::new(std::array::from_fn(|i| std::array::from_fn(|j| f(i, j))))
+    }
+
+    pub fn rows(&self) -> &[[T; C]; R] {
+        &self.rows
+    }
+
+    pub fn row(&self, i: usize) -> [T; C] {
+        self.rows[i]
+    }
+
+    pub fn column(&self, j: usize) -> [T; R] {
+        std::array::from_fn(|i| self.rows[i][j])
+    }
+
+    pub fn transpose(&self) -> Matrix<T, C, R> {
+        Matrix::from_fn(|i, j| self.rows[j][i])
+    }
+
+    pub fn scale(&self, factor: T) -> Self {
+        Self::from_fn(|i, j| self.rows[i][j] * factor)
+    }
+}
+
+impl<T: Scalar, const N: usize> Matrix<T, N, N> {
+    pub fn identity() -> Self {
+        Self::from_fn(|i, j| if i == j { T::ONE } else { T::ZERO })
+    }
+
+    pub fn trace(&self) -> T {
+        (0..N).fold(T::ZERO, |sum, i| sum + self.rows[i][i])
+    }
+}
+
+/*
+ * La matriz sin la fila `row` y la columna `column`, el tamaño `M` debe ser `N - 1` y se revisa al
+ * llamarla, las expresiones con `N` todavía no se pueden usar en los tipos
+ */
+fn minor<T: Scalar, const N: usize, const M: usize>(
+    matrix: &Matrix<T, N, N>,
+    row: usize,
+    column: usize,
+) -> Matrix<T, M, M> {
+    debug_assert_eq!(M + 1, N);
+    Matrix::from_fn(|i, j| {
+        let i = if i < row { i } else { i + 1 };
+        let j = if j < column { j } else { j + 1 };
+        matrix.rows[i][j]
+    })
+}
+
+/*
+ * Expansión por cofactores sobre la primera fila, los signos se alternan
+ */
+macro_rules! determinant_by_cofactors {
+    ($n:literal, $m:literal) => {
+        impl<T: Scalar> Matrix<T, $n, $n> {
+            pub fn determinant(&self) -> T {
+                (0..$n).fold(T::ZERO, |sum, j| {
+                    let term = self.rows[0][j] * minor::<T, $n, $m>(self, 0, j).determinant();
+                    if j % 2 == 0 {
+                        sum + term
+                    } else {
+                        sum - term
+                    }
+                })
+            }
+        }
+    };
+}
+
+impl<T: Scalar> Matrix<T, 1, 1> {
+    pub fn determinant(&self) -> T {
+        self.rows[0][0]
+    }
+}
+
+impl<T: Scalar> Matrix<T, 2, 2> {
+    pub fn determinant(&self) -> T {
+        let [[a, b], [c, d]] = self.rows;
+        a * d - b * c
+    }
+}
+
+determinant_by_cofactors!(3, 2);
+determinant_by_cofactors!(4, 3);
+
+impl<T: Float, const N: usize> Matrix<T, N, N> {
+    /*
+     * `Gauss-Jordan`, se reduce la matriz a la identidad y las mismas operaciones aplicadas a la
+     * identidad dan la inversa. En cada columna se usa como pivote el valor más grande para
+     * perder menos precisión, `None` si la matriz es singular.
+     * Un pivote se considera cero si es pequeño comparado con su fila original y no con `1`, así
+     * `scaling(1e-8, 1e-8)` tiene inversa y `[[0.1, 0.2], [0.3, 0.6]]` no, aunque el redondeo no
+     * deje exactamente un cero
+     */
+    pub fn inverse(&self) -> Option<Self> {
+        let mut left = self.rows;
+        let mut right = Self::identity().rows;
+        let mut scale = self.rows.map(|row| {
+            row.iter()
+                .fold(T::ZERO, |max, value| if value.abs() > max { value.abs() } else { max })
+        });
+        let n = (0..N).fold(T::ZERO, |n, _| n + T::ONE);
+        for column in 0..N {
+            let pivot = (column..N).max_by(|&a, &b| {
+                let (a, b) = (left[a][column].abs(), left[b][column].abs());
+                a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+            })?;
+            if left[pivot][column].abs() <= T::EPSILON * n * scale[pivot] {
+                return None;
+            }
+            scale.swap(column, pivot);
+            left.swap(column, pivot);
+            right.swap(column, pivot);
+            let divisor = left[column][column];
+            for j in 0..N {
+                left[column][j] = left[column][j] / divisor;
+                right[column][j] = right[column][j] / divisor;
+            }
+            for i in (0..N).filter(|&i| i != column) {
+                let factor = left[i][column];
+                for j in 0..N {
+                    left[i][j] = left[i][j] - factor * left[column][j];
+                    right[i][j] = right[i][j] - factor * right[column][j];
+                }
+            }
+        }
+        Some(Self::new(right))
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize, const K: usize> Mul<Matrix<T, C, K>>
+    for Matrix<T, R, C>
+{
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, other: Matrix<T, C, K>) -> Matrix<T, R, K> {
+        Matrix::from_fn(|i, j| {
+            (0..C).fold(T::ZERO, |sum, k| sum + self.rows[i][k] * other.rows[k][j])
+        })
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> Add for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::from_fn(|i, j| self.rows[i][j] + other.rows[i][j])
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> Sub for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::from_fn(|i, j| self.rows[i][j] - other.rows[i][j])
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> Neg for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::from_fn(|i, j| -self.rows[i][j])
+    }
+}
+
+impl<T, const R: usize, const C: usize> Index<(usize, usize)> for Matrix<T, R, C> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.rows[i][j]
+    }
+}
+
+impl<T, const R: usize, const C: usize> IndexMut<(usize, usize)> for Matrix<T, R, C> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        &mut self.rows[i][j]
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> From<[[T; C]; R]> for Matrix<T, R, C> {
+    fn from(rows: [[T; C]; R]) -> Self {
+        Self::new(rows)
+    }
+}
+
+/*
+ * Un `Point<T>` es un vector columna de 2x1
+ */
+impl<T: Scalar> From<Point<T>> for Matrix<T, 2, 1> {
+    fn from(point: Point<T>) -> Self {
+        Self::new([[point.x], [point.y]])
+    }
+}
+
+impl<T: Scalar> From<Matrix<T, 2, 1>> for Point<T> {
+    fn from(matrix: Matrix<T, 2, 1>) -> Self {
+        Point {
+            x: matrix.rows[0][0],
+            y: matrix.rows[1][0],
+        }
+    }
+}
+
+impl<T: Scalar> Transform2<T> {
+    pub fn translation(dx: T, dy: T) -> Self {
+        let (o, l) = (T::ZERO, T::ONE);
+        Self::new([[l, o, dx], [o, l, dy], [o, o, l]])
+    }
+
+    pub fn scaling(sx: T, sy: T) -> Self {
+        let (o, l) = (T::ZERO, T::ONE);
+        Self::new([[sx, o, o], [o, sy, o], [o, o, l]])
+    }
+
+    /*
+     * Se asume una transformación afín, la última fila es `0 0 1` y no hace falta dividir
+     */
+    pub fn transform(&self, point: Point<T>) -> Point<T> {
+        let [x, y, _] = (*self * Matrix::new([[point.x], [point.y], [T::ONE]])).column(0);
+        Point { x, y }
+    }
+}
+
+impl<T: Float> Transform2<T> {
+    /*
+     * Rotación en radianes contra las manecillas del reloj, alrededor del origen
+     */
+    pub fn rotation(angle: T) -> Self {
+        let (o, l) = (T::ZERO, T::ONE);
+        let (sin, cos) = (angle.sin(), angle.cos());
+        Self::new([[cos, -sin, o], [sin, cos, o], [o, o, l]])
+    }
+}
+
+impl<T: Scalar> Transform3<T> {
+    pub fn translation(dx: T, dy: T, dz: T) -> Self {
+        let (o, l) = (T::ZERO, T::ONE);
+        Self::new([[l, o, o, dx], [o, l, o, dy], [o, o, l, dz], [o, o, o, l]])
+    }
+
+    pub fn scaling(sx: T, sy: T, sz: T) -> Self {
+        let (o, l) = (T::ZERO, T::ONE);
+        Self::new([[sx, o, o, o], [o, sy, o, o], [o, o, sz, o], [o, o, o, l]])
+    }
+
+    /*
+     * Los puntos 3D son `tuples` `(x, y, z)`
+     */
+    pub fn transform(&self, (x, y, z): (T, T, T)) -> (T, T, T) {
+        let [x, y, z, _] = (*self * Matrix::new([[x], [y], [z], [T::ONE]])).column(0);
+        (x, y, z)
+    }
+}
+
+impl<T: Float> Transform3<T> {
+    /*
+     * Rotación alrededor del eje `z`, el plano `xy` gira igual que con `Transform2::rotation()`
+     */
+    pub fn rotation_z(angle: T) -> Self {
+        let (o, l) = (T::ZERO, T::ONE);
+        let (sin, cos) = (angle.sin(), angle.cos());
+        Self::new([
+            [cos, -sin, o, o],
+            [sin, cos, o, o],
+            [o, o, l, o],
+            [o, o, o, l],
+        ])
+    }
+}
+
+/*
+ * Las columnas se alinean a la derecha, la precisión se respeta, `{:.2}` imprime dos decimales
+ *
+ *  ┌ 1  0  5 ┐
+ *  │ 0  1  2 │
+ *  └ 0  0  1 ┘
+ */
+impl<T: fmt::Display, const R: usize, const C: usize> fmt::Display for Matrix<T, R, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|value| match f.precision() {
+                        Some(precision) => format!("{value:.precision$}"),
+                        None => value.to_string(),
+                    })
+                    .collect()
+            })
+            .collect();
+        let widths: Vec<usize> = (0..C)
+            .map(|j| {
+                cells
+                    .iter()
+                    .map(|row| row[j].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        for (i, row) in cells.iter().enumerate() {
+            let (open, close) = match i {
+                _ if R == 1 => ('[', ']'),
+                0 => ('┌', '┐'),
+                _ if i == R - 1 => ('└', '┘'),
+                _ => ('│', '│'),
+            };
+            write!(f, "{open}")?;
+            for (cell, width) in row.iter().zip(&widths) {
+                write!(f, " {cell:>width$}")?;
+            }
+            write!(f, " {close}")?;
+            if i + 1 < R {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close<const N: usize>(a: Matrix<f64, N, N>, b: Matrix<f64, N, N>) {
+        for i in 0..N {
+            for j in 0..N {
+                assert!((a[(i, j)] - b[(i, j)]).abs() < 1e-9, "\n{a:.6}\n!=\n{b:.6}");
+            }
+        }
+    }
+
+    #[test]
+    fn determinants() {
+        assert_eq!(Matrix::new([[7]]).determinant(), 7);
+        assert_eq!(Matrix::new([[1, 2], [3, 4]]).determinant(), -2);
+        assert_eq!(Matrix::new([[2, 0, 1], [1, 3, 2], [1, 1, 2]]).determinant(), 6);
+        let m = Matrix::new([[1, 0, 2, -1], [3, 0, 0, 5], [2, 1, 4, -3], [1, 0, 5, 0]]);
+        assert_eq!(m.determinant(), 30);
+        assert_eq!(m.transpose().determinant(), 30);
+        assert_eq!(Matrix::<i32, 4, 4>::identity().determinant(), 1);
+    }
+
+    #[test]
+    fn inverse_times_matrix_is_identity() {
+        let a = Matrix::new([[4.0, 7.0, 2.0], [3.0, 6.0, 1.0], [2.0, 5.0, 3.0]]);
+        let inverse = a.inverse().unwrap();
+        assert_close(inverse * a, Matrix::identity());
+        assert_close(a * inverse, Matrix::identity());
+        /*
+         * Necesita cambiar filas, el primer pivote es cero
+         */
+        let b = Matrix::new([
+            [0.0, 1.0, 2.0, 0.0],
+            [1.0, 0.0, 0.0, 3.0],
+            [0.0, 0.0, 1.0, 1.0],
+            [2.0, 1.0, 0.0, 0.0],
+        ]);
+        assert_close(b.inverse().unwrap() * b, Matrix::identity());
+    }
+
+    #[test]
+    fn small_but_invertible() {
+        let tiny = Transform2::<f32>::scaling(1e-8, 1e-8);
+        let inverse = tiny.inverse().expect("scaling(1e-8, 1e-8) is invertible");
+        assert_eq!(inverse.row(0), [1e8, 0.0, 0.0]);
+        assert_eq!(inverse.row(2), [0.0, 0.0, 1.0]);
+        let scaled = Matrix::new([[4e-12, 7e-12], [2e-12, 6e-12]]);
+        assert!(scaled.inverse().is_some());
+    }
+
+    #[test]
+    fn singular_matrices_have_no_inverse() {
+        assert_eq!(Matrix::new([[1.0, 2.0], [2.0, 4.0]]).inverse(), None);
+        assert_eq!(Matrix::new([[0.1, 0.2], [0.3, 0.6]]).inverse(), None);
+        assert_eq!(Matrix::<f64, 3, 3>::zeros().inverse(), None);
+        let rows = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        assert_eq!(rows.inverse(), None);
+    }
+
+    #[test]
+    fn transforms_2d() {
+        let point = Point { x: 1.0, y: 2.0 };
+        let moved = Transform2::translation(3.0, -1.0).transform(point);
+        assert_eq!(moved, Point { x: 4.0, y: 1.0 });
+        assert_eq!(Transform2::scaling(2.0, 3.0).transform(point), Point { x: 2.0, y: 6.0 });
+        let turned = Transform2::rotation(std::f64::consts::FRAC_PI_2).transform(point);
+        assert!((turned.x + 2.0).abs() < 1e-12 && (turned.y - 1.0).abs() < 1e-12, "{turned:?}");
+        /*
+         * Primero se escala y después se traslada, la matriz de la derecha se aplica primero
+         */
+        let both = Transform2::translation(1.0, 1.0) * Transform2::scaling(2.0, 2.0);
+        assert_eq!(both.transform(point), Point { x: 3.0, y: 5.0 });
+        let back = both.inverse().unwrap().transform(Point { x: 3.0, y: 5.0 });
+        assert!((back.x - 1.0).abs() < 1e-12 && (back.y - 2.0).abs() < 1e-12, "{back:?}");
+        assert_eq!(Point::from(Matrix::from(point)), point);
+    }
+
+    #[test]
+    fn transforms_3d() {
+        let point = (1.0, 2.0, 3.0);
+        assert_eq!(Transform3::translation(1.0, 1.0, 1.0).transform(point), (2.0, 3.0, 4.0));
+        assert_eq!(Transform3::scaling(2.0, 1.0, -1.0).transform(point), (2.0, 2.0, -3.0));
+        let (x, y, z) = Transform3::rotation_z(std::f64::consts::PI).transform(point);
+        assert!((x + 1.0).abs() < 1e-12 && (y + 2.0).abs() < 1e-12 && z == 3.0);
+        let inverse = Transform3::rotation_z(0.3).inverse().unwrap();
+        assert_close(inverse, Transform3::rotation_z(-0.3));
+    }
+
+    #[test]
+    fn products_and_display() {
+        let a = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        let b = Matrix::new([[7, 8], [9, 10], [11, 12]]);
+        assert_eq!(a * b, Matrix::new([[58, 64], [139, 154]]));
+        assert_eq!((a + a - a).scale(2), -(-a.scale(2)));
+        let text = Transform2::<i32>::translation(5, 2).to_string();
+        assert_eq!(text, "┌ 1 0 5 ┐\n│ 0 1 2 │\n└ 0 0 1 ┘");
+    }
+}
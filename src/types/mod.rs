@@ -0,0 +1,11 @@
+pub mod utf8;
+pub mod bigint;
+pub mod literal;
+pub mod overflow;
+pub mod casts;
+pub mod float;
+pub mod matrix;
+pub mod config;
+pub mod primitive_types;
+pub mod integers;
+pub mod variables_const;
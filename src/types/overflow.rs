@@ -0,0 +1,672 @@
+/*
+ * En `debug` una suma que se sale del rango lanza un `panic`, en `release` da la vuelta en silencio.
+ * Para no depender del perfil cada entero tiene cuatro familias de métodos que dicen qué hacer
+ *
+ *  checked_add(200u8, 100)      None           no hay resultado
+ *  wrapping_add(200u8, 100)     44             da la vuelta, módulo 2^8
+ *  saturating_add(200u8, 100)   255            se queda en el límite
+ *  overflowing_add(200u8, 100)  (44, true)     da la vuelta y avisa
+ *
+ * `explore()` evalúa una expresión en todos los anchos con las cuatro semánticas y la compara con
+ * el resultado exacto calculado con `BigInt`. Ojo con `<<`, los `bits` que salen por la izquierda
+ * se pierden sin que `overflowing_shl` avise, solo avisa cuando el corrimiento es mayor al ancho
+ */
+use std::fmt;
+
+use super::bigint::BigInt;
+use super::literal::{self, FromLiteral, LiteralError, Number, Ty};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Shl,
+    Shr,
+}
+
+impl Op {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+            Op::Rem => "%",
+            Op::Shl => "<<",
+            Op::Shr => ">>",
+        }
+    }
+
+    fn method(self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Sub => "sub",
+            Op::Mul => "mul",
+            Op::Div => "div",
+            Op::Rem => "rem",
+            Op::Shl => "shl",
+            Op::Shr => "shr",
+        }
+    }
+
+    /*
+     * La misma precedencia que en `Rust`, `*` antes que `+` y `+` antes que `<<`
+     */
+    fn precedence(self) -> u8 {
+        match self {
+            Op::Mul | Op::Div | Op::Rem => 3,
+            Op::Add | Op::Sub => 2,
+            Op::Shl | Op::Shr => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Literal(String),
+    Neg(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Literal(text) => f.write_str(text),
+            Expr::Neg(inner) => match **inner {
+                Expr::Binary(..) => write!(f, "-({inner})"),
+                _ => write!(f, "-{inner}"),
+            },
+            Expr::Binary(op, left, right) => {
+                let wrap = |side: &Expr, strict: bool| match side {
+                    Expr::Binary(inner, ..)
+                        if inner.precedence() < op.precedence()
+                            || (strict && inner.precedence() == op.precedence()) =>
+                    {
+                        format!("({side})")
+                    }
+                    _ => side.to_string(),
+                };
+                write!(
+                    f,
+                    "{} {} {}",
+                    wrap(left, false),
+                    op.symbol(),
+                    wrap(right, true)
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub index: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.index)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Number(String),
+    Op(Op),
+    Open,
+    Close,
+}
+
+/*
+ * Un `-` pegado a un número al inicio o después de un operador es parte del literal, así `-128` es
+ * un `i8` válido aunque `128` no lo sea, igual que en `Rust`
+ */
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut tokens: Vec<(Token, usize)> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let (index, c) = chars[i];
+        let operand_expected =
+            matches!(tokens.last(), None | Some((Token::Op(_) | Token::Open, _)));
+        let next_is_digit = chars
+            .get(i + 1)
+            .is_some_and(|(_, next)| next.is_ascii_digit());
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            c if c.is_ascii_digit() || (c == '-' && operand_expected && next_is_digit) => {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].1.is_ascii_alphanumeric() || chars[i].1 == '_') {
+                    i += 1;
+                }
+                let end = chars.get(i).map_or(text.len(), |(end, _)| *end);
+                tokens.push((Token::Number(text[chars[start].0..end].to_string()), index));
+                continue;
+            }
+            '+' => Token::Op(Op::Add),
+            '-' => Token::Op(Op::Sub),
+            '*' => Token::Op(Op::Mul),
+            '/' => Token::Op(Op::Div),
+            '%' => Token::Op(Op::Rem),
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '<' | '>' if chars.get(i + 1).map(|(_, next)| *next) == Some(c) => {
+                i += 1;
+                Token::Op(if c == '<' { Op::Shl } else { Op::Shr })
+            }
+            other => {
+                return Err(ParseError {
+                    message: format!("unexpected character {other:?}"),
+                    index,
+                })
+            }
+        };
+        tokens.push((token, index));
+        i += 1;
+    }
+    Ok(tokens)
+}
+
+/*
+ * Cada paréntesis o `-` prefijo anidado es una llamada recursiva más, el mismo límite que el
+ * `parser` de `flow::interpreter`
+ */
+const MAX_DEPTH: usize = 128;
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    end: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn index(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.end, |(_, index)| *index)
+    }
+
+    fn error(&self, message: &str) -> ParseError {
+        ParseError {
+            message: message.to_string(),
+            index: self.index(),
+        }
+    }
+
+    /*
+     * Se llama al entrar a un operando, `leave()` al salir
+     */
+    fn enter(&mut self) -> Result<(), ParseError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(self.error(&format!(
+                "expression nested more than {MAX_DEPTH} levels deep"
+            )));
+        }
+        Ok(())
+    }
+
+    fn leave(&mut self) {
+        self.depth -= 1;
+    }
+
+    /*
+     * `precedence climbing`, cada nivel solo toma operadores con al menos `min` de precedencia y
+     * todos asocian a la izquierda
+     */
+    fn binary(&mut self, min: u8) -> Result<Expr, ParseError> {
+        let mut left = self.unary()?;
+        while let Some(&Token::Op(op)) = self.peek() {
+            if op.precedence() < min {
+                break;
+            }
+            self.position += 1;
+            let right = self.binary(op.precedence() + 1)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        self.enter()?;
+        let expr = self.operand();
+        self.leave();
+        expr
+    }
+
+    fn operand(&mut self) -> Result<Expr, ParseError> {
+        match self.peek().cloned() {
+            Some(Token::Op(Op::Sub)) => {
+                self.position += 1;
+                Ok(Expr::Neg(Box::new(self.unary()?)))
+            }
+            Some(Token::Number(text)) => {
+                self.position += 1;
+                Ok(Expr::Literal(text))
+            }
+            Some(Token::Open) => {
+                self.position += 1;
+                let inner = self.binary(0)?;
+                if self.peek() != Some(&Token::Close) {
+                    return Err(self.error("expected `)`"));
+                }
+                self.position += 1;
+                Ok(inner)
+            }
+            _ => Err(self.error("expected a number")),
+        }
+    }
+}
+
+pub fn parse(text: &str) -> Result<Expr, ParseError> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        position: 0,
+        end: text.len(),
+        depth: 0,
+    };
+    let expr = parser.binary(0)?;
+    if parser.peek().is_some() {
+        return Err(parser.error("unexpected token"));
+    }
+    Ok(expr)
+}
+
+/*
+ * `checked_*` devuelve `None` justo cuando `overflowing_*` avisa, y `wrapping_*` es el valor de
+ * `overflowing_*`, por eso basta con dos métodos por tipo
+ */
+trait Arith: Copy + PartialEq + fmt::Display + FromLiteral {
+    const ZERO: Self;
+    fn overflowing(op: Op, a: Self, b: Self) -> (Self, bool);
+    /*
+     * `None` si no existe la versión `saturating_*` de la operación
+     */
+    fn saturating(op: Op, a: Self, b: Self) -> Option<Self>;
+}
+
+macro_rules! arith {
+    ($($t:ty),*) => {$(
+        impl Arith for $t {
+            const ZERO: Self = 0;
+
+            /*
+             * El corrimiento es un `u32`, uno negativo se convierte en uno enorme y avisa
+             */
+            fn overflowing(op: Op, a: Self, b: Self) -> (Self, bool) {
+                match op {
+                    Op::Add => a.overflowing_add(b),
+                    Op::Sub => a.overflowing_sub(b),
+                    Op::Mul => a.overflowing_mul(b),
+                    Op::Div => a.overflowing_div(b),
+                    Op::Rem => a.overflowing_rem(b),
+                    Op::Shl => a.overflowing_shl(u32::try_from(b).unwrap_or(u32::MAX)),
+                    Op::Shr => a.overflowing_shr(u32::try_from(b).unwrap_or(u32::MAX)),
+                }
+            }
+
+            fn saturating(op: Op, a: Self, b: Self) -> Option<Self> {
+                match op {
+                    Op::Add => Some(a.saturating_add(b)),
+                    Op::Sub => Some(a.saturating_sub(b)),
+                    Op::Mul => Some(a.saturating_mul(b)),
+                    Op::Div => Some(a.saturating_div(b)),
+                    Op::Rem | Op::Shl | Op::Shr => None,
+                }
+            }
+        }
+    )*};
+}
+
+arith!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Checked,
+    Wrapping,
+    Saturating,
+    Overflowing,
+}
+
+enum Stop {
+    Literal(LiteralError),
+    DivideByZero,
+    NoSaturating(Op),
+    Overflow,
+}
+
+/*
+ * Guarda en `first` la primera operación que se salió del rango, con los valores de ese ancho
+ */
+fn eval<T: Arith>(expr: &Expr, mode: Mode, first: &mut Option<String>) -> Result<T, Stop> {
+    let (op, a, b, step) = match expr {
+        Expr::Literal(text) => return literal::parse_as::<T>(text).map_err(Stop::Literal),
+        Expr::Neg(inner) => {
+            let value = eval::<T>(inner, mode, first)?;
+            (Op::Sub, T::ZERO, value, format!("-{value}"))
+        }
+        Expr::Binary(op, left, right) => {
+            let a = eval::<T>(left, mode, first)?;
+            let b = eval::<T>(right, mode, first)?;
+            (*op, a, b, format!("{a} {} {b}", op.symbol()))
+        }
+    };
+    if matches!(op, Op::Div | Op::Rem) && b == T::ZERO {
+        return Err(Stop::DivideByZero);
+    }
+    let (wrapped, overflowed) = T::overflowing(op, a, b);
+    if overflowed && first.is_none() {
+        *first = Some(step);
+    }
+    match mode {
+        Mode::Checked if overflowed => Err(Stop::Overflow),
+        Mode::Saturating => T::saturating(op, a, b).ok_or(Stop::NoSaturating(op)),
+        _ => Ok(wrapped),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub ty: Ty,
+    /*
+     * `checked`, `wrapping`, `saturating` y `overflowing`, o el error de un literal fuera de rango
+     */
+    pub cells: Result<[String; 4], String>,
+    pub overflow_at: Option<String>,
+    /*
+     * `true` si el resultado con `wrapping` no es el exacto
+     */
+    pub diverges: bool,
+}
+
+fn row<T: Arith>(expr: &Expr, exact: Option<&str>) -> Row {
+    let mut first = None;
+    let mut cells = Vec::new();
+    let mut wrapped = None;
+    for mode in [
+        Mode::Checked,
+        Mode::Wrapping,
+        Mode::Saturating,
+        Mode::Overflowing,
+    ] {
+        let mut step = None;
+        let cell = match (mode, eval::<T>(expr, mode, &mut step)) {
+            (_, Err(Stop::Literal(error))) => {
+                return Row {
+                    ty: T::TY,
+                    cells: Err(error.to_string()),
+                    overflow_at: None,
+                    diverges: false,
+                }
+            }
+            (Mode::Checked, Ok(value)) => format!("Some({value})"),
+            (Mode::Checked, Err(_)) => "None".to_string(),
+            (_, Err(Stop::DivideByZero)) => "panic: divide by zero".to_string(),
+            (_, Err(Stop::NoSaturating(op))) => format!("no saturating_{}", op.method()),
+            (Mode::Overflowing, Ok(value)) => format!("({value}, {})", step.is_some()),
+            (_, Ok(value)) => {
+                if mode == Mode::Wrapping {
+                    wrapped = Some(value.to_string());
+                }
+                value.to_string()
+            }
+            (_, Err(Stop::Overflow)) => unreachable!("only checked stops on overflow"),
+        };
+        first = first.or(step);
+        cells.push(cell);
+    }
+    let diverges = first.is_some() || (exact.is_some() && wrapped.as_deref() != exact);
+    Row {
+        ty: T::TY,
+        cells: Ok(cells.try_into().unwrap_or_default()),
+        overflow_at: first,
+        diverges,
+    }
+}
+
+/*
+ * El valor sin límite de ancho, `>>` redondea hacia abajo como en los enteros con signo
+ */
+fn exact(expr: &Expr) -> Option<BigInt> {
+    match expr {
+        Expr::Literal(text) => match text.parse() {
+            Ok(value) => Some(value),
+            Err(_) => match literal::parse_literal(text).ok()?.value {
+                Number::U128(value) => Some(BigInt::from(value)),
+                other => other.as_i128().map(BigInt::from),
+            },
+        },
+        Expr::Neg(inner) => Some(-exact(inner)?),
+        Expr::Binary(op, left, right) => {
+            let a = exact(left)?;
+            let b = exact(right)?;
+            let shift = || {
+                b.to_i64()
+                    .filter(|n| (0..=4096).contains(n))
+                    .map(|n| n as u32)
+            };
+            match op {
+                Op::Add => Some(a + b),
+                Op::Sub => Some(a - b),
+                Op::Mul => Some(a * b),
+                Op::Div => a.checked_div_rem(&b).map(|(q, _)| q),
+                Op::Rem => a.checked_div_rem(&b).map(|(_, r)| r),
+                Op::Shl => Some(a * BigInt::from(2).pow(shift()?)),
+                Op::Shr => {
+                    let (q, r) = a.div_rem(&BigInt::from(2).pow(shift()?));
+                    Some(if r.is_negative() {
+                        q - BigInt::one()
+                    } else {
+                        q
+                    })
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub expr: Expr,
+    pub exact: Option<String>,
+    pub rows: Vec<Row>,
+}
+
+pub fn explore(text: &str) -> Result<Report, ParseError> {
+    let expr = parse(text)?;
+    let exact = exact(&expr).map(|value| value.to_string());
+    let hint = exact.as_deref();
+    let rows = vec![
+        row::<i8>(&expr, hint),
+        row::<i16>(&expr, hint),
+        row::<i32>(&expr, hint),
+        row::<i64>(&expr, hint),
+        row::<i128>(&expr, hint),
+        row::<isize>(&expr, hint),
+        row::<u8>(&expr, hint),
+        row::<u16>(&expr, hint),
+        row::<u32>(&expr, hint),
+        row::<u64>(&expr, hint),
+        row::<u128>(&expr, hint),
+        row::<usize>(&expr, hint),
+    ];
+    Ok(Report { expr, exact, rows })
+}
+
+/*
+ * Una tabla con una fila por tipo, las filas marcadas con `*` no dan el resultado exacto
+ */
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "expression: {}", self.expr)?;
+        writeln!(
+            f,
+            "exact:      {}",
+            self.exact.as_deref().unwrap_or("undefined")
+        )?;
+        let header = ["checked", "wrapping", "saturating", "overflowing"];
+        let mut widths = header.map(str::len);
+        for row in &self.rows {
+            if let Ok(cells) = &row.cells {
+                for (width, cell) in widths.iter_mut().zip(cells) {
+                    *width = (*width).max(cell.len());
+                }
+            }
+        }
+        write!(f, "\n  {:<6}", "type")?;
+        for (name, width) in header.iter().zip(widths) {
+            write!(f, "{name:<width$}  ")?;
+        }
+        writeln!(f, "first overflow")?;
+        for row in &self.rows {
+            let mark = if row.diverges { '*' } else { ' ' };
+            write!(f, "{mark} {:<6}", row.ty.name())?;
+            match &row.cells {
+                Ok(cells) => {
+                    for (cell, width) in cells.iter().zip(widths) {
+                        write!(f, "{cell:<width$}  ")?;
+                    }
+                    match (&row.overflow_at, row.diverges) {
+                        (Some(step), _) => writeln!(f, "{step}")?,
+                        (None, true) => writeln!(f, "bits lost without overflow")?,
+                        (None, false) => writeln!(f, "-")?,
+                    }
+                }
+                Err(error) => writeln!(f, "{error}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+     * Las cuatro celdas y la primera operación que se salió del rango para un tipo
+     */
+    fn cells(text: &str, ty: &str) -> (Vec<String>, Option<String>) {
+        let report = explore(text).unwrap();
+        let row = report.rows.into_iter().find(|row| row.ty.name() == ty).unwrap();
+        (row.cells.unwrap().to_vec(), row.overflow_at)
+    }
+
+    #[test]
+    fn min_divided_by_minus_one() {
+        let (i8_cells, first) = cells("-128 / -1", "i8");
+        assert_eq!(i8_cells, ["None", "-128", "127", "(-128, true)"]);
+        assert_eq!(first.as_deref(), Some("-128 / -1"));
+        let (i16_cells, first) = cells("-128 / -1", "i16");
+        assert_eq!(i16_cells, ["Some(128)", "128", "128", "(128, false)"]);
+        assert_eq!(first, None);
+        assert_eq!(
+            cells("-128 % -1", "i8").0,
+            ["None", "0", "no saturating_rem", "(0, true)"]
+        );
+    }
+
+    #[test]
+    fn shift_wider_than_the_type() {
+        let report = explore("1 << 200").unwrap();
+        assert_eq!(report.exact.as_deref().map(str::len), Some(61));
+        /*
+         * `200` no cabe en un `i8`, en los demás el corrimiento se toma módulo el ancho
+         */
+        assert_eq!(report.rows[0].cells, Err("value 200 out of range for i8".to_string()));
+        let wrapping = |ty| cells("1 << 200", ty).0[1].clone();
+        assert_eq!(wrapping("u8"), "1");
+        assert_eq!(wrapping("u32"), (1u32 << (200 % 32)).to_string());
+        assert_eq!(wrapping("u128"), (1u128 << (200 % 128)).to_string());
+        assert!(report.rows[1..].iter().all(|row| row.diverges));
+    }
+
+    #[test]
+    fn remainder_by_zero() {
+        let report = explore("5 % 0").unwrap();
+        assert_eq!(report.exact, None);
+        for row in &report.rows {
+            let cells = row.cells.as_ref().unwrap();
+            assert_eq!(cells[0], "None");
+            assert!(cells[1..].iter().all(|cell| cell == "panic: divide by zero"));
+            assert_eq!(row.overflow_at, None);
+        }
+    }
+
+    #[test]
+    fn unsigned_negation() {
+        let (u8_cells, first) = cells("-(1)", "u8");
+        assert_eq!(u8_cells[0], "None");
+        assert_eq!(u8_cells[1], 1u8.wrapping_neg().to_string());
+        assert_eq!(u8_cells[3], format!("{:?}", 1u8.overflowing_neg()));
+        assert_eq!(first.as_deref(), Some("-1"));
+        assert_eq!(cells("-(0)", "u32").0[0], "Some(0)");
+        /*
+         * Un literal negativo no existe para un tipo sin signo
+         */
+        let report = explore("-1").unwrap();
+        let u8_row = report.rows.iter().find(|row| row.ty.name() == "u8").unwrap();
+        assert_eq!(
+            u8_row.cells,
+            Err("cannot apply unary operator `-` to type `u8`".to_string())
+        );
+    }
+
+    #[test]
+    fn precedence_like_rust() {
+        let check = |text: &str, shown: &str, value: i64| {
+            let report = explore(text).unwrap();
+            assert_eq!(report.expr.to_string(), shown, "{text}");
+            assert_eq!(report.exact, Some(value.to_string()), "{text}");
+        };
+        check("1 + 2 * 3 << 1", "1 + 2 * 3 << 1", (1 + 2 * 3) << 1);
+        check("(1 + 2) * 3", "(1 + 2) * 3", 9);
+        check("10 - 4 - 3", "10 - 4 - 3", 3);
+        check("10 - (4 - 3)", "10 - (4 - 3)", 9);
+        check("-(2 + 3) * 2", "-(2 + 3) * 2", -10);
+        check("-7 >> 1", "-7 >> 1", -4);
+        check("100 / 10 % 7", "100 / 10 % 7", 3);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |text| parse(text).unwrap_err();
+        assert_eq!(error("1 +").to_string(), "expected a number at position 3");
+        assert_eq!(error("(1 + 2").to_string(), "expected `)` at position 6");
+        assert_eq!(error("1 2").to_string(), "unexpected token at position 2");
+        assert_eq!(error("2 ^ 3").to_string(), "unexpected character '^' at position 2");
+    }
+
+    #[test]
+    fn nesting_is_capped() {
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(parse(&nested(MAX_DEPTH - 1)).is_ok());
+        assert_eq!(
+            parse(&nested(MAX_DEPTH)).unwrap_err(),
+            ParseError {
+                message: format!("expression nested more than {MAX_DEPTH} levels deep"),
+                index: MAX_DEPTH,
+            }
+        );
+        /*
+         * Sin el límite, miles de `-` desbordan el `stack` en lugar de dar un error
+         */
+        let negations = format!("{}1", "- ".repeat(100_000));
+        assert!(parse(&negations).unwrap_err().message.contains("nested"));
+    }
+}
@@ -1,5 +1,5 @@
 
-fn run() {
+pub fn run() {
     /*
      * Los tipos numéricos con parte decimal son `Floating-Point Types` que son los tipos
      * `f32` y `f64`, por defecto los literales numéricos decimales son de tipo `f64` ya que
@@ -21,9 +21,13 @@ fn run() {
      *  * 11 bits para el exponente
      *  * 52 bits para la parte decimal
      */
+    #[allow(clippy::excessive_precision)]
     let double = 1808.918230981092213123; // -> double-presicion
     println!("Double: {}", double);
     /*
+     * El literal tiene más dígitos de los que caben en 52 bits, se guarda el `f64` más cercano.
+     * `cargo run -- float 1808.918230981092213123` muestra sus bits y el valor exacto guardado
+     *
      * La precisión `single-precision` es de 32 bits
      * 
      *  * 1 bit para el signo
@@ -52,21 +56,21 @@ fn run() {
     let accent: char = 'é';      // U+00E9
     let emoji: char = '😊';      // U+1F60A
 
-    println!("letter: {}, {}", letter, a);
+    println!("letter: {}, {}, {}", c, letter, a);
     println!("accent: {}", accent);
     println!("emoji: {}", emoji);
     /*
      * El tipo char en Rust puede ser convertido a su valor Unicode usando el método `.escape_unicode()
      * o como un número `u32`
      */
-    println!("Unicode scalar value of 'é': U+{:X}", c as u32);
+    println!("Unicode scalar value of 'é': U+{:X}", accent as u32);
 }
 
 /*
  * Los tipos `compound types` son tipos de datos que pueden agrupar multiples valores en una
  * sola entidad. `Rust` tiene dos tipos primitivos las `tuples` y los `arrays`
  */
-fn compound_tuple() {
+pub fn compound_tuple() {
     /*
      * Una `tuple` es una estructura que agrupa multiples valores del mismo o diferentes tipos en
      * una sola entidad. Las tuplas tienen una longitud fija, una vez definida no pueden cambiar
@@ -99,7 +103,8 @@ fn compound_tuple() {
  * Los `arrays` en `Rust` son almacenados en el `stack`, dado que son estructuras de datos de
  * tamaño fijo `Rust` puede reservar espacio en memoria contigua en tiempo de compilación
  */
-fn compound_arrays() {
+#[allow(clippy::const_is_empty)]
+pub fn compound_arrays() {
     /*
      * Un `array` es una estructura que agrupa distintos valores del mismo tipo en una sola
      * entidad, son de tamaño fijo e inmutables por defecto y se almacenan en el `stack`
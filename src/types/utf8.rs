@@ -0,0 +1,430 @@
+/*
+ * Un `char` en `Rust` siempre ocupa 4 `bytes` porque guarda el valor escalar `Unicode` completo,
+ * pero dentro de un `String` o un `&str` cada `char` se codifica en `UTF-8` y ocupa de 1 a 4 `bytes`.
+ * Por eso `len()` mide `bytes` y no letras, y por eso no se puede indexar `s[1]`
+ *
+ *      'A'  U+0041   -> 41
+ *      'é'  U+00E9   -> C3 A9
+ *      '€'  U+20AC   -> E2 82 AC
+ *      '😊' U+1F60A  -> F0 9F 98 8A
+ *
+ * El inspector muestra para cada `char` su posición, sus `bytes` en `UTF-8` y `UTF-16` y una pista
+ * de su categoría general, para encontrar errores de codificación en los datos
+ */
+use std::fmt;
+
+use crate::ownership::tokenizer;
+
+/*
+ * La biblioteca estándar no incluye las tablas de categorías `Unicode`, la categoría es una pista
+ * calculada con los métodos de `char` y los bloques más comunes
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    UppercaseLetter,
+    LowercaseLetter,
+    OtherLetter,
+    Mark,
+    DecimalNumber,
+    OtherNumber,
+    Punctuation,
+    Symbol,
+    Space,
+    Control,
+    Format,
+    PrivateUse,
+    Unknown,
+}
+
+impl Category {
+    pub fn of(c: char) -> Self {
+        let code = c as u32;
+        if c.is_control() {
+            Category::Control
+        } else if c.is_whitespace() {
+            Category::Space
+        } else if matches!(code, 0x200B..=0x200F | 0x2028..=0x202E | 0x2060..=0x2064 | 0xFEFF) {
+            Category::Format
+        } else if matches!(code,
+            0x0300..=0x036F | 0x0483..=0x0489 | 0x0591..=0x05BD | 0x064B..=0x065F
+            | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF | 0x20D0..=0x20FF | 0xFE00..=0xFE0F
+            | 0xFE20..=0xFE2F | 0xE0100..=0xE01EF)
+        {
+            Category::Mark
+        } else if c.is_uppercase() {
+            Category::UppercaseLetter
+        } else if c.is_lowercase() {
+            Category::LowercaseLetter
+        } else if c.is_alphabetic() {
+            Category::OtherLetter
+        } else if c.is_ascii_digit() {
+            Category::DecimalNumber
+        } else if c.is_numeric() {
+            Category::OtherNumber
+        } else if matches!(c, '$' | '+' | '<' | '=' | '>' | '^' | '`' | '|' | '~') {
+            Category::Symbol
+        } else if tokenizer::is_punctuation(c) {
+            Category::Punctuation
+        } else if matches!(code, 0xE000..=0xF8FF | 0xF0000..=0x10FFFF) {
+            Category::PrivateUse
+        } else if matches!(code,
+            0x00A2..=0x00A9 | 0x00AC | 0x00AE..=0x00B1 | 0x00B4 | 0x00D7 | 0x00F7
+            | 0x20A0..=0x20CF | 0x2100..=0x2BFF | 0x1F000..=0x1FAFF)
+        {
+            Category::Symbol
+        } else {
+            Category::Unknown
+        }
+    }
+
+    /*
+     * La abreviatura que usa el estándar `Unicode`, cuando la pista no distingue entre
+     * subcategorías se usa solo la primera letra
+     */
+    pub fn code(&self) -> &'static str {
+        match self {
+            Category::UppercaseLetter => "Lu",
+            Category::LowercaseLetter => "Ll",
+            Category::OtherLetter => "Lo",
+            Category::Mark => "M",
+            Category::DecimalNumber => "Nd",
+            Category::OtherNumber => "N",
+            Category::Punctuation => "P",
+            Category::Symbol => "S",
+            Category::Space => "Zs",
+            Category::Control => "Cc",
+            Category::Format => "Cf",
+            Category::PrivateUse => "Co",
+            Category::Unknown => "?",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharInfo {
+    pub ch: char,
+    /*
+     * La posición del primer `byte` del `char` dentro del texto, siempre es un `char boundary`
+     */
+    pub byte_offset: usize,
+    pub char_index: usize,
+    pub utf8: Vec<u8>,
+    pub utf16: Vec<u16>,
+    pub category: Category,
+}
+
+impl CharInfo {
+    pub fn code_point(&self) -> u32 {
+        self.ch as u32
+    }
+}
+
+pub fn inspect_char(c: char) -> CharInfo {
+    CharInfo {
+        ch: c,
+        byte_offset: 0,
+        char_index: 0,
+        utf8: c.encode_utf8(&mut [0; 4]).as_bytes().to_vec(),
+        utf16: c.encode_utf16(&mut [0; 2]).to_vec(),
+        category: Category::of(c),
+    }
+}
+
+/*
+ * Un pedazo de los datos, o es un `char` válido o es una secuencia de `bytes` que no es `UTF-8`
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unit {
+    Char(CharInfo),
+    Invalid { byte_offset: usize, bytes: Vec<u8> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub units: Vec<Unit>,
+    pub byte_len: usize,
+}
+
+impl Report {
+    pub fn chars(&self) -> impl Iterator<Item = &CharInfo> {
+        self.units.iter().filter_map(|unit| match unit {
+            Unit::Char(info) => Some(info),
+            Unit::Invalid { .. } => None,
+        })
+    }
+
+    pub fn char_count(&self) -> usize {
+        self.chars().count()
+    }
+
+    pub fn utf16_len(&self) -> usize {
+        self.chars().map(|info| info.utf16.len()).sum()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.units.iter().all(|unit| matches!(unit, Unit::Char(_)))
+    }
+
+    /*
+     * Las posiciones donde se puede cortar el texto con `&s[a..b]` sin lanzar un `panic`, incluye
+     * el final del texto
+     */
+    pub fn char_boundaries(&self) -> Vec<usize> {
+        self.chars()
+            .map(|info| info.byte_offset)
+            .chain(std::iter::once(self.byte_len))
+            .collect()
+    }
+}
+
+pub fn inspect(text: &str) -> Report {
+    let units = text
+        .char_indices()
+        .enumerate()
+        .map(|(char_index, (byte_offset, c))| {
+            Unit::Char(CharInfo {
+                byte_offset,
+                char_index,
+                ..inspect_char(c)
+            })
+        })
+        .collect();
+    Report {
+        units,
+        byte_len: text.len(),
+    }
+}
+
+/*
+ * Los datos de los usuarios no siempre son `UTF-8` válido, `std::str::from_utf8()` indica hasta
+ * donde son válidos y cuántos `bytes` forman la secuencia inválida
+ */
+pub fn inspect_bytes(bytes: &[u8]) -> Report {
+    let mut units = Vec::new();
+    let mut offset = 0;
+    let mut char_index = 0;
+    while offset < bytes.len() {
+        let rest = &bytes[offset..];
+        let (valid, invalid_len) = match std::str::from_utf8(rest) {
+            Ok(text) => (text, 0),
+            Err(error) => {
+                let valid = &rest[..error.valid_up_to()];
+                let invalid_len = error.error_len().unwrap_or(rest.len() - valid.len());
+                (std::str::from_utf8(valid).unwrap_or_default(), invalid_len)
+            }
+        };
+        for (byte_offset, c) in valid.char_indices() {
+            units.push(Unit::Char(CharInfo {
+                byte_offset: offset + byte_offset,
+                char_index,
+                ..inspect_char(c)
+            }));
+            char_index += 1;
+        }
+        offset += valid.len();
+        if invalid_len > 0 {
+            units.push(Unit::Invalid {
+                byte_offset: offset,
+                bytes: bytes[offset..offset + invalid_len].to_vec(),
+            });
+            offset += invalid_len;
+        }
+    }
+    Report {
+        units,
+        byte_len: bytes.len(),
+    }
+}
+
+fn hex<T: fmt::UpperHex>(values: &[T], width: usize) -> String {
+    values
+        .iter()
+        .map(|value| format!("{value:0width$X}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/*
+ * Los `chars` de control y de formato no se imprimen directamente porque mueven la terminal
+ */
+fn printable(c: char) -> String {
+    match Category::of(c) {
+        Category::Control | Category::Format | Category::Space => c.escape_unicode().to_string(),
+        Category::Mark => format!("◌{c}"),
+        _ => c.to_string(),
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>6} {:>6}  {:<10} {:<8} {:<12} {:<10} cat",
+            "byte", "char", "code", "glyph", "utf-8", "utf-16"
+        )?;
+        for unit in &self.units {
+            match unit {
+                Unit::Char(info) => writeln!(
+                    f,
+                    "{:>6} {:>6}  U+{:<8X} {:<8} {:<12} {:<10} {}",
+                    info.byte_offset,
+                    info.char_index,
+                    info.code_point(),
+                    printable(info.ch),
+                    hex(&info.utf8, 2),
+                    hex(&info.utf16, 4),
+                    info.category.code()
+                )?,
+                Unit::Invalid { byte_offset, bytes } => writeln!(
+                    f,
+                    "{:>6} {:>6}  {:<10} {:<8} {:<12} {:<10} invalid UTF-8",
+                    byte_offset,
+                    "-",
+                    "-",
+                    "\u{FFFD}",
+                    hex(bytes, 2),
+                    "-"
+                )?,
+            }
+        }
+        write!(
+            f,
+            "{} bytes, {} chars, {} UTF-16 units{}",
+            self.byte_len,
+            self.char_count(),
+            self.utf16_len(),
+            if self.is_valid() { "" } else { ", contains invalid UTF-8" }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+     * El texto del reporte con cada secuencia inválida como `U+FFFD`, igual que `from_utf8_lossy`
+     */
+    fn lossy(report: &Report) -> String {
+        report
+            .units
+            .iter()
+            .map(|unit| match unit {
+                Unit::Char(info) => info.ch,
+                Unit::Invalid { .. } => char::REPLACEMENT_CHARACTER,
+            })
+            .collect()
+    }
+
+    fn invalid(report: &Report) -> Vec<(usize, Vec<u8>)> {
+        report
+            .units
+            .iter()
+            .filter_map(|unit| match unit {
+                Unit::Invalid { byte_offset, bytes } => Some((*byte_offset, bytes.clone())),
+                Unit::Char(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn invalid_bytes_match_std() {
+        let inputs: [&[u8]; 7] = [
+            b"ok",
+            b"a\xE2\x82",
+            b"a\xC0\x80b",
+            b"\xED\xA0\x80",
+            b"\xF0\x9F\x98",
+            b"\xFF\xFEa\xF4\x90\x80\x80",
+            "año €".as_bytes(),
+        ];
+        for bytes in inputs {
+            let report = inspect_bytes(bytes);
+            assert_eq!(lossy(&report), String::from_utf8_lossy(bytes), "{bytes:02X?}");
+            assert_eq!(report.byte_len, bytes.len());
+            match std::str::from_utf8(bytes) {
+                Ok(text) => {
+                    assert!(report.is_valid());
+                    assert_eq!(report, inspect(text));
+                }
+                Err(error) => {
+                    let (offset, first) = invalid(&report).remove(0);
+                    assert_eq!(offset, error.valid_up_to());
+                    let len = error.error_len().unwrap_or(bytes.len() - offset);
+                    assert_eq!(first.len(), len, "{bytes:02X?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn truncated_overlong_and_surrogate() {
+        /*
+         * Al final del texto la secuencia incompleta es un solo pedazo, `error_len()` es `None`
+         */
+        let truncated = inspect_bytes(b"a\xE2\x82");
+        assert_eq!(invalid(&truncated), [(1, vec![0xE2, 0x82])]);
+        assert_eq!(lossy(&truncated), "a\u{FFFD}");
+        /*
+         * `C0` nunca es válido, la forma larga de `'\0'` se rechaza byte por byte
+         */
+        let overlong = inspect_bytes(b"a\xC0\x80b");
+        assert_eq!(invalid(&overlong), [(1, vec![0xC0]), (2, vec![0x80])]);
+        assert_eq!(overlong.char_count(), 2);
+        assert_eq!(overlong.char_boundaries(), [0, 3, 4]);
+        /*
+         * `ED A0 80` sería `U+D800`, una mitad de `surrogate` que no es un `char`
+         */
+        let surrogate = inspect_bytes(b"\xED\xA0\x80");
+        assert_eq!(
+            invalid(&surrogate),
+            [(0, vec![0xED]), (1, vec![0xA0]), (2, vec![0x80])]
+        );
+        assert_eq!(surrogate.char_count(), 0);
+    }
+
+    #[test]
+    fn offsets_on_multibyte_text() {
+        let report = inspect("é😊a");
+        assert_eq!(report.char_boundaries(), [0, 2, 6, 7]);
+        assert_eq!(report.utf16_len(), "é😊a".encode_utf16().count());
+        let smile = report.chars().nth(1).unwrap();
+        assert_eq!((smile.byte_offset, smile.char_index), (2, 1));
+        assert_eq!(smile.utf8, [0xF0, 0x9F, 0x98, 0x8A]);
+        assert_eq!(smile.utf16, [0xD83D, 0xDE0A]);
+        assert_eq!(smile.code_point(), 0x1F60A);
+    }
+
+    #[test]
+    fn categories() {
+        let cases = [
+            ('A', Category::UppercaseLetter),
+            ('ñ', Category::LowercaseLetter),
+            ('漢', Category::OtherLetter),
+            ('\u{0301}', Category::Mark),
+            ('\u{20E3}', Category::Mark),
+            ('\u{FE0F}', Category::Mark),
+            ('\u{200D}', Category::Format),
+            ('\u{FEFF}', Category::Format),
+            ('7', Category::DecimalNumber),
+            ('½', Category::OtherNumber),
+            ('¿', Category::Punctuation),
+            ('€', Category::Symbol),
+            ('\u{00A0}', Category::Space),
+            ('\u{3000}', Category::Space),
+            ('\n', Category::Control),
+            ('\u{E000}', Category::PrivateUse),
+        ];
+        for (c, category) in cases {
+            assert_eq!(Category::of(c), category, "{}", c.escape_unicode());
+        }
+        assert_eq!(printable('\u{0301}'), "◌\u{0301}");
+        assert_eq!(printable('\u{200D}'), "\\u{200d}");
+        /*
+         * Una familia con `ZWJ` es un solo dibujo pero cinco `chars`
+         */
+        let family = inspect("👩\u{200D}👧\u{200D}👦");
+        let codes: Vec<&str> = family.chars().map(|info| info.category.code()).collect();
+        assert_eq!(codes, ["S", "Cf", "S", "Cf", "S"]);
+    }
+}
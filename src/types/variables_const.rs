@@ -1,6 +1,7 @@
 /*
  * Las variables son espacios de memoria que permiten almacenar datos con un nombre asociado.
  */
+#[allow(clippy::assign_op_pattern)]
 pub fn run() {
     /*
      * Por defecto las variables son `inmutables` para garantizar la seguridad de memoria y
@@ -21,17 +22,21 @@ pub fn run() {
     /*
      * Las constantes en `Rust` siempre serán `inmutables` y su valor se debe de conocer en
      * tiempo de compilación, se declaran con la palabra clave `const` especificar el tipo de dato,
-     * en mayúsculas y usando `snacke_case`
+     * en mayúsculas y usando `snacke_case`. Una constante puede usar otras constantes, el valor se
+     * calcula al compilar
      */
-    const DIMENTIONS: u8 = 3;
+    const MAX_COORDINATES: u32 = MAX_POINTS * DIMENSIONS as u32;
     /*
      * Tienen un ciclo de vida más amplio que las variables, ya que se almacenan en la memoria
      * de solo lectura.
      */
-    println!("The number of dimentions is {} and Max points are {} units", DIMENTIONS, MAX_POINTS);
+    println!("The number of dimentions is {} and Max points are {} units", DIMENSIONS, MAX_POINTS);
+    println!("Max coordinates: {}", MAX_COORDINATES);
 }
 
 /*
- * Las constantes pueden ser declaradas en cualquier `scope`
+ * Las constantes pueden ser declaradas en cualquier `scope`, `MAX_COORDINATES` está dentro de
+ * `run`. Estas son `pub` porque `types::config` las usa como valores por defecto
  */
-const MAX_POINTS: u32 = 100_000;
\ No newline at end of file
+pub const DIMENSIONS: u8 = 3;
+pub const MAX_POINTS: u32 = 100_000;